@@ -22,6 +22,7 @@ command-battlemode-desc = Set your battle mode to:
   + pve (player vs environment).
   If called without arguments will show current battle mode.
 command-battlemode_force-desc = Change your battle mode flag without any checks
+command-block_history-desc = Show who recently changed the blocks around you
 command-campfire-desc = Spawns a campfire
 command-clear_persisted_terrain-desc = Clears nearby persisted terrain
 command-create_location-desc = Create a location at the current position
@@ -36,6 +37,7 @@ command-dropall-desc = Drops all your items on the ground
 command-dummy-desc = Spawns a training dummy
 command-explosion-desc = Explodes the ground around you
 command-faction-desc = Send messages to your faction
+command-forecast-desc = Show the weather forecast for the next few hours where you are
 command-give_item-desc = Give yourself some items. For an example or to auto complete use Tab.
 command-goto-desc = Teleport to a position
 command-goto-rand = Teleport to a random position
@@ -45,6 +47,7 @@ command-group_kick-desc = Remove a player from a group
 command-group_leave-desc = Leave the current group
 command-group_promote-desc = Promote a player to group leader
 command-health-desc = Set your current health
+command-instance_portal-desc = Spawns a portal into an arena or a copy of the dungeon of a site, for the group entering it
 command-into_npc-desc = Convert yourself to an NPC. Be careful!
 command-join_faction-desc = Join/leave the specified faction
 command-jump-desc = Offset your current position
@@ -68,14 +71,17 @@ command-outcome-desc = Create an outcome
 command-permit_build-desc = Grants player a bounded box they can build in
 command-players-desc = Lists players currently online
 command-portal-desc = Spawns a portal
+command-profile-desc = Shows the regions and players putting the most load on the server
 command-region-desc = Send messages to everyone in your region of the world
 command-reload_chunks-desc = Reloads chunks loaded on the server
 command-remove_lights-desc = Removes all lights spawned by players
 command-repair_equipment-desc = Repairs all equipped items
 command-reset_recipes-desc = Resets your recipe book
 command-respawn-desc = Teleport to your waypoint
+command-restore_terrain-desc = Remove all persisted changes within an area, restoring the generated terrain
 command-revoke_build-desc = Revokes build area permission for player
 command-revoke_build_all-desc = Revokes all build area permissions for player
+command-rollback-desc = Undo the blocks a player changed around you in the last few minutes
 command-safezone-desc = Creates a safezone
 command-say-desc = Send messages to everyone within shouting distance
 command-scale-desc = Scale your character
@@ -169,7 +175,7 @@ command-revoke-build-all = Your build permissions have been revoked.
 command-revoked-all-build = All build permissions revoked.
 command-no-buid-perms = You do not have permission to build.
 command-set-build-mode-off = Toggled build mode off.
-command-set-build-mode-on-persistent = Toggled build mode on. Experimental terrain persistence is enabled. The server will attempt to persist changes, but this is not guaranteed.
+command-set-build-mode-on-persistent = Toggled build mode on. Terrain persistence is enabled, so changes will be saved.
 command-set-build-mode-on-unpersistent = Toggled build mode on. Changes will not be persisted when a chunk unloads.
 command-set_motd-message-added = Server message of the day set to { $message }
 command-set_motd-message-removed = Removed server message of the day
@@ -207,7 +213,7 @@ command-location-deleted = Deleted location '{ $location }'
 command-locations-empty = No locations currently exist
 command-locations-list = Available locations: { $locations }
 # Note: Do not translate these weather names
-command-weather-valid-values = Valid values are 'clear', 'rain', 'wind' and 'storm'.
+command-weather-valid-values = Valid values are 'clear', 'cloudy', 'rain', 'wind', 'storm', 'snow' and 'fog'.
 command-scale-set = Set scale to { $scale }
 command-repaired-items = Repaired all equipped items
 command-message-group-missing = You are using group chat but do not belong to a group. Use /world or
@@ -219,7 +225,7 @@ command-aura-spawn = Spawned new aura attached to entity
 command-aura-spawn-new-entity = Spawned new aura
 command-reloaded-chunks = Reloaded { $reloaded } chunks
 command-server-no-experimental-terrain-persistence = Server was compiled without terrain persistence enabled
-command-experimental-terrain-persistence-disabled = Experimental terrain persistence is disabled
+command-experimental-terrain-persistence-disabled = Terrain persistence is disabled
 command-adminify-assign-higher-than-own = Cannot assign someone a temporary role higher than your own permanent one.
 command-adminify-reassign-to-above = Cannot reassign a role for anyone with your role or higher.
 command-adminify-cannot-find-player = Cannot find player entity!
@@ -232,6 +238,22 @@ command-ban-added = Added { $player } to the banlist with reason: { $reason }
 command-ban-already-added = { $player } is already on the banlist
 command-ban-ip-added = Added { $player } to the regular banlist and IP banlist with reason: { $reason }
 command-faction-join = Please join a faction with /join_faction
+command-forecast-unavailable = No weather forecast is available yet.
+command-forecast-entry = { $time }: { $weather }, { $temperature }°C
+command-block_history-empty = No blocks around here were changed by players.
+command-block_history-entry = { $time } { $player }: { $old } -> { $new } at { $pos }
+command-profile-unavailable = No load report has been made yet.
+command-profile-header = Load over the last { $seconds }s, by region (key at world position):
+command-profile-entities = Entities:
+command-profile-agents = Agents:
+command-profile-physics-contacts = Physics contacts:
+command-profile-chunk-requests = Chunk requests:
+command-profile-region = { $value } ({ $key }) at ({ $pos })
+command-profile-players = Bytes sent by player:
+command-profile-player = { $bytes } { $player }
+command-rollback-done = Rolled back { $count } blocks changed by { $player }.
+command-restore_terrain-done = Restored the terrain of { $chunks } chunks.
+command-restore_terrain-too-large = The area is too large to restore at once.
 command-group-join = Please create a group first
 command-group_invite-invited-to-group = Invited { $player } to the group.
 command-group_invite-invited-to-your-group = { $player } has been invited to your group.
@@ -108,8 +108,15 @@ common-stats-energy_max = Max Energy
 common-stats-energy_reward = Energy Reward
 common-stats-precision_power = Crit Power
 common-stats-stealth = Stealth
+common-stats-resistances = Resistances
+common-stats-res-fire = Fire
+common-stats-res-frost = Frost
+common-stats-res-poison = Poison
+common-stats-res-lightning = Lightning
+common-stats-res-arcane = Arcane
 common-stats-slots = Slots
 common-stats-durability = Durability
+common-stats-set_bonus = Set ({ $pieces })
 
 ## Material names
 common-material-metal = Metal
@@ -123,3 +130,11 @@ common-sprite-chest = Chest
 common-sprite-mud = Mud
 common-sprite-grave = Grave
 common-sprite-crate = Crate
+
+## Weather
+common-weather-clear = Clear
+common-weather-cloudy = Cloudy
+common-weather-rain = Rain
+common-weather-snow = Snow
+common-weather-fog = Fog
+common-weather-storm = Storm
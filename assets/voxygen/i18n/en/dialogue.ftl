@@ -12,6 +12,19 @@ dialogue-question-sentiment =
     .a0 = How do you feel about...
     .a1 = What do you think about...
 dialogue-question-directions = Can you give me directions?
+dialogue-question-weather =
+    .a0 = What will the weather be like?
+    .a1 = Is it going to rain?
+dialogue-question-quest =
+    .a0 = Do you have any work for me?
+    .a1 = Is there anything I can help with?
+dialogue-question-bounty =
+    .a0 = Are there any bounties posted?
+    .a1 = Who is wanted around here?
+dialogue-bounty = { $target }: { $reward } coins for { $victims ->
+    [one] one victim
+   *[other] { $victims } victims
+}
 dialogue-cancel_hire = I want to stop hiring you.
 dialogue-me = Me
 dialogue-buy_hire_days =
@@ -21,6 +21,7 @@ hud-chat-message = [{ $alias }]: { $msg }
 hud-chat-message-with-name = [{ $alias }] { $name }: { $msg }
 hud-chat-message-in-group = ({ $group }) [{ $alias }]: { $msg }
 hud-chat-message-in-group-with-name = ({ $group }) [{ $alias }] { $name }: { $msg }
+hud-chat-bridged-message = <{ $source }> [{ $alias }]: { $msg }
 
 ## PvP Buff deaths, both $attacker_gender and $victim_gender are available
 
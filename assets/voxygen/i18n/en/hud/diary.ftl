@@ -5,4 +5,22 @@
 hud-diary-sections-skill_trees-title = Skill-Trees
 hud-diary-sections-abilities-title = Abilities
 hud-diary-sections-stats-title = Stats
-hud-diary-sections-recipes-title = Recipes
\ No newline at end of file
+hud-diary-sections-recipes-title = Recipes
+hud-diary-sections-quests-title = Quests
+
+## Quests
+
+hud-diary-quests-abandon = Abandon
+hud-diary-quests-kill = Hunt { $body }: { $progress }/{ $count }
+hud-diary-quests-fetch = Collect { $item }: { $progress }/{ $amount }
+hud-diary-quests-escort = Escort to { $site }: { $done ->
+    [0] on the way
+   *[other] arrived
+}
+hud-diary-quests-visit = Visit { $site }: { $done ->
+    [0] not yet visited
+   *[other] visited
+}
+hud-diary-quests-slay = Bring { $target } to justice
+hud-diary-quests-none = You have no quests. Ask the people you meet if they need help. Quests completed: { $completed }
+hud-diary-quests-completed = Quests completed: { $completed }
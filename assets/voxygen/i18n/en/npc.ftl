@@ -425,6 +425,36 @@ npc-question-directions =
     .a1 = What are you looking for?
 npc-response-directions = I've marked it on your map. Good luck!
 npc-response-doesnt_exist = We don't have one around these parts.
+npc-response-weather_stays = Looks like { $weather } for the next few hours.
+npc-response-weather_changes = It'll be { $now } for now, but I reckon we'll have { $later } in about { $hours } hours.
+npc-response-weather_freezing = It'll be freezing out there, wrap up warm!
+npc-weather =
+    .clear = clear skies
+    .cloudy = clouds
+    .rain = rain
+    .snow = snow
+    .fog = fog
+    .storm = a storm
+
+npc-question-quest = Will you help me?
+npc-response-quest_accepted =
+    .a0 = Thank you! Come back to me when it's done.
+    .a1 = Wonderful, I knew I could count on you.
+npc-response-no_quest =
+    .a0 = I don't have anything for you right now.
+    .a1 = Sorry, I can't think of anything.
+npc-quest-kill = I need someone to hunt { $count } { $body }.
+npc-quest-fetch = Could you bring me { $amount } { $item }?
+npc-quest-escort = I need to get to { $site } safely. Could you escort me there?
+npc-quest-visit = Something is going on in { $site }. Could you go there and take a look?
+npc-quest-none = I need a favour.
+npc-quest-creatures = creatures
+npc-quest-items = items
+npc-quest-slay = { $target } has blood on their hands. Bring them to justice.
+npc-question-bounty = These are the bounties posted here.
+npc-response-bounty_accepted =
+    .a0 = Good hunting. The reward is yours once they're dead.
+    .a1 = Our people will rest easier once it's done.
 
 ## NPC reactions
 
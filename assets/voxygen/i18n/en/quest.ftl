@@ -0,0 +1,16 @@
+## Quest titles
+
+quest-title-kill = A hunt for { $giver }
+quest-title-fetch = Supplies for { $giver }
+quest-title-escort = Escorting { $giver }
+quest-title-visit = An errand for { $giver }
+quest-title-bounty = Bounty on { $target }
+
+## Quest notifications
+
+quest-started = New quest: { $title }
+quest-completed = Quest completed: { $title }
+quest-abandoned = Quest abandoned: { $title }
+quest-failed = Quest failed: { $title }
+quest-already_accepted = You are already doing a quest for them.
+quest-too_many = You can't take on any more quests.
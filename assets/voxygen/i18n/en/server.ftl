@@ -0,0 +1,21 @@
+## Messages rendered by the server
+
+server-reason-none = No reason given
+server-kick-logged_in_elsewhere = You have logged in from another location.
+
+## Kick and ban reasons
+
+server-kick-reason-afk = Away from keyboard for too long
+server-ban-reason-griefing = Griefing
+server-ban-reason-cheating = Cheating
+server-ban-reason-spamming = Spamming
+server-ban-reason-harassment = Harassing other players
+
+## Shutdown
+
+server-shutdown-countdown = { $message } in { $time }
+server-shutdown-aborted = The shutdown has been aborted
+
+## Instances
+
+server-instance-unavailable = The portal leads nowhere, there is no room for another instance right now.
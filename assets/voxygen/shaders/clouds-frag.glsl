@@ -269,11 +269,22 @@ void main() {
     #if (CLOUD_MODE == CLOUD_MODE_NONE)
         color.rgb = apply_point_glow(cam_pos.xyz + focus_off.xyz, dir, dist, color.rgb);
     #else
-        if (medium.x == MEDIUM_AIR && rain_density > 0.001) {
+        // Weather fog, which hides distant terrain in the colour of the overcast sky
+        if (medium.x == MEDIUM_AIR && time_of_day.z > 0.001) {
+            float fog_light = get_sun_brightness() + get_moon_brightness() * 0.2 + 0.05;
+            float fog_amount = 1.0 - exp(-min(dist, 5000.0) * time_of_day.z * 0.01);
+            color.rgb = mix(color.rgb, vec3(0.55, 0.58, 0.62) * fog_light, fog_amount);
+        }
+
+        float precipitation_density = rain_density + snow_density;
+        if (medium.x == MEDIUM_AIR && precipitation_density > 0.001) {
             vec3 cam_wpos = cam_pos.xyz + focus_off.xyz;
 
             vec3 adjusted_dir = (vec4(cam_dir, 0) * rain_dir_mat).xyz;
 
+            // Snow falls as slow, round flakes rather than streaks
+            float snowiness = snow_density / precipitation_density;
+
             vec2 dir2d = adjusted_dir.xy;
             vec3 rorigin = cam_pos.xyz + focus_off.xyz + 0.5;
             vec3 rpos = vec3(0.0);
@@ -295,7 +306,7 @@ void main() {
 
                 vec2 diff = abs(round(rpos.xy) - rpos.xy);
                 vec3 wall_pos = vec3((diff.x > diff.y) ? rpos.xy : rpos.yx, rpos.z + integrated_rain_vel);
-                wall_pos.xz *= vec2(4, 0.3);
+                wall_pos.xz *= mix(vec2(4, 0.3), vec2(4, 2), snowiness);
                 wall_pos.z += hash_two(uvec2(wall_pos.xy + vec2(0, 0.5)));
 
                 float depth_adjust = fract(hash_two(uvec2(wall_pos.xz) + 500u));
@@ -305,12 +316,12 @@ void main() {
                 if (wpos_dist > dist) { break; }
                 vec2 wall_pos_half = fract(wall_pos.xz) - 0.5;
                 if (dot(wall_pos_half, wall_pos_half) < 0.01 + pow(max(0.0, wpos_dist - (dist - 0.25)) / 0.25, 4.0) * 0.2) {
-                    float density = rain_density * rain_occlusion_at(wpos);
+                    float density = precipitation_density * rain_occlusion_at(wpos);
                     if (fract(hash_two(uvec2(wall_pos.xz) + 1000u)) >= density) { continue; }
 
                     float alpha = 0.5 * clamp((wpos_dist - 1.0) * 0.5, 0.0, 1.0);
                     float light = dot(color.rgb, vec3(1)) + 0.05 + (get_sun_brightness() + get_moon_brightness()) * 0.2;
-                    color.rgb = mix(color.rgb, vec3(0.3, 0.35, 0.5) * light, alpha);
+                    color.rgb = mix(color.rgb, mix(vec3(0.3, 0.35, 0.5), vec3(0.9, 0.92, 0.95), snowiness) * light, alpha);
                 }
             }
         }
@@ -11,6 +11,7 @@ layout(std140, set = 0, binding = 0) uniform u_globals {
     vec4 view_distance;
     // .x = time of day, repeats every day.
     // .y = a continuous value for what day it is. Repeats every `tick_overflow` for precisions sake.
+    // .z = density of the fog in the weather around the player.
     vec4 time_of_day;
     vec4 sun_dir;
     vec4 moon_dir;
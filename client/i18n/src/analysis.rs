@@ -1,6 +1,7 @@
 use crate::{assets::Walk, error::ResourceErr};
 use fluent_syntax::{ast, parser};
 use std::{
+    collections::BTreeSet,
     fs, io,
     path::{Path, PathBuf},
 };
@@ -35,9 +36,17 @@ fn keys_from_file(filepath: &Path) -> Vec<MsgId> {
     for entry in ast.body {
         match entry {
             Entry::Message(m) => {
+                let mut args = BTreeSet::new();
+                if let Some(value) = &m.value {
+                    pattern_args(value, &mut args);
+                }
+                for attr in &m.attributes {
+                    pattern_args(&attr.value, &mut args);
+                }
                 keys.push(MsgId {
                     key: m.id.name.to_owned(),
                     file: file.clone(),
+                    args,
                 });
             },
             Entry::Term(_)
@@ -54,6 +63,56 @@ fn keys_from_file(filepath: &Path) -> Vec<MsgId> {
     keys
 }
 
+/// Collect the variables a pattern refers to.
+fn pattern_args(pattern: &ast::Pattern<&str>, args: &mut BTreeSet<String>) {
+    for element in &pattern.elements {
+        if let ast::PatternElement::Placeable { expression } = element {
+            expression_args(expression, args);
+        }
+    }
+}
+
+fn expression_args(expression: &ast::Expression<&str>, args: &mut BTreeSet<String>) {
+    match expression {
+        ast::Expression::Select { selector, variants } => {
+            inline_expression_args(selector, args);
+            for variant in variants {
+                pattern_args(&variant.value, args);
+            }
+        },
+        ast::Expression::Inline(inline) => inline_expression_args(inline, args),
+    }
+}
+
+fn inline_expression_args(expression: &ast::InlineExpression<&str>, args: &mut BTreeSet<String>) {
+    use ast::InlineExpression;
+
+    let call_args = |call: &ast::CallArguments<&str>, args: &mut BTreeSet<String>| {
+        for positional in &call.positional {
+            inline_expression_args(positional, args);
+        }
+        for named in &call.named {
+            inline_expression_args(&named.value, args);
+        }
+    };
+
+    match expression {
+        InlineExpression::VariableReference { id } => {
+            args.insert(id.name.to_owned());
+        },
+        InlineExpression::FunctionReference { arguments, .. } => call_args(arguments, args),
+        InlineExpression::TermReference {
+            arguments: Some(arguments),
+            ..
+        } => call_args(arguments, args),
+        InlineExpression::Placeable { expression } => expression_args(expression, args),
+        InlineExpression::StringLiteral { .. }
+        | InlineExpression::NumberLiteral { .. }
+        | InlineExpression::MessageReference { .. }
+        | InlineExpression::TermReference { .. } => {},
+    }
+}
+
 /// Grab keys from one language sitting at `from`.
 ///
 /// Tree of files assumed to have only .ftl files.
@@ -81,6 +140,19 @@ fn keys(from: &Path, tree: &Walk) -> Vec<MsgId> {
 pub struct MsgId {
     pub key: String,
     pub file: String,
+    /// Variables the message refers to, in its value and attributes.
+    pub args: BTreeSet<String>,
+}
+
+/// A translated message whose variables differ from the reference message.
+#[derive(Clone, Debug)]
+pub struct ArgsMismatch {
+    /// The translated message.
+    pub msg: MsgId,
+    /// Variables of the reference message the translation doesn't use.
+    pub missing: Vec<String>,
+    /// Variables the translation uses which are never passed to the message.
+    pub unknown: Vec<String>,
 }
 
 // TODO:
@@ -90,6 +162,7 @@ pub struct Stats {
     pub up_to_date: Vec<MsgId>,
     pub not_found: Vec<MsgId>,
     pub unused: Vec<MsgId>,
+    pub args_mismatch: Vec<ArgsMismatch>,
 }
 
 pub struct ReferenceLanguage {
@@ -116,10 +189,18 @@ impl ReferenceLanguage {
             up_to_date: Vec::new(),
             not_found: Vec::new(),
             unused: Vec::new(),
+            args_mismatch: Vec::new(),
         };
 
         for ref_key in &self.keys {
             if let Some(key) = keys.iter().find(|MsgId { key, .. }| &ref_key.key == key) {
+                if key.args != ref_key.args {
+                    stats.args_mismatch.push(ArgsMismatch {
+                        msg: key.clone(),
+                        missing: ref_key.args.difference(&key.args).cloned().collect(),
+                        unknown: key.args.difference(&ref_key.args).cloned().collect(),
+                    });
+                }
                 stats.up_to_date.push(key.clone());
             } else {
                 stats.not_found.push(ref_key.clone());
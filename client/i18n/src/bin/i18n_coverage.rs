@@ -0,0 +1,118 @@
+use clap::{Arg, Command};
+use common_assets::find_root;
+use veloren_client_i18n::{
+    PSEUDO_LANG, REFERENCE_LANG,
+    analysis::{Language, ReferenceLanguage, Stats},
+    list_localizations,
+    usage::Usage,
+};
+
+fn main() {
+    let args = Command::new("i18n-coverage")
+        .about(
+            "Tool to find localisation keys used by the code but missing from the reference \
+             language, unused keys and translations with wrong arguments",
+        )
+        .arg(
+            Arg::new("CODE")
+                .help("Only report this language code (de_DE, for example), with all details"),
+        )
+        .get_matches();
+
+    let root = find_root().unwrap();
+    let i18n_directory = root.join("assets/voxygen/i18n");
+    let reference = ReferenceLanguage::at(&i18n_directory.join(REFERENCE_LANG));
+    let usage = Usage::scan(&root).expect("failed to scan sources");
+
+    let mut missing = usage
+        .required
+        .iter()
+        .filter(|(key, _)| !reference.keys.iter().any(|msg| &msg.key == *key))
+        .collect::<Vec<_>>();
+    missing.sort_by(|(a, _), (b, _)| a.cmp(b));
+    println!("\t[Missing from {REFERENCE_LANG}]: {}", missing.len());
+    for (key, locations) in &missing {
+        for location in *locations {
+            let file = location.file.strip_prefix(&root).unwrap_or(&location.file);
+            println!("{key} ({}:{})", file.display(), location.line);
+        }
+    }
+
+    let mut unused = reference
+        .keys
+        .iter()
+        .filter(|msg| !usage.might_use(&msg.key))
+        .collect::<Vec<_>>();
+    unused.sort_by(|a, b| a.key.cmp(&b.key));
+    println!(
+        "\n\t[Not found in sources]: {} (keys built at runtime in unusual ways aren't detected)",
+        unused.len()
+    );
+    for msg in unused {
+        println!("{}", msg.key);
+    }
+
+    let only = args.get_one::<String>("CODE");
+    let languages = match only {
+        Some(code) => vec![code.clone()],
+        None => list_localizations()
+            .into_iter()
+            .map(|meta| meta.language_identifier)
+            .filter(|code| code != REFERENCE_LANG && code != PSEUDO_LANG)
+            .collect(),
+    };
+
+    println!("\n\t[Languages]");
+    for code in languages {
+        let lang = Language {
+            code: code.clone(),
+            path: i18n_directory.join(&code),
+        };
+        let stats = reference.compare_with(&lang);
+        println!(
+            "{code}: {} up to date, {} not found, {} unused, {} with wrong arguments",
+            stats.up_to_date.len(),
+            stats.not_found.len(),
+            stats.unused.len(),
+            stats.args_mismatch.len(),
+        );
+        if only.is_some() {
+            print_details(&stats);
+        }
+    }
+
+    if !missing.is_empty() {
+        std::process::exit(1);
+    }
+}
+
+fn print_details(stats: &Stats) {
+    println!("\n\t[Not found]: {}", stats.not_found.len());
+    for msg in &stats.not_found {
+        println!("{}", msg.key);
+    }
+
+    println!("\n\t[Unused]: {}", stats.unused.len());
+    for msg in &stats.unused {
+        println!("{}", msg.key);
+    }
+
+    println!("\n\t[Wrong arguments]: {}", stats.args_mismatch.len());
+    for mismatch in &stats.args_mismatch {
+        let vars = |names: &[String]| {
+            names
+                .iter()
+                .map(|name| format!("${name}"))
+                .collect::<Vec<_>>()
+                .join(", ")
+        };
+        print!("{}:", mismatch.msg.key);
+        if !mismatch.missing.is_empty() {
+            print!(" missing {}", vars(&mismatch.missing));
+        }
+        if !mismatch.unknown.is_empty() {
+            print!(" unknown {}", vars(&mismatch.unknown));
+        }
+        println!();
+    }
+}
@@ -1,7 +1,7 @@
 use common_assets::find_root;
 use std::{fs, io::Write, path::Path};
 use veloren_client_i18n::{
-    REFERENCE_LANG,
+    PSEUDO_LANG, REFERENCE_LANG,
     analysis::{Language, ReferenceLanguage},
     list_localizations,
 };
@@ -31,6 +31,9 @@ fn main() {
     };
     for meta in list {
         let code = meta.language_identifier;
+        if code == PSEUDO_LANG {
+            continue;
+        }
         let lang = Language {
             code: code.clone(),
             path: i18n_directory.join(code.clone()),
@@ -1,10 +1,12 @@
 mod error;
+mod pseudo;
 mod raw;
 
 use error::ResourceErr;
 
 #[cfg(any(feature = "bin", feature = "stat", test))]
 pub mod analysis;
+#[cfg(any(feature = "bin", test))] pub mod usage;
 
 use fluent_bundle::{FluentResource, bundle::FluentBundle};
 use intl_memoizer::concurrent::IntlLangMemoizer;
@@ -23,6 +25,8 @@ use common_assets as assets;
 use common_i18n::{Content, LocalizationArg};
 use tracing::warn;
 
+pub use pseudo::PSEUDO_LANG;
+
 // Re-export for argument creation
 pub use fluent::{FluentValue, fluent_args};
 pub use fluent_bundle::FluentArgs;
@@ -74,9 +78,21 @@ struct Language {
     pub(crate) fonts: Fonts,
     pub(crate) metadata: LanguageMetadata,
     pub(crate) convert_utf8_to_ascii: bool,
+    /// Whether this is the pseudo-localization, see [`pseudo`].
+    pub(crate) pseudo: bool,
 }
 
 impl Language {
+    fn postprocess<'a>(&self, msg: Cow<'a, str>) -> Cow<'a, str> {
+        if self.pseudo {
+            pseudo::wrap(&msg).into()
+        } else if self.convert_utf8_to_ascii {
+            deunicode(&msg).into()
+        } else {
+            msg
+        }
+    }
+
     fn try_msg<'a>(&'a self, key: &str, args: Option<&'a FluentArgs>) -> Option<Cow<'a, str>> {
         let bundle = &self.bundle;
         let msg = bundle.get_message(key)?;
@@ -86,13 +102,7 @@ impl Language {
             tracing::error!("err: {err} for {key}");
         }
 
-        let msg = if self.convert_utf8_to_ascii {
-            deunicode(&msg).into()
-        } else {
-            msg
-        };
-
-        Some(msg)
+        Some(self.postprocess(msg))
     }
 
     fn try_attr<'a>(
@@ -112,13 +122,7 @@ impl Language {
             tracing::error!("err: {err} for {key}");
         }
 
-        let msg = if self.convert_utf8_to_ascii {
-            deunicode(&msg).into()
-        } else {
-            msg
-        };
-
-        Some(msg)
+        Some(self.postprocess(msg))
     }
 
     /// NOTE: Exists for legacy reasons, avoid.
@@ -163,13 +167,7 @@ impl Language {
             tracing::error!("err: {err} for {key}");
         }
 
-        let msg = if self.convert_utf8_to_ascii {
-            deunicode(&msg).into()
-        } else {
-            msg
-        };
-
-        Some(msg)
+        Some(self.postprocess(msg))
     }
 }
 impl assets::Compound for Language {
@@ -183,11 +181,28 @@ impl assets::Compound for Language {
             metadata,
         } = manifest;
 
-        let lang_id: LanguageIdentifier = metadata.language_identifier.parse()?;
+        // The pseudo-localization is made of the messages of the reference
+        // language.
+        let pseudo = metadata.language_identifier == PSEUDO_LANG;
+        let (lang_id, resources): (LanguageIdentifier, _) = if pseudo {
+            (
+                REFERENCE_LANG.parse()?,
+                ["voxygen.i18n.", REFERENCE_LANG].concat(),
+            )
+        } else {
+            (metadata.language_identifier.parse()?, path.to_string())
+        };
         let mut bundle = FluentBundle::new_concurrent(vec![lang_id]);
+        if pseudo {
+            bundle.set_transform(Some(pseudo::transform));
+        }
 
         // Here go dragons
-        for id in cache.load_rec_dir::<raw::Resource>(path)?.read().ids() {
+        for id in cache
+            .load_rec_dir::<raw::Resource>(&resources)?
+            .read()
+            .ids()
+        {
             match cache.load(id) {
                 Ok(handle) => {
                     let source: &raw::Resource = &handle.read();
@@ -218,6 +233,7 @@ impl assets::Compound for Language {
             fonts,
             metadata,
             convert_utf8_to_ascii,
+            pseudo,
         })
     }
 }
@@ -681,6 +697,9 @@ mod tests {
 
         for meta in list {
             let code = meta.language_identifier;
+            if code == PSEUDO_LANG {
+                continue;
+            }
             let lang = Language {
                 code: code.clone(),
                 path: i18n_directory.join(code.clone()),
@@ -0,0 +1,87 @@
+//! Pseudo-localization, the reference language with accented and lengthened
+//! text.
+//!
+//! Selecting it makes text that skips localization stand out, since it stays
+//! plain, and shows where the UI truncates or overflows with languages longer
+//! than English.
+
+use std::borrow::Cow;
+
+/// Identifier of the pseudo-localization.
+pub const PSEUDO_LANG: &str = "pseudo";
+
+/// Accent the letters of a text element of a message and repeat its vowels.
+///
+/// Used as the transform of fluent bundles, so only the text written in the
+/// messages is changed, not the arguments passed to them.
+pub(crate) fn transform(text: &str) -> Cow<str> {
+    let mut pseudo = String::with_capacity(text.len() * 2);
+    for c in text.chars() {
+        let accented = accent(c);
+        pseudo.push(accented);
+        if matches!(c.to_ascii_lowercase(), 'a' | 'e' | 'i' | 'o' | 'u') {
+            pseudo.push(accented);
+        }
+    }
+    Cow::Owned(pseudo)
+}
+
+/// Wrap a whole message in brackets, so truncated text is easy to notice.
+pub(crate) fn wrap(msg: &str) -> String { format!("[{msg}]") }
+
+/// Only uses accented letters from Latin-1 and Latin Extended-A, which all UI
+/// fonts of the reference language support.
+fn accent(c: char) -> char {
+    match c {
+        'a' => 'á',
+        'c' => 'ç',
+        'd' => 'ď',
+        'e' => 'é',
+        'g' => 'ĝ',
+        'h' => 'ĥ',
+        'i' => 'î',
+        'j' => 'ĵ',
+        'k' => 'ķ',
+        'l' => 'ļ',
+        'n' => 'ñ',
+        'o' => 'ö',
+        'r' => 'ŕ',
+        's' => 'š',
+        't' => 'ţ',
+        'u' => 'û',
+        'w' => 'ŵ',
+        'y' => 'ý',
+        'z' => 'ž',
+        'A' => 'Å',
+        'C' => 'Ç',
+        'D' => 'Ď',
+        'E' => 'É',
+        'G' => 'Ĝ',
+        'H' => 'Ĥ',
+        'I' => 'Î',
+        'J' => 'Ĵ',
+        'K' => 'Ķ',
+        'L' => 'Ļ',
+        'N' => 'Ñ',
+        'O' => 'Ö',
+        'R' => 'Ŕ',
+        'S' => 'Š',
+        'T' => 'Ţ',
+        'U' => 'Û',
+        'W' => 'Ŵ',
+        'Y' => 'Ý',
+        'Z' => 'Ž',
+        c => c,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn pseudo_text() {
+        assert_eq!(transform("Quit Game"), "Qûûîîţ Ĝááméé");
+        assert_eq!(wrap("Ok"), "[Ok]");
+    }
+}
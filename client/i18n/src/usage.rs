@@ -0,0 +1,239 @@
+//! Find which localization keys are used by the code and the assets.
+//!
+//! This doesn't parse Rust or RON, it collects string literals that look like
+//! keys. Keys passed directly to localization functions (e.g.
+//! `Content::localized("hud-map")`) are known to be needed, other literals
+//! only tell that a key might be used somewhere. Keys are often built at
+//! runtime with `format!("hud-skill-{}", ..)`, the literal text before the
+//! first `{` is then recorded as a prefix of keys that might be used.
+//! Unused keys are only a hint, since keys can be built in other ways.
+
+use hashbrown::{HashMap, HashSet};
+use std::{
+    fs, io,
+    path::{Path, PathBuf},
+};
+
+/// Functions and constructors which take a key as their first argument.
+const LOCALIZATION_CALLS: &[&str] = &[
+    "localized(",
+    "localized_with_args(",
+    "Content::Key(",
+    "Content::Attr(",
+    "get_msg(",
+    "get_msg_ctx(",
+    "try_msg(",
+    "try_msg_ctx(",
+    "get_attr(",
+    "get_attr_ctx(",
+    "try_attr(",
+    "try_attr_ctx(",
+    "get_variation(",
+    "get_variation_ctx(",
+    "try_variation(",
+    "try_variation_ctx(",
+];
+
+/// Directories which are never scanned, relative to the root.
+const SKIPPED_DIRS: &[&str] = &["target", ".git", "assets/voxygen/i18n"];
+
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub struct Location {
+    pub file: PathBuf,
+    pub line: usize,
+}
+
+#[derive(Debug, Default)]
+pub struct Usage {
+    /// Keys passed directly to localization functions, with where they are
+    /// passed.
+    pub required: HashMap<String, Vec<Location>>,
+    /// All literals which look like keys.
+    pub literals: HashSet<String>,
+    /// Beginnings of keys which are built at runtime.
+    pub prefixes: HashSet<String>,
+}
+
+impl Usage {
+    /// Scan the Rust sources and RON assets below `root`.
+    pub fn scan(root: &Path) -> io::Result<Self> {
+        let mut usage = Self::default();
+        let skipped = SKIPPED_DIRS
+            .iter()
+            .map(|dir| root.join(dir))
+            .collect::<Vec<_>>();
+        usage.scan_dir(root, &skipped)?;
+        Ok(usage)
+    }
+
+    fn scan_dir(&mut self, dir: &Path, skipped: &[PathBuf]) -> io::Result<()> {
+        for entry in fs::read_dir(dir)? {
+            let path = entry?.path();
+            if path.is_dir() {
+                if !skipped.contains(&path) {
+                    self.scan_dir(&path, skipped)?;
+                }
+            } else if let Some(ext @ ("rs" | "ron")) = path.extension().and_then(|e| e.to_str()) {
+                let is_rust = ext == "rs";
+                let source = fs::read_to_string(&path)?;
+                self.scan_source(&path, &source, is_rust);
+            }
+        }
+        Ok(())
+    }
+
+    /// Record the keys used by one source file.
+    pub fn scan_source(&mut self, file: &Path, source: &str, is_rust: bool) {
+        for literal in literals(source, is_rust) {
+            if let Some((prefix, _)) = literal.text.split_once('{') {
+                if looks_like_prefix(prefix) {
+                    self.prefixes.insert(prefix.to_owned());
+                }
+                continue;
+            }
+            // Some keys are derived from asset specifiers, like
+            // `common-abilities-axe-riptide` from `common.abilities.axe.riptide`
+            let derived = literal.text.replace('.', "-");
+            if derived != literal.text && looks_like_key(&derived) {
+                self.literals.insert(derived);
+                continue;
+            }
+            if !looks_like_key(literal.text) {
+                continue;
+            }
+            self.literals.insert(literal.text.to_owned());
+            let before = source[..literal.start].trim_end();
+            if LOCALIZATION_CALLS.iter().any(|call| before.ends_with(call)) {
+                self.required
+                    .entry(literal.text.to_owned())
+                    .or_default()
+                    .push(Location {
+                        file: file.to_owned(),
+                        line: source[..literal.start].matches('\n').count() + 1,
+                    });
+            }
+        }
+    }
+
+    /// Whether the key might be used somewhere.
+    pub fn might_use(&self, key: &str) -> bool {
+        self.literals.contains(key) || self.prefixes.iter().any(|prefix| key.starts_with(prefix))
+    }
+}
+
+/// Keys are made of lowercase words separated by dashes, like
+/// `hud-settings-language`.
+fn looks_like_key(text: &str) -> bool {
+    text.starts_with(|c: char| c.is_ascii_lowercase())
+        && text.contains('-')
+        && text
+            .split('-')
+            .all(|part| !part.is_empty() && part.chars().all(is_key_char))
+}
+
+/// Whether the text can be the beginning of a key, like `hud-skill-`.
+fn looks_like_prefix(text: &str) -> bool {
+    text.starts_with(|c: char| c.is_ascii_lowercase())
+        && text.contains('-')
+        && text.chars().all(|c| is_key_char(c) || c == '-')
+}
+
+fn is_key_char(c: char) -> bool { c.is_ascii_lowercase() || c.is_ascii_digit() || c == '_' }
+
+struct Literal<'a> {
+    /// Byte offset of the opening quote.
+    start: usize,
+    text: &'a str,
+}
+
+/// Collect the string literals of a source, skipping comments.
+fn literals(source: &str, is_rust: bool) -> Vec<Literal<'_>> {
+    let bytes = source.as_bytes();
+    let mut literals = Vec::new();
+    let mut i = 0;
+    while i < bytes.len() {
+        match bytes[i] {
+            b'/' if bytes.get(i + 1) == Some(&b'/') => {
+                i = source[i..].find('\n').map_or(bytes.len(), |end| i + end);
+            },
+            b'/' if bytes.get(i + 1) == Some(&b'*') => {
+                i = source[i..]
+                    .find("*/")
+                    .map_or(bytes.len(), |end| i + end + 2);
+            },
+            // Char literals of quotes, `'"'` and `'\"'`
+            b'\'' if is_rust && source[i + 1..].starts_with("\"'") => i += 3,
+            b'\'' if is_rust && source[i + 1..].starts_with("\\\"'") => i += 4,
+            // Raw strings, `r"..."` and `r#"..."#`
+            b'r' if is_rust
+                && (i == 0 || !(bytes[i - 1].is_ascii_alphanumeric() || bytes[i - 1] == b'_'))
+                && matches!(bytes.get(i + 1), Some(b'"' | b'#')) =>
+            {
+                let hashes = bytes[i + 1..].iter().take_while(|b| **b == b'#').count();
+                let start = i + 1 + hashes;
+                if bytes.get(start) != Some(&b'"') {
+                    i += 1;
+                    continue;
+                }
+                let end_marker = ["\"", &"#".repeat(hashes)].concat();
+                let end = source[start + 1..]
+                    .find(&end_marker)
+                    .map_or(bytes.len(), |end| start + 1 + end);
+                literals.push(Literal {
+                    start,
+                    text: &source[start + 1..end],
+                });
+                i = end + end_marker.len();
+            },
+            b'"' => {
+                let start = i;
+                i += 1;
+                while i < bytes.len() && bytes[i] != b'"' {
+                    i += if bytes[i] == b'\\' { 2 } else { 1 };
+                }
+                let end = i.min(bytes.len());
+                literals.push(Literal {
+                    start,
+                    text: &source[start + 1..end],
+                });
+                i += 1;
+            },
+            _ => i += 1,
+        }
+    }
+    literals
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn finds_keys() {
+        let source = r##"
+            // Not a key: "hud-comment"
+            let c = '"';
+            let a = Content::localized("hud-map");
+            let b = i18n.get_msg_ctx(
+                "hud-chat-online_msg",
+                &args,
+            );
+            let d = format!("hud-skill-{}", skill);
+            let e = r#"npc-speech-greeting"#;
+            let f = "not a key";
+            let g = "common.abilities.axe.riptide";
+        "##;
+        let mut usage = Usage::default();
+        usage.scan_source(Path::new("test.rs"), source, true);
+
+        let mut required = usage.required.keys().cloned().collect::<Vec<_>>();
+        required.sort();
+        assert_eq!(required, ["hud-chat-online_msg", "hud-map"]);
+        assert_eq!(usage.required["hud-map"][0].line, 4);
+        assert!(usage.might_use("npc-speech-greeting"));
+        assert!(usage.might_use("hud-skill-climbing"));
+        assert!(usage.might_use("common-abilities-axe-riptide"));
+        assert!(!usage.might_use("hud-comment"));
+        assert!(!usage.might_use("not a key"));
+    }
+}
@@ -0,0 +1,607 @@
+//! Load tester connecting many bots to a server.
+//!
+//! Either spawns a number of bots that stand still or walk in squares, or runs
+//! a scenario file describing waves of logins, what the bots do once in game
+//! and disconnect storms (see `example.ron`). Statistics of the bots are
+//! summarized regularly and when the scenario ends.
+//!
+//! The bots connect to a running server over TCP or, with the `swarm_server`
+//! feature and `--in-process`, to a server started in the same process.
+//! End-to-end tests use `veloren-test-harness` instead.
+
+use clap::Parser;
+use common::{
+    comp,
+    recipe::RecipeBookManifest,
+    terrain::{CoordinateConversions, TerrainChunkSize},
+    vol::RectVolSize,
+};
+use hashbrown::HashSet;
+use std::{
+    path::PathBuf,
+    sync::{Arc, Mutex},
+    thread,
+    time::{Duration, Instant},
+};
+use tokio::runtime::Runtime;
+use vek::*;
+use veloren_client::{Client, ClientType, Event, addr::ConnectionArgs};
+
+mod scenario;
+#[cfg(feature = "swarm_server")] mod server;
+mod stats;
+
+use scenario::{Action, Scenario, Spawn, Wave, noise};
+use stats::{BotStats, Report};
+
+const CHUNK_SIZE: f32 = TerrainChunkSize::RECT_SIZE.x as f32;
+/// How often a report is printed while the scenario runs
+const REPORT_INTERVAL: Duration = Duration::from_secs(30);
+/// Blocks per second, typical super fast veloren walking speed
+const SPEED: f32 = 9.0;
+/// How long bots wait to be made admins by the first bot after logging in
+const ADMIN_TIMEOUT: Duration = Duration::from_secs(60);
+
+#[derive(Parser)]
+struct Opt {
+    /// Number of clients to spin up, when not running a scenario
+    #[arg(value_name = "CLIENT_COUNT", required_unless_present = "scenario")]
+    size: Option<u32>,
+    /// View distance of each client, when not running a scenario
+    #[arg(value_name = "VIEW_DISTANCE", required_unless_present = "scenario")]
+    vd: Option<u32>,
+    /// Distribution of the clients, if not clustered they are dispersed
+    #[arg(short, long)]
+    clustered: bool,
+    /// Whether the clients should move
+    #[arg(short, long)]
+    movement: bool,
+    /// Scenario file to run instead
+    #[arg(short, long)]
+    scenario: Option<PathBuf>,
+    /// Address of the server to connect to
+    #[arg(long, default_value = "localhost")]
+    server: String,
+    /// Run a server in-process on the data in this directory instead of
+    /// connecting to one
+    #[cfg(feature = "swarm_server")]
+    #[arg(long, value_name = "DATA_DIR", conflicts_with = "server")]
+    in_process: Option<PathBuf>,
+    /// File to write the statistics of each bot to when the scenario ends
+    #[arg(short, long)]
+    report: Option<PathBuf>,
+}
+
+fn main() {
+    let opt = Opt::parse();
+    // Start logging
+    let _guards = common_frontend::init_stdout(None);
+
+    let scenario = match &opt.scenario {
+        Some(path) => match Scenario::load(path) {
+            Ok(scenario) => scenario,
+            Err(e) => {
+                tracing::error!("Failed to load scenario {e}");
+                return;
+            },
+        },
+        None => Scenario::simple(
+            opt.size.unwrap_or_default(),
+            opt.vd.unwrap_or_default(),
+            opt.clustered,
+            opt.movement,
+        ),
+    };
+    let scenario = Arc::new(scenario);
+    // Run clients and stuff
+    //
+    // NOTE:  "swarm0" is assumed to be an admin already, it makes the other bots
+    // admins as they log in
+    //
+    // Since this requires a no-auth server use this command to add swarm0 as an
+    // admin:
+    //
+    // --no-auth admin add swarm0 Admin
+    //
+    // The in-process server makes swarm0 an admin itself.
+    //
+    let runtime = Arc::new(Runtime::new().unwrap());
+    #[cfg(feature = "swarm_server")]
+    let in_process_server = match &opt.in_process {
+        Some(data_dir) => match server::InProcessServer::start(data_dir, Arc::clone(&runtime)) {
+            Ok(server) => Some(server),
+            Err(e) => {
+                tracing::error!("Failed to start the server {e}");
+                return;
+            },
+        },
+        None => None,
+    };
+    #[cfg(feature = "swarm_server")]
+    let in_process = in_process_server
+        .as_ref()
+        .map(server::InProcessServer::connection_args);
+    #[cfg(not(feature = "swarm_server"))]
+    let in_process = None;
+    let connection = in_process.unwrap_or_else(|| ConnectionArgs::Tcp {
+        prefer_ipv6: false,
+        hostname: opt.server.clone(),
+    });
+    let start = Instant::now();
+    let bot_count = scenario.bot_count();
+    let stats = (0..bot_count)
+        .map(|index| {
+            Arc::new(Mutex::new(BotStats {
+                username: format!("swarm{index}"),
+                ..Default::default()
+            }))
+        })
+        .collect::<Vec<_>>();
+
+    for (index, (wave, login_secs)) in scenario.bots().into_iter().enumerate() {
+        let bot = Bot {
+            index: index as u32,
+            username: format!("swarm{index}"),
+            wave: wave.clone(),
+            login_at: start + Duration::from_secs_f32(login_secs),
+            scenario: Arc::clone(&scenario),
+            start,
+            runtime: Arc::clone(&runtime),
+            connection: connection.clone(),
+            stats: Arc::clone(&stats[index]),
+            adminified: HashSet::new(),
+            storms_done: 0,
+        };
+        thread::spawn(move || bot.run());
+    }
+
+    let end = scenario
+        .duration_secs
+        .map(|secs| start + Duration::from_secs_f32(secs));
+    loop {
+        let next_report = Instant::now() + REPORT_INTERVAL;
+        let finished = end.is_some_and(|end| end <= next_report);
+        thread::sleep(
+            end.filter(|_| finished)
+                // Leave the bots some time to log out
+                .map_or(next_report, |end| end + Duration::from_secs(2))
+                .saturating_duration_since(Instant::now()),
+        );
+
+        let stats = stats
+            .iter()
+            .map(|stats| stats.lock().unwrap().clone())
+            .collect::<Vec<_>>();
+        println!("{}", Report::new(start.elapsed().as_secs_f32(), &stats));
+        if finished {
+            if let Some(path) = &opt.report {
+                let ron = ron::ser::to_string_pretty(&stats, ron::ser::PrettyConfig::default())
+                    .expect("Failed to serialize bot statistics");
+                if let Err(e) = std::fs::write(path, ron) {
+                    tracing::error!(?e, ?path, "Failed to write the report");
+                }
+            }
+            break;
+        }
+    }
+}
+
+struct Bot {
+    index: u32,
+    username: String,
+    wave: Wave,
+    login_at: Instant,
+    scenario: Arc<Scenario>,
+    start: Instant,
+    runtime: Arc<Runtime>,
+    connection: ConnectionArgs,
+    stats: Arc<Mutex<BotStats>>,
+    /// Bots made admins by this bot, if it's the first one
+    adminified: HashSet<String>,
+    /// Number of disconnect storms that already happened
+    storms_done: usize,
+}
+
+impl Bot {
+    fn run(mut self) {
+        thread::sleep(self.login_at.saturating_duration_since(Instant::now()));
+        while !self.finished() {
+            let login_start = Instant::now();
+            let mut client = match self.login() {
+                Ok(client) => client,
+                Err(e) => {
+                    tracing::warn!(?e, "swarm member {} failed to log in", self.index);
+                    self.stats.lock().unwrap().errors += 1;
+                    thread::sleep(Duration::from_secs(1));
+                    continue;
+                },
+            };
+            {
+                let mut stats = self.stats.lock().unwrap();
+                stats.login_secs.push(login_start.elapsed().as_secs_f32());
+                stats.in_game = true;
+            }
+            // Storms that happened while logged out are skipped
+            let elapsed = self.start.elapsed().as_secs_f32();
+            self.storms_done = self
+                .scenario
+                .disconnect_storms
+                .iter()
+                .filter(|storm| storm.at_secs <= elapsed)
+                .count();
+
+            let reconnect_after = match self.play(&mut client) {
+                Ok(Some(reconnect_after)) => {
+                    self.stats.lock().unwrap().disconnects += 1;
+                    reconnect_after
+                },
+                Ok(None) => {
+                    client.logout();
+                    let _ = client.tick_network(Duration::ZERO);
+                    self.stats.lock().unwrap().in_game = false;
+                    return;
+                },
+                Err(e) => {
+                    tracing::error!("swarm member {} exited with an error: {:?}", self.index, e);
+                    self.stats.lock().unwrap().errors += 1;
+                    Duration::from_secs(1)
+                },
+            };
+            self.stats.lock().unwrap().in_game = false;
+            drop(client);
+            thread::sleep(reconnect_after);
+        }
+    }
+
+    fn finished(&self) -> bool {
+        self.scenario
+            .duration_secs
+            .is_some_and(|secs| self.start.elapsed().as_secs_f32() >= secs)
+    }
+
+    fn is_admin(&self) -> bool { self.index == 0 }
+
+    /// Connects and waits until the bot is in game
+    fn login(&mut self) -> Result<Client, veloren_client::Error> {
+        let mut client = loop {
+            let addr = self.connection.clone();
+            let runtime_clone = Arc::clone(&self.runtime);
+            // NOTE: use a no-auth server
+            match self.runtime.block_on(Client::new(
+                addr,
+                runtime_clone,
+                &mut None,
+                &self.username,
+                "",
+                None,
+                |_| false,
+                &|_| {},
+                |_| {},
+                PathBuf::new(),
+                ClientType::Game,
+            )) {
+                Err(e) => tracing::warn!(?e, "Client {} disconnected", self.index),
+                Ok(client) => break client,
+            }
+        };
+
+        let mut clock = common::clock::Clock::new(Duration::from_secs_f32(1.0 / 30.0));
+
+        let mut tick = |client: &mut Client| -> Result<(), veloren_client::Error> {
+            clock.tick();
+            client.tick_network(clock.dt())?;
+            Ok(())
+        };
+
+        // Wait for character list to load
+        client.load_character_list();
+        while client.character_list().loading {
+            tick(&mut client)?;
+        }
+
+        // Create character if none exist
+        if client.character_list().characters.is_empty() {
+            client.create_character(
+                self.username.clone(),
+                Some("common.items.weapons.sword.starter".into()),
+                None,
+                body(),
+                false,
+                None,
+            );
+
+            client.load_character_list();
+
+            while client.character_list().loading || client.character_list().characters.is_empty() {
+                tick(&mut client)?;
+            }
+        }
+
+        // Select the first character
+        let vd = self.scenario.view_distance;
+        client.request_character(
+            client
+                .character_list()
+                .characters
+                .first()
+                .expect("Just created new character if non were listed!!!")
+                .character
+                .id
+                .expect("Why is this an option?"),
+            common::ViewDistances {
+                terrain: vd,
+                entity: vd,
+            },
+        );
+        while client.position().is_none() {
+            tick(&mut client)?;
+        }
+
+        if self.is_admin() {
+            // Check that we are a moderator (assumes we are an admin if so)
+            if !client.is_moderator() {
+                return Err(veloren_client::Error::Other(format!(
+                    "\"{}\" needs to be registered as an admin on the server",
+                    self.username
+                )));
+            }
+            // Bots need to be made admins again when they log in again
+            self.adminified.clear();
+        } else {
+            // Wait for the first bot to make us an admin
+            let waiting_since = Instant::now();
+            while !client.is_moderator() {
+                if waiting_since.elapsed() >= ADMIN_TIMEOUT {
+                    return Err(veloren_client::Error::Other(format!(
+                        "Wasn't made an admin within {}s, is \"swarm0\" logged in as an admin?",
+                        ADMIN_TIMEOUT.as_secs()
+                    )));
+                }
+                tick(&mut client)?;
+            }
+        }
+
+        Ok(client)
+    }
+
+    /// Performs the actions of the wave until the scenario ends, or the bot
+    /// disconnects. Returns how long to wait before logging in again in the
+    /// latter case.
+    fn play(&mut self, client: &mut Client) -> Result<Option<Duration>, veloren_client::Error> {
+        let mut clock = common::clock::Clock::new(Duration::from_secs_f32(1.0 / 30.0));
+        let world_center = client.world_data().chunk_size().as_::<f32>().cpos_to_wpos() / 2.0;
+        let mut home = world_center + self.spawn_offset();
+        let mut pos = Vec3::from(home);
+        let mut wander_target = None;
+        let mut action_idx = 0;
+        let mut action_start = Instant::now();
+        let mut action_started = false;
+        let mut last_ping_sample = Instant::now();
+        let msgs_received_before = self.stats.lock().unwrap().msgs_received;
+
+        loop {
+            clock.tick();
+            // TODO: doesn't seem to produce an error when server is shutdown (process keeps
+            // running)
+            let events = client.tick_network(clock.dt())?;
+            {
+                let mut stats = self.stats.lock().unwrap();
+                stats.msgs_received = msgs_received_before + client.received_msg_count();
+                stats.chat_msgs_received += events
+                    .iter()
+                    .filter(|event| matches!(event, Event::Chat(_)))
+                    .count() as u64;
+                if last_ping_sample.elapsed() >= Duration::from_secs(1) {
+                    stats.ping_ms.push(client.get_ping_ms() as f32);
+                    last_ping_sample = Instant::now();
+                }
+            }
+
+            if self.finished() {
+                return Ok(None);
+            }
+            let elapsed = self.start.elapsed().as_secs_f32();
+            while let Some(storm) = self
+                .scenario
+                .disconnect_storms
+                .get(self.storms_done)
+                .filter(|storm| storm.at_secs <= elapsed)
+            {
+                let storm_idx = self.storms_done;
+                self.storms_done += 1;
+                if storm.affects(storm_idx, self.index) {
+                    return Ok(Some(Duration::from_secs_f32(storm.reconnect_after_secs)));
+                }
+            }
+            if self.is_admin() {
+                self.adminify_others(client);
+            }
+
+            let Some(action) = self
+                .wave
+                .actions
+                .get(action_idx % self.wave.actions.len().max(1))
+            else {
+                continue;
+            };
+            if !action_started {
+                action_started = true;
+                let sent = match action {
+                    Action::Idle { .. } | Action::Wander { .. } | Action::Square { .. } => false,
+                    Action::Fight { .. } => {
+                        client.handle_input(comp::InputKind::Primary, true, None, None);
+                        true
+                    },
+                    Action::Craft { recipe, amount } => craft(client, recipe, *amount),
+                    Action::Chat { msg } => {
+                        client.send_chat(msg.clone());
+                        true
+                    },
+                    Action::Command { name, args } => {
+                        client.send_command(name.clone(), args.clone());
+                        true
+                    },
+                    Action::Teleport { radius } => {
+                        let angle =
+                            noise(self.index as u64, client.get_tick()) * std::f32::consts::TAU;
+                        let distance = noise(client.get_tick(), self.index as u64) * radius;
+                        home = world_center + Vec2::new(angle.cos(), angle.sin()) * distance;
+                        pos = Vec3::from(home);
+                        false
+                    },
+                    Action::ViewDistance { vd } => {
+                        client.set_view_distances(common::ViewDistances {
+                            terrain: *vd,
+                            entity: *vd,
+                        });
+                        true
+                    },
+                    Action::Disconnect {
+                        reconnect_after_secs,
+                    } => return Ok(Some(Duration::from_secs_f32(*reconnect_after_secs))),
+                };
+                if sent {
+                    self.stats.lock().unwrap().msgs_sent += 1;
+                }
+            }
+
+            let action_secs = action_start.elapsed().as_secs_f32();
+            match action {
+                Action::Square { .. } => {
+                    pos =
+                        Vec3::from(home + square_offset(action_secs, self.scenario.view_distance));
+                },
+                Action::Wander { radius, .. } => {
+                    let target = *wander_target.get_or_insert_with(|| {
+                        let tick = client.get_tick();
+                        home + Vec2::new(
+                            noise(self.index as u64, tick) - 0.5,
+                            noise(tick, self.index as u64) - 0.5,
+                        ) * 2.0
+                            * *radius
+                    });
+                    let to_target = target - pos.xy();
+                    let step = SPEED * clock.dt().as_secs_f32();
+                    if to_target.magnitude() <= step {
+                        pos = Vec3::from(target);
+                        wander_target = None;
+                    } else {
+                        pos += Vec3::from(to_target.normalized() * step);
+                    }
+                },
+                _ => {},
+            }
+
+            if action_secs >= action.secs() {
+                if let Action::Fight { .. } = action {
+                    client.handle_input(comp::InputKind::Primary, false, None, None);
+                }
+                action_idx += 1;
+                action_start = Instant::now();
+                action_started = false;
+                wander_target = None;
+            }
+
+            // TODO: make sure server cheat protections aren't triggering
+            let entity = client.entity();
+            client
+                .state_mut()
+                .write_component_ignore_entity_dead(entity, comp::Pos(pos));
+            client
+                .state_mut()
+                .write_component_ignore_entity_dead(entity, comp::Vel(Default::default()));
+        }
+    }
+
+    /// Makes the other bots admins as they log in
+    fn adminify_others(&mut self, client: &mut Client) {
+        let players = client
+            .players()
+            .filter(|name| name.starts_with("swarm") && *name != self.username)
+            .map(|name| name.to_owned())
+            .collect::<HashSet<_>>();
+        // Bots that logged out need to be made admins again
+        self.adminified.retain(|name| players.contains(name));
+        for name in players {
+            if !self.adminified.contains(&name) {
+                client.send_command("adminify".into(), vec![name.clone(), "admin".into()]);
+                self.stats.lock().unwrap().msgs_sent += 1;
+                self.adminified.insert(name);
+            }
+        }
+    }
+
+    /// Where the bot spawns relative to the center of the world
+    fn spawn_offset(&self) -> Vec2<f32> {
+        let size = self.scenario.bot_count().max(1);
+        let width = (size as f32).sqrt().round().max(1.0) as u32;
+
+        let spacing = match self.wave.spawn {
+            Spawn::Clustered => 5.0,
+            Spawn::Dispersed => {
+                use common::region::REGION_SIZE;
+                // Attempt to make regions subscribed to by each client not overlapping
+                self.scenario.view_distance as f32 * 2.0 * CHUNK_SIZE + 2.0 * REGION_SIZE as f32
+            },
+        };
+
+        // Offset to center the grid of clients
+        let offset = Vec2::new(width as f32, (size / width) as f32) * spacing / 2.0;
+        // Position clients in a grid
+        Vec2::new(
+            (self.index % width) as f32 * spacing,
+            (self.index / width) as f32 * spacing,
+        ) - offset
+    }
+}
+
+/// Position along a square route, large enough to load new chunks
+fn square_offset(secs: f32, vd: u32) -> Vec2<f32> {
+    // in blocks
+    let route_side_length = CHUNK_SIZE * vd as f32 * 3.0;
+    let route_length = route_side_length * 4.0;
+    // in secs
+    let route_time = route_length / SPEED;
+    let route_progress = (secs % route_time) / route_time;
+
+    // clockwise square
+    (match route_progress * 4.0 {
+        // going up left side
+        t if t < 1.0 => Vec2::new(0.0, 0.0 + t),
+        // going across top side
+        t if t < 2.0 => Vec2::new(0.0 + (t - 1.0), 1.0),
+        // going down right side
+        t if t < 3.0 => Vec2::new(1.0, 1.0 - (t - 2.0)),
+        // going across bottom
+        t => Vec2::new(1.0 - (t - 3.0), 0.0),
+    }) * route_side_length
+}
+
+/// Crafts a recipe if the bot has the ingredients, returns whether it did
+fn craft(client: &mut Client, recipe: &str, amount: u32) -> bool {
+    let slots = {
+        let inventories = client.inventories();
+        let rbm = client.state().ecs().read_resource::<RecipeBookManifest>();
+        inventories.get(client.entity()).and_then(|inventory| {
+            inventory
+                .get_recipe(recipe, &rbm)
+                .and_then(|recipe| recipe.inventory_contains_ingredients(inventory, 1).ok())
+        })
+    };
+    slots.is_some_and(|slots| client.craft_recipe(recipe, slots, None, amount))
+}
+
+fn body() -> comp::Body {
+    comp::body::humanoid::Body {
+        species: comp::body::humanoid::Species::Human,
+        body_type: comp::body::humanoid::BodyType::Male,
+        hair_style: 0,
+        beard: 0,
+        eyes: 0,
+        accessory: 0,
+        hair_color: 0,
+        skin: 0,
+        eye_color: 0,
+    }
+    .into()
+}
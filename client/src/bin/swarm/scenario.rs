@@ -0,0 +1,198 @@
+//! Scenarios describe when the bots of the swarm log in and what they do once
+//! in game. They are loaded from RON files, see `example.ron` next to this
+//! file.
+
+use serde::Deserialize;
+use std::{fs, path::Path};
+
+#[derive(Clone, Debug, Deserialize)]
+pub struct Scenario {
+    /// How long the scenario runs for, in seconds. It runs until the process
+    /// is stopped if `None`.
+    #[serde(default)]
+    pub duration_secs: Option<f32>,
+    /// View distance the bots log in with
+    pub view_distance: u32,
+    pub waves: Vec<Wave>,
+    #[serde(default)]
+    pub disconnect_storms: Vec<DisconnectStorm>,
+}
+
+/// A group of bots logging in one after another and repeating the same
+/// actions
+#[derive(Clone, Debug, Deserialize)]
+pub struct Wave {
+    pub bots: u32,
+    /// Seconds after the start of the scenario at which the first bot of the
+    /// wave logs in
+    #[serde(default)]
+    pub start_secs: f32,
+    /// Seconds between two bots of the wave logging in
+    #[serde(default)]
+    pub interval_secs: f32,
+    #[serde(default)]
+    pub spawn: Spawn,
+    /// Actions the bots perform in order, starting over after the last one
+    pub actions: Vec<Action>,
+}
+
+/// How the bots of a wave are laid out around the center of the world
+#[derive(Clone, Copy, Debug, Default, Deserialize)]
+pub enum Spawn {
+    /// Close to each other, so that they see each other
+    Clustered,
+    /// Far enough from each other that they don't share any region
+    #[default]
+    Dispersed,
+}
+
+#[derive(Clone, Debug, Deserialize)]
+pub enum Action {
+    /// Stand still
+    Idle {
+        secs: f32,
+    },
+    /// Walk between random points around where the bot spawned
+    Wander {
+        secs: f32,
+        radius: f32,
+    },
+    /// Walk in a square around where the bot spawned, large enough for it to
+    /// load new chunks
+    Square {
+        secs: f32,
+    },
+    /// Keep attacking with the primary ability
+    Fight {
+        secs: f32,
+    },
+    /// Craft a recipe, if the bot has the ingredients
+    Craft {
+        recipe: String,
+        amount: u32,
+    },
+    /// Send a chat message
+    Chat {
+        msg: String,
+    },
+    /// Run a chat command, e.g. to give the bot crafting ingredients
+    Command {
+        name: String,
+        args: Vec<String>,
+    },
+    /// Jump to a random position within a radius of the center of the world
+    Teleport {
+        radius: f32,
+    },
+    ViewDistance {
+        vd: u32,
+    },
+    /// Disconnect and log in again after some time
+    Disconnect {
+        reconnect_after_secs: f32,
+    },
+}
+
+impl Action {
+    /// How long the action lasts, actions without duration only take a tick
+    pub fn secs(&self) -> f32 {
+        match self {
+            Action::Idle { secs }
+            | Action::Wander { secs, .. }
+            | Action::Square { secs }
+            | Action::Fight { secs } => *secs,
+            Action::Craft { .. }
+            | Action::Chat { .. }
+            | Action::Command { .. }
+            | Action::Teleport { .. }
+            | Action::ViewDistance { .. }
+            | Action::Disconnect { .. } => 0.0,
+        }
+    }
+}
+
+/// Many bots disconnecting at the same time
+#[derive(Clone, Debug, Deserialize)]
+pub struct DisconnectStorm {
+    /// Seconds after the start of the scenario at which the bots disconnect
+    pub at_secs: f32,
+    /// Fraction of the bots that disconnect, from 0 to 1
+    pub fraction: f32,
+    pub reconnect_after_secs: f32,
+}
+
+impl DisconnectStorm {
+    /// Whether the bot with this index is among the ones that disconnect
+    pub fn affects(&self, storm: usize, bot: u32) -> bool {
+        noise(bot as u64, storm as u64) < self.fraction
+    }
+}
+
+impl Scenario {
+    pub fn load(path: &Path) -> Result<Self, String> {
+        let file = fs::File::open(path).map_err(|e| format!("{}: {e}", path.display()))?;
+        ron::de::from_reader(file).map_err(|e| format!("{}: {e}", path.display()))
+    }
+
+    /// The scenario run when no file is given: all bots log in at once and
+    /// either stand still or walk in squares forever.
+    pub fn simple(bots: u32, view_distance: u32, clustered: bool, movement: bool) -> Self {
+        let secs = f32::INFINITY;
+        Self {
+            duration_secs: None,
+            view_distance,
+            waves: vec![Wave {
+                bots,
+                start_secs: 0.0,
+                interval_secs: 0.0,
+                spawn: if clustered {
+                    Spawn::Clustered
+                } else {
+                    Spawn::Dispersed
+                },
+                actions: vec![if movement {
+                    Action::Square { secs }
+                } else {
+                    Action::Idle { secs }
+                }],
+            }],
+            disconnect_storms: Vec::new(),
+        }
+    }
+
+    pub fn bot_count(&self) -> u32 { self.waves.iter().map(|wave| wave.bots).sum() }
+
+    /// The wave of each bot and when it logs in, in seconds after the start,
+    /// by index
+    pub fn bots(&self) -> Vec<(&Wave, f32)> {
+        self.waves
+            .iter()
+            .flat_map(|wave| {
+                (0..wave.bots).map(move |i| (wave, wave.start_secs + i as f32 * wave.interval_secs))
+            })
+            .collect()
+    }
+}
+
+/// A deterministic pseudo-random number between 0 and 1
+pub fn noise(a: u64, b: u64) -> f32 {
+    // splitmix64
+    let mut x = a
+        .wrapping_mul(0x9E37_79B9_7F4A_7C15)
+        .wrapping_add(b.wrapping_mul(0xBF58_476D_1CE4_E5B9));
+    x = (x ^ (x >> 30)).wrapping_mul(0xBF58_476D_1CE4_E5B9);
+    x = (x ^ (x >> 27)).wrapping_mul(0x94D0_49BB_1331_11EB);
+    x ^= x >> 31;
+    (x >> 40) as f32 / (1u64 << 24) as f32
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn example_scenario_parses() {
+        let example = ron::de::from_str::<Scenario>(include_str!("example.ron")).unwrap();
+        assert_eq!(example.bots().len() as u32, example.bot_count());
+    }
+}
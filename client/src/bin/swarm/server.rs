@@ -0,0 +1,104 @@
+//! Server run in-process for the bots, like the singleplayer server of voxygen.
+//!
+//! The bots connect to it over the in-memory mpsc channel, so its network
+//! stack isn't part of what is measured.
+
+use common::{clock::Clock, comp::AdminRole};
+use server::{
+    EditableSettings, Error, Input, Server, Settings,
+    persistence::{DatabaseSettings, SqlLogMode},
+};
+use std::{
+    path::Path,
+    sync::{
+        Arc,
+        mpsc::{self, Receiver, Sender, TryRecvError},
+    },
+    thread::{self, JoinHandle},
+    time::Duration,
+};
+use tokio::runtime::Runtime;
+use veloren_client::addr::ConnectionArgs;
+
+const TPS: u64 = 30;
+
+/// Stops the server when dropped.
+pub struct InProcessServer {
+    stop: Sender<()>,
+    thread: Option<JoinHandle<()>>,
+}
+
+impl InProcessServer {
+    /// Starts a server on the data in `data_dir`, waiting until it is ready.
+    /// The first bot, "swarm0", is made an admin.
+    pub fn start(data_dir: &Path, runtime: Arc<Runtime>) -> Result<Self, Error> {
+        let settings = Settings {
+            gameserver_protocols: Vec::new(),
+            auth_server_address: None,
+            ..Settings::load(data_dir)
+        };
+        let editable_settings = EditableSettings::load(data_dir);
+        let database_settings = DatabaseSettings {
+            db_dir: data_dir.join("saves"),
+            sql_log_mode: SqlLogMode::Disabled,
+        };
+
+        let (stop, stop_rx) = mpsc::channel();
+        let (result_tx, result_rx) = mpsc::sync_channel(1);
+        let data_dir = data_dir.to_path_buf();
+        let thread = thread::Builder::new()
+            .name("swarm-server".into())
+            .spawn(move || {
+                match Server::new(
+                    settings,
+                    editable_settings,
+                    database_settings,
+                    &data_dir,
+                    &|_| {},
+                    runtime,
+                ) {
+                    Ok(mut server) => {
+                        server.add_admin("swarm0", AdminRole::Admin);
+                        let _ = result_tx.send(Ok(()));
+                        run(server, stop_rx);
+                    },
+                    Err(e) => {
+                        let _ = result_tx.send(Err(e));
+                    },
+                }
+            })
+            .expect("Failed to spawn the server thread");
+
+        result_rx
+            .recv()
+            .map_err(|_| Error::Other("Server thread stopped while starting".to_string()))??;
+        Ok(Self {
+            stop,
+            thread: Some(thread),
+        })
+    }
+
+    /// How the bots connect to the server.
+    pub fn connection_args(&self) -> ConnectionArgs { ConnectionArgs::Mpsc(server::MPSC_ADDR) }
+}
+
+impl Drop for InProcessServer {
+    fn drop(&mut self) {
+        let _ = self.stop.send(());
+        if let Some(thread) = self.thread.take() {
+            let _ = thread.join();
+        }
+    }
+}
+
+fn run(mut server: Server, stop: Receiver<()>) {
+    let mut clock = Clock::new(Duration::from_secs_f64(1.0 / TPS as f64));
+    while let Err(TryRecvError::Empty) = stop.try_recv() {
+        clock.tick();
+        if let Err(e) = server.tick(Input::default(), clock.dt()) {
+            tracing::error!(?e, "Server failed to tick");
+            break;
+        }
+        server.cleanup();
+    }
+}
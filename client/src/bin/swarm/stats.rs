@@ -0,0 +1,125 @@
+//! Statistics collected by each bot, and the report summarizing them
+
+use serde::Serialize;
+use std::fmt;
+
+#[derive(Clone, Debug, Default, Serialize)]
+pub struct BotStats {
+    pub username: String,
+    pub in_game: bool,
+    /// Seconds from connecting until being in game, for each login
+    pub login_secs: Vec<f32>,
+    /// Ping measured once a second while in game, in milliseconds
+    pub ping_ms: Vec<f32>,
+    /// Messages received from the server
+    pub msgs_received: u64,
+    /// Actions that sent a message to the server
+    pub msgs_sent: u64,
+    pub chat_msgs_received: u64,
+    pub disconnects: u32,
+    /// Failed logins and errors that disconnected the bot
+    pub errors: u32,
+}
+
+/// Summary of the statistics of all bots
+pub struct Report {
+    pub elapsed_secs: f32,
+    pub bots: usize,
+    pub in_game: usize,
+    pub login_secs: Distribution,
+    pub ping_ms: Distribution,
+    pub msgs_received: u64,
+    pub msgs_sent: u64,
+    pub chat_msgs_received: u64,
+    pub disconnects: u32,
+    pub errors: u32,
+}
+
+#[derive(Default)]
+pub struct Distribution {
+    pub count: usize,
+    pub mean: f32,
+    pub p50: f32,
+    pub p95: f32,
+    pub max: f32,
+}
+
+impl Distribution {
+    fn new(mut samples: Vec<f32>) -> Self {
+        if samples.is_empty() {
+            return Self::default();
+        }
+        samples.sort_by(f32::total_cmp);
+        let percentile = |p: f32| samples[((samples.len() - 1) as f32 * p).round() as usize];
+        Self {
+            count: samples.len(),
+            mean: samples.iter().sum::<f32>() / samples.len() as f32,
+            p50: percentile(0.5),
+            p95: percentile(0.95),
+            max: samples[samples.len() - 1],
+        }
+    }
+}
+
+impl Report {
+    pub fn new<'a>(elapsed_secs: f32, stats: impl IntoIterator<Item = &'a BotStats>) -> Self {
+        let stats = stats.into_iter().collect::<Vec<_>>();
+        Self {
+            elapsed_secs,
+            bots: stats.len(),
+            in_game: stats.iter().filter(|s| s.in_game).count(),
+            login_secs: Distribution::new(
+                stats
+                    .iter()
+                    .flat_map(|s| s.login_secs.iter().copied())
+                    .collect(),
+            ),
+            ping_ms: Distribution::new(
+                stats
+                    .iter()
+                    .flat_map(|s| s.ping_ms.iter().copied())
+                    .collect(),
+            ),
+            msgs_received: stats.iter().map(|s| s.msgs_received).sum(),
+            msgs_sent: stats.iter().map(|s| s.msgs_sent).sum(),
+            chat_msgs_received: stats.iter().map(|s| s.chat_msgs_received).sum(),
+            disconnects: stats.iter().map(|s| s.disconnects).sum(),
+            errors: stats.iter().map(|s| s.errors).sum(),
+        }
+    }
+}
+
+impl fmt::Display for Distribution {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            f,
+            "mean {:.1}, p50 {:.1}, p95 {:.1}, max {:.1} ({} samples)",
+            self.mean, self.p50, self.p95, self.max, self.count
+        )
+    }
+}
+
+impl fmt::Display for Report {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let per_sec = |count: u64| count as f32 / self.elapsed_secs.max(1.0);
+        writeln!(f, "Swarm report after {:.0}s", self.elapsed_secs)?;
+        writeln!(f, "  bots in game:   {}/{}", self.in_game, self.bots)?;
+        writeln!(f, "  login time (s): {}", self.login_secs)?;
+        writeln!(f, "  ping (ms):      {}", self.ping_ms)?;
+        writeln!(
+            f,
+            "  msgs received:  {} ({:.1}/s)",
+            self.msgs_received,
+            per_sec(self.msgs_received)
+        )?;
+        writeln!(
+            f,
+            "  msgs sent:      {} ({:.1}/s)",
+            self.msgs_sent,
+            per_sec(self.msgs_sent)
+        )?;
+        writeln!(f, "  chat received:  {}", self.chat_msgs_received)?;
+        writeln!(f, "  disconnects:    {}", self.disconnects)?;
+        write!(f, "  errors:         {}", self.errors)
+    }
+}
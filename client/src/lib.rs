@@ -25,6 +25,7 @@ use common::{
         group,
         inventory::item::{ItemKind, modular, tool},
         invite::{InviteKind, InviteResponse},
+        quest::QuestId,
         skills::Skill,
         slot::{EquipSlot, InvSlotId, Slot},
     },
@@ -317,6 +318,8 @@ pub struct Client {
     last_server_pong: f64,
     last_ping_delta: f64,
     ping_deltas: VecDeque<f64>,
+    /// Number of messages received from the server
+    received_msg_count: u64,
 
     tick: u64,
     state: State,
@@ -1068,6 +1071,7 @@ impl Client {
             last_server_pong: 0.0,
             last_ping_delta: 0.0,
             ping_deltas: VecDeque::new(),
+            received_msg_count: 0,
 
             tick: 0,
             state,
@@ -1770,6 +1774,12 @@ impl Client {
         self.send_msg(ClientGeneral::UnlockSkill(skill));
     }
 
+    pub fn abandon_quest(&mut self, quest_id: QuestId) {
+        self.send_msg(ClientGeneral::ControlEvent(ControlEvent::AbandonQuest(
+            quest_id,
+        )));
+    }
+
     pub fn max_group_size(&self) -> u32 { self.max_group_size }
 
     pub fn invite(&self) -> Option<(Uid, Instant, Duration, InviteKind)> { self.invite }
@@ -2453,6 +2463,9 @@ impl Client {
             }
 
             let mut current_tick_send_chunk_requests = 0;
+            // Players outside the map are in an instance hosted east of it, whose chunks
+            // have to be requested instead of being filled with the default chunk.
+            let in_instance = !self.state.terrain().map_size_lg().contains_chunk(chunk_pos);
             // Request chunks from the server.
             self.loaded_distance = ((view_distance * TerrainChunkSize::RECT_SIZE.x) as f32).powi(2);
             // +1 so we can find a chunk that's outside the vd for better fog
@@ -2489,7 +2502,12 @@ impl Client {
                             .distance_squared(pos.0.into());
 
                         let terrain = self.state.terrain();
-                        if let Some(chunk) = terrain.get_key_arc(*key) {
+                        let chunk = if in_instance {
+                            terrain.get_key_arc_real(*key)
+                        } else {
+                            terrain.get_key_arc(*key)
+                        };
+                        if let Some(chunk) = chunk {
                             if !skip_mode && !terrain.contains_key_real(*key) {
                                 let chunk = Arc::clone(chunk);
                                 drop(terrain);
@@ -3164,6 +3182,7 @@ impl Client {
         }
 
         let msg_count = self.handle_messages(&mut frontend_events)?;
+        self.received_msg_count += msg_count;
 
         if msg_count == 0
             && self.state.get_program_time() - self.last_server_pong
@@ -3203,6 +3222,9 @@ impl Client {
 
     pub fn get_ping_ms(&self) -> f64 { self.last_ping_delta * 1000.0 }
 
+    /// Number of messages received from the server since connecting.
+    pub fn received_msg_count(&self) -> u64 { self.received_msg_count }
+
     pub fn get_ping_ms_rolling_avg(&self) -> f64 {
         let mut total_weight = 0.;
         let pings = self.ping_deltas.len() as f64;
@@ -3360,7 +3382,7 @@ impl Client {
     /// many clients connected.
     #[cfg(feature = "tick_network")]
     #[expect(clippy::needless_collect)] // False positive
-    pub fn tick_network(&mut self, dt: Duration) -> Result<(), Error> {
+    pub fn tick_network(&mut self, dt: Duration) -> Result<Vec<Event>, Error> {
         span!(_guard, "tick_network", "Client::tick_network");
         // Advance state time manually since we aren't calling `State::tick`
         self.state
@@ -3378,7 +3400,7 @@ impl Client {
             .0 += dt.as_secs_f64() * time_scale.0;
 
         // Handle new messages from the server.
-        self.handle_new_messages()?;
+        let frontend_events = self.handle_new_messages()?;
 
         // 5) Terrain
         self.tick_terrain()?;
@@ -3424,7 +3446,7 @@ impl Client {
         // 7) Finish the tick, pass control back to the frontend.
         self.tick += 1;
 
-        Ok(())
+        Ok(frontend_events)
     }
 
     /// another plugin data received, is this the last one
@@ -95,6 +95,11 @@ impl CpuTimeline {
         )
     }
 
+    /// Start and end of the last run of the system.
+    pub fn span(&self) -> Option<(Instant, Instant)> {
+        Some((self.measures.first()?.0, self.measures.last()?.0))
+    }
+
     fn get(&self, time: Instant) -> ParMode {
         match self.measures.binary_search_by_key(&time, |&(a, _)| a) {
             Ok(id) => self.measures[id].1,
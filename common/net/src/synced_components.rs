@@ -70,6 +70,7 @@ macro_rules! synced_components {
             admin: Admin,
             combo: Combo,
             active_abilities: ActiveAbilities,
+            quest_log: QuestLog,
             can_build: CanBuild,
             is_interactor: IsInteractor,
             interactors: Interactors,
@@ -294,6 +295,10 @@ impl NetSync for ActiveAbilities {
     const SYNC_FROM: SyncFrom = SyncFrom::ClientEntity;
 }
 
+impl NetSync for QuestLog {
+    const SYNC_FROM: SyncFrom = SyncFrom::ClientEntity;
+}
+
 impl NetSync for CanBuild {
     const SYNC_FROM: SyncFrom = SyncFrom::ClientEntity;
 }
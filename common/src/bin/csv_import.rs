@@ -182,6 +182,7 @@ fn armor_stats() -> Result<(), Box<dyn Error>> {
                                 energy_reward,
                                 precision_power,
                                 stealth,
+                                resistances: None,
                                 ground_contact: Default::default(),
                             };
                             let armor = comp::item::armor::Armor::new(
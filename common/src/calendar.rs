@@ -1,6 +1,7 @@
 use chrono::{DateTime, Datelike, Local, TimeZone, Utc};
 use chrono_tz::Tz;
 use serde::{Deserialize, Serialize};
+use std::f32::consts::TAU;
 use strum::EnumIter;
 
 #[derive(Copy, Clone, Debug, PartialEq, Eq, Hash, Serialize, Deserialize, EnumIter)]
@@ -12,9 +13,71 @@ pub enum CalendarEvent {
     Easter = 3,
 }
 
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash, Serialize, Deserialize, EnumIter)]
+pub enum Season {
+    Spring,
+    Summer,
+    Autumn,
+    Winter,
+}
+
+/// How far the in-game year has progressed through its seasons.
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub struct SeasonCycle {
+    /// The in-game day of the year, counting from the first day of spring.
+    day: u32,
+    days_per_season: u32,
+}
+
+impl SeasonCycle {
+    /// How much warmer than usual the world gets in mid-summer, and how much
+    /// colder it gets in mid-winter.
+    pub const TEMPERATURE_AMPLITUDE: f32 = 0.3;
+
+    /// `time_of_day` is the in-game time, in seconds.
+    pub fn new(time_of_day: f64, days_per_season: u32) -> Self {
+        let days_per_season = days_per_season.max(1);
+        let day = (time_of_day / (24.0 * 3600.0))
+            .floor()
+            .rem_euclid(days_per_season as f64 * 4.0) as u32;
+        Self {
+            day,
+            days_per_season,
+        }
+    }
+
+    pub fn season(&self) -> Season {
+        match self.day / self.days_per_season {
+            0 => Season::Spring,
+            1 => Season::Summer,
+            2 => Season::Autumn,
+            _ => Season::Winter,
+        }
+    }
+
+    /// Progress through the current season, from `0.0` on its first day to
+    /// just under `1.0` on its last.
+    pub fn season_progress(&self) -> f32 {
+        (self.day % self.days_per_season) as f32 / self.days_per_season as f32
+    }
+
+    /// Progress through the year, from `0.0` on the first day of spring to
+    /// just under `1.0` on the last day of winter.
+    pub fn year_progress(&self) -> f32 { self.day as f32 / (self.days_per_season * 4) as f32 }
+
+    /// The change in temperature caused by the season, peaking in the middle
+    /// of summer and bottoming out in the middle of winter.
+    pub fn temperature_offset(&self) -> f32 {
+        (TAU * (self.year_progress() - 0.375)).cos() * Self::TEMPERATURE_AMPLITUDE
+    }
+}
+
 #[derive(Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
 pub struct Calendar {
     events: Vec<CalendarEvent>,
+    /// `None` if the server has seasons disabled.
+    #[serde(default)]
+    seasons: Option<SeasonCycle>,
 }
 
 impl Calendar {
@@ -24,7 +87,24 @@ impl Calendar {
         self.events.iter()
     }
 
-    pub fn from_events(events: Vec<CalendarEvent>) -> Self { Self { events } }
+    pub fn seasons(&self) -> Option<&SeasonCycle> { self.seasons.as_ref() }
+
+    pub fn season(&self) -> Option<Season> { self.seasons.map(|s| s.season()) }
+
+    /// The seasonal change in temperature, or `0.0` without seasons.
+    pub fn temperature_offset(&self) -> f32 { self.seasons.map_or(0.0, |s| s.temperature_offset()) }
+
+    pub fn with_seasons(mut self, seasons: Option<SeasonCycle>) -> Self {
+        self.seasons = seasons;
+        self
+    }
+
+    pub fn from_events(events: Vec<CalendarEvent>) -> Self {
+        Self {
+            events,
+            seasons: None,
+        }
+    }
 
     pub fn from_tz(tz: Option<Tz>) -> Self {
         let mut this = Self::default();
@@ -57,3 +137,22 @@ impl Calendar {
         this
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const DAY: f64 = 24.0 * 3600.0;
+
+    #[test]
+    fn seasons_cycle() {
+        let at_day = |day: f64| SeasonCycle::new(day * DAY + 9.0 * 3600.0, 3);
+        assert_eq!(at_day(0.0).season(), Season::Spring);
+        assert_eq!(at_day(3.0).season(), Season::Summer);
+        assert_eq!(at_day(8.0).season(), Season::Autumn);
+        assert_eq!(at_day(11.0).season(), Season::Winter);
+        assert_eq!(at_day(12.0).season(), Season::Spring);
+        assert!(at_day(4.0).temperature_offset() > 0.0);
+        assert!(at_day(10.0).temperature_offset() < 0.0);
+    }
+}
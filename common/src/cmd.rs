@@ -135,7 +135,7 @@ lazy_static! {
     .collect();
 
     static ref WEATHERS: Vec<String> = [
-        "clear", "cloudy", "rain", "wind", "storm"
+        "clear", "cloudy", "rain", "wind", "storm", "snow", "fog"
     ]
     .iter()
     .map(|s| s.to_string())
@@ -371,6 +371,7 @@ pub enum ServerChatCommand {
     BanIp,
     BattleMode,
     BattleModeForce,
+    BlockHistory,
     Body,
     Buff,
     Build,
@@ -388,6 +389,7 @@ pub enum ServerChatCommand {
     Dummy,
     Explosion,
     Faction,
+    Forecast,
     GiveItem,
     Goto,
     GotoRand,
@@ -397,6 +399,7 @@ pub enum ServerChatCommand {
     GroupLeave,
     GroupPromote,
     Health,
+    InstancePortal,
     IntoNpc,
     JoinFaction,
     Jump,
@@ -419,14 +422,17 @@ pub enum ServerChatCommand {
     PermitBuild,
     Players,
     Portal,
+    Profile,
     Region,
     ReloadChunks,
     RemoveLights,
     RepairEquipment,
     ResetRecipes,
     Respawn,
+    RestoreTerrain,
     RevokeBuild,
     RevokeBuildAll,
+    Rollback,
     RtsimChunk,
     RtsimInfo,
     RtsimNpc,
@@ -554,6 +560,11 @@ impl ServerChatCommand {
                 Content::localized("command-into_npc-desc"),
                 Some(Admin),
             ),
+            ServerChatCommand::BlockHistory => cmd(
+                vec![Integer("radius", 3, Optional)],
+                Content::localized("command-block_history-desc"),
+                Some(Admin),
+            ),
             ServerChatCommand::Body => cmd(
                 vec![Enum("body", ENTITIES.clone(), Required)],
                 Content::localized("command-body-desc"),
@@ -657,6 +668,9 @@ impl ServerChatCommand {
                 Content::localized("command-faction-desc"),
                 None,
             ),
+            ServerChatCommand::Forecast => {
+                cmd(vec![], Content::localized("command-forecast-desc"), None)
+            },
             ServerChatCommand::GiveItem => cmd(
                 vec![
                     AssetPath("item", "common.items.", ITEM_SPECS.clone(), Required),
@@ -708,6 +722,18 @@ impl ServerChatCommand {
                 Content::localized("command-health-desc"),
                 Some(Admin),
             ),
+            ServerChatCommand::InstancePortal => cmd(
+                vec![
+                    Enum(
+                        "kind",
+                        vec!["arena".to_string(), "dungeon".to_string()],
+                        Required,
+                    ),
+                    SiteName(Optional),
+                ],
+                Content::localized("command-instance_portal-desc"),
+                Some(Admin),
+            ),
             ServerChatCommand::Respawn => cmd(
                 vec![],
                 Content::localized("command-respawn-desc"),
@@ -825,6 +851,11 @@ impl ServerChatCommand {
                 Content::localized("command-portal-desc"),
                 Some(Admin),
             ),
+            ServerChatCommand::Profile => cmd(
+                vec![Integer("count", 5, Optional)],
+                Content::localized("command-profile-desc"),
+                Some(Admin),
+            ),
             ServerChatCommand::ReloadChunks => cmd(
                 vec![Integer("chunk_radius", 6, Optional)],
                 Content::localized("command-reload_chunks-desc"),
@@ -840,6 +871,18 @@ impl ServerChatCommand {
                 Content::localized("command-remove_lights-desc"),
                 Some(Admin),
             ),
+            ServerChatCommand::RestoreTerrain => cmd(
+                vec![
+                    Integer("xlo", 0, Required),
+                    Integer("xhi", 10, Required),
+                    Integer("ylo", 0, Required),
+                    Integer("yhi", 10, Required),
+                    Integer("zlo", 0, Required),
+                    Integer("zhi", 10, Required),
+                ],
+                Content::localized("command-restore_terrain-desc"),
+                Some(Admin),
+            ),
             ServerChatCommand::RevokeBuild => cmd(
                 vec![Any("area_name", Required)],
                 Content::localized("command-revoke_build-desc"),
@@ -994,6 +1037,15 @@ impl ServerChatCommand {
                 Content::localized("command-rtsim_purge-desc"),
                 Some(Admin),
             ),
+            ServerChatCommand::Rollback => cmd(
+                vec![
+                    PlayerName(Required),
+                    Integer("radius", 32, Optional),
+                    Integer("minutes", 60, Optional),
+                ],
+                Content::localized("command-rollback-desc"),
+                Some(Admin),
+            ),
             ServerChatCommand::RtsimChunk => cmd(
                 vec![],
                 Content::localized("command-rtsim_chunk-desc"),
@@ -1119,6 +1171,7 @@ impl ServerChatCommand {
             ServerChatCommand::BanIp => "ban_ip",
             ServerChatCommand::BattleMode => "battlemode",
             ServerChatCommand::BattleModeForce => "battlemode_force",
+            ServerChatCommand::BlockHistory => "block_history",
             ServerChatCommand::Body => "body",
             ServerChatCommand::Buff => "buff",
             ServerChatCommand::Build => "build",
@@ -1132,6 +1185,7 @@ impl ServerChatCommand {
             ServerChatCommand::Dummy => "dummy",
             ServerChatCommand::Explosion => "explosion",
             ServerChatCommand::Faction => "faction",
+            ServerChatCommand::Forecast => "forecast",
             ServerChatCommand::GiveItem => "give_item",
             ServerChatCommand::Goto => "goto",
             ServerChatCommand::GotoRand => "goto_rand",
@@ -1141,6 +1195,7 @@ impl ServerChatCommand {
             ServerChatCommand::GroupLeave => "group_leave",
             ServerChatCommand::GroupPromote => "group_promote",
             ServerChatCommand::Health => "health",
+            ServerChatCommand::InstancePortal => "instance_portal",
             ServerChatCommand::IntoNpc => "into_npc",
             ServerChatCommand::JoinFaction => "join_faction",
             ServerChatCommand::Jump => "jump",
@@ -1150,6 +1205,7 @@ impl ServerChatCommand {
             ServerChatCommand::Kit => "kit",
             ServerChatCommand::Lantern => "lantern",
             ServerChatCommand::Respawn => "respawn",
+            ServerChatCommand::RestoreTerrain => "restore_terrain",
             ServerChatCommand::Light => "light",
             ServerChatCommand::MakeBlock => "make_block",
             ServerChatCommand::MakeNpc => "make_npc",
@@ -1160,6 +1216,7 @@ impl ServerChatCommand {
             ServerChatCommand::PermitBuild => "permit_build",
             ServerChatCommand::Players => "players",
             ServerChatCommand::Portal => "portal",
+            ServerChatCommand::Profile => "profile",
             ServerChatCommand::ResetRecipes => "reset_recipes",
             ServerChatCommand::Region => "region",
             ServerChatCommand::ReloadChunks => "reload_chunks",
@@ -1185,6 +1242,7 @@ impl ServerChatCommand {
             ServerChatCommand::RtsimInfo => "rtsim_info",
             ServerChatCommand::RtsimNpc => "rtsim_npc",
             ServerChatCommand::RtsimPurge => "rtsim_purge",
+            ServerChatCommand::Rollback => "rollback",
             ServerChatCommand::RtsimChunk => "rtsim_chunk",
             ServerChatCommand::Unban => "unban",
             ServerChatCommand::UnbanIp => "unban_ip",
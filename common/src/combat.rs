@@ -8,7 +8,7 @@ use crate::{
         inventory::{
             item::{
                 ItemDesc, ItemKind, MaterialStatManifest,
-                armor::Protection,
+                armor::{self, Protection},
                 tool::{self, ToolKind},
             },
             slot::EquipSlot,
@@ -29,7 +29,7 @@ use crate::{
 use rand::Rng;
 use serde::{Deserialize, Serialize};
 use specs::{Entity as EcsEntity, ReadStorage};
-use std::ops::{Mul, MulAssign};
+use std::ops::{Add, Mul, MulAssign, Sub};
 use vek::*;
 
 #[derive(Copy, Clone, Debug, Eq, PartialEq, Serialize, Deserialize)]
@@ -362,7 +362,9 @@ impl Attack {
                 attacker.map(|x| x.into()),
                 precision_mult,
                 self.precision_multiplier,
-                strength_modifier * damage_modifier,
+                strength_modifier
+                    * damage_modifier
+                    * damage.damage.kind.target_modifier(target.buffs),
                 time,
                 damage_instance,
             );
@@ -388,6 +390,7 @@ impl Attack {
                                     amount: -health_damage,
                                     by: attacker.map(|x| x.into()),
                                     cause: Some(damage.damage.source),
+                                    kind: Some(damage.damage.kind),
                                     time,
                                     precise: precision_mult.is_some(),
                                     instance: damage_instance,
@@ -440,6 +443,7 @@ impl Attack {
                                     amount: health_change,
                                     by: attacker.map(|x| x.into()),
                                     cause: Some(damage.damage.source),
+                                    kind: Some(damage.damage.kind),
                                     instance: damage_instance,
                                     precise: precision_mult.is_some(),
                                     time,
@@ -457,8 +461,15 @@ impl Attack {
                         }
                     },
                     // Piercing damage ignores some penetration, and is handled when damage
-                    // reduction is computed Energy is a placeholder damage type
-                    DamageKind::Piercing | DamageKind::Energy => {},
+                    // reduction is computed Energy is a placeholder damage type. Elemental
+                    // damage is handled when damage reduction is computed.
+                    DamageKind::Piercing
+                    | DamageKind::Energy
+                    | DamageKind::Fire
+                    | DamageKind::Frost
+                    | DamageKind::Poison
+                    | DamageKind::Lightning
+                    | DamageKind::Arcane => {},
                 }
                 for effect in damage.effects.iter() {
                     match effect {
@@ -505,6 +516,7 @@ impl Attack {
                                     amount: applied_damage * l,
                                     by: attacker.map(|a| a.into()),
                                     cause: None,
+                                    kind: None,
                                     time,
                                     precise: false,
                                     instance: rand::random(),
@@ -547,6 +559,7 @@ impl Attack {
                                 amount: *h * strength_modifier,
                                 by: attacker.map(|a| a.into()),
                                 cause: None,
+                                kind: None,
                                 time,
                                 precise: false,
                                 instance: rand::random(),
@@ -758,6 +771,7 @@ impl Attack {
                                 amount: accumulated_damage * l,
                                 by: attacker.map(|a| a.into()),
                                 cause: None,
+                                kind: None,
                                 time,
                                 precise: false,
                                 instance: rand::random(),
@@ -800,6 +814,7 @@ impl Attack {
                             amount: h * strength_modifier,
                             by: attacker.map(|a| a.into()),
                             cause: None,
+                            kind: None,
                             time,
                             precise: false,
                             instance: rand::random(),
@@ -1218,11 +1233,127 @@ pub enum DamageKind {
     /// Catch all for remaining damage kinds (TODO: differentiate further with
     /// staff/sceptre reworks
     Energy,
+    /// Elemental, reduced against wet targets
+    Fire,
+    /// Elemental
+    Frost,
+    /// Elemental
+    Poison,
+    /// Elemental, increased against wet targets
+    Lightning,
+    /// Elemental
+    Arcane,
+}
+
+impl DamageKind {
+    /// Elemental damage is mitigated by the target's resistance to that
+    /// element on top of armour protection
+    pub fn is_elemental(self) -> bool {
+        match self {
+            DamageKind::Piercing
+            | DamageKind::Slashing
+            | DamageKind::Crushing
+            | DamageKind::Energy => false,
+            DamageKind::Fire
+            | DamageKind::Frost
+            | DamageKind::Poison
+            | DamageKind::Lightning
+            | DamageKind::Arcane => true,
+        }
+    }
+
+    /// Multiplier to damage of this kind caused by the condition of the target,
+    /// e.g. wet targets conduct lightning but are hard to set alight
+    pub fn target_modifier(self, target_buffs: Option<&Buffs>) -> f32 {
+        let wet = target_buffs.is_some_and(|buffs| buffs.contains(BuffKind::Wet));
+        match self {
+            DamageKind::Fire if wet => WET_FIRE_MULTIPLIER,
+            DamageKind::Lightning if wet => WET_LIGHTNING_MULTIPLIER,
+            _ => 1.0,
+        }
+    }
 }
 
 const PIERCING_PENETRATION_FRACTION: f32 = 0.5;
 const SLASHING_ENERGY_FRACTION: f32 = 0.5;
 const CRUSHING_POISE_FRACTION: f32 = 1.0;
+const WET_FIRE_MULTIPLIER: f32 = 0.5;
+const WET_LIGHTNING_MULTIPLIER: f32 = 1.5;
+/// Chance per second, at full rain, that a burning entity is put out
+const RAIN_EXTINGUISH_RATE: f32 = 0.5;
+
+/// Chance that rain of the given intensity puts out a burning entity that
+/// isn't sheltered during a tick of `dt` seconds
+pub fn rain_extinguish_chance(rain: f32, dt: f32) -> f32 {
+    (dt * rain * RAIN_EXTINGUISH_RATE).clamp(0.0, 1.0)
+}
+
+/// Resistance to each of the elemental damage kinds. Like protection,
+/// resistances are summed and then non-linearly transformed to a damage
+/// reduction using (res / (60 + res))
+#[derive(Copy, Clone, Debug, Default, PartialEq, Serialize, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct Resistances {
+    pub fire: f32,
+    pub frost: f32,
+    pub poison: f32,
+    pub lightning: f32,
+    pub arcane: f32,
+}
+
+impl Resistances {
+    /// Resistance to the given damage kind, `None` if the kind is not
+    /// elemental
+    pub fn get(&self, kind: DamageKind) -> Option<f32> {
+        match kind {
+            DamageKind::Fire => Some(self.fire),
+            DamageKind::Frost => Some(self.frost),
+            DamageKind::Poison => Some(self.poison),
+            DamageKind::Lightning => Some(self.lightning),
+            DamageKind::Arcane => Some(self.arcane),
+            DamageKind::Piercing
+            | DamageKind::Slashing
+            | DamageKind::Crushing
+            | DamageKind::Energy => None,
+        }
+    }
+
+    fn map2(self, other: Self, f: impl Fn(f32, f32) -> f32) -> Self {
+        Self {
+            fire: f(self.fire, other.fire),
+            frost: f(self.frost, other.frost),
+            poison: f(self.poison, other.poison),
+            lightning: f(self.lightning, other.lightning),
+            arcane: f(self.arcane, other.arcane),
+        }
+    }
+}
+
+impl Add for Resistances {
+    type Output = Self;
+
+    fn add(self, other: Self) -> Self::Output { self.map2(other, |a, b| a + b) }
+}
+
+impl Sub for Resistances {
+    type Output = Self;
+
+    fn sub(self, other: Self) -> Self::Output { self.map2(other, |a, b| a - b) }
+}
+
+impl Mul<f32> for Resistances {
+    type Output = Self;
+
+    fn mul(self, val: f32) -> Self::Output {
+        Self {
+            fire: self.fire * val,
+            frost: self.frost * val,
+            poison: self.poison * val,
+            lightning: self.lightning * val,
+            arcane: self.arcane * val,
+        }
+    }
+}
 
 #[derive(Copy, Clone, Debug, PartialEq, Serialize, Deserialize)]
 #[serde(deny_unknown_fields)]
@@ -1254,9 +1385,16 @@ impl Damage {
 
         let protection = protection.map(|p| p - penetration);
 
+        // Elemental damage is mitigated by resistance to the element on top of
+        // protection
+        let mitigation = match damage.map(|d| d.kind).filter(|kind| kind.is_elemental()) {
+            Some(kind) => protection.map(|p| p + compute_resistance(kind, inventory, stats, msm)),
+            None => protection,
+        };
+
         const FIFTY_PERCENT_DR_THRESHOLD: f32 = 60.0;
 
-        let inventory_dr = match protection {
+        let inventory_dr = match mitigation {
             Some(dr) => dr / (FIFTY_PERCENT_DR_THRESHOLD + dr.abs()),
             None => 1.0,
         };
@@ -1304,6 +1442,7 @@ impl Damage {
                     amount: -damage,
                     by: damage_contributor,
                     cause: Some(self.source),
+                    kind: Some(self.kind),
                     time,
                     precise: precision_mult.is_some(),
                     instance,
@@ -1318,6 +1457,7 @@ impl Damage {
                     amount: -damage,
                     by: None,
                     cause: Some(self.source),
+                    kind: Some(self.kind),
                     time,
                     precise: false,
                     instance,
@@ -1327,6 +1467,7 @@ impl Damage {
                 amount: -damage,
                 by: None,
                 cause: Some(self.source),
+                kind: Some(self.kind),
                 time,
                 precise: false,
                 instance,
@@ -1632,22 +1773,36 @@ pub fn combat_rating(
     combined_rating * body.combat_multiplier()
 }
 
+/// Iterates over the stats of all equipped armour, followed by any stats
+/// granted by active armour set bonuses
+pub fn equipped_armor_stats<'a>(
+    inv: &'a Inventory,
+    msm: &'a MaterialStatManifest,
+) -> impl Iterator<Item = armor::Stats> + 'a {
+    let set_pieces = inv.armor_set_pieces();
+    let set_bonus_stats = armor::active_set_bonuses(&set_pieces, msm)
+        .filter_map(|(_, bonus)| bonus.stats())
+        .collect::<Vec<_>>();
+
+    inv.equipped_items()
+        .filter_map(move |item| {
+            if let ItemKind::Armor(armor) = &*item.kind() {
+                Some(armor.stats(msm, item.stats_durability_multiplier()))
+            } else {
+                None
+            }
+        })
+        .chain(set_bonus_stats)
+}
+
 pub fn compute_precision_mult(inventory: Option<&Inventory>, msm: &MaterialStatManifest) -> f32 {
     // Starts with a value of 0.1 when summing the stats from each armor piece, and
     // defaults to a value of 0.1 if no inventory is equipped. Precision multiplier
     // cannot go below 1
     1.0 + inventory
         .map_or(0.1, |inv| {
-            inv.equipped_items()
-                .filter_map(|item| {
-                    if let ItemKind::Armor(armor) = &*item.kind() {
-                        armor
-                            .stats(msm, item.stats_durability_multiplier())
-                            .precision_power
-                    } else {
-                        None
-                    }
-                })
+            equipped_armor_stats(inv, msm)
+                .filter_map(|stats| stats.precision_power)
                 .fold(0.1, |a, b| a + b)
         })
         .max(0.0)
@@ -1658,16 +1813,8 @@ pub fn compute_energy_reward_mod(inventory: Option<&Inventory>, msm: &MaterialSt
     // Starts with a value of 1.0 when summing the stats from each armor piece, and
     // defaults to a value of 1.0 if no inventory is present
     inventory.map_or(1.0, |inv| {
-        inv.equipped_items()
-            .filter_map(|item| {
-                if let ItemKind::Armor(armor) = &*item.kind() {
-                    armor
-                        .stats(msm, item.stats_durability_multiplier())
-                        .energy_reward
-                } else {
-                    None
-                }
-            })
+        equipped_armor_stats(inv, msm)
+            .filter_map(|stats| stats.energy_reward)
             .fold(1.0, |a, b| a + b)
     })
 }
@@ -1677,16 +1824,8 @@ pub fn compute_energy_reward_mod(inventory: Option<&Inventory>, msm: &MaterialSt
 pub fn compute_max_energy_mod(inventory: Option<&Inventory>, msm: &MaterialStatManifest) -> f32 {
     // Defaults to a value of 0 if no inventory is present
     inventory.map_or(0.0, |inv| {
-        inv.equipped_items()
-            .filter_map(|item| {
-                if let ItemKind::Armor(armor) = &*item.kind() {
-                    armor
-                        .stats(msm, item.stats_durability_multiplier())
-                        .energy_max
-                } else {
-                    None
-                }
-            })
+        equipped_armor_stats(inv, msm)
+            .filter_map(|stats| stats.energy_max)
             .sum()
     })
 }
@@ -1713,14 +1852,8 @@ pub fn stealth_multiplier_from_items(
     msm: &MaterialStatManifest,
 ) -> f32 {
     let stealth_sum = inventory.map_or(0.0, |inv| {
-        inv.equipped_items()
-            .filter_map(|item| {
-                if let ItemKind::Armor(armor) = &*item.kind() {
-                    armor.stats(msm, item.stats_durability_multiplier()).stealth
-                } else {
-                    None
-                }
-            })
+        equipped_armor_stats(inv, msm)
+            .filter_map(|stats| stats.stealth)
             .sum()
     });
 
@@ -1735,16 +1868,8 @@ pub fn compute_protection(
     msm: &MaterialStatManifest,
 ) -> Option<f32> {
     inventory.map_or(Some(0.0), |inv| {
-        inv.equipped_items()
-            .filter_map(|item| {
-                if let ItemKind::Armor(armor) = &*item.kind() {
-                    armor
-                        .stats(msm, item.stats_durability_multiplier())
-                        .protection
-                } else {
-                    None
-                }
-            })
+        equipped_armor_stats(inv, msm)
+            .filter_map(|stats| stats.protection)
             .map(|protection| match protection {
                 Protection::Normal(protection) => Some(protection),
                 Protection::Invincible => None,
@@ -1753,6 +1878,28 @@ pub fn compute_protection(
     })
 }
 
+/// Computes the total resistance to an elemental damage kind, from both worn
+/// armor and the base resistances of the body. Non-elemental damage kinds
+/// have no resistance.
+pub fn compute_resistance(
+    kind: DamageKind,
+    inventory: Option<&Inventory>,
+    stats: Option<&Stats>,
+    msm: &MaterialStatManifest,
+) -> f32 {
+    let armor_resistance = inventory.map_or(0.0, |inv| {
+        equipped_armor_stats(inv, msm)
+            .filter_map(|stats| stats.resistances)
+            .filter_map(|resistances| resistances.get(kind))
+            .sum()
+    });
+    let body_resistance = stats
+        .and_then(|stats| stats.original_body.base_resistances().get(kind))
+        .unwrap_or(0.0);
+
+    armor_resistance + body_resistance
+}
+
 /// Computes the total resilience provided from armor. Is used to determine the
 /// reduction applied to poise damage received by an entity. None indicates that
 /// the armor equipped makes the entity invulnerable to poise damage.
@@ -1761,16 +1908,8 @@ pub fn compute_poise_resilience(
     msm: &MaterialStatManifest,
 ) -> Option<f32> {
     inventory.map_or(Some(0.0), |inv| {
-        inv.equipped_items()
-            .filter_map(|item| {
-                if let ItemKind::Armor(armor) = &*item.kind() {
-                    armor
-                        .stats(msm, item.stats_durability_multiplier())
-                        .poise_resilience
-                } else {
-                    None
-                }
-            })
+        equipped_armor_stats(inv, msm)
+            .filter_map(|stats| stats.poise_resilience)
             .map(|protection| match protection {
                 Protection::Normal(protection) => Some(protection),
                 Protection::Invincible => None,
@@ -1877,3 +2016,109 @@ pub fn get_equip_slot_by_block_priority(inventory: Option<&Inventory>) -> EquipS
             },
         )
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        assets::AssetExt,
+        comp::body::{golem, humanoid},
+    };
+
+    fn fire(value: f32) -> Damage {
+        Damage {
+            source: DamageSource::Melee,
+            kind: DamageKind::Fire,
+            value,
+        }
+    }
+
+    #[test]
+    fn resistances_add_to_protection() {
+        let msm = &MaterialStatManifest::load().read();
+        let human = Stats::empty(Body::Humanoid(humanoid::Body::random()));
+        let golem = Stats::empty(Body::Golem(golem::Body {
+            species: golem::Species::IronGolem,
+            body_type: golem::BodyType::Male,
+        }));
+
+        // Without resistance, elemental damage is reduced like other damage
+        let physical = Damage::compute_damage_reduction(None, None, Some(&human), msm);
+        let elemental = Damage::compute_damage_reduction(Some(fire(10.0)), None, Some(&human), msm);
+        assert_eq!(physical, elemental);
+
+        // A body immune to burning resists fire, but not other damage
+        let resisted = Damage::compute_damage_reduction(Some(fire(10.0)), None, Some(&golem), msm);
+        let frost = Damage::compute_damage_reduction(
+            Some(Damage {
+                kind: DamageKind::Frost,
+                ..fire(10.0)
+            }),
+            None,
+            Some(&golem),
+            msm,
+        );
+        assert!(resisted > elemental);
+        assert_eq!(frost, elemental);
+        assert!((resisted - 120.0 / 180.0).abs() < 0.001);
+    }
+
+    #[test]
+    fn resistances_only_for_elements() {
+        let resistances = Resistances {
+            fire: 10.0,
+            lightning: 5.0,
+            ..Default::default()
+        };
+        assert_eq!(resistances.get(DamageKind::Fire), Some(10.0));
+        assert_eq!(resistances.get(DamageKind::Lightning), Some(5.0));
+        assert_eq!(resistances.get(DamageKind::Frost), Some(0.0));
+        assert_eq!(resistances.get(DamageKind::Slashing), None);
+        assert_eq!((resistances + resistances * 2.0).fire, 30.0);
+    }
+
+    #[test]
+    fn wet_targets() {
+        let mut buffs = Buffs::default();
+        for kind in [
+            DamageKind::Fire,
+            DamageKind::Lightning,
+            DamageKind::Slashing,
+        ] {
+            assert_eq!(kind.target_modifier(Some(&buffs)), 1.0);
+            assert_eq!(kind.target_modifier(None), 1.0);
+        }
+
+        let time = Time(0.0);
+        buffs.insert(
+            Buff::new(
+                BuffKind::Wet,
+                BuffData::new(1.0, Some(Secs(10.0))),
+                Vec::new(),
+                BuffSource::Unknown,
+                time,
+                DestInfo::default(),
+                None,
+            ),
+            time,
+        );
+        assert_eq!(
+            DamageKind::Fire.target_modifier(Some(&buffs)),
+            WET_FIRE_MULTIPLIER
+        );
+        assert_eq!(
+            DamageKind::Lightning.target_modifier(Some(&buffs)),
+            WET_LIGHTNING_MULTIPLIER
+        );
+        assert_eq!(DamageKind::Slashing.target_modifier(Some(&buffs)), 1.0);
+    }
+
+    #[test]
+    fn rain_puts_out_fire() {
+        assert_eq!(rain_extinguish_chance(0.0, 0.1), 0.0);
+        assert_eq!(rain_extinguish_chance(1.0, 0.1), 0.1 * RAIN_EXTINGUISH_RATE);
+        assert!(rain_extinguish_chance(1.0, 0.1) > rain_extinguish_chance(0.5, 0.1));
+        // Long ticks never go above certainty
+        assert_eq!(rain_extinguish_chance(1.0, 100.0), 1.0);
+    }
+}
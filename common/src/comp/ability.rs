@@ -1193,6 +1193,7 @@ impl Default for CharacterAbility {
                 multi_target: None,
                 damage_effect: None,
                 attack_effect: None,
+                elemental_damage: None,
                 simultaneous_hits: 1,
                 custom_combo: CustomCombo {
                     base: None,
@@ -28,7 +28,7 @@ const SECONDS_BEFORE_FORGET_SOUNDS: f64 = 180.0;
 
 /// The number of timers that a single Action node can track concurrently
 /// Define constants within a given action node to index between them.
-const ACTIONSTATE_NUMBER_OF_CONCURRENT_TIMERS: usize = 5;
+pub const ACTIONSTATE_NUMBER_OF_CONCURRENT_TIMERS: usize = 5;
 /// The number of float counters that a single Action node can track
 /// concurrently Define constants within a given action node to index between
 /// them.
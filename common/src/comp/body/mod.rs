@@ -21,6 +21,7 @@ pub mod theropod;
 
 use crate::{
     assets::{self, Asset},
+    combat::Resistances,
     consts::{HUMAN_DENSITY, WATER_DENSITY},
     npc::NpcKind,
 };
@@ -1292,6 +1293,27 @@ impl Body {
         }
     }
 
+    /// Elemental resistances the body has before any armor is taken into
+    /// account. Bodies immune to an element's debuff also resist its damage.
+    pub fn base_resistances(&self) -> Resistances {
+        const IMMUNE_RESISTANCE: f32 = 120.0;
+        let resistance = |buff| {
+            if self.immune_to(buff) {
+                IMMUNE_RESISTANCE
+            } else {
+                0.0
+            }
+        };
+
+        Resistances {
+            fire: resistance(BuffKind::Burning),
+            frost: resistance(BuffKind::Frozen),
+            poison: resistance(BuffKind::Poisoned),
+            lightning: 0.0,
+            arcane: 0.0,
+        }
+    }
+
     /// Returns a multiplier representing increased difficulty not accounted for
     /// due to AI or not using an actual weapon
     // TODO: Match on species
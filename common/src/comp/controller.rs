@@ -6,6 +6,7 @@ use crate::{
             slot::{EquipSlot, InvSlotId, Slot},
         },
         invite::{InviteKind, InviteResponse},
+        quest::QuestId,
     },
     mounting::VolumePos,
     rtsim,
@@ -175,6 +176,7 @@ pub enum ControlEvent {
         kind: crate::interaction::InteractionKind,
     },
     Dialogue(Uid, rtsim::Dialogue),
+    AbandonQuest(QuestId),
 }
 
 #[derive(Clone, Copy, Debug, PartialEq, Serialize, Deserialize)]
@@ -1,4 +1,4 @@
-use crate::{DamageSource, combat::DamageContributor, comp, resources::Time, uid::Uid};
+use crate::{DamageKind, DamageSource, combat::DamageContributor, comp, resources::Time, uid::Uid};
 use hashbrown::HashMap;
 use serde::{Deserialize, Serialize};
 use specs::{Component, DerefFlaggedStorage};
@@ -15,6 +15,8 @@ pub struct HealthChange {
     pub by: Option<DamageContributor>,
     /// The category of action that resulted in the health change
     pub cause: Option<DamageSource>,
+    /// The kind of damage that resulted in the health change, if any
+    pub kind: Option<DamageKind>,
     /// The time that the health change occurred at
     pub time: Time,
     /// A boolean that tells you if the change was a precsie hit
@@ -143,6 +145,7 @@ impl Health {
                 amount: 0.0,
                 by: None,
                 cause: None,
+                kind: None,
                 precise: false,
                 time: Time(0.0),
                 instance: rand::random(),
@@ -240,6 +243,7 @@ impl Health {
                 amount: 0.0,
                 by: None,
                 cause: None,
+                kind: None,
                 precise: false,
                 time: Time(0.0),
                 instance: rand::random(),
@@ -291,6 +295,7 @@ mod tests {
             time: Time(123.0),
             by: Some(damage_contrib),
             cause: None,
+            kind: None,
             precise: false,
             instance: rand::random(),
         };
@@ -318,6 +323,7 @@ mod tests {
             time: Time(123.0),
             by: Some(damage_contrib),
             cause: None,
+            kind: None,
             precise: false,
             instance: rand::random(),
         };
@@ -339,6 +345,7 @@ mod tests {
             time: Time(123.0),
             by: Some(damage_contrib),
             cause: None,
+            kind: None,
             precise: false,
             instance: rand::random(),
         };
@@ -366,6 +373,7 @@ mod tests {
             time: Time(10.0),
             by: Some(damage_contrib1),
             cause: None,
+            kind: None,
             precise: false,
             instance: rand::random(),
         };
@@ -377,6 +385,7 @@ mod tests {
             time: Time(100.0),
             by: Some(damage_contrib2),
             cause: None,
+            kind: None,
             precise: false,
             instance: rand::random(),
         };
@@ -392,6 +401,7 @@ mod tests {
             time: Time(620.0),
             by: Some(damage_contrib2),
             cause: None,
+            kind: None,
             precise: false,
             instance: rand::random(),
         };
@@ -1,7 +1,12 @@
 use crate::{
-    comp::item::{DurabilityMultiplier, MaterialStatManifest, Rgb},
+    combat::Resistances,
+    comp::{
+        BuffKind,
+        item::{DurabilityMultiplier, Item, ItemKind, MaterialStatManifest, Rgb},
+    },
     terrain::{Block, BlockKind},
 };
+use hashbrown::HashMap;
 use serde::{Deserialize, Serialize};
 use std::{
     cmp::Ordering,
@@ -119,6 +124,10 @@ pub struct Stats {
     /// Stealth is summed along with the base stealth bonus (2.0), and then
     /// the agent's perception distance is divided by this value
     pub stealth: Option<f32>,
+    /// Elemental resistances are summed per element, and then non-linearly
+    /// transformed to a reduction of damage of that element in the same way
+    /// as protection
+    pub resistances: Option<Resistances>,
     /// Ground contact type, mostly for shoes
     #[serde(default)]
     pub ground_contact: Friction,
@@ -133,6 +142,7 @@ impl Stats {
             energy_reward: None,
             precision_power: None,
             stealth: None,
+            resistances: None,
             ground_contact: Friction::Normal,
         }
     }
@@ -144,6 +154,64 @@ pub enum StatsSource {
     FromSet(String),
 }
 
+/// A bonus that is granted while enough pieces of the same armour set are
+/// equipped at once
+#[derive(Clone, Copy, Debug, PartialEq, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct SetBonus {
+    /// Number of pieces from the set that need to be equipped for the bonus
+    /// to be active
+    pub pieces: u32,
+    pub effect: SetBonusEffect,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Serialize, Deserialize)]
+pub enum SetBonusEffect {
+    /// Additional armour stats, summed together with the stats of the equipped
+    /// armour pieces
+    Stats(Stats),
+    /// The effects of the buff are applied for as long as the bonus is active
+    Buff { kind: BuffKind, strength: f32 },
+}
+
+impl SetBonus {
+    pub fn is_active(&self, equipped_pieces: u32) -> bool { equipped_pieces >= self.pieces }
+
+    pub fn stats(&self) -> Option<Stats> {
+        match self.effect {
+            SetBonusEffect::Stats(stats) => Some(stats),
+            SetBonusEffect::Buff { .. } => None,
+        }
+    }
+}
+
+/// Counts how many pieces of each armour set are in `items`
+pub fn set_piece_counts<'a>(items: impl Iterator<Item = &'a Item>) -> HashMap<String, u32> {
+    let mut counts = HashMap::new();
+    for item in items {
+        if let ItemKind::Armor(armor) = &*item.kind() {
+            if let Some(set) = armor.set() {
+                *counts.entry(set.to_owned()).or_insert(0) += 1;
+            }
+        }
+    }
+    counts
+}
+
+/// Returns every set bonus that is active for the given piece counts (as
+/// produced by [`set_piece_counts`]), together with the set it belongs to
+pub fn active_set_bonuses<'a>(
+    counts: &'a HashMap<String, u32>,
+    msm: &'a MaterialStatManifest,
+) -> impl Iterator<Item = (&'a str, &'a SetBonus)> + 'a {
+    counts.iter().flat_map(move |(set, count)| {
+        msm.armor_set_bonuses(set)
+            .iter()
+            .filter(move |bonus| bonus.is_active(*count))
+            .map(move |bonus| (set.as_str(), bonus))
+    })
+}
+
 impl Mul<f32> for Stats {
     type Output = Self;
 
@@ -155,6 +223,7 @@ impl Mul<f32> for Stats {
             energy_reward: self.energy_reward.map(|a| a * val),
             precision_power: self.precision_power.map(|a| a * val),
             stealth: self.stealth.map(|a| a * val),
+            resistances: self.resistances.map(|a| a * val),
             // There is nothing to multiply, it is just an enum
             ground_contact: self.ground_contact,
         }
@@ -181,6 +250,7 @@ impl Sub<Stats> for Stats {
                 .zip(other.precision_power)
                 .map(|(a, b)| a - b),
             stealth: self.stealth.zip(other.stealth).map(|(a, b)| a - b),
+            resistances: self.resistances.zip(other.resistances).map(|(a, b)| a - b),
             ground_contact: Friction::Normal,
         }
     }
@@ -242,6 +312,14 @@ pub struct Armor {
 impl Armor {
     pub fn new(kind: ArmorKind, stats: StatsSource) -> Self { Self { kind, stats } }
 
+    /// The armour set this piece belongs to, if any
+    pub fn set(&self) -> Option<&str> {
+        match &self.stats {
+            StatsSource::Direct(_) => None,
+            StatsSource::FromSet(set) => Some(set),
+        }
+    }
+
     pub fn stats(
         &self,
         msm: &MaterialStatManifest,
@@ -291,6 +369,7 @@ impl Armor {
                 energy_reward: None,
                 precision_power: None,
                 stealth: None,
+                resistances: None,
                 ground_contact: Friction::Normal,
             }),
         }
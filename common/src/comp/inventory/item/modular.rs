@@ -28,6 +28,10 @@ macro_rules! modular_item_id_prefix {
 pub struct MaterialStatManifest {
     tool_stats: HashMap<String, tool::Stats>,
     armor_stats: HashMap<String, armor::Stats>,
+    /// Bonuses granted for wearing several pieces of the same armour set,
+    /// keyed by the set name used in `StatsSource::FromSet`
+    #[serde(default)]
+    armor_set_bonuses: HashMap<String, Vec<armor::SetBonus>>,
 }
 
 impl MaterialStatManifest {
@@ -37,12 +41,20 @@ impl MaterialStatManifest {
         self.armor_stats.get(key).copied()
     }
 
+    pub fn armor_set_bonuses(&self, set: &str) -> &[armor::SetBonus] {
+        self.armor_set_bonuses
+            .get(set)
+            .map(Vec::as_slice)
+            .unwrap_or_default()
+    }
+
     #[doc(hidden)]
     /// needed for tests to load it without actual assets
     pub fn with_empty() -> Self {
         Self {
             tool_stats: HashMap::default(),
             armor_stats: HashMap::default(),
+            armor_set_bonuses: HashMap::default(),
         }
     }
 }
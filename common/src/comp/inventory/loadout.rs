@@ -362,6 +362,11 @@ impl Loadout {
         self.slots.iter().filter_map(|x| x.slot.as_ref())
     }
 
+    /// Number of equipped pieces of each armour set
+    pub(super) fn armor_set_pieces(&self) -> HashMap<String, u32> {
+        item::armor::set_piece_counts(self.items())
+    }
+
     pub(super) fn items_with_slot(&self) -> impl Iterator<Item = (EquipSlot, &Item)> {
         self.slots
             .iter()
@@ -569,6 +574,38 @@ mod tests {
         assert_eq!(None, result);
     }
 
+    #[test]
+    fn test_armor_set_pieces() {
+        let mut loadout = Loadout::new_empty();
+
+        loadout.swap(
+            EquipSlot::Armor(ArmorSlot::Chest),
+            Some(Item::new_from_asset_expect(
+                "common.items.armor.cultist.chest",
+            )),
+            Time(0.0),
+        );
+        loadout.swap(
+            EquipSlot::Armor(ArmorSlot::Legs),
+            Some(Item::new_from_asset_expect(
+                "common.items.armor.cultist.pants",
+            )),
+            Time(0.0),
+        );
+        loadout.swap(
+            EquipSlot::Armor(ArmorSlot::Feet),
+            Some(Item::new_from_asset_expect(
+                "common.items.testing.test_boots",
+            )),
+            Time(0.0),
+        );
+
+        let result = loadout.armor_set_pieces();
+
+        assert_eq!(Some(&2), result.get("Cultist"));
+        assert_eq!(1, result.len());
+    }
+
     #[test]
     fn test_get_slot_to_equip_into_second_bag_slot_free() {
         let mut loadout = Loadout::new_empty();
@@ -809,6 +809,10 @@ impl Inventory {
 
     pub fn equipped_items(&self) -> impl Iterator<Item = &Item> { self.loadout.items() }
 
+    /// Number of equipped pieces of each armour set, used to determine which
+    /// set bonuses are active
+    pub fn armor_set_pieces(&self) -> HashMap<String, u32> { self.loadout.armor_set_pieces() }
+
     pub fn equipped_items_with_slot(&self) -> impl Iterator<Item = (EquipSlot, &Item)> {
         self.loadout.items_with_slot()
     }
@@ -80,6 +80,9 @@ pub struct MeleeConstructor {
     pub multi_target: Option<MultiTarget>,
     pub damage_effect: Option<CombatEffect>,
     pub attack_effect: Option<(CombatEffect, CombatRequirement)>,
+    /// Elemental damage dealt in addition to the damage specified in `kind`
+    #[serde(default)]
+    pub elemental_damage: Option<ElementalDamage>,
     #[serde(default)]
     pub dodgeable: Dodgeable,
     #[serde(default = "default_simultaneous_hits")]
@@ -92,6 +95,16 @@ pub struct MeleeConstructor {
     pub precision_flank_invert: bool,
 }
 
+#[derive(Copy, Clone, Debug, PartialEq, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct ElementalDamage {
+    /// Should be one of the elemental damage kinds
+    pub kind: DamageKind,
+    /// Fraction of the damage specified in `kind` of the melee constructor that
+    /// is additionally dealt as elemental damage
+    pub fraction: f32,
+}
+
 #[derive(Copy, Clone, Debug, PartialEq, Serialize, Default, Deserialize)]
 pub struct CustomCombo {
     pub base: Option<i32>,
@@ -372,6 +385,20 @@ impl MeleeConstructor {
             },
         };
 
+        let attack = if let Some(elemental) = self.elemental_damage {
+            attack.with_damage(AttackDamage::new(
+                Damage {
+                    source: DamageSource::Melee,
+                    kind: elemental.kind,
+                    value: self.kind.damage() * elemental.fraction,
+                },
+                Some(GroupTarget::OutOfGroup),
+                instance,
+            ))
+        } else {
+            attack
+        };
+
         let attack = if let Some((effect, requirement)) = self.attack_effect {
             let effect = AttackEffect::new(Some(GroupTarget::OutOfGroup), effect)
                 .with_requirement(requirement);
@@ -599,6 +626,18 @@ pub enum MeleeConstructorKind {
 }
 
 impl MeleeConstructorKind {
+    pub fn damage(&self) -> f32 {
+        use MeleeConstructorKind::*;
+        match self {
+            Slash { damage, .. }
+            | Stab { damage, .. }
+            | Bash { damage, .. }
+            | Hook { damage, .. }
+            | NecroticVortex { damage, .. }
+            | SonicWave { damage, .. } => *damage,
+        }
+    }
+
     #[must_use]
     pub fn adjusted_by_stats(mut self, stats: Stats) -> Self {
         use MeleeConstructorKind::*;
@@ -1,4 +1,7 @@
-use crate::resources::{Secs, Time};
+use crate::{
+    resources::{Secs, Time},
+    trade::SiteId,
+};
 use serde::{Deserialize, Serialize};
 use specs::{Component, DerefFlaggedStorage};
 use std::time::Duration;
@@ -43,3 +46,12 @@ impl From<PortalData> for Object {
         }
     }
 }
+
+/// What an instance reached through a portal is made of.
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum InstanceKind {
+    /// A copy of the dungeon of a site, with its own NPCs.
+    Dungeon { site: SiteId },
+    /// An empty arena surrounded by walls.
+    Arena,
+}
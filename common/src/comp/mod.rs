@@ -31,6 +31,7 @@ mod player;
 pub mod poise;
 pub mod presence;
 pub mod projectile;
+pub mod quest;
 pub mod shockwave;
 pub mod skillset;
 mod stats;
@@ -99,6 +100,7 @@ pub use self::{
     poise::{Poise, PoiseChange, PoiseState},
     presence::{Presence, PresenceKind},
     projectile::{Projectile, ProjectileConstructor},
+    quest::{Quest, QuestLog},
     shockwave::{Shockwave, ShockwaveHitEntities},
     skillset::{
         SkillGroup, SkillGroupKind, SkillSet,
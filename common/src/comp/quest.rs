@@ -0,0 +1,493 @@
+use crate::{
+    assets::AssetExt,
+    comp::{Body, Content, Inventory, item::ItemDef},
+    rtsim::Actor,
+};
+use hashbrown::HashMap;
+use serde::{Deserialize, Serialize};
+use specs::{Component, DerefFlaggedStorage};
+use std::sync::Arc;
+use vek::*;
+
+/// Maximum number of quests a character can have accepted at once
+pub const MAX_ACTIVE_QUESTS: usize = 10;
+
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub struct QuestId(pub u64);
+
+/// Something that has to be done to complete a quest
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub enum Objective {
+    /// Kill creatures of the same species as `body`
+    Kill { body: Body, count: u32 },
+    /// Bring items to the quest giver, the items are taken when the quest is
+    /// handed in
+    Fetch { item: String, amount: u32 },
+    /// Accompany an NPC until it is within `radius` blocks of `wpos`
+    Escort {
+        npc: Actor,
+        wpos: Vec2<i32>,
+        radius: u32,
+        name: Content,
+    },
+    /// Travel to within `radius` blocks of `wpos`
+    Visit {
+        wpos: Vec2<i32>,
+        radius: u32,
+        name: Content,
+    },
+    /// Kill a specific actor, such as the target of a bounty
+    Slay { target: Actor, name: Content },
+}
+
+impl Objective {
+    /// Progress required for the objective to be complete
+    pub fn required(&self) -> u32 {
+        match self {
+            Objective::Kill { count, .. } => *count,
+            Objective::Fetch { amount, .. } => *amount,
+            Objective::Escort { .. } | Objective::Visit { .. } | Objective::Slay { .. } => 1,
+        }
+    }
+
+    /// The item that has to be fetched, if this is a fetch objective
+    pub fn item_def(&self) -> Option<Arc<ItemDef>> {
+        match self {
+            Objective::Fetch { item, .. } => Arc::<ItemDef>::load_cloned(item).ok(),
+            Objective::Kill { .. }
+            | Objective::Escort { .. }
+            | Objective::Visit { .. }
+            | Objective::Slay { .. } => None,
+        }
+    }
+}
+
+#[derive(Clone, Debug, Default, PartialEq, Serialize, Deserialize)]
+pub struct QuestReward {
+    /// Item asset specifiers and amounts
+    pub items: Vec<(String, u32)>,
+    pub exp: u32,
+}
+
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct Quest {
+    pub id: QuestId,
+    pub title: Content,
+    /// The quest has to be handed in to this actor once all objectives are
+    /// done. Quests without a giver are handed in as soon as the objectives
+    /// are done.
+    pub giver: Option<Actor>,
+    pub objectives: Vec<Objective>,
+    pub reward: QuestReward,
+}
+
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct QuestProgress {
+    pub quest: Quest,
+    /// Progress of each objective, in the same order as the objectives of the
+    /// quest. Fetch objectives are tracked by the inventory instead.
+    pub progress: Vec<u32>,
+}
+
+impl QuestProgress {
+    pub fn new(quest: Quest) -> Self {
+        Self {
+            progress: vec![0; quest.objectives.len()],
+            quest,
+        }
+    }
+
+    /// Progress towards the objective at `idx`, capped at the progress it
+    /// requires
+    pub fn objective_progress(&self, idx: usize, inventory: Option<&Inventory>) -> u32 {
+        let Some(objective) = self.quest.objectives.get(idx) else {
+            return 0;
+        };
+        let progress = match objective {
+            Objective::Fetch { .. } => objective
+                .item_def()
+                .zip(inventory)
+                .map_or(0, |(item_def, inv)| {
+                    inv.item_count(&item_def).min(u32::MAX as u64) as u32
+                }),
+            Objective::Kill { .. }
+            | Objective::Escort { .. }
+            | Objective::Visit { .. }
+            | Objective::Slay { .. } => self.progress.get(idx).copied().unwrap_or(0),
+        };
+        progress.min(objective.required())
+    }
+
+    pub fn is_complete(&self, inventory: Option<&Inventory>) -> bool {
+        self.quest
+            .objectives
+            .iter()
+            .enumerate()
+            .all(|(idx, objective)| self.objective_progress(idx, inventory) >= objective.required())
+    }
+
+    fn update(&mut self, mut f: impl FnMut(&Objective, &mut u32)) {
+        for (objective, progress) in self.quest.objectives.iter().zip(&mut self.progress) {
+            if *progress < objective.required() {
+                f(objective, progress);
+            }
+        }
+    }
+}
+
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum QuestError {
+    TooManyQuests,
+    AlreadyAccepted,
+}
+
+/// The quests a character has accepted but not yet completed
+#[derive(Clone, Debug, Default, PartialEq, Serialize, Deserialize)]
+pub struct QuestLog {
+    active: Vec<QuestProgress>,
+    /// Number of quests completed so far
+    completed: u32,
+}
+
+impl Component for QuestLog {
+    type Storage = DerefFlaggedStorage<Self, specs::VecStorage<Self>>;
+}
+
+impl QuestLog {
+    pub fn quests(&self) -> impl Iterator<Item = &QuestProgress> { self.active.iter() }
+
+    pub fn completed(&self) -> u32 { self.completed }
+
+    pub fn accept(&mut self, quest: Quest) -> Result<(), QuestError> {
+        if self.active.iter().any(|q| q.quest.id == quest.id) {
+            Err(QuestError::AlreadyAccepted)
+        } else if self.active.len() >= MAX_ACTIVE_QUESTS {
+            Err(QuestError::TooManyQuests)
+        } else {
+            self.active.push(QuestProgress::new(quest));
+            Ok(())
+        }
+    }
+
+    pub fn abandon(&mut self, id: QuestId) -> Option<Quest> {
+        let idx = self.active.iter().position(|q| q.quest.id == id)?;
+        Some(self.active.remove(idx).quest)
+    }
+
+    /// Whether a quest from this giver is currently accepted
+    pub fn has_quest_from(&self, giver: Actor) -> bool {
+        self.active.iter().any(|q| q.quest.giver == Some(giver))
+    }
+
+    /// Whether killing a creature with this body would make progress on any
+    /// quest
+    pub fn wants_kill(&self, body: &Body) -> bool {
+        self.active.iter().any(|q| {
+            q.quest.objectives.iter().zip(&q.progress).any(|(o, p)| {
+                matches!(o, Objective::Kill { body: b, count } if p < count && b.is_same_species_as(body))
+            })
+        })
+    }
+
+    pub fn record_kill(&mut self, body: &Body) {
+        for quest in &mut self.active {
+            quest.update(|objective, progress| {
+                if let Objective::Kill { body: b, .. } = objective
+                    && b.is_same_species_as(body)
+                {
+                    *progress += 1;
+                }
+            });
+        }
+    }
+
+    /// Whether killing this actor would make progress on any quest
+    pub fn wants_slain(&self, actor: Actor) -> bool {
+        self.active.iter().any(|q| {
+            q.quest.objectives.iter().zip(&q.progress).any(
+                |(o, p)| matches!(o, Objective::Slay { target, .. } if *p < 1 && *target == actor),
+            )
+        })
+    }
+
+    pub fn record_slain(&mut self, actor: Actor) {
+        for quest in &mut self.active {
+            quest.update(|objective, progress| {
+                if let Objective::Slay { target, .. } = objective
+                    && *target == actor
+                {
+                    *progress = 1;
+                }
+            });
+        }
+    }
+
+    /// Removes and returns the quests that can no longer be completed because
+    /// `actor` died without being slain by this character
+    pub fn fail_slay(&mut self, actor: Actor) -> Vec<Quest> {
+        let (failed, active) = std::mem::take(&mut self.active)
+            .into_iter()
+            .partition::<Vec<_>, _>(|q| {
+                q.quest.objectives.iter().zip(&q.progress).any(
+                    |(o, p)| matches!(o, Objective::Slay { target, .. } if *p < 1 && *target == actor),
+                )
+            });
+        self.active = active;
+        failed.into_iter().map(|q| q.quest).collect()
+    }
+
+    /// Whether any quest is waiting for the character or an escorted NPC to
+    /// reach a location
+    pub fn wants_arrivals(&self) -> bool {
+        self.active.iter().any(|q| {
+            q.quest.objectives.iter().zip(&q.progress).any(|(o, p)| {
+                matches!(o, Objective::Escort { .. } | Objective::Visit { .. }) && *p < 1
+            })
+        })
+    }
+
+    /// The escorted NPCs that have not yet reached their destination
+    pub fn escorted_npcs(&self) -> impl Iterator<Item = Actor> + '_ {
+        self.active.iter().flat_map(|q| {
+            q.quest
+                .objectives
+                .iter()
+                .zip(&q.progress)
+                .filter_map(|(o, p)| match o {
+                    Objective::Escort { npc, .. } if *p < 1 => Some(*npc),
+                    _ => None,
+                })
+        })
+    }
+
+    /// Whether an arrival at `wpos` would make progress on any quest. `escort`
+    /// is the escorted NPC that arrived, or `None` if the character itself
+    /// arrived.
+    pub fn would_arrive(&self, escort: Option<Actor>, wpos: Vec2<f32>) -> bool {
+        self.active.iter().any(|q| {
+            q.quest
+                .objectives
+                .iter()
+                .zip(&q.progress)
+                .any(|(o, p)| *p < 1 && arrives(o, escort, wpos))
+        })
+    }
+
+    pub fn record_arrival(&mut self, escort: Option<Actor>, wpos: Vec2<f32>) {
+        for quest in &mut self.active {
+            quest.update(|objective, progress| {
+                if arrives(objective, escort, wpos) {
+                    *progress = 1;
+                }
+            });
+        }
+    }
+
+    /// Whether any completed quest can be handed in to `giver`
+    pub fn can_hand_in(&self, giver: Option<Actor>, inventory: Option<&Inventory>) -> bool {
+        self.active.iter().any(|q| {
+            (q.quest.giver.is_none() || q.quest.giver == giver) && q.is_complete(inventory)
+        })
+    }
+
+    /// The completed quests that can be handed in to `giver`, including those
+    /// that don't need to be handed in at all. Fetch quests are only included
+    /// while the inventory holds enough items for all of them together, so
+    /// quests asking for the same item can't be paid for with the same stack.
+    pub fn ready_to_hand_in(
+        &self,
+        giver: Option<Actor>,
+        inventory: Option<&Inventory>,
+    ) -> Vec<QuestId> {
+        let mut fetched = HashMap::<&str, u64>::new();
+        self.active
+            .iter()
+            .filter(|q| {
+                (q.quest.giver.is_none() || q.quest.giver == giver) && q.is_complete(inventory)
+            })
+            .filter(|q| {
+                let mut needed = fetched.clone();
+                let covered = q.quest.objectives.iter().all(|objective| {
+                    let Objective::Fetch { item, amount } = objective else {
+                        return true;
+                    };
+                    let needed = needed.entry(item.as_str()).or_default();
+                    *needed += u64::from(*amount);
+                    objective
+                        .item_def()
+                        .zip(inventory)
+                        .is_some_and(|(item_def, inv)| inv.item_count(&item_def) >= *needed)
+                });
+                if covered {
+                    fetched = needed;
+                }
+                covered
+            })
+            .map(|q| q.quest.id)
+            .collect()
+    }
+
+    /// Removes and returns the quests with the given ids, counting them as
+    /// completed. Fetched items have to be taken by the caller beforehand.
+    pub fn complete(&mut self, ids: &[QuestId]) -> Vec<Quest> {
+        let (completed, active) = std::mem::take(&mut self.active)
+            .into_iter()
+            .partition::<Vec<_>, _>(|q| ids.contains(&q.quest.id));
+        self.active = active;
+        self.completed += completed.len() as u32;
+        completed.into_iter().map(|q| q.quest).collect()
+    }
+
+    /// Removes and returns the completed quests that can be handed in to
+    /// `giver`, see [`QuestLog::ready_to_hand_in`]
+    pub fn take_completed(
+        &mut self,
+        giver: Option<Actor>,
+        inventory: Option<&Inventory>,
+    ) -> Vec<Quest> {
+        let ids = self.ready_to_hand_in(giver, inventory);
+        self.complete(&ids)
+    }
+}
+
+fn arrives(objective: &Objective, escort: Option<Actor>, pos: Vec2<f32>) -> bool {
+    match objective {
+        Objective::Visit { wpos, radius, .. } if escort.is_none() => {
+            wpos.as_::<f32>().distance_squared(pos) < (*radius as f32).powi(2)
+        },
+        Objective::Escort {
+            npc, wpos, radius, ..
+        } if escort == Some(*npc) => {
+            wpos.as_::<f32>().distance_squared(pos) < (*radius as f32).powi(2)
+        },
+        _ => false,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::comp::{Item, humanoid, quadruped_medium};
+
+    fn wolf() -> Body {
+        Body::QuadrupedMedium(quadruped_medium::Body::random_with(
+            &mut rand::thread_rng(),
+            &quadruped_medium::Species::Wolf,
+        ))
+    }
+
+    fn quest(objectives: Vec<Objective>) -> Quest {
+        Quest {
+            id: QuestId(1),
+            title: Content::Plain("Test".to_string()),
+            giver: None,
+            objectives,
+            reward: QuestReward::default(),
+        }
+    }
+
+    #[test]
+    fn kill_objective() {
+        let mut log = QuestLog::default();
+        log.accept(quest(vec![Objective::Kill {
+            body: wolf(),
+            count: 2,
+        }]))
+        .unwrap();
+
+        let human = Body::Humanoid(humanoid::Body::random());
+        assert!(!log.wants_kill(&human));
+        log.record_kill(&human);
+        assert!(log.wants_kill(&wolf()));
+        log.record_kill(&wolf());
+        assert!(log.take_completed(None, None).is_empty());
+        log.record_kill(&wolf());
+        assert!(!log.wants_kill(&wolf()));
+
+        assert_eq!(log.take_completed(None, None).len(), 1);
+        assert_eq!(log.quests().count(), 0);
+        assert_eq!(log.completed(), 1);
+    }
+
+    #[test]
+    fn arrivals() {
+        let mut log = QuestLog::default();
+        let mut q = quest(vec![Objective::Visit {
+            wpos: Vec2::new(100, 100),
+            radius: 10,
+            name: Content::Plain("Town".to_string()),
+        }]);
+        let giver = Actor::Npc(Default::default());
+        q.giver = Some(giver);
+        log.accept(q.clone()).unwrap();
+        assert_eq!(log.accept(q), Err(QuestError::AlreadyAccepted));
+
+        assert!(log.wants_arrivals());
+        assert!(!log.would_arrive(None, Vec2::new(0.0, 0.0)));
+        assert!(log.would_arrive(None, Vec2::new(95.0, 100.0)));
+        // Escorted NPCs don't complete visits
+        assert!(!log.would_arrive(Some(giver), Vec2::new(95.0, 100.0)));
+        log.record_arrival(None, Vec2::new(95.0, 100.0));
+        assert!(!log.wants_arrivals());
+
+        // Must be handed in to the giver
+        assert!(log.take_completed(None, None).is_empty());
+        assert_eq!(log.take_completed(Some(giver), None).len(), 1);
+    }
+
+    #[test]
+    fn slay_objective() {
+        let mut log = QuestLog::default();
+        let target = Actor::Npc(Default::default());
+        log.accept(quest(vec![Objective::Slay {
+            target,
+            name: Content::Plain("Bandit".to_string()),
+        }]))
+        .unwrap();
+
+        assert!(!log.can_hand_in(None, None));
+        assert!(log.wants_slain(target));
+        log.record_slain(target);
+        assert!(!log.wants_slain(target));
+        assert!(log.can_hand_in(None, None));
+        assert_eq!(log.take_completed(None, None).len(), 1);
+
+        // Someone else got to the target first
+        log.accept(quest(vec![Objective::Slay {
+            target,
+            name: Content::Plain("Bandit".to_string()),
+        }]))
+        .unwrap();
+        assert_eq!(log.fail_slay(target).len(), 1);
+        assert_eq!(log.quests().count(), 0);
+        assert_eq!(log.completed(), 1);
+    }
+
+    #[test]
+    fn overlapping_fetch_objectives() {
+        let fetch = |id, amount| Quest {
+            id: QuestId(id),
+            ..quest(vec![Objective::Fetch {
+                item: "common.items.food.apple".to_string(),
+                amount,
+            }])
+        };
+        let mut log = QuestLog::default();
+        log.accept(fetch(1, 3)).unwrap();
+        log.accept(fetch(2, 3)).unwrap();
+
+        let mut inventory = Inventory::with_empty();
+        let mut apples = Item::new_from_asset_expect("common.items.food.apple");
+        apples.set_amount(4).unwrap();
+        assert!(inventory.push(apples).is_ok());
+
+        // Both quests are complete on their own, but the apples only cover one
+        assert!(log.quests().all(|q| q.is_complete(Some(&inventory))));
+        assert_eq!(log.ready_to_hand_in(None, Some(&inventory)), vec![QuestId(
+            1
+        )]);
+        assert_eq!(log.take_completed(None, Some(&inventory)).len(), 1);
+        assert_eq!(log.quests().count(), 1);
+        assert_eq!(log.completed(), 1);
+    }
+}
@@ -235,6 +235,7 @@ pub struct UpdateCharacterDataEvent {
         Vec<(comp::Pet, comp::Body, comp::Stats)>,
         comp::ActiveAbilities,
         Option<comp::MapMarker>,
+        comp::QuestLog,
     ),
     pub metadata: UpdateCharacterMetadata,
 }
@@ -404,6 +405,11 @@ pub struct NpcInteractEvent(pub EcsEntity, pub EcsEntity);
 
 pub struct DialogueEvent(pub EcsEntity, pub EcsEntity, pub rtsim::Dialogue);
 
+/// Offers a quest from the first entity, to be accepted by the second
+pub struct StartQuestEvent(pub EcsEntity, pub EcsEntity, pub Box<comp::Quest>);
+
+pub struct AbandonQuestEvent(pub EcsEntity, pub comp::quest::QuestId);
+
 pub struct InviteResponseEvent(pub EcsEntity, pub InviteResponse);
 
 pub struct InitiateInviteEvent(pub EcsEntity, pub Uid, pub InviteKind);
@@ -515,6 +521,17 @@ pub struct StartTeleportingEvent {
     pub portal: EcsEntity,
 }
 
+/// Move entities through a portal into the instance of the group of `leader`,
+/// which is created if it doesn't exist yet.
+pub struct EnterInstanceEvent {
+    pub leader: EcsEntity,
+    /// The leader and the pets following it.
+    pub entities: Vec<EcsEntity>,
+    pub kind: comp::misc::InstanceKind,
+    /// Where the exit of the instance leads to.
+    pub exit: Vec3<f32>,
+}
+
 pub struct ToggleSpriteLightEvent {
     pub entity: EcsEntity,
     pub pos: Vec3<i32>,
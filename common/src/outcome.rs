@@ -1,5 +1,5 @@
 use crate::{
-    DamageSource,
+    DamageKind, DamageSource,
     combat::DamageContributor,
     comp::{self, item::ToolKind},
     terrain::SpriteKind,
@@ -17,6 +17,7 @@ pub struct HealthChangeInfo {
     pub target: Uid,
     pub by: Option<DamageContributor>,
     pub cause: Option<DamageSource>,
+    pub kind: Option<DamageKind>,
     pub instance: u64,
 }
 
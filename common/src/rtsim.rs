@@ -5,7 +5,7 @@
 
 use crate::{
     character::CharacterId,
-    comp::{agent::FlightMode, inventory::item::ItemDef},
+    comp::{Quest, agent::FlightMode, inventory::item::ItemDef},
     util::Dir,
 };
 use common_i18n::Content;
@@ -273,6 +273,8 @@ pub enum NpcAction {
     /// Attack the given target
     Attack(Actor),
     Dialogue(Actor, Dialogue),
+    /// Give the target a quest
+    StartQuest(Actor, Box<Quest>),
 }
 
 #[derive(Copy, Clone, Debug, Hash, PartialEq, Eq, Serialize, Deserialize)]
@@ -82,6 +82,7 @@ struct Queue {
     task: JobType,
 }
 
+#[derive(Clone)]
 pub struct JobMetrics {
     pub queue_created: Instant,
     pub execution_start: Instant,
@@ -406,6 +407,15 @@ impl SlowJobPool {
             .expect("lock poisoned while take_metrics")
             .take_metrics()
     }
+
+    /// Like `take_metrics` but leaves the metrics in place.
+    pub fn peek_metrics(&self) -> HashMap<String, Vec<JobMetrics>> {
+        self.internal
+            .lock()
+            .expect("lock poisoned while peek_metrics")
+            .jobs_metrics
+            .clone()
+    }
 }
 
 #[cfg(test)]
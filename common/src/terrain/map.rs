@@ -199,6 +199,14 @@ impl MapSizeLg {
     /// Get the size of an array of the correct size to hold all chunks.
     pub const fn chunks_len(self) -> usize { 1 << (self.0.x + self.0.y) }
 
+    /// Size of the terrain of a map, which is twice as wide as the map to make
+    /// room for the instances hosted by the server east of it. When that would
+    /// be too large, the size of the map itself, and there is no room for
+    /// instances.
+    pub fn with_instances(self) -> Self {
+        MapSizeLg::new(Vec2::new(self.0.x + 1, self.0.y)).unwrap_or(self)
+    }
+
     #[inline(always)]
     /// Determine whether a chunk position is in bounds.
     pub const fn contains_chunk(&self, chunk_key: Vec2<i32>) -> bool {
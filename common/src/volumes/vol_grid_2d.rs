@@ -175,6 +175,10 @@ impl<V: RectRasterableVol> VolGrid2d<V> {
     #[inline(always)]
     pub fn chunk_size() -> Vec2<u32> { V::RECT_SIZE }
 
+    /// Size of the area where chunks are stored, outside of it every chunk is
+    /// the default chunk.
+    pub fn map_size_lg(&self) -> MapSizeLg { self.map_size_lg }
+
     pub fn insert(&mut self, key: Vec2<i32>, chunk: Arc<V>) -> Option<Arc<V>> {
         self.chunks.insert(key, chunk)
     }
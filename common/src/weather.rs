@@ -6,25 +6,60 @@ use vek::{Lerp, Vec2, Vec3};
 use crate::{grid::Grid, terrain::TerrainChunkSize, vol::RectVolSize};
 
 /// Weather::default is Clear, 0 degrees C and no wind
-#[derive(Debug, Clone, Copy, Serialize, Deserialize, Default)]
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize, Default)]
 pub struct Weather {
     /// Clouds currently in the area between 0 and 1
     pub cloud: f32,
     /// Rain per time, between 0 and 1
     pub rain: f32,
+    /// Snow per time, between 0 and 1
+    pub snow: f32,
+    /// Density of fog near the ground, between 0 and 1
+    pub fog: f32,
+    /// Air temperature near the ground in degrees C
+    pub temperature: f32,
     /// Wind velocity in block / second
     pub wind: Vec2<f32>,
 }
 
 impl Weather {
-    pub fn new(cloud: f32, rain: f32, wind: Vec2<f32>) -> Self { Self { cloud, rain, wind } }
+    /// Below this temperature, in degrees C, precipitation falls as snow
+    /// rather than rain.
+    pub const FREEZING_POINT: f32 = 0.0;
+
+    pub fn new(cloud: f32, rain: f32, wind: Vec2<f32>) -> Self {
+        Self {
+            cloud,
+            rain,
+            wind,
+            ..Default::default()
+        }
+    }
+
+    /// Splits `precipitation` into rain and snow, depending on the
+    /// temperature. Returns `(rain, snow)`.
+    pub fn split_precipitation(precipitation: f32, temperature: f32) -> (f32, f32) {
+        // Sleet between -1 and 1 degrees C
+        let snow_fraction = ((Self::FREEZING_POINT + 1.0 - temperature) / 2.0).clamp(0.0, 1.0);
+        (
+            precipitation * (1.0 - snow_fraction),
+            precipitation * snow_fraction,
+        )
+    }
+
+    /// Rain and snow combined
+    pub fn precipitation(&self) -> f32 { self.rain + self.snow }
 
     pub fn get_kind(&self) -> WeatherKind {
         // Over 24.5 m/s wind is a storm
         if self.wind.magnitude_squared() >= 24.5f32.powi(2) {
             WeatherKind::Storm
-        } else if (0.1..=1.0).contains(&self.rain) {
+        } else if (0.1..=1.0).contains(&self.snow) && self.snow >= self.rain {
+            WeatherKind::Snow
+        } else if (0.1..=1.0).contains(&self.precipitation()) {
             WeatherKind::Rain
+        } else if (0.4..=1.0).contains(&self.fog) {
+            WeatherKind::Fog
         } else if (0.2..=1.0).contains(&self.cloud) {
             WeatherKind::Cloudy
         } else {
@@ -32,10 +67,22 @@ impl Weather {
         }
     }
 
+    /// How far can be seen in this weather, as a fraction of the distance that
+    /// can be seen on a clear day.
+    pub fn visibility(&self) -> f32 {
+        let fog = 1.0 - self.fog.clamp(0.0, 1.0) * 0.75;
+        // Snow obscures more than rain does
+        let precipitation = 1.0 - (self.rain * 0.3 + self.snow * 0.6).clamp(0.0, 0.6);
+        fog * precipitation
+    }
+
     pub fn lerp_unclamped(&self, to: &Self, t: f32) -> Self {
         Self {
             cloud: f32::lerp_unclamped(self.cloud, to.cloud, t),
             rain: f32::lerp_unclamped(self.rain, to.rain, t),
+            snow: f32::lerp_unclamped(self.snow, to.snow, t),
+            fog: f32::lerp_unclamped(self.fog, to.fog, t),
+            temperature: f32::lerp_unclamped(self.temperature, to.temperature, t),
             wind: Vec2::<f32>::lerp_unclamped(self.wind, to.wind, t),
         }
     }
@@ -43,7 +90,11 @@ impl Weather {
     // Get the rain velocity for this weather
     pub fn rain_vel(&self) -> Vec3<f32> {
         const FALL_RATE: f32 = 30.0;
-        self.wind.with_z(-FALL_RATE)
+        const SNOW_FALL_RATE: f32 = 5.0;
+        // Snowflakes fall slowly and drift with the wind
+        let snow_fraction = self.snow / self.precipitation().max(f32::EPSILON);
+        self.wind
+            .with_z(-f32::lerp(FALL_RATE, SNOW_FALL_RATE, snow_fraction))
     }
 
     // Get the wind velocity for this weather
@@ -55,6 +106,8 @@ pub enum WeatherKind {
     Clear,
     Cloudy,
     Rain,
+    Snow,
+    Fog,
     Storm,
 }
 
@@ -64,11 +117,27 @@ impl fmt::Display for WeatherKind {
             WeatherKind::Clear => write!(f, "Clear"),
             WeatherKind::Cloudy => write!(f, "Cloudy"),
             WeatherKind::Rain => write!(f, "Rain"),
+            WeatherKind::Snow => write!(f, "Snow"),
+            WeatherKind::Fog => write!(f, "Fog"),
             WeatherKind::Storm => write!(f, "Storm"),
         }
     }
 }
 
+impl WeatherKind {
+    /// The i18n key describing this kind of weather
+    pub fn localization_key(&self) -> &'static str {
+        match self {
+            WeatherKind::Clear => "common-weather-clear",
+            WeatherKind::Cloudy => "common-weather-cloudy",
+            WeatherKind::Rain => "common-weather-rain",
+            WeatherKind::Snow => "common-weather-snow",
+            WeatherKind::Fog => "common-weather-fog",
+            WeatherKind::Storm => "common-weather-storm",
+        }
+    }
+}
+
 // How many chunks wide a weather cell is.
 // So one weather cell has (CHUNKS_PER_CELL * CHUNKS_PER_CELL) chunks.
 pub const CHUNKS_PER_CELL: u32 = 16;
@@ -85,15 +154,15 @@ pub struct WeatherGrid {
 pub struct CompressedWeather {
     cloud: u8,
     rain: u8,
+    snow: u8,
+    fog: u8,
+    /// In whole degrees C
+    temperature: i8,
 }
 
 impl CompressedWeather {
     pub fn lerp_unclamped(&self, to: &CompressedWeather, t: f32) -> Weather {
-        Weather {
-            cloud: f32::lerp_unclamped(self.cloud as f32, to.cloud as f32, t) / 255.0,
-            rain: f32::lerp_unclamped(self.rain as f32, to.rain as f32, t) / 255.0,
-            wind: Vec2::zero(),
-        }
+        Weather::lerp_unclamped(&Weather::from(*self), &Weather::from(*to), t)
     }
 }
 
@@ -102,6 +171,9 @@ impl From<Weather> for CompressedWeather {
         Self {
             cloud: (weather.cloud * 255.0).round() as u8,
             rain: (weather.rain * 255.0).round() as u8,
+            snow: (weather.snow * 255.0).round() as u8,
+            fog: (weather.fog * 255.0).round() as u8,
+            temperature: weather.temperature.round() as i8,
         }
     }
 }
@@ -111,6 +183,9 @@ impl From<CompressedWeather> for Weather {
         Self {
             cloud: weather.cloud as f32 / 255.0,
             rain: weather.rain as f32 / 255.0,
+            snow: weather.snow as f32 / 255.0,
+            fog: weather.fog as f32 / 255.0,
+            temperature: weather.temperature as f32,
             wind: Vec2::zero(),
         }
     }
@@ -215,30 +290,23 @@ impl WeatherGrid {
     }
 
     /// Get the weather at a given world position by doing bilinear
-    /// interpolation between four cells.
+    /// interpolation between four cells. Positions past the edge of the grid
+    /// take the weather of the nearest edge cell.
     pub fn get_interpolated(&self, wpos: Vec2<f32>) -> Weather {
+        let size = self.weather.size();
+        if size.reduce_min() <= 0 {
+            return Weather::default();
+        }
         let cell_pos = to_cell_pos(wpos);
         let rpos = cell_pos.map(|e| e.fract() + (1.0 - e.signum()) / 2.0);
         let cell_pos = cell_pos.map(|e| e.floor());
 
         let cpos = cell_pos.as_::<i32>();
+        let cell =
+            |offs: Vec2<i32>| self.weather[(cpos + offs).map2(size, |e, sz| e.clamp(0, sz - 1))];
         Weather::lerp_unclamped(
-            &Weather::lerp_unclamped(
-                self.weather.get(cpos).unwrap_or(&Weather::default()),
-                self.weather
-                    .get(cpos + Vec2::unit_x())
-                    .unwrap_or(&Weather::default()),
-                rpos.x,
-            ),
-            &Weather::lerp_unclamped(
-                self.weather
-                    .get(cpos + Vec2::unit_y())
-                    .unwrap_or(&Weather::default()),
-                self.weather
-                    .get(cpos + Vec2::one())
-                    .unwrap_or(&Weather::default()),
-                rpos.x,
-            ),
+            &Weather::lerp_unclamped(&cell(Vec2::zero()), &cell(Vec2::unit_x()), rpos.x),
+            &Weather::lerp_unclamped(&cell(Vec2::unit_y()), &cell(Vec2::one()), rpos.x),
             rpos.y,
         )
     }
@@ -257,9 +325,108 @@ impl WeatherGrid {
             .reduce(|a, b| Weather {
                 cloud: a.cloud.max(b.cloud),
                 rain: a.rain.max(b.rain),
+                snow: a.snow.max(b.snow),
+                fog: a.fog.max(b.fog),
+                // The harshest weather nearby is the coldest
+                temperature: a.temperature.min(b.temperature),
                 wind: a.wind.map2(b.wind, |a, b| a.max(b)),
             })
             // There will always be 9 elements in locality
             .unwrap()
     }
 }
+
+/// A short-term forecast of the weather over the whole world, predicted by
+/// running the weather simulation ahead of time.
+#[derive(Debug, Clone, Default)]
+pub struct WeatherForecast {
+    /// Time of day at which the forecast was made
+    pub time_of_day: f64,
+    /// Predicted weather, one grid every [`WeatherForecast::STEP`] in-game
+    /// seconds after `time_of_day`
+    pub steps: Vec<WeatherGrid>,
+}
+
+impl WeatherForecast {
+    /// In-game seconds between two steps of the forecast
+    pub const STEP: f64 = 3600.0;
+    /// How many steps ahead the weather is forecast
+    pub const STEPS: usize = 6;
+
+    /// Whether the forecast should be remade at this time of day. Time of day
+    /// can be set back, which makes the forecast stale too.
+    pub fn is_stale(&self, time_of_day: f64) -> bool {
+        self.steps.is_empty() || (time_of_day - self.time_of_day).abs() >= Self::STEP
+    }
+
+    /// The predicted weather at a world position, as pairs of hours ahead of
+    /// `time_of_day` and the weather at that time.
+    pub fn at(&self, wpos: Vec2<f32>) -> impl Iterator<Item = (u32, Weather)> + '_ {
+        self.steps
+            .iter()
+            .enumerate()
+            .map(move |(i, grid)| (i as u32 + 1, grid.get_interpolated(wpos)))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn precipitation_freezes() {
+        assert_eq!(Weather::split_precipitation(0.5, 10.0), (0.5, 0.0));
+        assert_eq!(Weather::split_precipitation(0.5, -10.0), (0.0, 0.5));
+        let (rain, snow) = Weather::split_precipitation(0.5, 0.0);
+        assert!(rain > 0.0 && snow > 0.0);
+    }
+
+    #[test]
+    fn compression_keeps_new_fields() {
+        let weather = Weather {
+            snow: 0.5,
+            fog: 1.0,
+            temperature: -12.3,
+            ..Default::default()
+        };
+        let weather = Weather::from(CompressedWeather::from(weather));
+        assert!((weather.snow - 0.5).abs() < 0.01);
+        assert_eq!(weather.fog, 1.0);
+        assert_eq!(weather.temperature, -12.0);
+        assert_eq!(weather.get_kind(), WeatherKind::Snow);
+    }
+
+    #[test]
+    fn interpolation_clamps_to_edge() {
+        let mut grid = WeatherGrid::new(Vec2::new(2, 2));
+        for (_, cell) in grid.iter_mut() {
+            cell.cloud = 1.0;
+            cell.temperature = 20.0;
+        }
+        for wpos in [
+            Vec2::new(-1000.0, -1000.0),
+            Vec2::new(0.0, CELL_SIZE as f32),
+            Vec2::new(CELL_SIZE as f32 * 2.5, CELL_SIZE as f32 * 10.0),
+        ] {
+            let weather = grid.get_interpolated(wpos);
+            assert!((weather.cloud - 1.0).abs() < 0.001, "{wpos:?}: {weather:?}");
+            assert!(
+                (weather.temperature - 20.0).abs() < 0.001,
+                "{wpos:?}: {weather:?}"
+            );
+        }
+    }
+
+    #[test]
+    fn forecast_goes_stale() {
+        let forecast = WeatherForecast {
+            time_of_day: 10.0 * WeatherForecast::STEP,
+            steps: vec![WeatherGrid::new(Vec2::one())],
+        };
+        assert!(!forecast.is_stale(10.5 * WeatherForecast::STEP));
+        assert!(forecast.is_stale(11.0 * WeatherForecast::STEP));
+        // Time was set back
+        assert!(forecast.is_stale(2.0 * WeatherForecast::STEP));
+        assert!(WeatherForecast::default().is_stale(0.0));
+    }
+}
@@ -244,6 +244,7 @@ impl State {
         ecs.register::<comp::Stats>();
         ecs.register::<comp::SkillSet>();
         ecs.register::<comp::ActiveAbilities>();
+        ecs.register::<comp::QuestLog>();
         ecs.register::<comp::Buffs>();
         ecs.register::<comp::Auras>();
         ecs.register::<comp::EnteredAuras>();
@@ -1,5 +1,5 @@
 use common::{
-    Damage, DamageSource,
+    Damage, DamageKind, DamageSource,
     combat::{self, DamageContributor},
     comp::{
         Alignment, Energy, Group, Health, HealthChange, Inventory, LightEmitter, Mass,
@@ -12,7 +12,10 @@ use common::{
             Buffs, DestInfo,
         },
         fluid_dynamics::{Fluid, LiquidKind},
-        item::MaterialStatManifest,
+        item::{
+            MaterialStatManifest,
+            armor::{self, SetBonusEffect},
+        },
     },
     event::{
         BuffEvent, ChangeBodyEvent, CreateSpriteEvent, EmitExt, EnergyChangeEvent,
@@ -21,8 +24,10 @@ use common::{
     event_emitters,
     outcome::Outcome,
     resources::{DeltaTime, Secs, Time},
-    terrain::SpriteKind,
+    terrain::{SpriteKind, TerrainGrid},
     uid::{IdMaps, Uid},
+    vol::ReadVol,
+    weather::WeatherGrid,
 };
 use common_base::prof_span;
 use common_ecs::{Job, Origin, ParMode, Phase, System};
@@ -69,6 +74,23 @@ pub struct ReadData<'a> {
     alignments: ReadStorage<'a, Alignment>,
     players: ReadStorage<'a, Player>,
     masses: ReadStorage<'a, Mass>,
+    terrain: ReadExpect<'a, TerrainGrid>,
+    weather: Option<Read<'a, WeatherGrid>>,
+}
+
+/// Below this temperature, in degrees C, unsheltered players slowly freeze
+const COLD_EXPOSURE_TEMPERATURE: f32 = -5.0;
+
+/// Whether there is a solid block shortly above `pos` that shelters it from
+/// the weather
+fn is_sheltered(terrain: &TerrainGrid, pos: Vec3<f32>) -> bool {
+    const SHELTER_HEIGHT: i32 = 24;
+    let wpos = pos.map(|e| e.floor() as i32);
+    (1..SHELTER_HEIGHT).any(|dz| {
+        terrain
+            .get(wpos + Vec3::unit_z() * dz)
+            .is_ok_and(|block| block.is_solid())
+    })
 }
 
 #[derive(Default)]
@@ -422,6 +444,51 @@ impl<'a> System<'a> for Sys {
                         entity,
                         buff_change: BuffChange::RemoveByKind(BuffKind::Burning),
                     });
+                } else if buff_comp.contains(BuffKind::Burning)
+                    && let Some(weather) = &read_data.weather
+                    && let Some(pos) = read_data.positions.get(entity)
+                {
+                    // Rain has a chance to put out burning entities that aren't sheltered
+                    let rain = weather.get_interpolated(pos.0.xy()).rain;
+                    if rain > 0.0
+                        && rng.gen_bool(combat::rain_extinguish_chance(rain, dt).into())
+                        && !is_sheltered(&read_data.terrain, pos.0)
+                    {
+                        emitters.emit(BuffEvent {
+                            entity,
+                            buff_change: BuffChange::RemoveByKind(BuffKind::Burning),
+                        });
+                    }
+                }
+            }
+
+            // Players caught out in freezing weather get cold, unless they're sheltered
+            // or warming up at a campfire
+            if read_data.players.contains(entity)
+                && let Some(weather) = &read_data.weather
+                && let Some(pos) = read_data.positions.get(entity)
+            {
+                let temperature = weather.get_interpolated(pos.0.xy()).temperature;
+                if temperature < COLD_EXPOSURE_TEMPERATURE
+                    && !buff_comp.contains(BuffKind::CampfireHeal)
+                    && !buff_comp.contains(BuffKind::Burning)
+                    && rng.gen_bool(dt.clamp(0.0, 1.0).into())
+                    && !is_sheltered(&read_data.terrain, pos.0)
+                {
+                    let strength =
+                        ((COLD_EXPOSURE_TEMPERATURE - temperature) / 40.0).clamp(0.05, 0.4);
+                    emitters.emit(BuffEvent {
+                        entity,
+                        buff_change: BuffChange::Add(Buff::new(
+                            BuffKind::Frozen,
+                            BuffData::new(strength, Some(Secs(5.0))),
+                            vec![BuffCategory::Natural],
+                            BuffSource::World,
+                            *read_data.time,
+                            dest_info,
+                            None,
+                        )),
+                    });
                 }
             }
 
@@ -511,6 +578,38 @@ impl<'a> System<'a> for Sys {
 
             let mut body_override = None;
 
+            // Apply the effects of buffs granted by active armour set bonuses. These are
+            // not added to the buffs component, and so last exactly as long as the set
+            // pieces are equipped.
+            if let Some(inventory) = read_data.inventories.get(entity) {
+                let set_pieces = inventory.armor_set_pieces();
+                for (_, bonus) in armor::active_set_bonuses(&set_pieces, &read_data.msm) {
+                    if let SetBonusEffect::Buff { kind, strength } = bonus.effect {
+                        for effect in kind.effects(&BuffData::new(strength, None)) {
+                            execute_effect(
+                                &effect,
+                                kind,
+                                Time(0.0),
+                                Time(0.0),
+                                &read_data,
+                                &mut stat,
+                                body,
+                                &mut body_override,
+                                health,
+                                energy,
+                                entity,
+                                None,
+                                &mut emitters,
+                                dt,
+                                *read_data.time,
+                                false,
+                                buff_comp,
+                            );
+                        }
+                    }
+                }
+            }
+
             // Iterator over the lists of buffs by kind
             let mut buff_kinds = buff_comp
                 .kinds
@@ -686,6 +785,11 @@ fn execute_effect(
                         amount,
                         by: damage_contributor,
                         cause,
+                        // Lets burning show up as fire damage in combat text
+                        kind: match buff_kind {
+                            BuffKind::Burning if cause.is_some() => Some(DamageKind::Fire),
+                            _ => None,
+                        },
                         time: *read_data.time,
                         precise: false,
                         instance: *instance,
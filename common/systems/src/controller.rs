@@ -23,6 +23,7 @@ event_emitters! {
         lantern: event::SetLanternEvent,
         npc_interact: event::NpcInteractEvent,
         dialogue: event::DialogueEvent,
+        abandon_quest: event::AbandonQuestEvent,
         initiate_invite: event::InitiateInviteEvent,
         invite_response: event::InviteResponseEvent,
         process_trade_action: event::ProcessTradeActionEvent,
@@ -212,6 +213,9 @@ impl<'a> System<'a> for Sys {
                                 warn!("Control event Dialogue sent to non-existent target entity");
                             }
                         },
+                        ControlEvent::AbandonQuest(quest) => {
+                            emitters.emit(event::AbandonQuestEvent(entity, quest));
+                        },
                     }
                 }
             });
@@ -63,6 +63,11 @@ impl Message {
         }
     }
 
+    /// Size of the serialized, and possibly compressed, message in bytes.
+    pub fn len(&self) -> usize { self.data.len() }
+
+    pub fn is_empty(&self) -> bool { self.data.is_empty() }
+
     /// deserialize this `Message`. This consumes the struct, as deserialization
     /// is only expected once. Use this when deserialize a [`recv_raw`]
     /// `Message`. If you are resending this message, deserialization might need
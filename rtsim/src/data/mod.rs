@@ -12,7 +12,7 @@ pub use self::{
     npc::{Npc, NpcId, Npcs},
     report::{Report, ReportId, ReportKind, Reports},
     sentiment::{Sentiment, Sentiments},
-    site::{Site, SiteId, Sites},
+    site::{Bounty, Site, SiteId, Sites},
 };
 use airship::AirshipSim;
 use common::resources::TimeOfDay;
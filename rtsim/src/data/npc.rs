@@ -6,7 +6,7 @@ use crate::{
 pub use common::rtsim::{NpcId, Profession};
 use common::{
     character::CharacterId,
-    comp::{self, agent::FlightMode},
+    comp::{self, Quest, agent::FlightMode},
     grid::Grid,
     resources::Time,
     rtsim::{
@@ -128,6 +128,11 @@ impl Controller {
 
     pub fn end_hiring(&mut self) { self.hiring = Some(None); }
 
+    pub fn start_quest(&mut self, target: Actor, quest: Quest) {
+        self.actions
+            .push(NpcAction::StartQuest(target, Box::new(quest)));
+    }
+
     /// Start a new dialogue.
     pub fn dialogue_start(&mut self, target: impl Into<Actor>) -> DialogueSession {
         let target = target.into();
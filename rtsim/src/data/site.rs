@@ -21,6 +21,11 @@ pub struct Site {
     /// noticeboard or something).
     pub known_reports: HashSet<ReportId>,
 
+    /// Bounties that the site has posted on actors that harmed it, generated
+    /// from its known reports.
+    #[serde(default)]
+    pub bounties: Vec<Bounty>,
+
     /// The site generated during initial worldgen that this site corresponds
     /// to.
     ///
@@ -61,6 +66,20 @@ impl Site {
             .retain(|report| reports.contains_key(*report));
         // TODO: Limit number of reports
     }
+
+    pub fn bounty_on(&self, target: NpcId) -> Option<&Bounty> {
+        self.bounties.iter().find(|b| b.target == target)
+    }
+}
+
+/// A reward offered by a site for killing an NPC
+#[derive(Clone, Serialize, Deserialize)]
+pub struct Bounty {
+    pub target: NpcId,
+    /// Reward, in coins
+    pub reward: u32,
+    /// Number of the site's people known to have been killed by the target
+    pub victims: u32,
 }
 
 #[derive(Clone, Default, Serialize, Deserialize)]
@@ -21,7 +21,7 @@ use rand_chacha::ChaChaRng;
 use tracing::info;
 use vek::*;
 use world::{
-    CONFIG, IndexRef, World,
+    IndexRef, World,
     civ::airship_travel::{AirshipDockingSide, Airships},
     site::SiteKind,
     site2::{PlotKind, plot::PlotKindMeta},
@@ -281,7 +281,7 @@ impl Data {
                     )
                 })
             {
-                let biome = chunk.get_biome();
+                let biome = chunk.get_biome(&world.sim().config);
                 let Some(species) = [
                     Some(comp::body::biped_large::Species::Ogre),
                     Some(comp::body::biped_large::Species::Cyclops),
@@ -295,7 +295,7 @@ impl Data {
                     Some(comp::body::biped_large::Species::Blueoni),
                     Some(comp::body::biped_large::Species::Redoni),
                     Some(comp::body::biped_large::Species::Tursus)
-                        .filter(|_| chunk.temp < CONFIG.snow_temp),
+                        .filter(|_| chunk.temp < world.sim().config.snow_temp),
                 ]
                 .into_iter()
                 .flatten()
@@ -329,7 +329,7 @@ impl Data {
             })
         {
             let species = Some(comp::body::biped_large::Species::Gigasfrost)
-                .filter(|_| chunk.temp < CONFIG.snow_temp)
+                .filter(|_| chunk.temp < world.sim().config.snow_temp)
                 .unwrap_or(comp::body::biped_large::Species::Gigasfrost);
 
             this.npcs.create_npc(Npc::new(
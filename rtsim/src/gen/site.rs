@@ -73,6 +73,7 @@ impl Site {
             }),
             population: Default::default(),
             known_reports: Default::default(),
+            bounties: Vec::new(),
             nearby_sites_by_size: Vec::new(),
         }
     }
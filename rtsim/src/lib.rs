@@ -199,6 +199,7 @@ impl RtState {
         self.start_rule::<rule::migrate::Migrate>();
         self.start_rule::<rule::replenish_resources::ReplenishResources>();
         self.start_rule::<rule::report::ReportEvents>();
+        self.start_rule::<rule::bounty::Bounties>();
         self.start_rule::<rule::sync_npcs::SyncNpcs>();
         self.start_rule::<rule::simulate_npcs::SimulateNpcs>();
         self.start_rule::<rule::npc_ai::NpcAi>();
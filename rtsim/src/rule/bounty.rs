@@ -0,0 +1,120 @@
+use crate::{
+    RtState, Rule, RuleError,
+    data::{Bounty, ReportKind, Sentiment},
+    event::{EventCtx, OnDeath, OnTick},
+};
+use common::rtsim::{Actor, NpcId};
+use hashbrown::HashMap;
+
+/// Prevent updating the bounties of every site every tick
+const SITE_BOUNTY_TICK_SKIP: u64 = 60;
+/// The reward offered for each victim of a bounty's target, in coins
+const REWARD_PER_VICTIM: u32 = 80;
+/// The most bounties a site will have posted at once
+const MAX_SITE_BOUNTIES: usize = 5;
+
+/// A rule that lets sites post bounties on NPCs that killed their people, based
+/// on the death reports that the site knows about.
+///
+/// Monsters simulated by rtsim are NPCs too, so they can have a bounty put on
+/// them. Characters are left to the sentiments of the people hearing about
+/// their murders instead, and killers unknown to rtsim can't be tracked down.
+pub struct Bounties;
+
+impl Rule for Bounties {
+    fn start(rtstate: &mut RtState) -> Result<Self, RuleError> {
+        rtstate.bind::<Self, OnTick>(on_tick);
+        rtstate.bind::<Self, OnDeath>(on_death);
+
+        Ok(Self)
+    }
+}
+
+fn on_tick(ctx: EventCtx<Bounties, OnTick>) {
+    let data = &mut *ctx.state.data_mut();
+
+    for (site_id, site) in data
+        .sites
+        .iter_mut()
+        .filter(|(_, site)| (site.seed as u64 + ctx.event.tick) % SITE_BOUNTY_TICK_SKIP == 0)
+    {
+        // Withdraw bounties on NPCs that are no longer around to be killed
+        site.bounties.retain(|bounty| {
+            data.npcs
+                .get(bounty.target)
+                .is_some_and(|npc| !npc.is_dead())
+        });
+
+        // Count the site's people that each killer is known to have murdered
+        let mut victims = HashMap::<NpcId, u32>::default();
+        for report in site.known_reports.iter() {
+            let Some(report) = data.reports.get(*report) else {
+                continue;
+            };
+            let ReportKind::Death {
+                actor: Actor::Npc(victim),
+                killer,
+            } = report.kind
+            else {
+                continue;
+            };
+            let killer = match killer {
+                Some(Actor::Npc(killer)) => killer,
+                // Characters come back after being killed, so a bounty on them could
+                // never be claimed
+                Some(Actor::Character(_)) => continue,
+                // Wild creatures aren't simulated by rtsim, so there is no one to put a
+                // bounty on
+                None => continue,
+            };
+            if site.population.contains(&victim)
+                && !site.population.contains(&killer)
+                && data.npcs.get(killer).is_some_and(|npc| !npc.is_dead())
+            {
+                *victims.entry(killer).or_default() += 1;
+            }
+        }
+
+        for (killer, count) in victims {
+            if let Some(bounty) = site.bounties.iter_mut().find(|b| b.target == killer) {
+                // Reports are forgotten over time, but the bounty stays at its highest
+                if count > bounty.victims {
+                    bounty.victims = count;
+                    bounty.reward = count * REWARD_PER_VICTIM;
+                }
+            } else if site.bounties.len() < MAX_SITE_BOUNTIES {
+                tracing::debug!(?site_id, ?killer, "Site posted a bounty");
+                site.bounties.push(Bounty {
+                    target: killer,
+                    reward: count * REWARD_PER_VICTIM,
+                    victims: count,
+                });
+            }
+        }
+    }
+}
+
+fn on_death(ctx: EventCtx<Bounties, OnDeath>) {
+    let data = &mut *ctx.state.data_mut();
+
+    let Actor::Npc(npc_id) = ctx.event.actor else {
+        return;
+    };
+
+    for site in data.sites.values_mut() {
+        let Some(victims) = site.bounty_on(npc_id).map(|bounty| bounty.victims) else {
+            continue;
+        };
+        site.bounties.retain(|bounty| bounty.target != npc_id);
+
+        // Sites are grateful to characters that bring their murderers to justice
+        if let Some(killer @ Actor::Character(_)) = ctx.event.killer
+            && let Some(faction) = site.faction.and_then(|f| data.factions.get_mut(f))
+        {
+            faction
+                .sentiments
+                .toward_mut(killer)
+                .change_by(0.1 * victims as f32, Sentiment::FRIEND);
+        }
+    }
+}
@@ -33,10 +33,26 @@ pub fn general<S: State>(tgt: Actor, session: DialogueSession) -> impl Action<S>
                 dialogue::hire(tgt, session).boxed(),
             ));
         }
+        if !is_hired_by_tgt {
+            responses.push((
+                Response::from(Content::localized("dialogue-question-quest")),
+                dialogue::quest(tgt, session).boxed(),
+            ));
+        }
+        if matches!(ctx.npc.profession(), Some(Profession::Guard)) && bounty_site(ctx).is_some() {
+            responses.push((
+                Response::from(Content::localized("dialogue-question-bounty")),
+                dialogue::bounties(tgt, session).boxed(),
+            ));
+        }
         responses.push((
             Response::from(Content::localized("dialogue-question-directions")),
             dialogue::directions(session).boxed(),
         ));
+        responses.push((
+            Response::from(Content::localized("dialogue-question-weather")),
+            dialogue::weather(session).boxed(),
+        ));
 
         session.ask_question(Content::localized("npc-question-general"), responses)
     })
@@ -191,6 +207,171 @@ fn hire<S: State>(tgt: Actor, session: DialogueSession) -> impl Action<S> {
     })
 }
 
+fn quest<S: State>(tgt: Actor, session: DialogueSession) -> impl Action<S> {
+    now(move |ctx, _| {
+        if let Some(quest) = quest::generate(ctx) {
+            let is_escort = quest
+                .objectives
+                .iter()
+                .any(|o| matches!(o, comp::quest::Objective::Escort { .. }));
+            session
+                .say_statement(quest::describe(&quest))
+                .then(
+                    session.ask_question(Content::localized("npc-question-quest"), [
+                        (
+                            Response::from(Content::localized("dialogue-accept")),
+                            session
+                                .say_statement(Content::localized("npc-response-quest_accepted"))
+                                .then(just(move |ctx, _| {
+                                    ctx.controller.start_quest(tgt, quest.clone());
+                                    // Escorted NPCs follow the character until they arrive
+                                    if is_escort {
+                                        ctx.controller.set_newly_hired(
+                                            tgt,
+                                            ctx.time
+                                                .add_days(1.0, &ctx.system_data.server_constants),
+                                        );
+                                    }
+                                }))
+                                .boxed(),
+                        ),
+                        (
+                            Response::from(Content::localized("dialogue-decline")),
+                            session
+                                .say_statement(Content::localized("npc-response-no_problem"))
+                                .boxed(),
+                        ),
+                    ]),
+                )
+                .boxed()
+        } else {
+            session
+                .say_statement(Content::localized("npc-response-no_quest"))
+                .boxed()
+        }
+    })
+}
+
+/// The site whose bounties the NPC can hand out, if any are posted
+fn bounty_site(ctx: &NpcCtx) -> Option<SiteId> {
+    let data = ctx.state.data();
+    [ctx.npc.current_site, ctx.npc.home]
+        .into_iter()
+        .flatten()
+        .find(|site| {
+            data.sites
+                .get(*site)
+                .is_some_and(|s| !s.bounties.is_empty())
+        })
+}
+
+fn bounties<S: State>(tgt: Actor, session: DialogueSession) -> impl Action<S> {
+    now(move |ctx, _| {
+        let bounties = bounty_site(ctx)
+            .and_then(|site| ctx.state.data().sites.get(site).map(|s| s.bounties.clone()))
+            .unwrap_or_default();
+
+        let mut responses = Vec::new();
+        responses.push((
+            Response::from(Content::localized("dialogue-cancel_interaction")),
+            session
+                .say_statement(Content::localized("npc-response-no_problem"))
+                .boxed(),
+        ));
+        for bounty in bounties.iter().take(3) {
+            let Some(quest) = quest::bounty(ctx, bounty) else {
+                continue;
+            };
+            let Some(target) = ctx
+                .state
+                .data()
+                .npcs
+                .get(bounty.target)
+                .map(|npc| npc.get_name())
+            else {
+                continue;
+            };
+            responses.push((
+                Response::from(Content::localized_with_args("dialogue-bounty", [
+                    ("target", Content::Plain(target)),
+                    ("reward", Content::Plain(bounty.reward.to_string())),
+                    ("victims", Content::Plain(bounty.victims.to_string())),
+                ])),
+                session
+                    .say_statement(Content::localized("npc-response-bounty_accepted"))
+                    .then(just(move |ctx, _| {
+                        ctx.controller.start_quest(tgt, quest.clone());
+                    }))
+                    .boxed(),
+            ));
+        }
+
+        session.ask_question(Content::localized("npc-question-bounty"), responses)
+    })
+}
+
+fn weather_content(kind: WeatherKind) -> Content {
+    Content::localized_attr("npc-weather", match kind {
+        WeatherKind::Clear => "clear",
+        WeatherKind::Cloudy => "cloudy",
+        WeatherKind::Rain => "rain",
+        WeatherKind::Snow => "snow",
+        WeatherKind::Fog => "fog",
+        WeatherKind::Storm => "storm",
+    })
+}
+
+fn weather<S: State>(session: DialogueSession) -> impl Action<S> {
+    now(move |ctx, _| {
+        let forecast = ctx
+            .system_data
+            .weather_forecast
+            .at(ctx.npc.wpos.xy())
+            .collect::<Vec<_>>();
+
+        let Some((_, next)) = forecast.first() else {
+            return session
+                .say_statement(Content::localized("npc-info-unknown"))
+                .boxed();
+        };
+        let next_kind = next.get_kind();
+
+        let mut action = if let Some((hours, later)) = forecast
+            .iter()
+            .find(|(_, weather)| weather.get_kind() != next_kind)
+        {
+            session
+                .say_statement(Content::localized_with_args(
+                    "npc-response-weather_changes",
+                    [
+                        ("now", weather_content(next_kind)),
+                        ("later", weather_content(later.get_kind())),
+                        ("hours", Content::Plain(hours.to_string())),
+                    ],
+                ))
+                .boxed()
+        } else {
+            session
+                .say_statement(Content::localized_with_args(
+                    "npc-response-weather_stays",
+                    [("weather", weather_content(next_kind))],
+                ))
+                .boxed()
+        };
+
+        if forecast
+            .iter()
+            .any(|(_, weather)| weather.temperature < Weather::FREEZING_POINT)
+        {
+            action = action
+                .then(session.say_statement(Content::localized("npc-response-weather_freezing")))
+                .boxed();
+        }
+
+        action
+    })
+}
+
 fn directions<S: State>(session: DialogueSession) -> impl Action<S> {
     now(move |ctx, _| {
         let mut responses = Vec::new();
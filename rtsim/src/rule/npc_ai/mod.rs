@@ -26,6 +26,7 @@
 mod airship_ai;
 pub mod dialogue;
 pub mod movement;
+pub mod quest;
 pub mod util;
 
 use std::{collections::VecDeque, hash::BuildHasherDefault, sync::Arc};
@@ -61,6 +62,7 @@ use common::{
     terrain::{CoordinateConversions, TerrainChunkSize, sprite},
     time::DayPeriod,
     util::Dir,
+    weather::{Weather, WeatherKind},
 };
 use core::ops::ControlFlow;
 use fxhash::FxHasher64;
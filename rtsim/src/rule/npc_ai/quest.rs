@@ -0,0 +1,177 @@
+use super::*;
+use crate::data::Bounty;
+use common::{
+    comp::{
+        item::{ItemDesc, ItemI18n},
+        quadruped_medium, quadruped_small,
+        quest::{Objective, Quest, QuestId, QuestReward},
+    },
+    npc::NPC_NAMES,
+};
+
+const COINS: &str = "common.items.utility.coins";
+
+/// Creates a quest suited to the NPC's profession, if it has one to give
+pub fn generate(ctx: &mut NpcCtx) -> Option<Quest> {
+    let objective = match ctx.npc.profession()? {
+        Profession::Hunter => {
+            let body = match ctx.rng.gen_range(0..3) {
+                0 => comp::Body::QuadrupedMedium(quadruped_medium::Body::random_with(
+                    &mut ctx.rng,
+                    &quadruped_medium::Species::Wolf,
+                )),
+                1 => comp::Body::QuadrupedMedium(quadruped_medium::Body::random_with(
+                    &mut ctx.rng,
+                    &quadruped_medium::Species::Deer,
+                )),
+                _ => comp::Body::QuadrupedSmall(quadruped_small::Body::random_with(
+                    &mut ctx.rng,
+                    &quadruped_small::Species::Boar,
+                )),
+            };
+            Objective::Kill {
+                body,
+                count: ctx.rng.gen_range(3..=6),
+            }
+        },
+        Profession::Farmer | Profession::Chef | Profession::Herbalist | Profession::Alchemist => {
+            let item = *[
+                "common.items.food.apple",
+                "common.items.food.carrot",
+                "common.items.food.mushroom",
+                "common.items.food.lettuce",
+                "common.items.crafting_ing.cotton_boll",
+                "common.items.crafting_ing.honey",
+            ]
+            .choose(&mut ctx.rng)?;
+            Objective::Fetch {
+                item: item.to_string(),
+                amount: ctx.rng.gen_range(3..=8),
+            }
+        },
+        Profession::Guard => {
+            let (wpos, name) = nearby_site(ctx)?;
+            Objective::Visit {
+                wpos,
+                radius: 64,
+                name,
+            }
+        },
+        Profession::Merchant | Profession::Adventurer(_) => {
+            let (wpos, name) = nearby_site(ctx)?;
+            Objective::Escort {
+                npc: Actor::Npc(ctx.npc_id),
+                wpos,
+                radius: 48,
+                name,
+            }
+        },
+        Profession::Blacksmith | Profession::Pirate | Profession::Cultist | Profession::Captain => {
+            return None;
+        },
+    };
+
+    let reward_scale = match &objective {
+        Objective::Kill { count, .. } => *count,
+        Objective::Fetch { amount, .. } => *amount / 2 + 1,
+        Objective::Escort { .. } => 6,
+        Objective::Visit { .. } => 4,
+        Objective::Slay { .. } => 10,
+    };
+    let giver = ("giver", Content::Plain(ctx.npc.get_name()));
+    let (title, arg) = match &objective {
+        Objective::Kill { .. } => ("quest-title-kill", giver),
+        Objective::Fetch { .. } => ("quest-title-fetch", giver),
+        Objective::Escort { .. } => ("quest-title-escort", giver),
+        Objective::Visit { .. } => ("quest-title-visit", giver),
+        Objective::Slay { name, .. } => ("quest-title-bounty", ("target", name.clone())),
+    };
+
+    Some(Quest {
+        id: QuestId(ctx.rng.gen()),
+        title: Content::localized_with_args(title, [arg]),
+        giver: Some(Actor::Npc(ctx.npc_id)),
+        objectives: vec![objective],
+        reward: QuestReward {
+            items: vec![(COINS.to_string(), reward_scale * 15)],
+            exp: reward_scale * 20,
+        },
+    })
+}
+
+/// Creates a quest to claim a bounty posted at a site. Bounties have no giver:
+/// the reward is paid out as soon as the target is dead.
+pub fn bounty(ctx: &mut NpcCtx, bounty: &Bounty) -> Option<Quest> {
+    let name = ctx.state.data().npcs.get(bounty.target)?.get_name();
+    Some(Quest {
+        id: QuestId(ctx.rng.gen()),
+        title: Content::localized_with_args("quest-title-bounty", [(
+            "target",
+            Content::Plain(name.clone()),
+        )]),
+        giver: None,
+        objectives: vec![Objective::Slay {
+            target: Actor::Npc(bounty.target),
+            name: Content::Plain(name),
+        }],
+        reward: QuestReward {
+            items: vec![(COINS.to_string(), bounty.reward)],
+            exp: bounty.reward,
+        },
+    })
+}
+
+/// A site near the NPC's current site, along with its name
+fn nearby_site(ctx: &mut NpcCtx) -> Option<(Vec2<i32>, Content)> {
+    let data = ctx.state.data();
+    let site_id = *data
+        .sites
+        .get(ctx.npc.current_site?)?
+        .nearby_sites_by_size
+        .iter()
+        .take(4)
+        .choose(&mut ctx.rng)?;
+    let wpos = data.sites.get(site_id)?.wpos;
+    drop(data);
+    Some((wpos, Content::Plain(util::site_name(ctx, site_id)?)))
+}
+
+/// Describes what the quest involves, used when offering it
+pub fn describe(quest: &Quest) -> Content {
+    match quest.objectives.first() {
+        Some(Objective::Kill { body, count }) => {
+            let name = NPC_NAMES
+                .read()
+                .get_default_name(body)
+                .unwrap_or_else(|| Content::localized("npc-quest-creatures"));
+            Content::localized_with_args("npc-quest-kill", [
+                ("count", Content::Plain(count.to_string())),
+                ("body", name),
+            ])
+        },
+        Some(objective @ Objective::Fetch { amount, .. }) => {
+            let name = objective.item_def().map_or_else(
+                || Content::localized("npc-quest-items"),
+                |item_def| {
+                    item_def
+                        .i18n(&ItemI18n::load_expect("common.item_i18n_manifest").read())
+                        .0
+                },
+            );
+            Content::localized_with_args("npc-quest-fetch", [
+                ("amount", Content::Plain(amount.to_string())),
+                ("item", name),
+            ])
+        },
+        Some(Objective::Escort { name, .. }) => {
+            Content::localized_with_args("npc-quest-escort", [("site", name.clone())])
+        },
+        Some(Objective::Visit { name, .. }) => {
+            Content::localized_with_args("npc-quest-visit", [("site", name.clone())])
+        },
+        Some(Objective::Slay { name, .. }) => {
+            Content::localized_with_args("npc-quest-slay", [("target", name.clone())])
+        },
+        None => Content::localized("npc-quest-none"),
+    }
+}
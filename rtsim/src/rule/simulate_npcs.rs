@@ -15,7 +15,7 @@ use rand_chacha::ChaChaRng;
 use slotmap::SecondaryMap;
 use tracing::{error, warn};
 use vek::{Clamp, Vec2};
-use world::{CONFIG, site::SiteKind};
+use world::site::SiteKind;
 
 pub struct SimulateNpcs;
 
@@ -153,7 +153,8 @@ fn on_death(ctx: EventCtx<SimulateNpcs, OnDeath>) {
                             })
                             .find(|pos| {
                                 ctx.world.sim().get(*pos).is_some_and(|c| {
-                                    !c.is_underwater() && (!is_gigas || c.temp < CONFIG.snow_temp)
+                                    !c.is_underwater()
+                                        && (!is_gigas || c.temp < ctx.world.sim().config.snow_temp)
                                 })
                             })
                             .unwrap_or(ctx.world.sim().get_size().as_() / 2);
@@ -262,6 +263,7 @@ fn on_tick(ctx: EventCtx<SimulateNpcs, OnTick>) {
                     NpcAction::Say(_, _) => {}, // Currently, just swallow interactions
                     NpcAction::Attack(_) => {}, // TODO: Implement simulated combat
                     NpcAction::Dialogue(_, _) => {},
+                    NpcAction::StartQuest(_, _) => {},
                 }
             }
 
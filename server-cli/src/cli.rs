@@ -3,9 +3,9 @@
 )]
 
 use clap::{Parser, builder::ValueParser};
-use common::comp;
-use server::persistence::SqlLogMode;
-use std::{str::FromStr, sync::mpsc::Sender};
+use common::{comp, weather::Weather};
+use server::{RuntimeSettings, RuntimeSettingsPatch, persistence::SqlLogMode};
+use std::{path::PathBuf, str::FromStr, sync::mpsc::Sender};
 use tracing::error;
 
 // Custom value parser for case-insensitive parsing of AdminRole
@@ -86,12 +86,30 @@ pub enum Message {
     SendGlobalMsg {
         msg: String,
     },
+    /// returns the weather forecast at a world position
+    Forecast {
+        x: f32,
+        y: f32,
+    },
+    /// Shows the settings that can be changed while the server is running
+    Settings,
+    /// Reloads the settings that can be changed while the server is running
+    /// from settings.ron
+    ReloadSettings,
+    /// Changes settings while the server is running and saves them to
+    /// settings.ron
+    #[command(skip)]
+    UpdateSettings(RuntimeSettingsPatch),
 }
 
 #[derive(Debug, Clone)]
 pub enum MessageReturn {
     Players(Vec<String>),
     Logs(Vec<String>),
+    /// Hours ahead and the weather predicted at that time
+    Forecast(Vec<(u32, Weather)>),
+    /// The settings after they were changed, or why they couldn't be
+    Settings(Result<RuntimeSettings, String>),
 }
 
 #[derive(Parser)]
@@ -124,6 +142,16 @@ pub enum ArgvCommand {
     /// Load an area, run the server for some time, and then exit (useful for
     /// profiling).
     Bench(BenchParams),
+    /// Print the contents of the terrain persistence region files and check
+    /// them for corruption, without starting the server.
+    InspectTerrain,
+    /// Replay recorded client inputs (see the `record_replay` setting) tick by
+    /// tick and report the first tick where the players' characters diverge.
+    Replay {
+        /// Directory of the recording, or its name in the `replays` directory
+        /// of the server data
+        recording: PathBuf,
+    },
 }
 
 #[derive(Parser)]
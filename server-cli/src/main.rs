@@ -30,6 +30,7 @@ use common::{
     clock::Clock,
     comp::{ChatType, Player},
     consts::MIN_RECOMMENDED_TOKIO_THREADS,
+    weather::WeatherForecast,
 };
 use common_base::span;
 use core::sync::atomic::{AtomicUsize, Ordering};
@@ -39,8 +40,8 @@ use std::{
     sync::{Arc, atomic::AtomicBool},
     time::{Duration, Instant},
 };
-use tokio::sync::Notify;
-use tracing::{info, trace};
+use tokio::{runtime::Runtime, sync::Notify};
+use tracing::{error, info, trace};
 
 lazy_static::lazy_static! {
     pub static ref LOG: TuiLog<'static> = TuiLog::default();
@@ -179,6 +180,18 @@ fn main() -> io::Result<()> {
                     },
                 };
             },
+            #[cfg(feature = "persistent_world")]
+            ArgvCommand::InspectTerrain => {
+                return inspect_terrain(&server_data_dir);
+            },
+            #[cfg(not(feature = "persistent_world"))]
+            ArgvCommand::InspectTerrain => {
+                tracing::error!("The server was compiled without terrain persistence");
+                return Ok(());
+            },
+            ArgvCommand::Replay { recording } => {
+                return replay(&server_data_dir, &recording, runtime);
+            },
             ArgvCommand::Bench(params) => {
                 bench = Some(params);
                 // If we are trying to benchmark, don't limit the server view distance.
@@ -283,6 +296,70 @@ fn main() -> io::Result<()> {
     Ok(())
 }
 
+#[cfg(feature = "persistent_world")]
+fn inspect_terrain(data_dir: &std::path::Path) -> io::Result<()> {
+    use server::terrain_persistence::{TerrainPersistence, region};
+
+    let path = TerrainPersistence::path_in(data_dir.to_owned());
+    let reports = region::inspect(&path)?;
+    let mut problems = 0;
+    for report in &reports {
+        println!(
+            "region {:>4} {:>4}: {:>3} chunks, {:>8} blocks, {:>9} bytes ({} live, {} stale)",
+            report.region.x,
+            report.region.y,
+            report.chunks,
+            report.blocks,
+            report.file_len,
+            report.live_bytes,
+            report.stale_bytes,
+        );
+        if report.invalid_chunks > 0 {
+            println!("    {} chunks could not be decoded", report.invalid_chunks);
+        }
+        if report.corrupt_tail {
+            println!("    ends with invalid data");
+        }
+        if report.invalid_chunks > 0 || report.corrupt_tail {
+            problems += 1;
+        }
+    }
+    println!(
+        "{} regions in {:?}, {} with problems",
+        reports.len(),
+        path,
+        problems
+    );
+    Ok(())
+}
+
+fn replay(
+    data_dir: &std::path::Path,
+    recording: &std::path::Path,
+    runtime: Arc<Runtime>,
+) -> io::Result<()> {
+    let dir = if recording.exists() {
+        recording.to_owned()
+    } else {
+        data_dir.join(server::replay::REPLAYS_DIR).join(recording)
+    };
+    let report = server::replay::replay(&dir, runtime, &|_| {})
+        .map_err(|e| io::Error::other(e.to_string()))?;
+    match report.divergence {
+        None => println!("Replayed {} ticks without divergence", report.ticks),
+        Some(divergence) => {
+            println!(
+                "State diverged in tick {} (replayed tick {}): recorded hash {:016x}, replayed \
+                 hash {:016x}",
+                divergence.tick, report.ticks, divergence.recorded_hash, divergence.replayed_hash
+            );
+            println!("Recorded events: {:?}", divergence.recorded_events);
+            println!("Replayed events: {:?}", divergence.replayed_events);
+        },
+    }
+    Ok(())
+}
+
 fn server_loop(
     mut server: Server,
     bench: Option<BenchParams>,
@@ -404,6 +481,27 @@ fn server_loop(
                     let msg = ChatType::Meta.into_plain_msg(msg);
                     server.state().send_chat(msg, false);
                 },
+                Message::Forecast { x, y } => {
+                    let forecast = server
+                        .state()
+                        .ecs()
+                        .try_fetch::<WeatherForecast>()
+                        .map(|forecast| forecast.at([x, y].into()).collect())
+                        .unwrap_or_default();
+                    let _ = response.send(MessageReturn::Forecast(forecast));
+                },
+                Message::Settings => {
+                    let settings = server.settings().runtime();
+                    let _ = response.send(MessageReturn::Settings(Ok(settings)));
+                },
+                Message::ReloadSettings => {
+                    let result = server.reload_settings().map_err(|e| e.to_string());
+                    let _ = response.send(MessageReturn::Settings(result));
+                },
+                Message::UpdateSettings(patch) => {
+                    let result = server.update_settings(patch).map_err(|e| e.to_string());
+                    let _ = response.send(MessageReturn::Settings(result));
+                },
             }
             false
         };
@@ -419,6 +517,21 @@ fn server_loop(
                     match msg_answ {
                         MessageReturn::Players(players) => info!("Players: {:?}", players),
                         MessageReturn::Logs(_) => info!("skipp sending logs to tui"),
+                        MessageReturn::Forecast(forecast) => {
+                            for (hours, weather) in forecast {
+                                info!(
+                                    "In {hours}h: {}, {:.0}°C",
+                                    weather.get_kind(),
+                                    weather.temperature
+                                );
+                            }
+                        },
+                        MessageReturn::Settings(Ok(settings)) => info!(
+                            "Settings:\n{}",
+                            ron::ser::to_string_pretty(&settings, Default::default())
+                                .unwrap_or_default()
+                        ),
+                        MessageReturn::Settings(Err(e)) => error!("{e}"),
                     };
                 }
             }
@@ -70,7 +70,7 @@ impl ShutdownCoordinator {
     pub fn abort_shutdown(&mut self, server: &mut Server) {
         if self.shutdown_initiated_at.is_some() {
             self.shutdown_initiated_at = None;
-            ShutdownCoordinator::send_msg(server, "The shutdown has been aborted".to_owned());
+            ShutdownCoordinator::send_msg(server, Content::localized("server-shutdown-aborted"));
         } else {
             error!("There is no shutdown in progress");
         }
@@ -132,11 +132,13 @@ impl ShutdownCoordinator {
     /// clients
     fn send_shutdown_msg(&mut self, server: &mut Server) {
         if let Some(time_until_shutdown) = self.time_until_shutdown() {
-            let msg = format!(
-                "{} in {}",
-                self.shutdown_message,
-                ShutdownCoordinator::duration_to_text(time_until_shutdown)
-            );
+            let msg = Content::localized_with_args("server-shutdown-countdown", [
+                ("message", self.shutdown_message.clone()),
+                (
+                    "time",
+                    ShutdownCoordinator::duration_to_text(time_until_shutdown),
+                ),
+            ]);
             ShutdownCoordinator::send_msg(server, msg);
             self.last_shutdown_msg = Instant::now();
         }
@@ -154,12 +156,9 @@ impl ShutdownCoordinator {
     }
 
     /// Logs and sends a message to all connected clients
-    fn send_msg(server: &mut Server, msg: String) {
-        info!("{}", &msg);
-        server.notify_players(ServerGeneral::server_msg(
-            ChatType::CommandError,
-            Content::Plain(msg),
-        ));
+    fn send_msg(server: &mut Server, msg: Content) {
+        info!("{}", server.localize(&msg));
+        server.notify_players(ServerGeneral::server_msg(ChatType::CommandError, msg));
     }
 
     /// Converts a `Duration` into text in the format XsXm for example 1 minute
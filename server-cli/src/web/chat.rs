@@ -2,20 +2,24 @@ use axum::{
     Json, Router,
     extract::{ConnectInfo, Query, Request, State},
     middleware::Next,
-    response::{IntoResponse, Response},
-    routing::get,
+    response::{
+        IntoResponse, Response,
+        sse::{Event, KeepAlive, Sse},
+    },
+    routing::{get, post},
 };
 use chrono::DateTime;
+use futures_util::{Stream, stream};
 use hyper::StatusCode;
 use serde::{Deserialize, Deserializer};
-use server::chat::ChatCache;
+use server::chat::{ChatCache, ChatParties, ExternalChatMessage, ImportError};
 use std::{
     collections::HashSet,
     net::{IpAddr, SocketAddr},
     str::FromStr,
     sync::Arc,
 };
-use tokio::sync::Mutex;
+use tokio::sync::{Mutex, broadcast::error::RecvError};
 
 /// Keep Size small, so we dont have to Clone much for each request.
 #[derive(Clone)]
@@ -71,6 +75,8 @@ pub fn router(cache: ChatCache, secret_token: Option<String>) -> Router {
     let ip_addrs = IpAddresses::default();
     Router::new()
         .route("/history", get(history))
+        .route("/stream", get(live))
+        .route("/send", post(send))
         .layer(axum::middleware::from_fn_with_state(ip_addrs, log_users))
         .layer(axum::middleware::from_fn_with_state(token, validate_secret))
         .with_state(cache)
@@ -120,3 +126,61 @@ async fn history(
     };
     Ok(Json(filtered))
 }
+
+#[derive(Debug, Deserialize)]
+struct StreamParams {
+    #[serde(default, deserialize_with = "empty_string_as_none")]
+    /// Leave out the messages bridged from this source, so that a relay
+    /// doesn't receive its own messages
+    exclude_source: Option<String>,
+}
+
+/// Streams the chat messages as server-sent events as they are sent
+async fn live(
+    State(cache): State<ChatCache>,
+    Query(params): Query<StreamParams>,
+) -> Sse<impl Stream<Item = Result<Event, axum::Error>>> {
+    let messages = stream::unfold(cache.subscribe(), move |mut receiver| {
+        let exclude_source = params.exclude_source.clone();
+        async move {
+            loop {
+                match receiver.recv().await {
+                    Ok(msg) => match &msg.parties {
+                        ChatParties::Bridged(user, _)
+                            if exclude_source.as_ref() == Some(&user.source) => {},
+                        _ => return Some((Event::default().json_data(&msg), receiver)),
+                    },
+                    // Let the consumer know it missed messages, it can still get them from
+                    // the history
+                    Err(RecvError::Lagged(skipped)) => {
+                        let event = Event::default().event("lagged").data(skipped.to_string());
+                        return Some((Ok(event), receiver));
+                    },
+                    Err(RecvError::Closed) => return None,
+                }
+            }
+        }
+    });
+    Sse::new(messages).keep_alive(KeepAlive::default())
+}
+
+/// Sends a message from an external chat into the world or a faction chat
+async fn send(
+    State(cache): State<ChatCache>,
+    Json(message): Json<ExternalChatMessage>,
+) -> Result<StatusCode, (StatusCode, String)> {
+    if message.user.source.is_empty() || message.user.alias.is_empty() || message.msg.is_empty() {
+        return Err((
+            StatusCode::BAD_REQUEST,
+            "source, alias and msg must not be empty".to_string(),
+        ));
+    }
+    match cache.import(message).await {
+        Ok(()) => Ok(StatusCode::NO_CONTENT),
+        Err(ImportError::Rejected(reason)) => Err((StatusCode::UNPROCESSABLE_ENTITY, reason)),
+        Err(ImportError::Unavailable) => Err((
+            StatusCode::SERVICE_UNAVAILABLE,
+            "server is not running".to_string(),
+        )),
+    }
+}
@@ -1,14 +1,16 @@
 use crate::cli::{Message, MessageReturn};
 use axum::{
     Json, Router,
-    extract::{ConnectInfo, Request, State},
+    extract::{ConnectInfo, Query, Request, State},
     http::header::COOKIE,
     middleware::Next,
     response::{IntoResponse, Response},
     routing::{get, post},
 };
+use common::weather::{Weather, WeatherKind};
 use hyper::StatusCode;
-use serde::Deserialize;
+use serde::{Deserialize, Serialize};
+use server::RuntimeSettingsPatch;
 use std::{
     collections::HashSet,
     net::{IpAddr, SocketAddr},
@@ -72,7 +74,9 @@ pub fn router(web_ui_request_s: UiRequestSender, secret_token: String) -> Router
     Router::new()
         .route("/players", get(players))
         .route("/logs", get(logs))
+        .route("/forecast", get(forecast))
         .route("/send_global_msg", post(send_global_msg))
+        .route("/settings", get(settings).patch(update_settings))
         .layer(axum::middleware::from_fn_with_state(ip_addrs, log_users))
         .layer(axum::middleware::from_fn_with_state(token, validate_secret))
         .with_state(web_ui_request_s)
@@ -106,6 +110,52 @@ async fn logs(
     }
 }
 
+#[derive(Deserialize)]
+struct ForecastParams {
+    x: f32,
+    y: f32,
+}
+
+#[derive(Serialize)]
+struct ForecastStep {
+    hours_ahead: u32,
+    kind: WeatherKind,
+    #[serde(flatten)]
+    weather: Weather,
+}
+
+async fn forecast(
+    State(web_ui_request_s): State<UiRequestSender>,
+    Query(params): Query<ForecastParams>,
+) -> Result<impl IntoResponse, StatusCode> {
+    let (sender, receiver) = tokio::sync::oneshot::channel();
+    let _ = web_ui_request_s
+        .send((
+            Message::Forecast {
+                x: params.x,
+                y: params.y,
+            },
+            sender,
+        ))
+        .await;
+    match receiver
+        .await
+        .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?
+    {
+        MessageReturn::Forecast(forecast) => Ok(Json(
+            forecast
+                .into_iter()
+                .map(|(hours_ahead, weather)| ForecastStep {
+                    hours_ahead,
+                    kind: weather.get_kind(),
+                    weather,
+                })
+                .collect::<Vec<_>>(),
+        )),
+        _ => Err(StatusCode::INTERNAL_SERVER_ERROR),
+    }
+}
+
 #[derive(Deserialize)]
 struct SendWorldMsgBody {
     msg: String,
@@ -121,3 +171,33 @@ async fn send_global_msg(
         .await;
     Ok(())
 }
+
+async fn settings(
+    State(web_ui_request_s): State<UiRequestSender>,
+) -> Result<impl IntoResponse, StatusCode> {
+    let (sender, receiver) = tokio::sync::oneshot::channel();
+    let _ = web_ui_request_s.send((Message::Settings, sender)).await;
+    match receiver
+        .await
+        .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?
+    {
+        MessageReturn::Settings(Ok(settings)) => Ok(Json(settings)),
+        _ => Err(StatusCode::INTERNAL_SERVER_ERROR),
+    }
+}
+
+async fn update_settings(
+    State(web_ui_request_s): State<UiRequestSender>,
+    Json(patch): Json<RuntimeSettingsPatch>,
+) -> Result<impl IntoResponse, (StatusCode, String)> {
+    let internal_error = || (StatusCode::INTERNAL_SERVER_ERROR, String::new());
+    let (sender, receiver) = tokio::sync::oneshot::channel();
+    let _ = web_ui_request_s
+        .send((Message::UpdateSettings(patch), sender))
+        .await;
+    match receiver.await.map_err(|_| internal_error())? {
+        MessageReturn::Settings(Ok(settings)) => Ok(Json(settings)),
+        MessageReturn::Settings(Err(e)) => Err((StatusCode::BAD_REQUEST, e)),
+        _ => Err(internal_error()),
+    }
+}
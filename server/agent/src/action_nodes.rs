@@ -4,6 +4,7 @@ use crate::{
         PARTIAL_PATH_DIST, SEPARATION_BIAS, SEPARATION_DIST, STD_AWARENESS_DECAY_RATE,
     },
     data::{AgentData, AgentEmitters, AttackData, Path, ReadData, Tactic, TargetData},
+    tactics::tactic_for_ability_spec,
     util::{
         aim_projectile, are_our_owners_hostile, entities_have_line_of_sight, get_attacker,
         get_entity_by_id, is_dead_or_invulnerable, is_dressed_as_cultist, is_invulnerable,
@@ -1234,7 +1235,6 @@ impl AgentData<'_> {
                                 radius: 6,
                                 circle_time: 1,
                             },
-                            "Quad Med Basic" => Tactic::QuadMedBasic,
                             "Quad Med Hoof" => Tactic::QuadMedHoof,
                             "ClaySteed" => Tactic::ClaySteed,
                             "Rocksnapper" => Tactic::Rocksnapper,
@@ -1246,7 +1246,6 @@ impl AgentData<'_> {
                             "Organ" => Tactic::OrganAura,
                             "Quad Low Tail" | "Husk Brute" => Tactic::TailSlap,
                             "Quad Low Quick" => Tactic::QuadLowQuick,
-                            "Quad Low Basic" => Tactic::QuadLowBasic,
                             "Theropod Basic" | "Theropod Bird" | "Theropod Small" => {
                                 Tactic::Theropod
                             },
@@ -1267,13 +1266,10 @@ impl AgentData<'_> {
                             "Flame Wyvern" | "Frost Wyvern" | "Cloud Wyvern" | "Sea Wyvern"
                             | "Weald Wyvern" => Tactic::Wyvern,
                             "Bird Medium Basic" => Tactic::BirdMediumBasic,
-                            "Bushly" | "Cactid" | "Irrwurz" | "Driggle" | "Mossy Snail"
-                            | "Strigoi Claws" | "Harlequin" => Tactic::SimpleDouble,
                             "Clay Golem" => Tactic::ClayGolem,
                             "Ancient Effigy" => Tactic::AncientEffigy,
                             "TerracottaStatue" | "Mogwai" => Tactic::TerracottaStatue,
                             "TerracottaBesieger" => Tactic::Bow,
-                            "TerracottaDemolisher" => Tactic::SimpleDouble,
                             "TerracottaPunisher" => Tactic::SimpleMelee,
                             "TerracottaPursuer" => Tactic::SwordSimple,
                             "Cursekeeper" => Tactic::Cursekeeper,
@@ -1334,7 +1330,8 @@ impl AgentData<'_> {
                             "Vampire Bat" => Tactic::VampireBat,
                             "Bloodmoon Heiress" => Tactic::BloodmoonHeiress,
 
-                            _ => Tactic::SimpleMelee,
+                            _ => tactic_for_ability_spec(spec)
+                                .map_or(Tactic::SimpleMelee, Tactic::Data),
                         },
                         AbilitySpec::Tool(tool_kind) => tool_tactic(*tool_kind),
                     }
@@ -1589,13 +1586,6 @@ impl AgentData<'_> {
                 tgt_data,
                 read_data,
             ),
-            Tactic::QuadLowBasic => self.handle_quadlow_basic_attack(
-                agent,
-                controller,
-                &attack_data,
-                tgt_data,
-                read_data,
-            ),
             Tactic::QuadMedJump => self.handle_quadmed_jump_attack(
                 agent,
                 controller,
@@ -1603,13 +1593,6 @@ impl AgentData<'_> {
                 tgt_data,
                 read_data,
             ),
-            Tactic::QuadMedBasic => self.handle_quadmed_basic_attack(
-                agent,
-                controller,
-                &attack_data,
-                tgt_data,
-                read_data,
-            ),
             Tactic::QuadMedHoof => self.handle_quadmed_hoof_attack(
                 agent,
                 controller,
@@ -1715,13 +1698,6 @@ impl AgentData<'_> {
             Tactic::BirdMediumBasic => {
                 self.handle_simple_melee(agent, controller, &attack_data, tgt_data, read_data, rng)
             },
-            Tactic::SimpleDouble => self.handle_simple_double_attack(
-                agent,
-                controller,
-                &attack_data,
-                tgt_data,
-                read_data,
-            ),
             Tactic::Jiangshi => {
                 self.handle_jiangshi_attack(agent, controller, &attack_data, tgt_data, read_data)
             },
@@ -1918,6 +1894,15 @@ impl AgentData<'_> {
                 read_data,
                 rng,
             ),
+            Tactic::Data(tactic) => self.handle_data_tactic(
+                agent,
+                controller,
+                &attack_data,
+                tgt_data,
+                read_data,
+                rng,
+                tactic,
+            ),
             Tactic::RandomAbilities {
                 primary,
                 secondary,
@@ -2223,8 +2208,14 @@ impl AgentData<'_> {
             perception_dist_multiplier_from_stealth(other_inventory, other_char_state, self.msm)
         };
 
+        // Fog, rain and snow make it harder to see far
+        let weather_visibility = read_data.weather.as_ref().map_or(1.0, |weather| {
+            weather.get_interpolated(self.pos.0.xy()).visibility()
+        });
+
         let within_sight_dist = {
-            let sight_dist = agent.psyche.sight_dist * other_stealth_multiplier;
+            let sight_dist =
+                agent.psyche.sight_dist * other_stealth_multiplier * weather_visibility;
             let dist_sqrd = other_pos.0.distance_squared(self.pos.0);
 
             dist_sqrd < sight_dist.powi(2)
@@ -1,9 +1,11 @@
 use crate::{
     consts::MAX_PATH_DIST,
     data::*,
+    tactics::{LoadedTactic, Movement, TacticContext},
     util::{entities_have_line_of_sight, handle_attack_aggression},
 };
 use common::{
+    assets::AssetExt,
     combat::{self, AttackSource},
     comp::{
         Ability, AbilityInput, Agent, CharacterAbility, CharacterState, ControlAction,
@@ -2954,44 +2956,6 @@ impl AgentData<'_> {
         }
     }
 
-    pub fn handle_quadlow_basic_attack(
-        &self,
-        agent: &mut Agent,
-        controller: &mut Controller,
-        attack_data: &AttackData,
-        tgt_data: &TargetData,
-        read_data: &ReadData,
-    ) {
-        enum ActionStateTimers {
-            TimerQuadLowBasic = 0,
-        }
-
-        if attack_data.angle < 70.0
-            && attack_data.dist_sqrd < (1.3 * attack_data.min_attack_dist).powi(2)
-        {
-            controller.inputs.move_dir = Vec2::zero();
-            if agent.combat_state.timers[ActionStateTimers::TimerQuadLowBasic as usize] > 5.0 {
-                agent.combat_state.timers[ActionStateTimers::TimerQuadLowBasic as usize] = 0.0;
-            } else if agent.combat_state.timers[ActionStateTimers::TimerQuadLowBasic as usize] > 2.0
-            {
-                controller.push_basic_input(InputKind::Secondary);
-                agent.combat_state.timers[ActionStateTimers::TimerQuadLowBasic as usize] +=
-                    read_data.dt.0;
-            } else {
-                controller.push_basic_input(InputKind::Primary);
-                agent.combat_state.timers[ActionStateTimers::TimerQuadLowBasic as usize] +=
-                    read_data.dt.0;
-            }
-        } else {
-            let path = if attack_data.dist_sqrd < MAX_PATH_DIST.powi(2) {
-                Path::Separate
-            } else {
-                Path::Partial
-            };
-            self.path_toward_target(agent, controller, tgt_data.pos.0, read_data, path, None);
-        }
-    }
-
     pub fn handle_quadmed_jump_attack(
         &self,
         agent: &mut Agent,
@@ -3045,53 +3009,6 @@ impl AgentData<'_> {
         }
     }
 
-    pub fn handle_quadmed_basic_attack(
-        &self,
-        agent: &mut Agent,
-        controller: &mut Controller,
-        attack_data: &AttackData,
-        tgt_data: &TargetData,
-        read_data: &ReadData,
-    ) {
-        enum ActionStateTimers {
-            TimerQuadMedBasic = 0,
-        }
-
-        if attack_data.angle < 90.0 && attack_data.in_min_range() {
-            controller.inputs.move_dir = Vec2::zero();
-            if agent.combat_state.timers[ActionStateTimers::TimerQuadMedBasic as usize] < 2.0 {
-                controller.push_basic_input(InputKind::Secondary);
-                agent.combat_state.timers[ActionStateTimers::TimerQuadMedBasic as usize] +=
-                    read_data.dt.0;
-            } else if agent.combat_state.timers[ActionStateTimers::TimerQuadMedBasic as usize] < 3.0
-            {
-                controller.push_basic_input(InputKind::Primary);
-                agent.combat_state.timers[ActionStateTimers::TimerQuadMedBasic as usize] +=
-                    read_data.dt.0;
-            } else {
-                agent.combat_state.timers[ActionStateTimers::TimerQuadMedBasic as usize] = 0.0;
-            }
-        } else if attack_data.dist_sqrd < MAX_PATH_DIST.powi(2) {
-            self.path_toward_target(
-                agent,
-                controller,
-                tgt_data.pos.0,
-                read_data,
-                Path::Separate,
-                None,
-            );
-        } else {
-            self.path_toward_target(
-                agent,
-                controller,
-                tgt_data.pos.0,
-                read_data,
-                Path::Partial,
-                None,
-            );
-        }
-    }
-
     pub fn handle_quadmed_hoof_attack(
         &self,
         agent: &mut Agent,
@@ -7458,41 +7375,65 @@ impl AgentData<'_> {
         }
     }
 
-    pub fn handle_simple_double_attack(
+    /// Executes a tactic described by a
+    /// [`TacticSpec`](crate::tactics::TacticSpec) asset
+    pub fn handle_data_tactic(
         &self,
         agent: &mut Agent,
         controller: &mut Controller,
         attack_data: &AttackData,
         tgt_data: &TargetData,
         read_data: &ReadData,
+        rng: &mut impl Rng,
+        tactic: LoadedTactic,
     ) {
-        const MAX_ATTACK_RANGE: f32 = 20.0;
+        let spec = tactic.0.read();
+        let ctx = TacticContext {
+            dt: read_data.dt.0,
+            dist_sqrd: attack_data.dist_sqrd,
+            min_attack_dist: attack_data.min_attack_dist,
+            angle: attack_data.angle,
+            health_fraction: self.health.map_or(1.0, |h| h.fraction()),
+            target_health_fraction: tgt_data.health.map_or(1.0, |h| h.fraction()),
+            energy: self.energy.current(),
+            line_of_sight: spec.needs_line_of_sight()
+                && entities_have_line_of_sight(
+                    self.pos,
+                    self.body,
+                    self.scale,
+                    tgt_data.pos,
+                    tgt_data.body,
+                    tgt_data.scale,
+                    read_data,
+                ),
+            on_ground: self.physics_state.on_ground.is_some(),
+        };
+        let decision = spec.decide(&mut agent.combat_state, &ctx, rng);
 
-        if attack_data.angle < 60.0 && attack_data.dist_sqrd < MAX_ATTACK_RANGE.powi(2) {
-            controller.inputs.move_dir = Vec2::zero();
-            if attack_data.in_min_range() {
-                controller.push_basic_input(InputKind::Primary);
-            } else {
-                controller.push_basic_input(InputKind::Secondary);
-            }
-        } else if attack_data.dist_sqrd < MAX_PATH_DIST.powi(2) {
-            self.path_toward_target(
-                agent,
-                controller,
-                tgt_data.pos.0,
-                read_data,
-                Path::Separate,
-                None,
-            );
-        } else {
-            self.path_toward_target(
-                agent,
-                controller,
-                tgt_data.pos.0,
-                read_data,
-                Path::Partial,
-                None,
-            );
+        if let Some(input) = decision.input {
+            controller.push_basic_input(input);
+        }
+        match decision.movement {
+            Movement::Stop => controller.inputs.move_dir = Vec2::zero(),
+            Movement::Retreat => {
+                controller.inputs.move_dir = (self.pos.0 - tgt_data.pos.0)
+                    .xy()
+                    .try_normalized()
+                    .unwrap_or_else(Vec2::zero);
+            },
+            Movement::Keep => {},
+            Movement::Approach | Movement::Chase => {
+                if let Some(path) = decision.movement.path(attack_data.dist_sqrd) {
+                    self.path_toward_target(
+                        agent,
+                        controller,
+                        tgt_data.pos.0,
+                        read_data,
+                        path,
+                        None,
+                    );
+                }
+            },
         }
     }
 
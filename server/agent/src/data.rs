@@ -28,6 +28,7 @@ use common::{
     states::utils::{ForcedMovement, StageSection},
     terrain::TerrainGrid,
     uid::{IdMaps, Uid},
+    weather::WeatherGrid,
 };
 use common_base::dev_panic;
 use specs::{Entities, Entity as EcsEntity, Read, ReadExpect, ReadStorage, SystemData, shred};
@@ -37,6 +38,7 @@ event_emitters! {
         chat: event::ChatEvent,
         sound: event::SoundEvent,
         process_trade_action: event::ProcessTradeActionEvent,
+        start_quest: event::StartQuestEvent,
     }
 }
 
@@ -194,7 +196,6 @@ pub enum Tactic {
     RotatingTurret,
     RadialTurret,
     FieryTornado,
-    SimpleDouble,
     ClayGolem,
     ClaySteed,
     AncientEffigy,
@@ -204,6 +205,8 @@ pub enum Tactic {
         secondary: u8,
         abilities: [u8; BASE_ABILITY_LIMIT],
     },
+    // Tactic loaded from an asset
+    Data(crate::tactics::LoadedTactic),
 
     // Tool specific tactics
     Axe,
@@ -223,10 +226,8 @@ pub enum Tactic {
     QuadLowRanged,
     TailSlap,
     QuadLowQuick,
-    QuadLowBasic,
     QuadLowBeam,
     QuadMedJump,
-    QuadMedBasic,
     QuadMedHoof,
     Theropod,
     BirdLargeBreathe,
@@ -426,6 +427,7 @@ pub struct ReadData<'a> {
     pub is_volume_riders: ReadStorage<'a, Is<VolumeRider>>,
     pub interactors: ReadStorage<'a, Interactors>,
     pub time_of_day: Read<'a, TimeOfDay>,
+    pub weather: Option<Read<'a, WeatherGrid>>,
     pub light_emitter: ReadStorage<'a, LightEmitter>,
     #[cfg(feature = "worldgen")]
     pub world: ReadExpect<'a, std::sync::Arc<world::World>>,
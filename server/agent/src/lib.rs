@@ -7,6 +7,7 @@ pub mod action_nodes;
 pub mod attack;
 pub mod consts;
 pub mod data;
+pub mod tactics;
 pub mod util;
 
 #[cfg(feature = "use-dyn-lib")]
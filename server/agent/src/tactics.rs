@@ -0,0 +1,469 @@
+//! Data-driven combat tactics.
+//!
+//! A [`TacticSpec`] describes when an agent should use each of its abilities
+//! and how it should position itself, and is loaded from the
+//! `common.tactics` asset directory. Creatures are mapped to a tactic through
+//! the tactic manifest using the name of their custom ability spec, so new
+//! creatures can be given a tactic without touching the agent code.
+
+use crate::{consts::MAX_PATH_DIST, data::Path};
+use common::{
+    assets::{self, Asset, AssetExt, AssetHandle},
+    comp::{
+        InputKind,
+        agent::{ACTIONSTATE_NUMBER_OF_CONCURRENT_TIMERS, ActionState},
+    },
+};
+use lazy_static::lazy_static;
+use rand::Rng;
+use serde::Deserialize;
+use std::{
+    collections::{HashMap, HashSet},
+    sync::{Mutex, RwLock},
+};
+use tracing::warn;
+
+/// Manifest mapping custom ability spec names to tactic asset specifiers
+const TACTIC_MANIFEST: &str = "common.tactics.manifest";
+
+/// Combat timer used to track the progress through a combo
+const COMBO_TIMER: usize = 0;
+/// Combat timers from this index onwards track action cooldowns
+const COOLDOWN_TIMERS_START: usize = 1;
+/// Maximum number of actions in a tactic that can have a cooldown
+pub const MAX_COOLDOWNS: usize = ACTIONSTATE_NUMBER_OF_CONCURRENT_TIMERS - COOLDOWN_TIMERS_START;
+
+lazy_static! {
+    /// Tactics which failed to load or are invalid, so that it is only reported
+    /// once
+    static ref REPORTED_TACTICS: Mutex<HashSet<String>> = Mutex::default();
+    /// Tactic used by each custom ability spec, resolved the first time an
+    /// agent with that spec fights
+    static ref RESOLVED_TACTICS: RwLock<HashMap<String, Option<LoadedTactic>>> =
+        RwLock::default();
+}
+
+/// A tactic which was loaded and validated
+#[derive(Copy, Clone)]
+pub struct LoadedTactic(pub AssetHandle<TacticSpec>);
+
+impl PartialEq for LoadedTactic {
+    fn eq(&self, other: &Self) -> bool { std::ptr::eq(self.0, other.0) }
+}
+
+impl Eq for LoadedTactic {}
+
+/// The tactic used by agents with the given custom ability spec, if one
+/// exists and is valid. The tactic is only looked up in the manifest and
+/// validated once for each spec.
+pub fn tactic_for_ability_spec(spec: &str) -> Option<LoadedTactic> {
+    if let Some(tactic) = RESOLVED_TACTICS.read().unwrap().get(spec) {
+        return *tactic;
+    }
+    let tactic = assets::Ron::<HashMap<String, String>>::load(TACTIC_MANIFEST)
+        .ok()
+        .and_then(|manifest| manifest.read().0.get(spec).cloned())
+        .and_then(|specifier| load_tactic(&specifier))
+        .map(LoadedTactic);
+    RESOLVED_TACTICS
+        .write()
+        .unwrap()
+        .insert(spec.to_owned(), tactic);
+    tactic
+}
+
+/// Loads a tactic, `None` if it can't be loaded or isn't valid. Failures are
+/// logged the first time they happen for each tactic.
+fn load_tactic(specifier: &str) -> Option<AssetHandle<TacticSpec>> {
+    let result = TacticSpec::load(specifier)
+        .map_err(|err| err.to_string())
+        .and_then(|handle| handle.read().validate().map(|()| handle));
+    match result {
+        Ok(handle) => Some(handle),
+        Err(err) => {
+            if REPORTED_TACTICS
+                .lock()
+                .unwrap()
+                .insert(specifier.to_owned())
+            {
+                warn!(?err, "Tactic {specifier} can't be used");
+            }
+            None
+        },
+    }
+}
+
+#[derive(Clone, Debug, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct TacticSpec {
+    /// Duration in seconds of a repeating combo. While set, the combo timer
+    /// advances whenever an action with an input is performed and restarts
+    /// once it reaches this duration.
+    #[serde(default)]
+    pub combo_duration: Option<f32>,
+    /// Actions are considered in order, the first one whose conditions are
+    /// all met is performed
+    pub actions: Vec<TacticAction>,
+    /// How to move when none of the actions can be performed
+    #[serde(default = "Movement::approach")]
+    pub fallback: Movement,
+}
+
+impl Asset for TacticSpec {
+    type Loader = assets::RonLoader;
+
+    const EXTENSION: &'static str = "ron";
+}
+
+#[derive(Clone, Debug, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct TacticAction {
+    /// Input pressed when performing the action, `None` for actions that only
+    /// reposition the agent
+    #[serde(default)]
+    pub input: Option<InputKind>,
+    #[serde(default)]
+    pub conditions: Vec<Condition>,
+    #[serde(default = "Movement::stop")]
+    pub movement: Movement,
+    /// Seconds after this action is performed before it can be performed
+    /// again
+    #[serde(default)]
+    pub cooldown: Option<f32>,
+}
+
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Deserialize)]
+pub enum Movement {
+    /// Stand still
+    Stop,
+    /// Path towards the target, keeping some separation from allies when close
+    Approach,
+    /// Path directly towards the target
+    Chase,
+    /// Move directly away from the target
+    Retreat,
+    /// Leave movement untouched
+    Keep,
+}
+
+impl Movement {
+    fn approach() -> Self { Self::Approach }
+
+    fn stop() -> Self { Self::Stop }
+}
+
+#[derive(Clone, Debug, Deserialize)]
+pub enum Condition {
+    /// Angle to the target in degrees is less than this
+    MaxAngle(f32),
+    /// Target is within this multiple of the minimum attack distance
+    InAttackRange(f32),
+    /// Target is further than this multiple of the minimum attack distance
+    OutOfAttackRange(f32),
+    /// Target is within this many blocks
+    WithinDistance(f32),
+    /// Target is further than this many blocks
+    BeyondDistance(f32),
+    /// Own health fraction is below this
+    HealthBelow(f32),
+    /// Own health fraction is above this
+    HealthAbove(f32),
+    /// Target health fraction is below this
+    TargetHealthBelow(f32),
+    /// Own energy is at least this much
+    EnergyAbove(f32),
+    /// Target can be seen
+    LineOfSight,
+    /// Agent is standing on the ground
+    OnGround,
+    /// Combo timer is within `[start, end)` seconds
+    ComboWindow(f32, f32),
+    /// Passes with this probability each tick
+    Chance(f32),
+}
+
+/// Everything a tactic needs to know about the agent and its target for a
+/// single tick
+#[derive(Copy, Clone, Debug)]
+pub struct TacticContext {
+    pub dt: f32,
+    pub dist_sqrd: f32,
+    pub min_attack_dist: f32,
+    pub angle: f32,
+    pub health_fraction: f32,
+    pub target_health_fraction: f32,
+    pub energy: f32,
+    pub line_of_sight: bool,
+    pub on_ground: bool,
+}
+
+/// What the agent should do this tick
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub struct TacticDecision {
+    pub input: Option<InputKind>,
+    pub movement: Movement,
+}
+
+impl Condition {
+    fn is_met(&self, ctx: &TacticContext, state: &ActionState, rng: &mut impl Rng) -> bool {
+        match *self {
+            Condition::MaxAngle(angle) => ctx.angle < angle,
+            Condition::InAttackRange(mult) => ctx.dist_sqrd < (mult * ctx.min_attack_dist).powi(2),
+            Condition::OutOfAttackRange(mult) => {
+                ctx.dist_sqrd >= (mult * ctx.min_attack_dist).powi(2)
+            },
+            Condition::WithinDistance(dist) => ctx.dist_sqrd < dist.powi(2),
+            Condition::BeyondDistance(dist) => ctx.dist_sqrd >= dist.powi(2),
+            Condition::HealthBelow(frac) => ctx.health_fraction < frac,
+            Condition::HealthAbove(frac) => ctx.health_fraction > frac,
+            Condition::TargetHealthBelow(frac) => ctx.target_health_fraction < frac,
+            Condition::EnergyAbove(energy) => ctx.energy >= energy,
+            Condition::LineOfSight => ctx.line_of_sight,
+            Condition::OnGround => ctx.on_ground,
+            Condition::ComboWindow(start, end) => (start..end).contains(&state.timers[COMBO_TIMER]),
+            Condition::Chance(chance) => rng.gen_bool(chance.clamp(0.0, 1.0) as f64),
+        }
+    }
+}
+
+impl TacticSpec {
+    /// Whether any action needs line of sight to be checked, which is too
+    /// expensive to compute every tick for every agent
+    pub fn needs_line_of_sight(&self) -> bool {
+        self.actions.iter().any(|action| {
+            action
+                .conditions
+                .iter()
+                .any(|c| matches!(c, Condition::LineOfSight))
+        })
+    }
+
+    /// Checks that the tactic can be executed with the available combat state
+    pub fn validate(&self) -> Result<(), String> {
+        let cooldowns = self
+            .actions
+            .iter()
+            .filter(|action| action.cooldown.is_some())
+            .count();
+        if cooldowns > MAX_COOLDOWNS {
+            return Err(format!(
+                "{cooldowns} actions have cooldowns, at most {MAX_COOLDOWNS} are supported"
+            ));
+        }
+        if self.combo_duration.is_none()
+            && self.actions.iter().any(|action| {
+                action
+                    .conditions
+                    .iter()
+                    .any(|c| matches!(c, Condition::ComboWindow(..)))
+            })
+        {
+            return Err("combo windows are used without a combo duration".to_string());
+        }
+        Ok(())
+    }
+
+    /// Picks the action to perform this tick, updating the combo and
+    /// cooldown timers stored in the agent's combat state
+    pub fn decide(
+        &self,
+        state: &mut ActionState,
+        ctx: &TacticContext,
+        rng: &mut impl Rng,
+    ) -> TacticDecision {
+        for timer in &mut state.timers[COOLDOWN_TIMERS_START..] {
+            *timer = (*timer - ctx.dt).max(0.0);
+        }
+        if let Some(duration) = self.combo_duration
+            && state.timers[COMBO_TIMER] >= duration
+        {
+            state.timers[COMBO_TIMER] = 0.0;
+        }
+
+        let mut cooldown_timers = COOLDOWN_TIMERS_START..state.timers.len();
+        for action in &self.actions {
+            let cooldown_timer = action.cooldown.and_then(|_| cooldown_timers.next());
+            if cooldown_timer.is_some_and(|timer| state.timers[timer] > 0.0) {
+                continue;
+            }
+            if action.conditions.iter().all(|c| c.is_met(ctx, state, rng)) {
+                if let (Some(cooldown), Some(timer)) = (action.cooldown, cooldown_timer) {
+                    state.timers[timer] = cooldown;
+                }
+                if self.combo_duration.is_some() && action.input.is_some() {
+                    state.timers[COMBO_TIMER] += ctx.dt;
+                }
+                return TacticDecision {
+                    input: action.input,
+                    movement: action.movement,
+                };
+            }
+        }
+
+        TacticDecision {
+            input: None,
+            movement: self.fallback,
+        }
+    }
+}
+
+impl Movement {
+    /// Path used to approach a target at the given distance
+    pub fn path(self, dist_sqrd: f32) -> Option<Path> {
+        match self {
+            Movement::Approach if dist_sqrd < MAX_PATH_DIST.powi(2) => Some(Path::Separate),
+            Movement::Approach => Some(Path::Partial),
+            Movement::Chase => Some(Path::Full),
+            Movement::Stop | Movement::Retreat | Movement::Keep => None,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rand::{SeedableRng, rngs::SmallRng};
+    use vek::Vec2;
+
+    const DT: f32 = 0.1;
+    const MIN_ATTACK_DIST: f32 = 2.0;
+    const SPEED: f32 = 0.5;
+
+    /// Drives a tactic against a dummy target standing at the origin, starting
+    /// from `start` blocks away, and returns the inputs pressed each tick
+    fn drive(spec: &TacticSpec, start: f32, ticks: usize) -> (Vec<Option<InputKind>>, f32) {
+        let mut rng = SmallRng::seed_from_u64(0);
+        let mut state = ActionState::default();
+        let mut pos = Vec2::new(start, 0.0);
+        let inputs = (0..ticks)
+            .map(|_| {
+                let ctx = TacticContext {
+                    dt: DT,
+                    dist_sqrd: pos.magnitude_squared(),
+                    min_attack_dist: MIN_ATTACK_DIST,
+                    angle: 0.0,
+                    health_fraction: 1.0,
+                    target_health_fraction: 1.0,
+                    energy: 100.0,
+                    line_of_sight: true,
+                    on_ground: true,
+                };
+                let decision = spec.decide(&mut state, &ctx, &mut rng);
+                let dir = -pos.try_normalized().unwrap_or_default();
+                match decision.movement {
+                    Movement::Approach | Movement::Chase => pos += dir * SPEED.min(pos.magnitude()),
+                    Movement::Retreat => pos -= dir * SPEED,
+                    Movement::Stop | Movement::Keep => {},
+                }
+                decision.input
+            })
+            .collect();
+        (inputs, pos.magnitude())
+    }
+
+    fn load(specifier: &str) -> TacticSpec { TacticSpec::load_expect_cloned(specifier) }
+
+    #[test]
+    fn all_tactics_valid() {
+        let manifest =
+            assets::Ron::<HashMap<String, String>>::load_expect_cloned(TACTIC_MANIFEST).0;
+        for (ability_spec, tactic) in manifest {
+            if let Err(err) = load(&tactic).validate() {
+                panic!("Tactic {tactic} used by {ability_spec} is invalid: {err}");
+            }
+        }
+    }
+
+    #[test]
+    fn approaches_then_attacks() {
+        let spec = load("common.tactics.simple_double");
+        let (inputs, dist) = drive(&spec, 40.0, 100);
+        // No attacks while out of range
+        assert!(inputs[..10].iter().all(Option::is_none));
+        // Stops once in range of the ranged attack and keeps using it
+        assert!(dist < 20.0 && dist > MIN_ATTACK_DIST);
+        assert_eq!(inputs.last(), Some(&Some(InputKind::Secondary)));
+        assert!(!inputs.contains(&Some(InputKind::Primary)));
+
+        let (inputs, _) = drive(&spec, 1.0, 10);
+        assert!(inputs.iter().all(|i| *i == Some(InputKind::Primary)));
+    }
+
+    #[test]
+    fn combo_cycles() {
+        // 2 seconds of secondary followed by 1 second of primary
+        let spec = load("common.tactics.quad_med_basic");
+        let (inputs, _) = drive(&spec, 1.0, 60);
+        let secondary = inputs
+            .iter()
+            .filter(|i| **i == Some(InputKind::Secondary))
+            .count();
+        let primary = inputs
+            .iter()
+            .filter(|i| **i == Some(InputKind::Primary))
+            .count();
+        assert_eq!(inputs[0], Some(InputKind::Secondary));
+        assert!(inputs[25..].contains(&Some(InputKind::Primary)));
+        assert!(secondary > primary && primary > 0);
+    }
+
+    #[test]
+    fn cooldown_and_thresholds() {
+        let spec = TacticSpec {
+            combo_duration: None,
+            actions: vec![
+                TacticAction {
+                    input: Some(InputKind::Ability(0)),
+                    conditions: vec![Condition::InAttackRange(1.0)],
+                    movement: Movement::Stop,
+                    cooldown: Some(1.0),
+                },
+                TacticAction {
+                    input: None,
+                    conditions: vec![Condition::HealthBelow(0.0)],
+                    movement: Movement::Retreat,
+                    cooldown: None,
+                },
+                TacticAction {
+                    input: Some(InputKind::Primary),
+                    conditions: vec![Condition::InAttackRange(1.0)],
+                    movement: Movement::Stop,
+                    cooldown: None,
+                },
+            ],
+            fallback: Movement::Approach,
+        };
+        assert!(spec.validate().is_ok());
+        let (inputs, _) = drive(&spec, 1.0, 21);
+        let abilities: Vec<_> = inputs
+            .iter()
+            .enumerate()
+            .filter(|(_, i)| **i == Some(InputKind::Ability(0)))
+            .map(|(tick, _)| tick)
+            .collect();
+        // Used on the first tick and then roughly once every second
+        assert_eq!(abilities.first(), Some(&0));
+        assert!(abilities.len() >= 2);
+        assert!(
+            abilities
+                .windows(2)
+                .all(|w| (10..=11).contains(&(w[1] - w[0])))
+        );
+        assert_eq!(inputs[1], Some(InputKind::Primary));
+    }
+
+    #[test]
+    fn invalid_combo_window() {
+        let spec = TacticSpec {
+            combo_duration: None,
+            actions: vec![TacticAction {
+                input: Some(InputKind::Primary),
+                conditions: vec![Condition::ComboWindow(0.0, 1.0)],
+                movement: Movement::Stop,
+                cooldown: None,
+            }],
+            fallback: Movement::Approach,
+        };
+        assert!(spec.validate().is_err());
+    }
+}
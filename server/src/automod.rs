@@ -58,6 +58,10 @@ pub struct AutoMod {
     settings: ModerationSettings,
     censor: Arc<Censor>,
     players: HashMap<Uuid, PlayerState>,
+    /// Users of external chats whose messages are bridged into the game, by
+    /// source and alias. Aliases are chosen by the bridge, so there are at
+    /// most [`MAX_EXTERNAL_USERS`] of them.
+    external_users: HashMap<(String, String), PlayerState>,
 }
 
 impl AutoMod {
@@ -79,11 +83,18 @@ impl AutoMod {
             settings: settings.clone(),
             censor,
             players: HashMap::default(),
+            external_users: HashMap::default(),
         }
     }
 
     pub fn enabled(&self) -> bool { self.settings.automod }
 
+    /// Applies changed moderation settings. Banned words files are only read on
+    /// startup.
+    pub fn update_settings(&mut self, settings: &ModerationSettings) {
+        self.settings = settings.clone();
+    }
+
     fn player_mut(&mut self, player: Uuid) -> &mut PlayerState {
         self.players.entry(player).or_default()
     }
@@ -109,15 +120,55 @@ impl AutoMod {
         } else if self.censor.check(msg) {
             Err(ActionErr::BannedWord)
         } else {
-            let volume = self.player_mut(player).enforce_message_volume(now);
-
-            if let Some(until) = self.player_mut(player).muted_until {
-                Err(ActionErr::SpamMuted(until.saturating_duration_since(now)))
-            } else if volume > 0.75 {
-                Ok(Some(ActionNote::SpamWarn))
-            } else {
-                Ok(None)
+            Self::enforce_volume(self.player_mut(player), now)
+        }
+    }
+
+    /// Validates a message bridged into the game from an external chat, which
+    /// is subject to the same filters as public messages of players.
+    pub fn validate_external_msg(
+        &mut self,
+        source: &str,
+        alias: &str,
+        now: Instant,
+        msg: &str,
+    ) -> Result<Option<ActionNote>, ActionErr> {
+        if msg.len() > ChatMsg::MAX_BYTES_PLAYER_CHAT_MSG {
+            Err(ActionErr::TooLong)
+        } else if !self.settings.automod {
+            Ok(None)
+        } else if self.censor.check(msg) {
+            Err(ActionErr::BannedWord)
+        } else {
+            let key = (source.to_string(), alias.to_string());
+            if !self.external_users.contains_key(&key)
+                && self.external_users.len() >= MAX_EXTERNAL_USERS
+            {
+                // Users that haven't sent a message in a while are as good as new
+                self.external_users.retain(|_, state| !state.is_idle(now));
+                // Lots of users talking at once is spam from the bridge itself
+                if self.external_users.len() >= MAX_EXTERNAL_USERS {
+                    return Err(ActionErr::SpamMuted(Duration::from_secs_f32(
+                        CHAT_VOLUME_PERIOD,
+                    )));
+                }
             }
+            Self::enforce_volume(self.external_users.entry(key).or_default(), now)
+        }
+    }
+
+    fn enforce_volume(
+        state: &mut PlayerState,
+        now: Instant,
+    ) -> Result<Option<ActionNote>, ActionErr> {
+        let volume = state.enforce_message_volume(now);
+
+        if let Some(until) = state.muted_until {
+            Err(ActionErr::SpamMuted(until.saturating_duration_since(now)))
+        } else if volume > 0.75 {
+            Ok(Some(ActionNote::SpamWarn))
+        } else {
+            Ok(None)
         }
     }
 }
@@ -131,6 +182,9 @@ const MAX_AVG_MSG_PER_SECOND: f32 = 1.0 / 5.0; // No more than a message every 5
 /// The period for which a player should be muted when they exceed the message
 /// spam threshold.
 const SPAM_MUTE_PERIOD: Duration = Duration::from_secs(180);
+/// The maximum number of external chat users whose chat volume is tracked at
+/// once.
+const MAX_EXTERNAL_USERS: usize = 1_000;
 
 #[derive(Default)]
 pub struct PlayerState {
@@ -141,6 +195,15 @@ pub struct PlayerState {
 }
 
 impl PlayerState {
+    /// Whether the player is neither muted nor sent a message within the chat
+    /// volume period.
+    fn is_idle(&self, now: Instant) -> bool {
+        self.muted_until.is_none_or(|until| until <= now)
+            && self.last_msg_time.is_none_or(|last| {
+                now.saturating_duration_since(last).as_secs_f32() >= CHAT_VOLUME_PERIOD
+            })
+    }
+
     // 0.0 => message is permitted, nothing unusual
     // >=1.0 => message is not permitted, chat volume exceeded
     pub fn enforce_message_volume(&mut self, now: Instant) -> f32 {
@@ -172,3 +235,105 @@ impl PlayerState {
         volume
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn automod(automod: bool) -> AutoMod {
+        let settings = ModerationSettings {
+            banned_words_files: Vec::new(),
+            automod,
+            admins_exempt: false,
+        };
+        let censor = Censor::Custom(["griefer".to_string()].into_iter().collect());
+        AutoMod::new(&settings, Arc::new(censor))
+    }
+
+    #[test]
+    fn external_messages_are_filtered() {
+        let mut automod = automod(true);
+        let now = Instant::now();
+        let long = "a".repeat(ChatMsg::MAX_BYTES_PLAYER_CHAT_MSG + 1);
+
+        assert!(matches!(
+            automod.validate_external_msg("discord", "a", now, &long),
+            Err(ActionErr::TooLong)
+        ));
+        assert!(matches!(
+            automod.validate_external_msg("discord", "a", now, "what a griefer"),
+            Err(ActionErr::BannedWord)
+        ));
+        assert!(matches!(
+            automod.validate_external_msg("discord", "a", now, "hello"),
+            Ok(None)
+        ));
+
+        // Without automod, only the length is checked
+        let mut automod = self::automod(false);
+        assert!(
+            automod
+                .validate_external_msg("discord", "a", now, &long)
+                .is_err()
+        );
+        assert!(
+            automod
+                .validate_external_msg("discord", "a", now, "what a griefer")
+                .is_ok()
+        );
+    }
+
+    #[test]
+    fn external_users_are_rate_limited() {
+        let mut automod = automod(true);
+        let start = Instant::now();
+        let at = |secs: f32| start + Duration::from_secs_f32(secs);
+
+        let muted = (0..20).any(|i| {
+            matches!(
+                automod.validate_external_msg("discord", "spammer", at(i as f32 * 0.5), "hi"),
+                Err(ActionErr::SpamMuted(_))
+            )
+        });
+        assert!(muted, "Spammer wasn't muted");
+        // The same alias from another source is someone else
+        assert!(
+            automod
+                .validate_external_msg("matrix", "spammer", at(10.0), "hi")
+                .is_ok()
+        );
+        // Mutes wear off
+        assert!(
+            automod
+                .validate_external_msg("discord", "spammer", at(20.0) + SPAM_MUTE_PERIOD, "hi")
+                .is_ok()
+        );
+    }
+
+    #[test]
+    fn external_users_are_capped() {
+        let mut automod = automod(true);
+        let now = Instant::now();
+        for i in 0..MAX_EXTERNAL_USERS {
+            assert!(
+                automod
+                    .validate_external_msg("discord", &i.to_string(), now, "hi")
+                    .is_ok()
+            );
+        }
+        assert!(matches!(
+            automod.validate_external_msg("discord", "one too many", now, "hi"),
+            Err(ActionErr::SpamMuted(_))
+        ));
+        assert_eq!(automod.external_users.len(), MAX_EXTERNAL_USERS);
+
+        // Once the others have gone quiet, they make room for new users
+        let later = now + Duration::from_secs_f32(CHAT_VOLUME_PERIOD);
+        assert!(
+            automod
+                .validate_external_msg("discord", "one too many", later, "hi")
+                .is_ok()
+        );
+        assert_eq!(automod.external_users.len(), 1);
+    }
+}
@@ -1,3 +1,4 @@
+use crate::{automod::AutoMod, client::Client, localization::ServerLocalization};
 use chrono::{DateTime, Utc};
 use common::{
     comp,
@@ -5,10 +6,16 @@ use common::{
     uid::IdMaps,
     uuid::Uuid,
 };
+use common_net::msg::ServerGeneral;
 use serde::{Deserialize, Serialize};
 use specs::{Join, World, WorldExt};
-use std::{collections::VecDeque, ops::Sub, sync::Arc, time::Duration};
-use tokio::sync::Mutex;
+use std::{
+    collections::VecDeque,
+    ops::Sub,
+    sync::Arc,
+    time::{Duration, Instant},
+};
+use tokio::sync::{Mutex, broadcast, mpsc, oneshot};
 use tracing::{Instrument, info_span};
 
 #[derive(Clone, Serialize, Deserialize)]
@@ -30,6 +37,15 @@ pub enum KillSource {
     Other,
 }
 
+/// A user of an external chat, such as a Discord or Matrix relay, whose
+/// messages are bridged into the game
+#[derive(Clone, Serialize, Deserialize)]
+pub struct ExternalUser {
+    /// Name of the external chat, e.g. `discord`
+    pub source: String,
+    pub alias: String,
+}
+
 #[derive(Clone, Serialize, Deserialize)]
 /// partially mapped to common::comp::ChatMsg
 pub enum ChatParties {
@@ -46,6 +62,9 @@ pub enum ChatParties {
     Faction(PlayerInfo, String),
     Region(PlayerInfo),
     World(PlayerInfo),
+    /// A message bridged from an external chat, into the given faction's chat
+    /// or the world chat
+    Bridged(ExternalUser, Option<String>),
 }
 
 #[derive(Clone, Serialize, Deserialize)]
@@ -53,33 +72,70 @@ pub struct ChatMessage {
     pub time: DateTime<Utc>,
     pub parties: ChatParties,
     pub content: Content,
+    /// The content rendered in the language of the server
+    #[serde(default)]
+    pub text: String,
+}
+
+/// A message sent into the game from an external chat
+#[derive(Clone, Deserialize)]
+pub struct ExternalChatMessage {
+    pub user: ExternalUser,
+    /// The faction to send the message to, the world chat if `None`
+    #[serde(default)]
+    pub faction: Option<String>,
+    pub msg: String,
+}
+
+/// Why a message from an external chat was not sent
+#[derive(Debug)]
+pub enum ImportError {
+    /// The message was rejected by automod, with the reason
+    Rejected(String),
+    /// The server is not processing messages, e.g. because it is shutting down
+    Unavailable,
 }
 
 type MessagesStore = Arc<Mutex<VecDeque<ChatMessage>>>;
+type ImportRequest = (ExternalChatMessage, oneshot::Sender<Result<(), String>>);
 
 /// The chat cache gets it data from the gameserver and will keep it for some
 /// time It will be made available for its consumers, the REST Api
 #[derive(Clone)]
 pub struct ChatCache {
     pub messages: MessagesStore,
+    live_s: broadcast::Sender<ChatMessage>,
+    import_s: mpsc::Sender<ImportRequest>,
 }
 
 /// Will internally run on tokio and take stress from main loop
 struct ChatForwarder {
-    chat_r: tokio::sync::mpsc::Receiver<ChatMessage>,
+    chat_r: mpsc::Receiver<ChatMessage>,
     messages: MessagesStore,
+    live_s: broadcast::Sender<ChatMessage>,
     keep_duration: chrono::Duration,
 }
 
 pub struct ChatExporter {
-    chat_s: tokio::sync::mpsc::Sender<ChatMessage>,
+    chat_s: mpsc::Sender<ChatMessage>,
+}
+
+/// Receives the messages sent into the game from external chats, to be
+/// handled by the main loop
+pub struct ChatImporter {
+    import_r: mpsc::Receiver<ImportRequest>,
 }
 
 impl ChatMessage {
-    fn new(chatmsg: &UnresolvedChatMsg, parties: ChatParties) -> Self {
+    fn new(
+        chatmsg: &UnresolvedChatMsg,
+        parties: ChatParties,
+        localization: &ServerLocalization,
+    ) -> Self {
         ChatMessage {
             time: Utc::now(),
             content: chatmsg.content().clone(),
+            text: localization.localize(chatmsg.content()),
             parties,
         }
     }
@@ -89,6 +145,8 @@ impl ChatExporter {
     pub fn generate(chatmsg: &UnresolvedChatMsg, ecs: &World) -> Option<ChatMessage> {
         let id_maps = ecs.read_resource::<IdMaps>();
         let players = ecs.read_storage::<Player>();
+        let localization = ecs.read_resource::<ServerLocalization>();
+        let localization = &*localization;
         let player_info_from_uid = |uid| {
             id_maps
                 .uid_entity(uid)
@@ -118,27 +176,47 @@ impl ChatExporter {
         match &chatmsg.chat_type {
             ChatType::Offline(from) => {
                 if let Some(player_info) = player_info_from_uid(*from) {
-                    return Some(ChatMessage::new(chatmsg, ChatParties::Offline(player_info)));
+                    return Some(ChatMessage::new(
+                        chatmsg,
+                        ChatParties::Offline(player_info),
+                        localization,
+                    ));
                 }
             },
             ChatType::Online(from) => {
                 if let Some(player_info) = player_info_from_uid(*from) {
-                    return Some(ChatMessage::new(chatmsg, ChatParties::Online(player_info)));
+                    return Some(ChatMessage::new(
+                        chatmsg,
+                        ChatParties::Online(player_info),
+                        localization,
+                    ));
                 }
             },
             ChatType::Region(from) => {
                 if let Some(player_info) = player_info_from_uid(*from) {
-                    return Some(ChatMessage::new(chatmsg, ChatParties::Region(player_info)));
+                    return Some(ChatMessage::new(
+                        chatmsg,
+                        ChatParties::Region(player_info),
+                        localization,
+                    ));
                 }
             },
             ChatType::World(from) => {
                 if let Some(player_info) = player_info_from_uid(*from) {
-                    return Some(ChatMessage::new(chatmsg, ChatParties::World(player_info)));
+                    return Some(ChatMessage::new(
+                        chatmsg,
+                        ChatParties::World(player_info),
+                        localization,
+                    ));
                 }
             },
             ChatType::Say(from) => {
                 if let Some(player_info) = player_info_from_uid(*from) {
-                    return Some(ChatMessage::new(chatmsg, ChatParties::Say(player_info)));
+                    return Some(ChatMessage::new(
+                        chatmsg,
+                        ChatParties::Say(player_info),
+                        localization,
+                    ));
                 }
             },
             ChatType::Tell(from, to) => {
@@ -148,6 +226,7 @@ impl ChatExporter {
                     return Some(ChatMessage::new(
                         chatmsg,
                         ChatParties::Tell(from_player_info, to_player_info),
+                        localization,
                     ));
                 }
             },
@@ -170,6 +249,7 @@ impl ChatExporter {
                     return Some(ChatMessage::new(
                         chatmsg,
                         ChatParties::Kill(kill_source, player_info),
+                        localization,
                     ));
                 }
             },
@@ -177,6 +257,7 @@ impl ChatExporter {
                 return Some(ChatMessage::new(
                     chatmsg,
                     ChatParties::FactionMeta(s.clone()),
+                    localization,
                 ));
             },
             ChatType::Faction(from, s) => {
@@ -184,12 +265,17 @@ impl ChatExporter {
                     return Some(ChatMessage::new(
                         chatmsg,
                         ChatParties::Faction(player_info, s.clone()),
+                        localization,
                     ));
                 }
             },
             ChatType::GroupMeta(g) => {
                 let members = group_members_from_group(g);
-                return Some(ChatMessage::new(chatmsg, ChatParties::GroupMeta(members)));
+                return Some(ChatMessage::new(
+                    chatmsg,
+                    ChatParties::GroupMeta(members),
+                    localization,
+                ));
             },
             ChatType::Group(from, g) => {
                 let members = group_members_from_group(g);
@@ -197,6 +283,7 @@ impl ChatExporter {
                     return Some(ChatMessage::new(
                         chatmsg,
                         ChatParties::Group(player_info, members),
+                        localization,
                     ));
                 }
             },
@@ -226,6 +313,8 @@ impl ChatForwarder {
             {
                 messages.pop_front();
             }
+            // Sending only fails when nobody is listening to the live chat
+            let _ = self.live_s.send(msg.clone());
             messages.push_back(msg);
             const MAX_CACHE_MESSAGES: usize = 10_000; // in case we have a short spam of many many messages, we dont want to keep the capacity forever
             if messages.capacity() > messages.len() + MAX_CACHE_MESSAGES {
@@ -237,10 +326,70 @@ impl ChatForwarder {
     }
 }
 
+impl ChatImporter {
+    /// Sends the messages received from external chats since the last call,
+    /// after validating them with automod.
+    pub fn handle(&mut self, ecs: &World) {
+        while let Ok((message, result_s)) = self.import_r.try_recv() {
+            let ExternalChatMessage { user, faction, msg } = message;
+            let validated = ecs.write_resource::<AutoMod>().validate_external_msg(
+                &user.source,
+                &user.alias,
+                Instant::now(),
+                &msg,
+            );
+            if let Err(err) = validated {
+                let _ = result_s.send(Err(err.to_string()));
+                continue;
+            }
+
+            let content = Content::localized_with_args("hud-chat-bridged-message", [
+                ("source", user.source.clone()),
+                ("alias", user.alias.clone()),
+                ("msg", msg),
+            ]);
+            ecs.read_resource::<ChatExporter>().send(ChatMessage {
+                time: Utc::now(),
+                parties: ChatParties::Bridged(user, faction.clone()),
+                text: ecs.read_resource::<ServerLocalization>().localize(&content),
+                content: content.clone(),
+            });
+
+            match faction {
+                Some(faction) => {
+                    let msg = ServerGeneral::server_msg(ChatType::FactionMeta(faction), content);
+                    for (client, client_faction) in (
+                        &ecs.read_storage::<Client>(),
+                        &ecs.read_storage::<comp::Faction>(),
+                    )
+                        .join()
+                    {
+                        if faction == client_faction.0 {
+                            client.send_fallible(msg.clone());
+                        }
+                    }
+                },
+                None => {
+                    let msg = ServerGeneral::server_msg(ChatType::Meta, content);
+                    for client in ecs.read_storage::<Client>().join() {
+                        client.send_fallible(msg.clone());
+                    }
+                },
+            }
+            let _ = result_s.send(Ok(()));
+        }
+    }
+}
+
 impl ChatCache {
-    pub fn new(keep_duration: Duration, runtime: &tokio::runtime::Runtime) -> (Self, ChatExporter) {
+    pub fn new(
+        keep_duration: Duration,
+        runtime: &tokio::runtime::Runtime,
+    ) -> (Self, ChatExporter, ChatImporter) {
         const BUFFER_SIZE: usize = 1_000;
-        let (chat_s, chat_r) = tokio::sync::mpsc::channel(BUFFER_SIZE);
+        let (chat_s, chat_r) = mpsc::channel(BUFFER_SIZE);
+        let (live_s, _) = broadcast::channel(BUFFER_SIZE);
+        let (import_s, import_r) = mpsc::channel(BUFFER_SIZE);
         let messages: Arc<Mutex<VecDeque<ChatMessage>>> = Default::default();
         let messages_clone = Arc::clone(&messages);
         let keep_duration = chrono::Duration::from_std(keep_duration).unwrap();
@@ -249,10 +398,96 @@ impl ChatCache {
             keep_duration,
             chat_r,
             messages: messages_clone,
+            live_s: live_s.clone(),
         };
 
         runtime.spawn(worker.run().instrument(info_span!("chat_forwarder")));
 
-        (Self { messages }, ChatExporter { chat_s })
+        (
+            Self {
+                messages,
+                live_s,
+                import_s,
+            },
+            ChatExporter { chat_s },
+            ChatImporter { import_r },
+        )
+    }
+
+    /// Subscribes to the messages exported from now on.
+    pub fn subscribe(&self) -> broadcast::Receiver<ChatMessage> { self.live_s.subscribe() }
+
+    /// Sends a message from an external chat into the game, waiting until it
+    /// was handled by the server.
+    pub async fn import(&self, message: ExternalChatMessage) -> Result<(), ImportError> {
+        let (result_s, result_r) = oneshot::channel();
+        self.import_s
+            .send((message, result_s))
+            .await
+            .map_err(|_| ImportError::Unavailable)?;
+        result_r
+            .await
+            .map_err(|_| ImportError::Unavailable)?
+            .map_err(ImportError::Rejected)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::settings::ModerationSettings;
+    use censor::Censor;
+    use futures_util::FutureExt;
+
+    #[test]
+    fn imported_messages_are_exported() {
+        let runtime = tokio::runtime::Builder::new_current_thread()
+            .build()
+            .unwrap();
+        let (cache, exporter, mut importer) = ChatCache::new(Duration::from_secs(60), &runtime);
+        let mut world = World::new();
+        world.register::<Client>();
+        world.register::<comp::Faction>();
+        world.insert(exporter);
+        world.insert(ServerLocalization::new("en"));
+        let settings = ModerationSettings {
+            banned_words_files: Vec::new(),
+            automod: true,
+            admins_exempt: false,
+        };
+        let censor = Censor::Custom(["griefer".to_string()].into_iter().collect());
+        world.insert(AutoMod::new(&settings, Arc::new(censor)));
+
+        let mut live = cache.subscribe();
+        let mut import = |msg: &str| {
+            let mut import = Box::pin(cache.import(ExternalChatMessage {
+                user: ExternalUser {
+                    source: "discord".to_string(),
+                    alias: "Alice".to_string(),
+                },
+                faction: None,
+                msg: msg.to_string(),
+            }));
+            // The message is queued until the main loop handles it
+            assert!((&mut import).now_or_never().is_none());
+            importer.handle(&world);
+            import.now_or_never().expect("Message wasn't handled")
+        };
+
+        assert!(matches!(
+            import("what a griefer"),
+            Err(ImportError::Rejected(_))
+        ));
+        assert!(import("hello").is_ok());
+
+        // Only the accepted message is exported
+        let msg = runtime.block_on(live.recv()).unwrap();
+        assert!(matches!(
+            &msg.parties,
+            ChatParties::Bridged(user, None) if user.source == "discord" && user.alias == "Alice"
+        ));
+        assert!(msg.text.contains("hello"), "{}", msg.text);
+        let messages = runtime.block_on(cache.messages.lock());
+        assert_eq!(messages.len(), 1);
     }
 }
@@ -1,8 +1,8 @@
-use crate::metrics::ChunkGenMetrics;
 #[cfg(feature = "worldgen")]
 use crate::rtsim::RtSim;
 #[cfg(not(feature = "worldgen"))]
 use crate::test_world::{IndexOwned, World};
+use crate::{instance::InstanceChunk, metrics::ChunkGenMetrics};
 use common::{
     calendar::Calendar, generation::ChunkSupplement, resources::TimeOfDay, slowjob::SlowJobPool,
     terrain::TerrainChunk,
@@ -27,6 +27,9 @@ pub struct ChunkGenerator {
     chunk_tx: crossbeam_channel::Sender<ChunkGenResult>,
     chunk_rx: crossbeam_channel::Receiver<ChunkGenResult>,
     pending_chunks: HashMap<Vec2<i32>, Arc<AtomicBool>>,
+    /// Chunks of instances, which are generated from these instead of the
+    /// world.
+    instance_chunks: HashMap<Vec2<i32>, Arc<InstanceChunk>>,
     metrics: Arc<ChunkGenMetrics>,
 }
 impl ChunkGenerator {
@@ -36,6 +39,7 @@ impl ChunkGenerator {
             chunk_tx,
             chunk_rx,
             pending_chunks: HashMap::new(),
+            instance_chunks: HashMap::new(),
             metrics: Arc::new(metrics),
         }
     }
@@ -61,16 +65,35 @@ impl ChunkGenerator {
         let chunk_tx = self.chunk_tx.clone();
         self.metrics.chunks_requested.inc();
 
+        let instance_chunk = self.instance_chunks.get(&key).cloned();
+
         // Get state for this chunk from rtsim
         #[cfg(feature = "worldgen")]
-        let rtsim_resources = Some(rtsim.get_chunk_resources(key));
+        let rtsim_resources = Some(
+            rtsim.get_chunk_resources(
+                instance_chunk
+                    .as_ref()
+                    .map_or(key, |chunk| chunk.resource_key(key)),
+            ),
+        );
         #[cfg(not(feature = "worldgen"))]
         let rtsim_resources = None;
 
         slowjob_pool.spawn("CHUNK_GENERATOR", move || {
             let index = index.as_index_ref();
-            let payload = world
-                .generate_chunk(index, key, rtsim_resources, || cancel.load(Ordering::Relaxed), Some(time))
+            let cancelled = || cancel.load(Ordering::Relaxed);
+            let payload = match &instance_chunk {
+                Some(chunk) => crate::instance::generate_chunk(
+                    &world,
+                    index,
+                    key,
+                    chunk,
+                    rtsim_resources,
+                    cancelled,
+                    Some(time),
+                ),
+                None => world.generate_chunk(index, key, rtsim_resources, cancelled, Some(time)),
+            }
                 // FIXME: Since only the first entity who cancels a chunk is notified, we end up
                 // delaying chunk re-requests for up to 3 seconds for other clients, which isn't
                 // great.  We *could* store all the other requesting clients here, but it could
@@ -113,6 +136,17 @@ impl ChunkGenerator {
         }
     }
 
+    /// Generate the chunk at `key` for an instance.
+    pub fn set_instance_chunk(&mut self, key: Vec2<i32>, chunk: InstanceChunk) {
+        self.instance_chunks.insert(key, Arc::new(chunk));
+    }
+
+    /// Stop generating the chunks of an instance in `area`.
+    pub fn remove_instance_chunks(&mut self, area: Aabr<i32>) {
+        self.instance_chunks
+            .retain(|key, _| !area.contains_point(*key));
+    }
+
     pub fn cancel_all(&mut self) {
         let metrics = Arc::clone(&self.metrics);
         self.pending_chunks.drain().for_each(|(_, cancel)| {
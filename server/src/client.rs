@@ -1,8 +1,12 @@
+use crate::replay::ReplayTap;
 use common_net::msg::{ClientType, ServerGeneral, ServerMsg};
 use network::{ConnectAddr, Message, Participant, Stream, StreamError, StreamParams};
 use serde::{Serialize, de::DeserializeOwned};
 use specs::Component;
-use std::{net::SocketAddr, sync::atomic::AtomicBool};
+use std::{
+    net::SocketAddr,
+    sync::atomic::{AtomicBool, AtomicU64, Ordering},
+};
 
 /// Client handles ALL network related information of everything that connects
 /// to the server Client DOES NOT handle game states
@@ -18,6 +22,10 @@ pub struct Client {
     pub last_ping: f64,
     pub login_msg_sent: AtomicBool,
     pub locale: Option<String>,
+    /// Set while the inputs of the server are recorded or replayed.
+    pub(crate) replay_tap: Option<ReplayTap>,
+    /// Bytes sent since the last call of `take_sent_bytes`.
+    sent_bytes: AtomicU64,
 
     //TODO: Consider splitting each of these out into their own components so all the message
     //processing systems can run in parallel with each other (though it may turn out not to
@@ -73,6 +81,8 @@ impl Client {
             connected_from_addr: connected_from,
             last_ping,
             locale,
+            replay_tap: None,
+            sent_bytes: AtomicU64::new(0),
             login_msg_sent: AtomicBool::new(false),
             general_stream,
             ping_stream,
@@ -155,6 +165,8 @@ impl Client {
     pub(crate) fn send_fallible<M: Into<ServerMsg>>(&self, msg: M) { let _ = self.send(msg); }
 
     pub(crate) fn send_prepared(&self, msg: &PreparedMsg) -> Result<(), StreamError> {
+        self.sent_bytes
+            .fetch_add(msg.message.len() as u64, Ordering::Relaxed);
         match msg.stream_id {
             0 => self.register_stream.send_raw(&msg.message),
             1 => self.character_screen_stream.send_raw(&msg.message),
@@ -233,6 +245,10 @@ impl Client {
         }
     }
 
+    /// Bytes sent to the client since the previous call, used to find the
+    /// clients using the most bandwidth.
+    pub(crate) fn take_sent_bytes(&self) -> u64 { self.sent_bytes.swap(0, Ordering::Relaxed) }
+
     pub(crate) fn terrain_params(&self) -> StreamParams { self.terrain_stream_params.clone() }
 
     /// Only used for Serialize Chunks in a SlowJob.
@@ -250,12 +266,19 @@ impl Client {
         PreparedMsg::new(5, &terrain_chunk_update, params)
     }
 
-    pub(crate) fn recv<M: DeserializeOwned>(
+    pub(crate) fn recv<M: Serialize + DeserializeOwned>(
         &mut self,
         stream_id: u8,
     ) -> Result<Option<M>, StreamError> {
+        if let Some(replayed) = self
+            .replay_tap
+            .as_ref()
+            .and_then(|tap| tap.replayed(stream_id))
+        {
+            return replayed;
+        }
         // TODO: are two systems using the same stream?? why is there contention here?
-        match stream_id {
+        let received = match stream_id {
             0 => self.register_stream.try_recv(),
             1 => self.character_screen_stream.try_recv(),
             2 => self.in_game_stream.try_recv(),
@@ -263,7 +286,11 @@ impl Client {
             4 => self.ping_stream.try_recv(),
             5 => self.terrain_stream.try_recv(),
             _ => unreachable!("invalid stream id"),
+        };
+        if let Some(tap) = &self.replay_tap {
+            tap.record(stream_id, &received);
         }
+        received
     }
 }
 
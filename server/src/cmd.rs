@@ -4,8 +4,10 @@
 #[cfg(feature = "worldgen")]
 use crate::weather::WeatherJob;
 use crate::{
-    Server, Settings, StateExt,
+    Server, ServerLocalization, Settings, StateExt,
     client::Client,
+    instance::InstanceEntrance,
+    load::{LoadTracker, RegionLoad},
     location::Locations,
     login_provider::LoginProvider,
     settings::{
@@ -42,7 +44,7 @@ use common::{
             slot::Slot,
         },
         invite::InviteKind,
-        misc::PortalData,
+        misc::{InstanceKind, PortalData},
     },
     depot,
     effect::Effect,
@@ -56,11 +58,13 @@ use common::{
     npc::{self, get_npc_name},
     outcome::Outcome,
     parse_cmd_args,
+    region::RegionMap,
     resources::{BattleMode, ProgramTime, Secs, Time, TimeOfDay, TimeScale},
     rtsim::{Actor, Role},
     spiral::Spiral2d,
     terrain::{Block, BlockKind, CoordinateConversions, SpriteKind, StructureSprite},
     tether::Tethered,
+    trade::SiteId,
     uid::Uid,
     vol::ReadVol,
 };
@@ -155,6 +159,7 @@ fn do_command(
         ServerChatCommand::BanIp => handle_ban_ip,
         ServerChatCommand::BattleMode => handle_battlemode,
         ServerChatCommand::BattleModeForce => handle_battlemode_force,
+        ServerChatCommand::BlockHistory => handle_block_history,
         ServerChatCommand::Body => handle_body,
         ServerChatCommand::Buff => handle_buff,
         ServerChatCommand::Build => handle_build,
@@ -168,6 +173,7 @@ fn do_command(
         ServerChatCommand::Dummy => handle_spawn_training_dummy,
         ServerChatCommand::Explosion => handle_explosion,
         ServerChatCommand::Faction => handle_faction,
+        ServerChatCommand::Forecast => handle_forecast,
         ServerChatCommand::GiveItem => handle_give_item,
         ServerChatCommand::Goto => handle_goto,
         ServerChatCommand::GotoRand => handle_goto_rand,
@@ -177,6 +183,7 @@ fn do_command(
         ServerChatCommand::GroupLeave => handle_group_leave,
         ServerChatCommand::GroupPromote => handle_group_promote,
         ServerChatCommand::Health => handle_health,
+        ServerChatCommand::InstancePortal => handle_instance_portal,
         ServerChatCommand::IntoNpc => handle_into_npc,
         ServerChatCommand::JoinFaction => handle_join_faction,
         ServerChatCommand::Jump => handle_jump,
@@ -195,13 +202,16 @@ fn do_command(
         ServerChatCommand::PermitBuild => handle_permit_build,
         ServerChatCommand::Players => handle_players,
         ServerChatCommand::Portal => handle_spawn_portal,
+        ServerChatCommand::Profile => handle_profile,
         ServerChatCommand::ResetRecipes => handle_reset_recipes,
         ServerChatCommand::Region => handle_region,
         ServerChatCommand::ReloadChunks => handle_reload_chunks,
         ServerChatCommand::RemoveLights => handle_remove_lights,
         ServerChatCommand::Respawn => handle_respawn,
+        ServerChatCommand::RestoreTerrain => handle_restore_terrain,
         ServerChatCommand::RevokeBuild => handle_revoke_build,
         ServerChatCommand::RevokeBuildAll => handle_revoke_build_all,
+        ServerChatCommand::Rollback => handle_rollback,
         ServerChatCommand::Safezone => handle_safezone,
         ServerChatCommand::Say => handle_say,
         ServerChatCommand::ServerPhysics => handle_server_physics,
@@ -539,8 +549,8 @@ fn edit_setting_error_feedback<S: EditableSetting>(
             warn!(
                 ?err,
                 "Failed to write settings file to disk, but succeeded in memory (success message: \
-                 {:?})",
-                info,
+                 {})",
+                server.localize(&info),
             );
             server.notify_client(
                 client,
@@ -701,9 +711,33 @@ fn handle_give_item(
     }
 }
 
+/// Sets a block for a command, persisting it and recording it in the block
+/// history as an edit by `client`
+fn set_block_by(server: &Server, client: EcsEntity, pos: Vec3<i32>, block: Block) {
+    let _old_block = server.state.get_block(pos);
+    server.state.set_block(pos, block);
+    #[cfg(feature = "persistent_world")]
+    if let Some(mut terrain_persistence) = server
+        .state
+        .ecs()
+        .try_fetch_mut::<crate::TerrainPersistence>()
+    {
+        terrain_persistence.set_block(pos, block);
+        if let Some(old_block) = _old_block
+            && let Some(player) = server
+                .state
+                .ecs()
+                .read_storage::<comp::Player>()
+                .get(client)
+        {
+            terrain_persistence.record_edit(pos, old_block, block, player.uuid());
+        }
+    }
+}
+
 fn handle_make_block(
     server: &mut Server,
-    _client: EcsEntity,
+    client: EcsEntity,
     target: EcsEntity,
     args: Vec<String>,
     action: &ServerChatCommand,
@@ -713,16 +747,7 @@ fn handle_make_block(
             let pos = position(server, target, "target")?;
             let new_block = Block::new(bk, Rgb::new(r, g, b).map(|e| e.unwrap_or(255)));
             let pos = pos.0.map(|e| e.floor() as i32);
-            server.state.set_block(pos, new_block);
-            #[cfg(feature = "persistent_world")]
-            if let Some(terrain_persistence) = server
-                .state
-                .ecs()
-                .try_fetch_mut::<crate::TerrainPersistence>()
-                .as_mut()
-            {
-                terrain_persistence.set_block(pos, new_block);
-            }
+            set_block_by(server, client, pos, new_block);
             Ok(())
         } else {
             Err(Content::localized_with_args(
@@ -875,7 +900,7 @@ fn handle_make_npc(
 
 fn handle_make_sprite(
     server: &mut Server,
-    _client: EcsEntity,
+    client: EcsEntity,
     target: EcsEntity,
     args: Vec<String>,
     action: &ServerChatCommand,
@@ -888,24 +913,17 @@ fn handle_make_sprite(
                 .get_block(pos)
                 // TODO: Make more principled.
                 .unwrap_or_else(|| Block::air(SpriteKind::Empty));
-        let set_block = |block| {
-            server.state.set_block(pos, block);
-            #[cfg(feature = "persistent_world")]
-            if let Some(terrain_persistence) = server
-                .state
-                .ecs()
-                .try_fetch_mut::<crate::TerrainPersistence>()
-                .as_mut()
-            {
-                terrain_persistence.set_block(pos, block);
-            }
-        };
         if let Ok(sk) = SpriteKind::try_from(sprite_name.as_str()) {
-            set_block(old_block.with_sprite(sk));
+            set_block_by(server, client, pos, old_block.with_sprite(sk));
 
             Ok(())
         } else if let Ok(sprite) = ron::from_str::<StructureSprite>(sprite_name.as_str()) {
-            set_block(sprite.get_block(|s| old_block.with_sprite(s)));
+            set_block_by(
+                server,
+                client,
+                pos,
+                sprite.get_block(|s| old_block.with_sprite(s)),
+            );
 
             Ok(())
         } else {
@@ -1459,6 +1477,7 @@ fn handle_health(
                 amount: hp - health.current(),
                 by: None,
                 cause: None,
+                kind: None,
                 precise: false,
                 time: *time,
                 instance: rand::random(),
@@ -2259,6 +2278,237 @@ fn handle_clear_persisted_terrain(
     ))
 }
 
+#[cfg(feature = "persistent_world")]
+fn handle_block_history(
+    server: &mut Server,
+    client: EcsEntity,
+    target: EcsEntity,
+    args: Vec<String>,
+    _action: &ServerChatCommand,
+) -> CmdResult<()> {
+    // Clamp the radius to prevent accidentally passing too large radiuses
+    let radius = parse_cmd_args!(args, i32).unwrap_or(3).clamp(0, 32);
+    let pos = position(server, target, "target")?
+        .0
+        .map(|e| e.floor() as i32);
+
+    let edits = server
+        .state
+        .ecs()
+        .try_fetch::<crate::terrain_persistence::TerrainPersistence>()
+        .map(|terrain_persistence| {
+            terrain_persistence.history().edits_in(Aabb {
+                min: pos - radius,
+                max: pos + radius,
+            })
+        })
+        .ok_or_else(|| Content::localized("command-experimental-terrain-persistence-disabled"))?;
+    if edits.is_empty() {
+        server.notify_client(
+            client,
+            ServerGeneral::server_msg(
+                ChatType::CommandInfo,
+                Content::localized("command-block_history-empty"),
+            ),
+        );
+        return Ok(());
+    }
+
+    let describe_block = |block: Block| {
+        block.get_sprite().map_or_else(
+            || format!("{:?}", block.kind()),
+            |sprite| format!("{sprite:?}"),
+        )
+    };
+    let players = server.state.ecs().read_storage::<comp::Player>();
+    let entries = edits
+        .iter()
+        .rev()
+        .take(10)
+        .map(|edit| {
+            let player = players
+                .join()
+                .find(|player| player.uuid() == edit.player)
+                .map_or_else(|| edit.player.to_string(), |player| player.alias.clone());
+            let time = chrono::DateTime::from_timestamp(edit.time as i64, 0).map_or_else(
+                || edit.time.to_string(),
+                |time| time.format("%Y-%m-%d %H:%M").to_string(),
+            );
+            Content::localized_with_args("command-block_history-entry", [
+                ("time", time),
+                ("player", player),
+                ("old", describe_block(edit.old)),
+                ("new", describe_block(edit.new)),
+                (
+                    "pos",
+                    format!("{} {} {}", edit.pos.x, edit.pos.y, edit.pos.z),
+                ),
+            ])
+        })
+        .collect::<Vec<_>>();
+    drop(players);
+
+    for entry in entries {
+        server.notify_client(
+            client,
+            ServerGeneral::server_msg(ChatType::CommandInfo, entry),
+        );
+    }
+    Ok(())
+}
+
+#[cfg(not(feature = "persistent_world"))]
+fn handle_block_history(
+    _server: &mut Server,
+    _client: EcsEntity,
+    _target: EcsEntity,
+    _args: Vec<String>,
+    _action: &ServerChatCommand,
+) -> CmdResult<()> {
+    Err(Content::localized(
+        "command-server-no-experimental-terrain-persistence",
+    ))
+}
+
+#[cfg(feature = "persistent_world")]
+fn handle_rollback(
+    server: &mut Server,
+    client: EcsEntity,
+    target: EcsEntity,
+    args: Vec<String>,
+    action: &ServerChatCommand,
+) -> CmdResult<()> {
+    let (Some(player_name), radius, minutes) = parse_cmd_args!(args, String, i32, u64) else {
+        return Err(action.help_content());
+    };
+    // Clamp the radius to prevent accidentally passing too large radiuses
+    let radius = radius.unwrap_or(32).clamp(0, 256);
+    let since =
+        crate::terrain_persistence::history::now().saturating_sub(minutes.unwrap_or(60) * 60);
+    let player = find_username(server, &player_name)?;
+    let admin = server
+        .state
+        .ecs()
+        .read_storage::<comp::Player>()
+        .get(client)
+        .map(|player| player.uuid());
+    let pos = position(server, target, "target")?
+        .0
+        .map(|e| e.floor() as i32);
+    let area = Aabb {
+        min: Vec3::new(pos.x - radius, pos.y - radius, i32::MIN),
+        max: Vec3::new(pos.x + radius, pos.y + radius, i32::MAX),
+    };
+
+    let Some(mut terrain_persistence) = server
+        .state
+        .ecs()
+        .try_fetch_mut::<crate::terrain_persistence::TerrainPersistence>(
+    ) else {
+        return Err(Content::localized(
+            "command-experimental-terrain-persistence-disabled",
+        ));
+    };
+    let blocks = terrain_persistence.history().rollback(player, area, since);
+    for &(pos, block) in &blocks {
+        let old_block = server.state.terrain().get(pos).ok().copied();
+        server.state.set_block(pos, block);
+        terrain_persistence.set_block(pos, block);
+        // Record the rollback too, so that it can be found and undone as well
+        if let (Some(admin), Some(old_block)) = (admin, old_block) {
+            terrain_persistence.record_edit(pos, old_block, block, admin);
+        }
+    }
+    drop(terrain_persistence);
+
+    server.notify_client(
+        client,
+        ServerGeneral::server_msg(
+            ChatType::CommandInfo,
+            Content::localized_with_args("command-rollback-done", [
+                ("count", blocks.len().to_string()),
+                ("player", player_name),
+            ]),
+        ),
+    );
+    Ok(())
+}
+
+#[cfg(not(feature = "persistent_world"))]
+fn handle_rollback(
+    _server: &mut Server,
+    _client: EcsEntity,
+    _target: EcsEntity,
+    _args: Vec<String>,
+    _action: &ServerChatCommand,
+) -> CmdResult<()> {
+    Err(Content::localized(
+        "command-server-no-experimental-terrain-persistence",
+    ))
+}
+
+#[cfg(feature = "persistent_world")]
+fn handle_restore_terrain(
+    server: &mut Server,
+    client: EcsEntity,
+    _target: EcsEntity,
+    args: Vec<String>,
+    action: &ServerChatCommand,
+) -> CmdResult<()> {
+    let (Some(xlo), Some(xhi), Some(ylo), Some(yhi), Some(zlo), Some(zhi)) =
+        parse_cmd_args!(args, i32, i32, i32, i32, i32, i32)
+    else {
+        return Err(action.help_content());
+    };
+    let area = Aabb {
+        min: Vec3::new(xlo, ylo, zlo),
+        max: Vec3::new(xhi, yhi, zhi),
+    }
+    .made_valid();
+    // Limit the size of the area to prevent accidentally restoring large parts
+    // of the world
+    if area.size().w > 1024 || area.size().h > 1024 {
+        return Err(Content::localized("command-restore_terrain-too-large"));
+    }
+
+    let changed = server
+        .state
+        .ecs()
+        .try_fetch_mut::<crate::terrain_persistence::TerrainPersistence>()
+        .map(|mut terrain_persistence| terrain_persistence.restore_area(area))
+        .ok_or_else(|| Content::localized("command-experimental-terrain-persistence-disabled"))?;
+    // Unload the changed chunks so they are regenerated without the removed
+    // blocks
+    for key in &changed {
+        server.state.remove_chunk(*key);
+    }
+
+    server.notify_client(
+        client,
+        ServerGeneral::server_msg(
+            ChatType::CommandInfo,
+            Content::localized_with_args("command-restore_terrain-done", [(
+                "chunks",
+                changed.len().to_string(),
+            )]),
+        ),
+    );
+    Ok(())
+}
+
+#[cfg(not(feature = "persistent_world"))]
+fn handle_restore_terrain(
+    _server: &mut Server,
+    _client: EcsEntity,
+    _target: EcsEntity,
+    _args: Vec<String>,
+    _action: &ServerChatCommand,
+) -> CmdResult<()> {
+    Err(Content::localized(
+        "command-server-no-experimental-terrain-persistence",
+    ))
+}
+
 fn handle_safezone(
     server: &mut Server,
     client: EcsEntity,
@@ -2430,6 +2680,58 @@ fn handle_players(
     Ok(())
 }
 
+fn handle_profile(
+    server: &mut Server,
+    client: EcsEntity,
+    _target: EcsEntity,
+    args: Vec<String>,
+    _action: &ServerChatCommand,
+) -> CmdResult<()> {
+    let count = parse_cmd_args!(args, usize).unwrap_or(5);
+    let tracker = server.state.ecs().read_resource::<LoadTracker>();
+    let report = tracker
+        .report()
+        .ok_or_else(|| Content::localized("command-profile-unavailable"))?;
+
+    let mut lines = vec![Content::localized_with_args("command-profile-header", [(
+        "seconds",
+        format!("{:.1}", report.period.as_secs_f32()),
+    )])];
+    let region_metrics: [(&str, fn(&RegionLoad) -> u64); 4] = [
+        ("command-profile-entities", |r| r.entities),
+        ("command-profile-agents", |r| r.agents),
+        ("command-profile-physics-contacts", |r| r.physics_contacts),
+        ("command-profile-chunk-requests", |r| r.chunk_requests),
+    ];
+    for (key, value) in region_metrics {
+        lines.push(Content::localized(key));
+        for region in report.top_regions(count, value) {
+            let wpos = RegionMap::key_pos(region.key);
+            lines.push(Content::localized_with_args("command-profile-region", [
+                ("value", value(region).to_string()),
+                ("key", format!("{},{}", region.key.x, region.key.y)),
+                ("pos", format!("{}, {}", wpos.x, wpos.y)),
+            ]));
+        }
+    }
+    lines.push(Content::localized("command-profile-players"));
+    for player in report.top_players(count) {
+        lines.push(Content::localized_with_args("command-profile-player", [
+            ("bytes", player.sent_bytes.to_string()),
+            ("player", player.alias.clone()),
+        ]));
+    }
+    drop(tracker);
+
+    for line in lines {
+        server.notify_client(
+            client,
+            ServerGeneral::server_msg(ChatType::CommandInfo, line),
+        );
+    }
+    Ok(())
+}
+
 fn handle_spawn_portal(
     server: &mut Server,
     client: EcsEntity,
@@ -2466,6 +2768,63 @@ fn handle_spawn_portal(
     }
 }
 
+fn handle_instance_portal(
+    server: &mut Server,
+    client: EcsEntity,
+    target: EcsEntity,
+    args: Vec<String>,
+    action: &ServerChatCommand,
+) -> CmdResult<()> {
+    let pos = position(server, target, "target")?;
+
+    let kind = match parse_cmd_args!(args, String, String) {
+        (Some(kind), _) if kind == "arena" => InstanceKind::Arena,
+        (Some(kind), Some(site_name)) if kind == "dungeon" => InstanceKind::Dungeon {
+            site: find_site_id(server, &site_name)?,
+        },
+        _ => return Err(action.help_content()),
+    };
+    server
+        .state
+        .create_teleporter(pos, PortalData {
+            target: pos.0,
+            buildup_time: Secs(5.0),
+            requires_no_aggro: true,
+        })
+        .with(InstanceEntrance(kind))
+        .build();
+
+    server.notify_client(
+        client,
+        ServerGeneral::server_msg(
+            ChatType::CommandInfo,
+            Content::Plain("Spawned instance portal".to_string()),
+        ),
+    );
+    Ok(())
+}
+
+#[cfg(feature = "worldgen")]
+fn find_site_id(server: &Server, name: &str) -> CmdResult<SiteId> {
+    server
+        .world
+        .civs()
+        .sites()
+        .find_map(|site| {
+            site.site_tmp
+                .filter(|id| server.index.sites[*id].name() == name)
+        })
+        .map(|id| id.id())
+        .ok_or_else(|| Content::localized("command-site-not-found"))
+}
+
+#[cfg(not(feature = "worldgen"))]
+fn find_site_id(_server: &Server, _name: &str) -> CmdResult<SiteId> {
+    Err(Content::Plain(
+        "Unsupported without worldgen enabled".into(),
+    ))
+}
+
 fn handle_build(
     server: &mut Server,
     client: EcsEntity,
@@ -2482,10 +2841,7 @@ fn handle_build(
         can_build.enabled ^= true;
 
         let msg = Content::localized(
-            match (
-                can_build.enabled,
-                server.settings().experimental_terrain_persistence,
-            ) {
+            match (can_build.enabled, server.settings().terrain_persistence) {
                 (false, _) => "command-set-build-mode-off",
                 (true, false) => "command-set-build-mode-on-unpersistent",
                 (true, true) => "command-set-build-mode-on-persistent",
@@ -3174,6 +3530,7 @@ fn handle_outcome(
                 target: uid_arg!().unwrap_or(target_uid),
                 by: uid_arg!().map(common::combat::DamageContributor::Solo).ok(),
                 cause: None,
+                kind: None,
                 instance: rng.gen(),
             },
         },
@@ -3715,6 +4072,52 @@ fn handle_faction(
     }
 }
 
+fn handle_forecast(
+    server: &mut Server,
+    client: EcsEntity,
+    target: EcsEntity,
+    _args: Vec<String>,
+    _action: &ServerChatCommand,
+) -> CmdResult<()> {
+    const DAY: u64 = 86400;
+
+    let pos = position(server, target, "target")?;
+    let forecast = server
+        .state
+        .ecs()
+        .try_fetch::<common::weather::WeatherForecast>()
+        .filter(|forecast| !forecast.steps.is_empty())
+        .map(|forecast| forecast.clone())
+        .ok_or_else(|| Content::localized("command-forecast-unavailable"))?;
+
+    for (hours, weather) in forecast.at(pos.0.xy()) {
+        let time = forecast.time_of_day + hours as f64 * common::weather::WeatherForecast::STEP;
+        let time = NaiveTime::from_num_seconds_from_midnight_opt((time as u64 % DAY) as u32, 0)
+            .map_or_else(
+                || "??:??".to_string(),
+                |time| time.format("%H:%M").to_string(),
+            );
+        server.notify_client(
+            client,
+            ServerGeneral::server_msg(
+                ChatType::CommandInfo,
+                Content::localized_with_args("command-forecast-entry", [
+                    ("time", Content::Plain(time)),
+                    (
+                        "weather",
+                        Content::localized(weather.get_kind().localization_key()),
+                    ),
+                    (
+                        "temperature",
+                        Content::Plain(format!("{:.0}", weather.temperature)),
+                    ),
+                ]),
+            ),
+        );
+    }
+    Ok(())
+}
+
 fn handle_group(
     server: &mut Server,
     client: EcsEntity,
@@ -4305,26 +4708,12 @@ fn reload_chunks_inner(server: &mut Server, pos: Vec3<f32>, radius: Option<i32>)
         let chunk_key = server.state.terrain().pos_key(pos.as_());
 
         for key_offset in Spiral2d::with_radius(radius) {
-            let chunk_key = chunk_key + key_offset;
-
-            #[cfg(feature = "persistent_world")]
-            server
-                .state
-                .ecs()
-                .try_fetch_mut::<crate::terrain_persistence::TerrainPersistence>()
-                .map(|mut terrain_persistence| terrain_persistence.unload_chunk(chunk_key));
-            if server.state.remove_chunk(chunk_key) {
+            if server.reload_chunk(chunk_key + key_offset) {
                 removed += 1;
             }
         }
     } else {
-        #[cfg(feature = "persistent_world")]
-        server
-            .state
-            .ecs()
-            .try_fetch_mut::<crate::terrain_persistence::TerrainPersistence>()
-            .map(|mut terrain_persistence| terrain_persistence.unload_all());
-        removed = server.state.clear_terrain();
+        removed = server.reload_all_chunks();
     }
 
     removed
@@ -4595,6 +4984,15 @@ fn kick_player(
         (target_player, target_player_uuid),
         Content::localized("command-kick-higher-role"),
     )?;
+    let reason = {
+        let ecs = server.state.ecs();
+        let locale = ecs
+            .read_storage::<Client>()
+            .get(target_player)
+            .and_then(|client| client.locale.clone());
+        ecs.read_resource::<ServerLocalization>()
+            .localize_disconnect(locale.as_deref(), reason)
+    };
     server.notify_client(target_player, ServerGeneral::Disconnect(reason));
     server
         .state
@@ -5519,6 +5917,7 @@ fn handle_weather_zone(
                     cloud: 0.0,
                     rain: 0.0,
                     wind: Vec2::zero(),
+                    ..Default::default()
                 });
                 Ok(())
             },
@@ -5527,6 +5926,7 @@ fn handle_weather_zone(
                     cloud: 0.4,
                     rain: 0.0,
                     wind: Vec2::zero(),
+                    ..Default::default()
                 });
                 Ok(())
             },
@@ -5535,6 +5935,7 @@ fn handle_weather_zone(
                     cloud: 0.1,
                     rain: 0.15,
                     wind: Vec2::new(1.0, -1.0),
+                    ..Default::default()
                 });
                 Ok(())
             },
@@ -5543,6 +5944,7 @@ fn handle_weather_zone(
                     cloud: 0.0,
                     rain: 0.0,
                     wind: Vec2::new(10.0, 10.0),
+                    ..Default::default()
                 });
                 Ok(())
             },
@@ -5551,6 +5953,24 @@ fn handle_weather_zone(
                     cloud: 0.3,
                     rain: 0.3,
                     wind: Vec2::new(15.0, 20.0),
+                    ..Default::default()
+                });
+                Ok(())
+            },
+            "snow" => {
+                add_zone(weather::Weather {
+                    cloud: 0.3,
+                    snow: 0.4,
+                    wind: Vec2::new(1.0, -1.0),
+                    ..Default::default()
+                });
+                Ok(())
+            },
+            "fog" => {
+                add_zone(weather::Weather {
+                    cloud: 0.2,
+                    fog: 0.8,
+                    ..Default::default()
                 });
                 Ok(())
             },
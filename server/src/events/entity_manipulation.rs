@@ -1,7 +1,10 @@
 #[cfg(feature = "worldgen")]
 use crate::rtsim::RtSim;
+#[cfg(not(feature = "worldgen"))]
+use crate::test_world::{IndexOwned, World};
 use crate::{
     Server, Settings, SpawnPoint,
+    chunk_generator::ChunkGenerator,
     client::Client,
     comp::{
         BuffKind, BuffSource, PhysicsState,
@@ -11,6 +14,7 @@ use crate::{
     },
     error,
     events::entity_creation::handle_create_npc,
+    instance::{Instances, Owner},
     pet::tame_pet,
     state_ext::StateExt,
     sys::terrain::{NpcData, SAFE_ZONE_RADIUS, SpawnEntityData},
@@ -26,8 +30,9 @@ use common::{
     },
     comp::{
         self, Alignment, Auras, BASE_ABILITY_LIMIT, Body, BuffCategory, BuffEffect, CharacterState,
-        Energy, Group, Hardcore, Health, Inventory, Object, PickupItem, Player, Poise, PoiseChange,
-        Pos, Presence, PresenceKind, ProjectileConstructor, SkillSet, Stats,
+        ChatType, Content, Energy, Group, Hardcore, Health, Inventory, Object, PickupItem, Player,
+        Poise, PoiseChange, Pos, Presence, PresenceKind, ProjectileConstructor, QuestLog, SkillSet,
+        Stats,
         aura::{self, EnteredAuras},
         buff,
         chat::{KillSource, KillType},
@@ -41,10 +46,10 @@ use common::{
         AuraEvent, BonkEvent, BuffEvent, ChangeAbilityEvent, ChangeBodyEvent, ChangeStanceEvent,
         ChatEvent, ComboChangeEvent, CreateItemDropEvent, CreateNpcEvent, CreateObjectEvent,
         DeleteEvent, DestroyEvent, DownedEvent, EmitExt, Emitter, EnergyChangeEvent,
-        EntityAttackedHookEvent, EventBus, ExplosionEvent, HealthChangeEvent, HelpDownedEvent,
-        KillEvent, KnockbackEvent, LandOnGroundEvent, MakeAdminEvent, ParryHookEvent,
-        PoiseChangeEvent, RegrowHeadEvent, RemoveLightEmitterEvent, RespawnEvent, ShootEvent,
-        SoundEvent, StartInteractionEvent, StartTeleportingEvent, TeleportToEvent,
+        EnterInstanceEvent, EntityAttackedHookEvent, EventBus, ExplosionEvent, HealthChangeEvent,
+        HelpDownedEvent, KillEvent, KnockbackEvent, LandOnGroundEvent, MakeAdminEvent,
+        ParryHookEvent, PoiseChangeEvent, RegrowHeadEvent, RemoveLightEmitterEvent, RespawnEvent,
+        ShootEvent, SoundEvent, StartInteractionEvent, StartTeleportingEvent, TeleportToEvent,
         TeleportToPositionEvent, TransformEvent, UpdateMapMarkerEvent,
     },
     event_emitters,
@@ -67,14 +72,11 @@ use common_net::{msg::ServerGeneral, sync::WorldSyncExt, synced_components::Head
 use common_state::{AreasContainer, BlockChange, NoDurabilityArea};
 use hashbrown::HashSet;
 use rand::Rng;
-#[cfg(feature = "worldgen")]
-use specs::WriteExpect;
 use specs::{
     DispatcherBuilder, Entities, Entity as EcsEntity, Entity, Join, LendJoin, Read, ReadExpect,
-    ReadStorage, SystemData, WorldExt, Write, WriteStorage, shred,
+    ReadStorage, SystemData, WorldExt, Write, WriteExpect, WriteStorage, shred,
 };
-#[cfg(feature = "worldgen")] use std::sync::Arc;
-use std::{borrow::Cow, collections::HashMap, iter, time::Duration};
+use std::{borrow::Cow, collections::HashMap, iter, sync::Arc, time::Duration};
 use tracing::{debug, warn};
 use vek::{Vec2, Vec3};
 #[cfg(feature = "worldgen")]
@@ -109,6 +111,7 @@ pub(super) fn register_event_systems(builder: &mut DispatcherBuilder) {
     event_dispatch::<RemoveLightEmitterEvent>(builder, &[]);
     event_dispatch::<TeleportToPositionEvent>(builder, &[]);
     event_dispatch::<StartTeleportingEvent>(builder, &[]);
+    event_dispatch::<EnterInstanceEvent>(builder, &[]);
     event_dispatch::<RegrowHeadEvent>(builder, &[]);
 }
 
@@ -286,6 +289,7 @@ impl ServerEvent for HealthChangeEvent {
                                 by: ev.change.by,
                                 target: *uid,
                                 cause: ev.change.cause,
+                                kind: ev.change.kind,
                                 precise: ev.change.precise,
                                 instance: ev.change.instance,
                             },
@@ -459,7 +463,7 @@ impl ServerEvent for KnockbackEvent {
     }
 }
 
-fn handle_exp_gain(
+pub(crate) fn handle_exp_gain(
     exp_reward: f32,
     inventory: &Inventory,
     skill_set: &mut SkillSet,
@@ -536,6 +540,7 @@ pub struct DestroyEventData<'a> {
     melees: WriteStorage<'a, comp::Melee>,
     beams: WriteStorage<'a, comp::Beam>,
     skill_sets: WriteStorage<'a, SkillSet>,
+    quest_logs: WriteStorage<'a, QuestLog>,
     inventories: WriteStorage<'a, Inventory>,
     item_drops: WriteStorage<'a, comp::ItemDrops>,
     velocities: WriteStorage<'a, comp::Vel>,
@@ -939,6 +944,17 @@ impl ServerEvent for DestroyEvent {
                         );
                     }
                 });
+
+                // Everyone rewarded for the kill makes progress on their kill quests
+                if let Some(body) = data.bodies.get(ev.entity) {
+                    for (attacker, _, _) in &exp_awards {
+                        if let Some(mut quest_log) = data.quest_logs.get_mut(*attacker)
+                            && quest_log.wants_kill(body)
+                        {
+                            quest_log.record_kill(body);
+                        }
+                    }
+                }
             };
 
             should_delete &= if data.clients.contains(ev.entity) {
@@ -1087,22 +1103,48 @@ impl ServerEvent for DestroyEvent {
                 // removed, producing 2 entities having the same RtsimEntityId.
                 && should_delete
             {
+                let killer = ev.cause.by.as_ref().and_then(
+                    |(DamageContributor::Solo(entity_uid)
+                     | DamageContributor::Group { entity_uid, .. })| {
+                        data.id_maps.uid_entity(*entity_uid)
+                    },
+                );
                 data.rtsim.hook_rtsim_actor_death(
                     &data.world,
                     data.index.as_index_ref(),
                     actor,
                     data.positions.get(ev.entity).map(|p| p.0),
-                    ev.cause
-                        .by
-                        .as_ref()
-                        .and_then(
-                            |(DamageContributor::Solo(entity_uid)
-                             | DamageContributor::Group { entity_uid, .. })| {
-                                data.id_maps.uid_entity(*entity_uid)
-                            },
-                        )
-                        .and_then(entity_as_actor),
+                    killer.and_then(entity_as_actor),
                 );
+                // Bounty targets only count when slain by the character holding the bounty
+                if let Some(killer) = killer
+                    && let Some(mut quest_log) = data.quest_logs.get_mut(killer)
+                    && quest_log.wants_slain(actor)
+                {
+                    quest_log.record_slain(actor);
+                }
+                // Anyone else still hunting the target can't claim the bounty anymore
+                let hunters = (&data.entities, &data.quest_logs)
+                    .join()
+                    .filter(|(_, quest_log)| quest_log.wants_slain(actor))
+                    .map(|(entity, _)| entity)
+                    .collect::<Vec<_>>();
+                for entity in hunters {
+                    let Some(mut quest_log) = data.quest_logs.get_mut(entity) else {
+                        continue;
+                    };
+                    for quest in quest_log.fail_slay(actor) {
+                        if let Some(client) = data.clients.get(entity) {
+                            client.send_fallible(ServerGeneral::server_msg(
+                                ChatType::Meta,
+                                Content::localized_with_args("quest-failed", [(
+                                    "title",
+                                    quest.title,
+                                )]),
+                            ));
+                        }
+                    }
+                }
             }
 
             if should_delete {
@@ -2590,6 +2632,75 @@ impl ServerEvent for StartTeleportingEvent {
     }
 }
 
+impl ServerEvent for EnterInstanceEvent {
+    type SystemData<'a> = (
+        Read<'a, Time>,
+        ReadExpect<'a, Arc<World>>,
+        ReadExpect<'a, IndexOwned>,
+        WriteExpect<'a, Instances>,
+        WriteExpect<'a, ChunkGenerator>,
+        Read<'a, EventBus<TeleportToPositionEvent>>,
+        Read<'a, EventBus<Outcome>>,
+        ReadStorage<'a, Uid>,
+        ReadStorage<'a, Group>,
+        ReadStorage<'a, Client>,
+    );
+
+    fn handle(
+        events: impl ExactSizeIterator<Item = Self>,
+        (
+            time,
+            world,
+            index,
+            mut instances,
+            mut chunk_generator,
+            teleport_to_position_events,
+            outcomes,
+            uids,
+            groups,
+            clients,
+        ): Self::SystemData<'_>,
+    ) {
+        let mut teleport_to_position_emitter = teleport_to_position_events.emitter();
+        let mut outcome_emitter = outcomes.emitter();
+        for ev in events {
+            let owner = match (groups.get(ev.leader), uids.get(ev.leader)) {
+                (Some(group), _) => Owner::Group(*group),
+                (None, Some(uid)) => Owner::Player(*uid),
+                (None, None) => continue,
+            };
+            match instances.enter(
+                owner,
+                ev.kind,
+                ev.exit,
+                *time,
+                &world,
+                index.as_index_ref(),
+                &mut chunk_generator,
+            ) {
+                Ok(spawn) => {
+                    for entity in ev.entities {
+                        teleport_to_position_emitter.emit(TeleportToPositionEvent {
+                            entity,
+                            position: spawn,
+                        });
+                        outcome_emitter.emit(Outcome::TeleportedByPortal { pos: spawn });
+                    }
+                },
+                Err(error) => {
+                    warn!(?error, kind = ?ev.kind, "Failed to enter instance");
+                    if let Some(client) = clients.get(ev.leader) {
+                        client.send_fallible(ServerGeneral::server_msg(
+                            ChatType::Meta,
+                            Content::localized("server-instance-unavailable"),
+                        ));
+                    }
+                },
+            }
+        }
+    }
+}
+
 impl ServerEvent for RegrowHeadEvent {
     type SystemData<'a> = (
         Read<'a, EventBus<HealthChangeEvent>>,
@@ -2615,6 +2726,7 @@ impl ServerEvent for RegrowHeadEvent {
                         amount,
                         by: None,
                         cause: Some(DamageSource::Other),
+                        kind: None,
                         time: *time,
                         precise: false,
                         instance: rand::random(),
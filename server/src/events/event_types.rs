@@ -1,18 +1,18 @@
 pub use common::event::{
-    AuraEvent, BonkEvent, BuffEvent, ChangeAbilityEvent, ChangeBodyEvent, ChangeStanceEvent,
-    ChatEvent, ClientDisconnectEvent, ClientDisconnectWithoutPersistenceEvent, ComboChangeEvent,
-    CommandEvent, CreateAuraEntityEvent, CreateItemDropEvent, CreateNpcEvent, CreateObjectEvent,
-    CreateShipEvent, CreateSpecialEntityEvent, CreateSpriteEvent, DeleteCharacterEvent,
-    DeleteEvent, DestroyEvent, DialogueEvent, DownedEvent, EnergyChangeEvent,
-    EntityAttackedHookEvent, EventBus, ExitIngameEvent, ExplosionEvent, GroupManipEvent,
-    HealthChangeEvent, HelpDownedEvent, InitializeCharacterEvent, InitializeSpectatorEvent,
-    InitiateInviteEvent, InventoryManipEvent, InviteResponseEvent, KillEvent, KnockbackEvent,
-    LandOnGroundEvent, MakeAdminEvent, MineBlockEvent, MountEvent, NpcInteractEvent,
-    ParryHookEvent, PoiseChangeEvent, PossessEvent, ProcessTradeActionEvent, RegrowHeadEvent,
-    RemoveLightEmitterEvent, RequestPluginsEvent, RequestSiteInfoEvent, RespawnEvent,
-    SetBattleModeEvent, SetLanternEvent, SetPetStayEvent, ShockwaveEvent, ShootEvent, SoundEvent,
-    StartInteractionEvent, StartTeleportingEvent, TamePetEvent, TeleportToEvent,
-    TeleportToPositionEvent, ThrowEvent, ToggleSpriteLightEvent, TransformEvent,
+    AbandonQuestEvent, AuraEvent, BonkEvent, BuffEvent, ChangeAbilityEvent, ChangeBodyEvent,
+    ChangeStanceEvent, ChatEvent, ClientDisconnectEvent, ClientDisconnectWithoutPersistenceEvent,
+    ComboChangeEvent, CommandEvent, CreateAuraEntityEvent, CreateItemDropEvent, CreateNpcEvent,
+    CreateObjectEvent, CreateShipEvent, CreateSpecialEntityEvent, CreateSpriteEvent,
+    DeleteCharacterEvent, DeleteEvent, DestroyEvent, DialogueEvent, DownedEvent, EnergyChangeEvent,
+    EnterInstanceEvent, EntityAttackedHookEvent, EventBus, ExitIngameEvent, ExplosionEvent,
+    GroupManipEvent, HealthChangeEvent, HelpDownedEvent, InitializeCharacterEvent,
+    InitializeSpectatorEvent, InitiateInviteEvent, InventoryManipEvent, InviteResponseEvent,
+    KillEvent, KnockbackEvent, LandOnGroundEvent, MakeAdminEvent, MineBlockEvent, MountEvent,
+    NpcInteractEvent, ParryHookEvent, PoiseChangeEvent, PossessEvent, ProcessTradeActionEvent,
+    RegrowHeadEvent, RemoveLightEmitterEvent, RequestPluginsEvent, RequestSiteInfoEvent,
+    RespawnEvent, SetBattleModeEvent, SetLanternEvent, SetPetStayEvent, ShockwaveEvent, ShootEvent,
+    SoundEvent, StartInteractionEvent, StartQuestEvent, StartTeleportingEvent, TamePetEvent,
+    TeleportToEvent, TeleportToPositionEvent, ThrowEvent, ToggleSpriteLightEvent, TransformEvent,
     UpdateCharacterDataEvent, UpdateMapMarkerEvent,
 };
 
@@ -50,6 +50,8 @@ macro_rules! server_events {
             SetLanternEvent
             NpcInteractEvent
             DialogueEvent
+            StartQuestEvent
+            AbandonQuestEvent
             InviteResponseEvent
             InitiateInviteEvent
             ProcessTradeActionEvent
@@ -81,6 +83,7 @@ macro_rules! server_events {
             RemoveLightEmitterEvent
             TeleportToPositionEvent
             StartTeleportingEvent
+            EnterInstanceEvent
             ToggleSpriteLightEvent
             TransformEvent
             StartInteractionEvent
@@ -39,7 +39,11 @@ use hashbrown::{HashMap, HashSet};
 use lazy_static::lazy_static;
 use serde::Deserialize;
 
-use super::{ServerEvent, event_dispatch, mounting::within_mounting_range};
+use super::{
+    ServerEvent, event_dispatch,
+    mounting::within_mounting_range,
+    quest::{QuestHandInData, hand_in_quests},
+};
 
 pub(super) fn register_event_systems(builder: &mut DispatcherBuilder) {
     event_dispatch::<SetLanternEvent>(builder, &[]);
@@ -99,21 +103,18 @@ impl ServerEvent for SetLanternEvent {
 }
 
 impl ServerEvent for NpcInteractEvent {
-    type SystemData<'a> = (
-        WriteStorage<'a, comp::Agent>,
-        ReadStorage<'a, comp::Pos>,
-        ReadStorage<'a, Uid>,
-    );
+    type SystemData<'a> = (WriteStorage<'a, comp::Agent>, QuestHandInData<'a>);
 
     fn handle(
         events: impl ExactSizeIterator<Item = Self>,
-        (mut agents, positions, uids): Self::SystemData<'_>,
+        (mut agents, mut quest_data): Self::SystemData<'_>,
     ) {
         for NpcInteractEvent(interactor, npc_entity) in events {
             let within_range = {
-                positions
+                quest_data
+                    .positions
                     .get(interactor)
-                    .zip(positions.get(npc_entity))
+                    .zip(quest_data.positions.get(npc_entity))
                     .is_some_and(|(interactor_pos, npc_pos)| {
                         interactor_pos.0.distance_squared(npc_pos.0)
                             <= MAX_NPCINTERACT_RANGE.powi(2)
@@ -124,7 +125,8 @@ impl ServerEvent for NpcInteractEvent {
                 && let Some(agent) = agents.get_mut(npc_entity)
                 && agent.target.is_none()
             {
-                if let Some(interactor_uid) = uids.get(interactor) {
+                hand_in_quests(&mut quest_data, interactor, Some(npc_entity));
+                if let Some(interactor_uid) = quest_data.uids.get(interactor) {
                     agent.inbox.push_back(AgentEvent::Talk(*interactor_uid));
                 }
             }
@@ -39,6 +39,7 @@ mod inventory_manip;
 mod invite;
 mod mounting;
 mod player;
+mod quest;
 mod trade;
 
 pub(crate) use event_types::register_event_busses;
@@ -47,6 +48,7 @@ pub(crate) mod shared {
     pub(crate) use super::{
         entity_manipulation::{TransformEntityError, transform_entity},
         group_manip::update_map_markers,
+        quest::{QuestHandInData, hand_in_quests},
         trade::cancel_trades_for,
     };
 }
@@ -108,6 +110,7 @@ pub fn register_event_systems(builder: &mut DispatcherBuilder) {
     invite::register_event_systems(builder);
     group_manip::register_event_systems(builder);
     information::register_event_systems(builder);
+    quest::register_event_systems(builder);
 }
 
 /// Server frontend events.
@@ -312,6 +312,7 @@ pub(super) fn persist_entity(state: &mut State, entity: EcsEntity) -> EcsEntity
         Some(skill_set),
         Some(inventory),
         Some(active_abilities),
+        Some(quest_log),
         Some(player_uid),
         Some(player_info),
         mut character_updater,
@@ -323,6 +324,7 @@ pub(super) fn persist_entity(state: &mut State, entity: EcsEntity) -> EcsEntity
         state
             .read_storage::<comp::ability::ActiveAbilities>()
             .get(entity),
+        state.read_storage::<comp::QuestLog>().get(entity),
         state.read_storage::<Uid>().get(entity),
         state.read_storage::<comp::Player>().get(entity),
         state.ecs().fetch_mut::<CharacterUpdater>(),
@@ -391,6 +393,7 @@ pub(super) fn persist_entity(state: &mut State, entity: EcsEntity) -> EcsEntity
                         waypoint,
                         active_abilities.clone(),
                         map_marker,
+                        quest_log.clone(),
                     ));
                 }
             },
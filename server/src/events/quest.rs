@@ -0,0 +1,254 @@
+use common::{
+    comp::{
+        self, ChatType, Content, Inventory, Item, Presence, PresenceKind, QuestLog, SkillSet,
+        item::MaterialStatManifest,
+        loot_owner::{LootOwner, LootOwnerKind},
+        quest::{Objective, Quest, QuestError},
+        tool::AbilityMap,
+    },
+    event::{AbandonQuestEvent, CreateItemDropEvent, EventBus, StartQuestEvent},
+    outcome::Outcome,
+    resources::ProgramTime,
+    rtsim::{Actor, RtSimEntity},
+    uid::Uid,
+};
+use common_net::msg::ServerGeneral;
+use specs::{
+    DispatcherBuilder, Entity as EcsEntity, Read, ReadExpect, ReadStorage, SystemData,
+    WriteStorage, shred,
+};
+use tracing::warn;
+
+use crate::client::Client;
+#[cfg(feature = "worldgen")]
+use crate::rtsim::RtSim;
+#[cfg(feature = "worldgen")]
+use specs::WriteExpect;
+
+use super::{ServerEvent, entity_manipulation::handle_exp_gain, event_dispatch};
+
+pub(super) fn register_event_systems(builder: &mut DispatcherBuilder) {
+    event_dispatch::<StartQuestEvent>(builder, &[]);
+    event_dispatch::<AbandonQuestEvent>(builder, &[]);
+}
+
+fn notify(clients: &ReadStorage<'_, Client>, entity: EcsEntity, content: Content) {
+    if let Some(client) = clients.get(entity) {
+        client.send_fallible(ServerGeneral::server_msg(ChatType::Meta, content));
+    }
+}
+
+impl ServerEvent for StartQuestEvent {
+    type SystemData<'a> = (WriteStorage<'a, QuestLog>, ReadStorage<'a, Client>);
+
+    fn handle(
+        events: impl ExactSizeIterator<Item = Self>,
+        (mut quest_logs, clients): Self::SystemData<'_>,
+    ) {
+        for StartQuestEvent(_giver, target, quest) in events {
+            let Some(mut quest_log) = quest_logs.get_mut(target) else {
+                continue;
+            };
+
+            let title = quest.title.clone();
+            let result = if let Some(giver) = quest.giver
+                && quest_log.has_quest_from(giver)
+            {
+                Err(QuestError::AlreadyAccepted)
+            } else {
+                quest_log.accept(*quest)
+            };
+            let content = match result {
+                Ok(()) => Content::localized_with_args("quest-started", [("title", title)]),
+                Err(QuestError::AlreadyAccepted) => Content::localized("quest-already_accepted"),
+                Err(QuestError::TooManyQuests) => Content::localized("quest-too_many"),
+            };
+            notify(&clients, target, content);
+        }
+    }
+}
+
+impl ServerEvent for AbandonQuestEvent {
+    type SystemData<'a> = (
+        WriteStorage<'a, QuestLog>,
+        ReadStorage<'a, Client>,
+        EscortData<'a>,
+    );
+
+    fn handle(
+        events: impl ExactSizeIterator<Item = Self>,
+        (mut quest_logs, clients, mut escorts): Self::SystemData<'_>,
+    ) {
+        for AbandonQuestEvent(entity, quest_id) in events {
+            if let Some(mut quest_log) = quest_logs.get_mut(entity)
+                && let Some(quest) = quest_log.abandon(quest_id)
+            {
+                escorts.end_escorts(entity, &quest);
+                notify(
+                    &clients,
+                    entity,
+                    Content::localized_with_args("quest-abandoned", [("title", quest.title)]),
+                );
+            }
+        }
+    }
+}
+
+/// Everything needed to release NPCs that were hired to be escorted for a
+/// quest
+#[derive(SystemData)]
+pub struct EscortData<'a> {
+    #[cfg(feature = "worldgen")]
+    rtsim: WriteExpect<'a, RtSim>,
+    presences: ReadStorage<'a, Presence>,
+    rtsim_entities: ReadStorage<'a, RtSimEntity>,
+}
+
+impl EscortData<'_> {
+    /// Ends the hire of the NPCs escorted by `entity` for `quest`, once the
+    /// quest was completed or abandoned
+    fn end_escorts(&mut self, entity: EcsEntity, quest: &Quest) {
+        let hirer = if let Some(rtsim_entity) = self.rtsim_entities.get(entity) {
+            Actor::Npc(rtsim_entity.0)
+        } else if let Some(PresenceKind::Character(character)) =
+            self.presences.get(entity).map(|p| p.kind)
+        {
+            Actor::Character(character)
+        } else {
+            return;
+        };
+        for objective in &quest.objectives {
+            if let Objective::Escort {
+                npc: Actor::Npc(npc),
+                ..
+            } = objective
+            {
+                #[cfg(feature = "worldgen")]
+                self.rtsim.hook_escort_ended(*npc, hirer);
+                #[cfg(not(feature = "worldgen"))]
+                let _ = (npc, hirer);
+            }
+        }
+    }
+}
+
+/// Everything needed to hand in quests and pay out their rewards
+#[derive(SystemData)]
+pub struct QuestHandInData<'a> {
+    pub(crate) quest_logs: WriteStorage<'a, QuestLog>,
+    pub(crate) inventories: WriteStorage<'a, Inventory>,
+    skill_sets: WriteStorage<'a, SkillSet>,
+    rtsim_entities: ReadStorage<'a, RtSimEntity>,
+    pub(crate) positions: ReadStorage<'a, comp::Pos>,
+    pub(crate) uids: ReadStorage<'a, Uid>,
+    clients: ReadStorage<'a, Client>,
+    ability_map: ReadExpect<'a, AbilityMap>,
+    msm: ReadExpect<'a, MaterialStatManifest>,
+    program_time: ReadExpect<'a, ProgramTime>,
+    outcomes: Read<'a, EventBus<Outcome>>,
+    create_item_drop: Read<'a, EventBus<CreateItemDropEvent>>,
+    escorts: EscortData<'a>,
+}
+
+/// Hands in every completed quest that `entity` can hand in to `giver`, paying
+/// out the rewards. Without a giver, only quests that don't need to be handed
+/// in are paid out.
+pub(crate) fn hand_in_quests(
+    data: &mut QuestHandInData,
+    entity: EcsEntity,
+    giver: Option<EcsEntity>,
+) {
+    let giver = giver
+        .and_then(|giver| data.rtsim_entities.get(giver))
+        .map(|e| Actor::Npc(e.0));
+    let Some(quest_log) = data.quest_logs.get(entity) else {
+        return;
+    };
+    // The quest log only hands in fetch quests while the inventory holds the
+    // items for all of them together, so none of the items are missing below
+    let ready = quest_log.ready_to_hand_in(giver, data.inventories.get(entity));
+    if ready.is_empty() {
+        return;
+    }
+    let ready = quest_log
+        .quests()
+        .filter(|q| ready.contains(&q.quest.id))
+        .map(|q| q.quest.clone())
+        .collect::<Vec<_>>();
+    let handed_in = ready
+        .iter()
+        .filter(|quest| {
+            let taken = take_fetched_items(data, entity, quest);
+            if !taken {
+                warn!("Failed to take fetched items for completed quest, not paying reward");
+            }
+            taken
+        })
+        .map(|quest| quest.id)
+        .collect::<Vec<_>>();
+    let Some(mut quest_log) = data.quest_logs.get_mut(entity) else {
+        return;
+    };
+    let completed = quest_log.complete(&handed_in);
+
+    for quest in completed {
+        data.escorts.end_escorts(entity, &quest);
+        pay_reward(data, entity, &quest);
+        notify(
+            &data.clients,
+            entity,
+            Content::localized_with_args("quest-completed", [("title", quest.title)]),
+        );
+    }
+}
+
+fn pay_reward(data: &mut QuestHandInData, entity: EcsEntity, quest: &Quest) {
+    let mut outcomes = data.outcomes.emitter();
+    let mut create_item_drop = data.create_item_drop.emitter();
+
+    for (spec, amount) in &quest.reward.items {
+        let mut item = match Item::new_from_asset(spec) {
+            Ok(item) => item,
+            Err(err) => {
+                warn!(?err, "Invalid quest reward item {spec}");
+                continue;
+            },
+        };
+        if item.set_amount(*amount).is_err() {
+            warn!("Failed to set amount of quest reward item {spec}");
+        }
+        let leftover = match data.inventories.get_mut(entity) {
+            Some(mut inventory) => inventory.push(item).err().map(|(item, _)| item),
+            None => Some(item),
+        };
+        // Rewards that don't fit are dropped at the character's feet
+        if let Some(item) = leftover
+            && let Some(pos) = data.positions.get(entity)
+        {
+            create_item_drop.emit(CreateItemDropEvent {
+                pos: *pos,
+                vel: comp::Vel::default(),
+                ori: comp::Ori::default(),
+                item: comp::PickupItem::new(item, *data.program_time),
+                loot_owner: data
+                    .uids
+                    .get(entity)
+                    .map(|uid| LootOwner::new(LootOwnerKind::Player(*uid), false)),
+            });
+        }
+    }
+
+    if quest.reward.exp > 0
+        && let Some(inventory) = data.inventories.get(entity)
+        && let Some(mut skill_set) = data.skill_sets.get_mut(entity)
+        && let Some(uid) = data.uids.get(entity)
+    {
+        handle_exp_gain(
+            quest.reward.exp as f32,
+            inventory,
+            &mut skill_set,
+            uid,
+            &mut outcomes,
+        );
+    }
+}
@@ -0,0 +1,490 @@
+//! Instances, private copies of a dungeon or arenas entered by a group through
+//! a portal.
+//!
+//! The server runs a single world, so instances are placed in the terrain east
+//! of the map, which is twice as wide as the map for this (see
+//! [`MapSizeLg::with_instances`]). Each instance gets a slot of
+//! [`SLOT_CHUNKS`] chunks, and slots are further apart than the maximum view
+//! distance, so players in an instance only load its chunks and only see its
+//! entities. The chunks of a dungeon instance are generated from the chunks of
+//! the site in the world, with the NPCs spawned by the site moved along, while
+//! an arena is a flat floor surrounded by walls.
+//!
+//! Players enter through a portal with an [`InstanceEntrance`] and leave
+//! through the portal generated next to the spawn point of the instance, both
+//! use the usual teleporting. The members of a group enter the same instance.
+//! An instance is closed once no player has been in it for [`EMPTY_TIMEOUT`],
+//! its chunks then unload like any other chunk and their entities are removed
+//! with them, after which the slot is reused. No waypoints are generated in
+//! instances, so players logging out in one return to the world when they log
+//! back in.
+
+use crate::chunk_generator::ChunkGenerator;
+#[cfg(not(feature = "worldgen"))]
+use crate::test_world::{IndexRef, World};
+use common::{
+    calendar::Calendar,
+    comp::{
+        Group,
+        misc::{InstanceKind, PortalData},
+    },
+    generation::{ChunkSupplement, EntityInfo, SpecialEntity},
+    region::REGION_SIZE,
+    resources::{Secs, Time, TimeOfDay},
+    rtsim::ChunkResource,
+    terrain::{
+        Block, BlockKind, CoordinateConversions, MapSizeLg, SpriteKind, TerrainChunk,
+        TerrainChunkMeta, TerrainChunkSize, TerrainGrid,
+    },
+    uid::Uid,
+    vol::{RectVolSize, WriteVol},
+};
+use enum_map::EnumMap;
+use specs::{Component, DenseVecStorage};
+use tracing::{info, warn};
+use vek::*;
+#[cfg(feature = "worldgen")]
+use world::{IndexRef, World, site2::plot::PlotKindMeta};
+
+/// Width of the square slot of an instance, in chunks. Larger dungeons are
+/// cut off around their center.
+pub const SLOT_CHUNKS: i32 = 16;
+/// Width of an arena, in chunks.
+const ARENA_CHUNKS: i32 = 4;
+/// Altitude of the floor of arenas, above the sea level of generated worlds.
+const ARENA_FLOOR_Z: i32 = 256;
+const ARENA_WALL_HEIGHT: i32 = 12;
+/// Time in seconds after which an instance without players is closed.
+pub const EMPTY_TIMEOUT: f64 = 30.0;
+/// Distance between the spawn point and the exit portal of an instance.
+const EXIT_OFFSET: f32 = 4.0;
+
+/// Marks a portal leading into an instance instead of its target.
+pub struct InstanceEntrance(pub InstanceKind);
+
+impl Component for InstanceEntrance {
+    type Storage = DenseVecStorage<Self>;
+}
+
+/// Who an instance belongs to, the members of a group share the instance.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum Owner {
+    Group(Group),
+    Player(Uid),
+}
+
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum InstanceError {
+    /// All slots are taken, or the map is too large to have room for them.
+    NoFreeSlot,
+    UnknownSite,
+    /// The site has no dungeon to make an instance of.
+    NoDungeon,
+    /// Dungeon instances need the generated world.
+    Unsupported,
+}
+
+/// What the chunk at a key of an instance is generated from.
+#[derive(Clone, Debug)]
+pub struct InstanceChunk {
+    pub source: ChunkSource,
+    /// The exit portal of the instance, when it is in this chunk.
+    pub exit: Option<(Vec3<f32>, PortalData)>,
+}
+
+#[derive(Copy, Clone, Debug)]
+pub enum ChunkSource {
+    /// Copy of the chunk of the world with this key.
+    World(Vec2<i32>),
+    /// Part of an arena covering these chunks.
+    Arena { area: Aabr<i32> },
+}
+
+impl InstanceChunk {
+    /// Key of the chunk the rtsim resources are taken from.
+    pub fn resource_key(&self, key: Vec2<i32>) -> Vec2<i32> {
+        match self.source {
+            ChunkSource::World(source) => source,
+            ChunkSource::Arena { .. } => key,
+        }
+    }
+}
+
+struct Instance {
+    kind: InstanceKind,
+    owner: Owner,
+    spawn: Vec3<f32>,
+    /// Last time a player was in the instance.
+    last_occupied: Time,
+    /// Closed instances can't be entered, their slot is freed once all their
+    /// chunks are unloaded.
+    closed: bool,
+}
+
+pub struct Instances {
+    /// Key of the chunk in the south-west corner of each slot.
+    slots: Vec<Vec2<i32>>,
+    /// The view distance the slots are spaced for.
+    view_distance: i32,
+    instances: Vec<Option<Instance>>,
+}
+
+impl Instances {
+    /// `view_distance` is the largest distance in chunks players keep chunks
+    /// loaded at. Instances are placed further apart than that, with room for
+    /// the regions players are subscribed to for entity updates, which reach
+    /// further.
+    pub fn new(map_size: MapSizeLg, terrain_size: MapSizeLg, view_distance: i32) -> Self {
+        let region_chunks = (REGION_SIZE / TerrainChunkSize::RECT_SIZE.x) as i32;
+        let slots = slot_corners(
+            map_size.chunks().as_(),
+            terrain_size.chunks().as_(),
+            view_distance * 3 / 2 + region_chunks + 1,
+        );
+        if slots.is_empty() {
+            warn!(
+                "No instance fits east of the map for a view distance of {} chunks, portals to \
+                 instances won't open",
+                view_distance
+            );
+        }
+        Self {
+            instances: slots.iter().map(|_| None).collect(),
+            slots,
+            view_distance,
+        }
+    }
+
+    /// The largest view distance in chunks the instances are far enough apart
+    /// for.
+    pub fn view_distance(&self) -> i32 { self.view_distance }
+
+    /// Spawn point of the instance of `kind` owned by `owner`, which is
+    /// created if it doesn't exist yet. Its exit portal leads to `exit`.
+    pub fn enter(
+        &mut self,
+        owner: Owner,
+        kind: InstanceKind,
+        exit: Vec3<f32>,
+        time: Time,
+        world: &World,
+        index: IndexRef,
+        chunk_generator: &mut ChunkGenerator,
+    ) -> Result<Vec3<f32>, InstanceError> {
+        if let Some(instance) =
+            self.instances.iter_mut().flatten().find(|instance| {
+                !instance.closed && instance.owner == owner && instance.kind == kind
+            })
+        {
+            // Players on their way in don't count as occupying it yet
+            instance.last_occupied = time;
+            return Ok(instance.spawn);
+        }
+
+        let slot = self
+            .instances
+            .iter()
+            .position(Option::is_none)
+            .ok_or(InstanceError::NoFreeSlot)?;
+        let corner = self.slots[slot];
+        let (chunks, spawn) = match kind {
+            InstanceKind::Arena => arena_chunks(corner),
+            InstanceKind::Dungeon { site } => dungeon_chunks(world, index, site, corner)?,
+        };
+
+        let exit_pos = spawn + Vec3::unit_x() * EXIT_OFFSET;
+        let exit_key = TerrainGrid::chunk_key(exit_pos.xy().as_::<i32>());
+        for (key, source) in chunks {
+            chunk_generator.set_instance_chunk(key, InstanceChunk {
+                source,
+                exit: (key == exit_key).then(|| {
+                    (exit_pos, PortalData {
+                        target: exit,
+                        requires_no_aggro: false,
+                        buildup_time: Secs(5.0),
+                    })
+                }),
+            });
+        }
+
+        info!(?kind, ?owner, ?slot, "Opened instance");
+        self.instances[slot] = Some(Instance {
+            kind,
+            owner,
+            spawn,
+            last_occupied: time,
+            closed: false,
+        });
+        Ok(spawn)
+    }
+
+    /// Close the instances without players and free the slots of closed
+    /// instances whose chunks are all unloaded.
+    pub fn maintain(
+        &mut self,
+        players: impl Iterator<Item = Vec3<f32>>,
+        time: Time,
+        terrain: &TerrainGrid,
+        chunk_generator: &mut ChunkGenerator,
+    ) {
+        for pos in players {
+            let key = TerrainGrid::chunk_key(pos.xy().as_::<i32>());
+            if let Some(slot) = self.slot_at(key)
+                && let Some(instance) = &mut self.instances[slot]
+            {
+                instance.last_occupied = time;
+            }
+        }
+
+        for (slot, corner) in self.slots.iter().enumerate() {
+            let Some(instance) = &mut self.instances[slot] else {
+                continue;
+            };
+            if !instance.closed && time.0 - instance.last_occupied.0 > EMPTY_TIMEOUT {
+                let (kind, owner) = (instance.kind, instance.owner);
+                info!(?kind, ?owner, ?slot, "Closed empty instance");
+                instance.closed = true;
+            }
+            if instance.closed {
+                let area = slot_area(*corner);
+                let loaded = (area.min.x..=area.max.x)
+                    .flat_map(|x| (area.min.y..=area.max.y).map(move |y| Vec2::new(x, y)))
+                    .any(|key| terrain.get_key_real(key).is_some())
+                    || chunk_generator
+                        .pending_chunks()
+                        .any(|key| area.contains_point(key));
+                if !loaded {
+                    chunk_generator.remove_instance_chunks(area);
+                    self.instances[slot] = None;
+                }
+            }
+        }
+    }
+
+    /// Chunks of the slot of the instance the chunk at `key` is in, if any.
+    pub fn area_at(&self, key: Vec2<i32>) -> Option<Aabr<i32>> {
+        let slot = self.slot_at(key)?;
+        self.instances[slot]
+            .is_some()
+            .then(|| slot_area(self.slots[slot]))
+    }
+
+    fn slot_at(&self, key: Vec2<i32>) -> Option<usize> {
+        self.slots
+            .iter()
+            .position(|corner| slot_area(*corner).contains_point(key))
+    }
+}
+
+/// Chunks of the slot with this corner.
+fn slot_area(corner: Vec2<i32>) -> Aabr<i32> {
+    Aabr {
+        min: corner,
+        max: corner + SLOT_CHUNKS - 1,
+    }
+}
+
+/// Corners of the slots which fit east of the map, `gap` chunks apart from each
+/// other and from the map.
+fn slot_corners(map_size: Vec2<i32>, terrain_size: Vec2<i32>, gap: i32) -> Vec<Vec2<i32>> {
+    let pitch = SLOT_CHUNKS + gap;
+    let columns = (terrain_size.x - map_size.x - gap) / pitch;
+    let rows = (terrain_size.y + gap) / pitch;
+    (0..columns.max(0))
+        .flat_map(|column| {
+            (0..rows.max(0))
+                .map(move |row| Vec2::new(map_size.x + gap + column * pitch, row * pitch))
+        })
+        .collect()
+}
+
+fn arena_chunks(corner: Vec2<i32>) -> (Vec<(Vec2<i32>, ChunkSource)>, Vec3<f32>) {
+    let min = corner + (SLOT_CHUNKS - ARENA_CHUNKS) / 2;
+    let area = Aabr {
+        min,
+        max: min + ARENA_CHUNKS - 1,
+    };
+    let chunks = (area.min.x..=area.max.x)
+        .flat_map(|x| (area.min.y..=area.max.y).map(move |y| Vec2::new(x, y)))
+        .map(|key| (key, ChunkSource::Arena { area }))
+        .collect();
+    let center = (min * TerrainChunkSize::RECT_SIZE.as_::<i32>()
+        + ARENA_CHUNKS * TerrainChunkSize::RECT_SIZE.as_::<i32>() / 2)
+        .as_::<f32>();
+    (chunks, center.with_z(ARENA_FLOOR_Z as f32))
+}
+
+#[cfg(feature = "worldgen")]
+fn dungeon_chunks(
+    world: &World,
+    index: IndexRef,
+    site: common::trade::SiteId,
+    corner: Vec2<i32>,
+) -> Result<(Vec<(Vec2<i32>, ChunkSource)>, Vec3<f32>), InstanceError> {
+    let site = index
+        .sites
+        .recreate_id(site)
+        .map(|id| index.sites.get(id))
+        .ok_or(InstanceError::UnknownSite)?;
+    let site2 = site.site2().ok_or(InstanceError::NoDungeon)?;
+    let area = site2
+        .plots()
+        .filter(|plot| matches!(plot.kind().meta(), Some(PlotKindMeta::Dungeon)))
+        .map(|plot| {
+            let tiles = plot.find_bounds();
+            Aabr {
+                min: site2.tile_wpos(tiles.min),
+                max: site2.tile_wpos(tiles.max + 1),
+            }
+        })
+        .reduce(|a, b| a.union(b))
+        .ok_or(InstanceError::NoDungeon)?;
+
+    // Chunks covering the dungeon, cut off around its center to fit the slot
+    let center = area.center().wpos_to_cpos();
+    let min = area
+        .min
+        .wpos_to_cpos()
+        .map2(center, |min, center| min.max(center - SLOT_CHUNKS / 2));
+    let max = area
+        .max
+        .wpos_to_cpos()
+        .map2(min, |max, min| max.min(min + SLOT_CHUNKS - 1));
+    let offset = corner - min;
+    let chunks = (min.x..=max.x)
+        .flat_map(|x| (min.y..=max.y).map(move |y| Vec2::new(x, y)))
+        .map(|source| (source + offset, ChunkSource::World(source)))
+        .collect();
+
+    let entrance = site.get_origin().map3(
+        min.cpos_to_wpos(),
+        (max + 1).cpos_to_wpos() - 1,
+        |e, min, max| e.clamp(min, max),
+    );
+    let spawn = world.find_accessible_pos(index, entrance, false)
+        + offset.cpos_to_wpos().as_::<f32>().with_z(0.0);
+    Ok((chunks, spawn))
+}
+
+#[cfg(not(feature = "worldgen"))]
+fn dungeon_chunks(
+    _world: &World,
+    _index: IndexRef,
+    _site: common::trade::SiteId,
+    _corner: Vec2<i32>,
+) -> Result<(Vec<(Vec2<i32>, ChunkSource)>, Vec3<f32>), InstanceError> {
+    Err(InstanceError::Unsupported)
+}
+
+/// Generate the chunk at `key` of an instance.
+pub fn generate_chunk(
+    world: &World,
+    index: IndexRef,
+    key: Vec2<i32>,
+    chunk: &InstanceChunk,
+    rtsim_resources: Option<EnumMap<ChunkResource, f32>>,
+    should_continue: impl FnMut() -> bool,
+    time: Option<(TimeOfDay, Calendar)>,
+) -> Result<(TerrainChunk, ChunkSupplement), ()> {
+    let (terrain_chunk, mut supplement) = match chunk.source {
+        ChunkSource::World(source) => {
+            let (terrain_chunk, mut supplement) =
+                world.generate_chunk(index, source, rtsim_resources, should_continue, time)?;
+            let offset = (key - source).cpos_to_wpos().as_::<f32>().with_z(0.0);
+            // Waypoints would make players respawn in the instance after it is closed
+            supplement
+                .entities
+                .retain(|entity| !matches!(entity.special_entity, Some(SpecialEntity::Waypoint)));
+            for entity in &mut supplement.entities {
+                entity.pos += offset;
+                if let Some(SpecialEntity::Teleporter(portal)) = &mut entity.special_entity {
+                    portal.target += offset;
+                }
+            }
+            (terrain_chunk, supplement)
+        },
+        ChunkSource::Arena { area } => (arena_chunk(key, area), ChunkSupplement::default()),
+    };
+
+    if let Some((pos, portal)) = &chunk.exit {
+        supplement.add_entity(
+            EntityInfo::at(*pos).into_special(SpecialEntity::Teleporter(portal.clone())),
+        );
+    }
+    Ok((terrain_chunk, supplement))
+}
+
+fn arena_chunk(key: Vec2<i32>, area: Aabr<i32>) -> TerrainChunk {
+    let floor = Block::new(BlockKind::Rock, Rgb::new(110, 100, 90));
+    let wall = Block::new(BlockKind::Rock, Rgb::new(80, 70, 60));
+    let mut chunk = TerrainChunk::new(
+        ARENA_FLOOR_Z,
+        floor,
+        Block::air(SpriteKind::Empty),
+        TerrainChunkMeta::void(),
+    );
+
+    let size = TerrainChunkSize::RECT_SIZE.as_::<i32>();
+    let min_wpos = area.min.cpos_to_wpos();
+    let max_wpos = (area.max + 1).cpos_to_wpos() - 1;
+    for x in 0..size.x {
+        for y in 0..size.y {
+            let wpos = key.cpos_to_wpos() + Vec2::new(x, y);
+            if wpos.x == min_wpos.x
+                || wpos.x == max_wpos.x
+                || wpos.y == min_wpos.y
+                || wpos.y == max_wpos.y
+            {
+                for z in ARENA_FLOOR_Z..ARENA_FLOOR_Z + ARENA_WALL_HEIGHT {
+                    let _ = chunk.set(Vec3::new(x, y, z), wall);
+                }
+            }
+        }
+    }
+    chunk
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use common::vol::ReadVol;
+
+    #[test]
+    fn slots_are_apart() {
+        let map_size = Vec2::new(1024, 1024);
+        let gap = 68;
+        let corners = slot_corners(map_size, Vec2::new(2048, 1024), gap);
+        assert!(!corners.is_empty());
+        for (i, a) in corners.iter().enumerate() {
+            let area = slot_area(*a);
+            assert!(area.min.x >= map_size.x + gap);
+            assert!(area.max.x < 2048);
+            assert!(area.max.y < 1024);
+            for b in &corners[i + 1..] {
+                let distance = (*b - *a).map(i32::abs).reduce_max() - SLOT_CHUNKS;
+                assert!(distance >= gap);
+            }
+        }
+        // No room without the space east of the map
+        assert!(slot_corners(map_size, map_size, gap).is_empty());
+    }
+
+    #[test]
+    fn arena_walls() {
+        let (chunks, spawn) = arena_chunks(Vec2::new(1100, 0));
+        assert_eq!(chunks.len(), (ARENA_CHUNKS * ARENA_CHUNKS) as usize);
+        let (key, ChunkSource::Arena { area }) = chunks[0] else {
+            panic!("arena chunks are generated as arena");
+        };
+        let chunk = arena_chunk(key, area);
+        let floor = |x, y, z| chunk.get(Vec3::new(x, y, z)).unwrap().is_solid();
+        // The corner of the arena is walled, the inside is an open floor
+        assert!(floor(0, 0, ARENA_FLOOR_Z));
+        assert!(floor(5, 5, ARENA_FLOOR_Z - 1));
+        assert!(!floor(5, 5, ARENA_FLOOR_Z));
+        assert_eq!(
+            TerrainGrid::chunk_key(spawn.xy().as_::<i32>()),
+            area.min + ARENA_CHUNKS / 2
+        );
+    }
+}
@@ -18,6 +18,9 @@ mod data_dir;
 pub mod error;
 pub mod events;
 pub mod input;
+pub mod instance;
+pub mod load;
+pub mod localization;
 pub mod location;
 pub mod lod;
 pub mod login_provider;
@@ -25,6 +28,7 @@ pub mod metrics;
 pub mod persistence;
 mod pet;
 pub mod presence;
+pub mod replay;
 pub mod rtsim;
 pub mod settings;
 pub mod state_ext;
@@ -32,6 +36,7 @@ pub mod sys;
 #[cfg(feature = "persistent_world")]
 pub mod terrain_persistence;
 #[cfg(not(feature = "worldgen"))] mod test_world;
+pub mod tick_trace;
 
 #[cfg(feature = "worldgen")] mod weather;
 
@@ -43,7 +48,11 @@ pub use crate::{
     error::Error,
     events::Event,
     input::Input,
-    settings::{CalendarMode, EditableSettings, Settings},
+    localization::ServerLocalization,
+    settings::{
+        CalendarMode, EditableSettings, InvalidSettingsError, RuntimeSettings,
+        RuntimeSettingsPatch, ServerBattleMode, Settings,
+    },
 };
 
 #[cfg(feature = "persistent_world")]
@@ -71,7 +80,7 @@ use common::terrain::CoordinateConversions;
 use common::terrain::TerrainChunkSize;
 use common::{
     assets::AssetExt,
-    calendar::Calendar,
+    calendar::{Calendar, SeasonCycle},
     character::{CharacterId, CharacterItem},
     cmd::ServerChatCommand,
     comp::{self, ChatType, Content},
@@ -138,7 +147,10 @@ use {
     common_state::plugin::{PluginMgr, memory_manager::EcsWorld},
 };
 
-use crate::{chat::ChatCache, persistence::character_loader::CharacterScreenResponseKind};
+use crate::{
+    chat::{ChatCache, ChatImporter},
+    persistence::character_loader::CharacterScreenResponseKind,
+};
 use common::comp::Anchor;
 #[cfg(feature = "worldgen")]
 pub use world::{
@@ -152,6 +164,9 @@ pub use world::{
 /// TODO: Discuss time
 const BATTLE_MODE_COOLDOWN: f64 = 60.0 * 5.0;
 
+/// How many chunks generated in an earlier season are reloaded each tick.
+const SEASON_RELOAD_CHUNKS_PER_TICK: usize = 16;
+
 /// SpawnPoint corresponds to the default location that players are positioned
 /// at if they have no waypoint. Players *should* always have a waypoint, so
 /// this should basically never be used in practice.
@@ -168,6 +183,10 @@ impl Default for SpawnPoint {
 // various mechanics working fluidly (i.e: not unloading nearby entities).
 pub const MIN_VD: u32 = 6;
 
+/// Channel the server always listens on for in-process connections, such as
+/// replayed clients and the clients of the test harness.
+pub const MPSC_ADDR: u64 = 14004;
+
 // Tick count used for throttling network updates
 // Note this doesn't account for dt (so update rate changes with tick rate)
 #[derive(Copy, Clone, Default)]
@@ -233,8 +252,14 @@ pub struct Server {
 
     metrics_registry: Arc<Registry>,
     chat_cache: ChatCache,
+    chat_importer: ChatImporter,
+    recorder: Option<replay::Recorder>,
+    tick_tracer: Option<tick_trace::TickTracer>,
     database_settings: Arc<RwLock<DatabaseSettings>>,
     disconnect_all_clients_requested: bool,
+    /// Chunks generated in an earlier season, reloaded a few at a time so that
+    /// the season change doesn't regenerate all terrain at once.
+    stale_chunks: Vec<Vec2<i32>>,
 
     event_dispatcher: SendDispatcher<'static>,
 }
@@ -255,6 +280,22 @@ impl Server {
             info!("Authentication is disabled");
         }
 
+        // The backup of the recording has to be made before anything changes
+        let recorder = settings
+            .record_replay
+            .then(|| replay::Recorder::start(data_dir, &database_settings, &settings))
+            .and_then(|recorder| {
+                recorder
+                    .inspect_err(|e| error!(?e, "Failed to start recording the replay"))
+                    .ok()
+            });
+
+        let tick_tracer = settings.tick_traces.as_ref().and_then(|tick_traces| {
+            tick_trace::TickTracer::new(tick_traces, data_dir)
+                .inspect_err(|e| error!(?e, "Failed to start exporting tick traces"))
+                .ok()
+        });
+
         report_stage(ServerInitStage::DbMigrations);
         // Run pending DB migrations (if any)
         debug!("Running DB migrations...");
@@ -277,6 +318,9 @@ impl Server {
         let physics_metrics = PhysicsMetrics::new(&registry).unwrap();
         let server_event_metrics = metrics::ServerEventMetrics::new(&registry).unwrap();
         let query_server_metrics = metrics::QueryServerMetrics::new(&registry).unwrap();
+        let labelled_metrics = settings
+            .labelled_metrics_limit
+            .map(|limit| metrics::LabelledMetrics::new(&registry, limit).unwrap());
 
         let battlemode_buffer = BattleModeBuffer::default();
 
@@ -304,7 +348,8 @@ impl Server {
             &|stage| {
                 report_stage(ServerInitStage::WorldGen(stage));
             },
-        );
+        )
+        .map_err(|e| Error::Other(format!("Can't generate the world: {e}")))?;
         #[cfg(not(feature = "worldgen"))]
         let (world, index) = World::generate(settings.world_seed);
 
@@ -332,9 +377,10 @@ impl Server {
 
         report_stage(ServerInitStage::StartingSystems);
 
+        // The terrain is wider than the map, the instances are hosted east of it
         let mut state = State::server(
             Arc::clone(&pools),
-            map_size_lg,
+            map_size_lg.with_instances(),
             Arc::clone(&map.default_chunk),
             |dispatcher_builder| {
                 add_local_systems(dispatcher_builder);
@@ -352,6 +398,9 @@ impl Server {
         events::register_event_busses(state.ecs_mut());
         state.ecs_mut().insert(battlemode_buffer);
         state.ecs_mut().insert(settings.clone());
+        state
+            .ecs_mut()
+            .insert(ServerLocalization::new(&settings.language));
         state.ecs_mut().insert(editable_settings);
         state.ecs_mut().insert(DataDir {
             path: data_dir.to_owned(),
@@ -383,23 +432,19 @@ impl Server {
         state.ecs_mut().insert(physics_metrics);
         state.ecs_mut().insert(server_event_metrics);
         state.ecs_mut().insert(query_server_metrics);
-        if settings.experimental_terrain_persistence {
+        if let Some(labelled_metrics) = labelled_metrics {
+            state.ecs_mut().insert(labelled_metrics);
+        }
+        state.ecs_mut().insert(load::LoadTracker::default());
+        if settings.terrain_persistence {
             #[cfg(feature = "persistent_world")]
-            {
-                warn!(
-                    "Experimental terrain persistence support is enabled. This feature may break, \
-                     be disabled, or otherwise change under your feet at *any time*. \
-                     Additionally, it is expected to be replaced in the future *without* \
-                     migration or warning. You have been warned."
-                );
-                state
-                    .ecs_mut()
-                    .insert(TerrainPersistence::new(data_dir.to_owned()));
-            }
+            state
+                .ecs_mut()
+                .insert(TerrainPersistence::new(data_dir.to_owned(), map_size_lg));
             #[cfg(not(feature = "persistent_world"))]
             error!(
-                "Experimental terrain persistence support was requested, but the server was not \
-                 compiled with the feature. Terrain modifications will *not* be persisted."
+                "Terrain persistence was requested, but the server was not compiled with the \
+                 feature. Terrain modifications will *not* be persisted."
             );
         }
         {
@@ -409,10 +454,21 @@ impl Server {
             pool.configure("CHUNK_SERIALIZER", |n| n / 2);
             pool.configure("RTSIM_SAVE", |_| 1);
             pool.configure("WEATHER", |_| 1);
+            pool.configure("TERRAIN_COMPACTION", |_| 1);
         }
         state
             .ecs_mut()
             .insert(ChunkGenerator::new(chunk_gen_metrics));
+        let max_view_distance = sys::terrain::convert_to_loaded_vd(
+            u32::MAX,
+            settings.max_view_distance.unwrap_or(u32::MAX),
+        );
+        let instances = instance::Instances::new(
+            map_size_lg,
+            state.terrain().map_size_lg(),
+            max_view_distance,
+        );
+        state.ecs_mut().insert(instances);
         {
             let (sender, receiver) =
                 crossbeam_channel::bounded::<chunk_serialize::SerializedChunk>(10_000);
@@ -457,6 +513,7 @@ impl Server {
         state.ecs_mut().register::<comp::Pet>();
         state.ecs_mut().register::<login_provider::PendingLogin>();
         state.ecs_mut().register::<RepositionOnChunkLoad>();
+        state.ecs_mut().register::<instance::InstanceEntrance>();
         state.ecs_mut().register::<RtSimEntity>();
 
         // Load banned words list
@@ -536,7 +593,8 @@ impl Server {
         state.ecs_mut().insert(DeletedEntities::default());
 
         let network = Network::new_with_registry(Pid::new(), &runtime, &registry);
-        let (chat_cache, chat_tracker) = ChatCache::new(Duration::from_secs(60), &runtime);
+        let (chat_cache, chat_tracker, chat_importer) =
+            ChatCache::new(Duration::from_secs(60), &runtime);
         state.ecs_mut().insert(chat_tracker);
 
         let mut printed_quic_warning = false;
@@ -644,7 +702,7 @@ impl Server {
             state.ecs_mut().insert(query_server_metrics);
         }
 
-        runtime.block_on(network.listen(ListenAddr::Mpsc(14004)))?;
+        runtime.block_on(network.listen(ListenAddr::Mpsc(MPSC_ADDR)))?;
 
         let connection_handler = ConnectionHandler::new(network, &runtime);
 
@@ -678,11 +736,19 @@ impl Server {
 
             metrics_registry: registry,
             chat_cache,
+            chat_importer,
+            recorder,
+            tick_tracer,
             database_settings,
             disconnect_all_clients_requested: false,
+            stale_chunks: Vec::new(),
 
             event_dispatcher: Self::create_event_dispatcher(pools),
         };
+        // Start in the current season, so that it doesn't count as a change on
+        // the first tick
+        let calendar = this.calendar_now();
+        *this.state.ecs().write_resource::<Calendar>() = calendar;
 
         debug!(?settings, "created veloren server with");
 
@@ -741,6 +807,14 @@ impl Server {
     /// Get a reference to the server's world.
     pub fn world(&self) -> &World { &self.world }
 
+    /// Render content in the language of the server, for its own outputs.
+    pub fn localize(&self, content: &comp::Content) -> String {
+        self.state
+            .ecs()
+            .read_resource::<ServerLocalization>()
+            .localize(content)
+    }
+
     /// Get a reference to the Metrics Registry
     pub fn metrics_registry(&self) -> &Arc<Registry> { &self.metrics_registry }
 
@@ -771,16 +845,30 @@ impl Server {
         self.state.ecs().write_resource::<Tick>().0 += 1;
         self.state.ecs().write_resource::<TickStart>().0 = Instant::now();
 
-        // Update calendar events as time changes
+        // Update calendar events and seasons as time changes
         // TODO: If a lot of calendar events get added, this might become expensive.
         // Maybe don't do this every tick?
-        let new_calendar = self
-            .state
-            .ecs()
-            .read_resource::<Settings>()
-            .calendar_mode
-            .calendar_now();
+        let new_calendar = self.calendar_now();
+        let old_season = self.state.ecs().read_resource::<Calendar>().season();
+        let new_season = new_calendar.season();
         *self.state.ecs_mut().write_resource::<Calendar>() = new_calendar;
+        // Snow cover, leaves and crops depend on the season, so terrain generated
+        // in the last one is out of date
+        if new_season != old_season {
+            self.stale_chunks = self.state.terrain().iter().map(|(key, _)| key).collect();
+            info!(
+                ?new_season,
+                stale = self.stale_chunks.len(),
+                "Season changed, reloading terrain"
+            );
+        }
+        let stale = self
+            .stale_chunks
+            .len()
+            .saturating_sub(SEASON_RELOAD_CHUNKS_PER_TICK);
+        for key in self.stale_chunks.split_off(stale) {
+            self.reload_chunk(key);
+        }
 
         // This tick function is the centre of the Veloren universe. Most server-side
         // things are managed from here, and as such it's important that it
@@ -851,6 +939,9 @@ impl Server {
         // Handle entity links (such as mounting)
         self.state.maintain_links();
 
+        // Send messages bridged from external chats
+        self.chat_importer.handle(self.state.ecs());
+
         // Handle game events
         frontend_events.append(&mut self.handle_events());
 
@@ -1089,6 +1180,7 @@ impl Server {
                                         pets,
                                         active_abilities,
                                         map_marker,
+                                        quest_log,
                                     } = character_data;
                                     let character_data = (
                                         body,
@@ -1100,6 +1192,7 @@ impl Server {
                                         pets,
                                         active_abilities,
                                         map_marker,
+                                        quest_log,
                                     );
                                     // TODO: Does this need to be a server event? E.g. we could
                                     // just handle it here.
@@ -1181,6 +1274,35 @@ impl Server {
 
         let end_of_server_tick = Instant::now();
 
+        if let Some(tick_tracer) = &mut self.tick_tracer {
+            tick_tracer.end_tick(self.state.ecs(), &[
+                ("new connections", before_new_connections, before_state_tick),
+                ("state", before_state_tick, before_handle_events),
+                (
+                    "handle server events",
+                    before_handle_events,
+                    before_update_terrain_and_regions,
+                ),
+                (
+                    "update terrain and region map",
+                    before_update_terrain_and_regions,
+                    before_sync,
+                ),
+                ("sync", before_sync, before_world_tick),
+                ("world tick", before_world_tick, before_entity_cleanup),
+                (
+                    "entity cleanup",
+                    before_entity_cleanup,
+                    before_persistence_updates,
+                ),
+                (
+                    "persistence updates",
+                    before_persistence_updates,
+                    end_of_server_tick,
+                ),
+            ]);
+        }
+
         // 8) Update Metrics
         run_now::<sys::metrics::Sys>(self.state.ecs());
 
@@ -1216,6 +1338,14 @@ impl Server {
             );
         }
 
+        if let Some(recorder) = &mut self.recorder {
+            let tick = self.state.ecs().read_resource::<Tick>().0;
+            if let Err(e) = recorder.end_tick(tick, dt, self.state.ecs(), &frontend_events) {
+                error!(?e, "Failed to write the replay, recording stopped");
+                self.recorder = None;
+            }
+        }
+
         // 9) Finish the tick, pass control back to the frontend.
 
         Ok(frontend_events)
@@ -1231,7 +1361,7 @@ impl Server {
         self.state
             .ecs()
             .try_fetch_mut::<TerrainPersistence>()
-            .map(|mut t| t.maintain());
+            .map(|mut t| t.maintain(&self.state.slow_job_pool()));
     }
 
     // Run RegionMap tick to update entity region occupancy
@@ -1246,7 +1376,21 @@ impl Server {
         );
     }
 
-    fn initialize_client(&mut self, client: connection_handler::IncomingClient) -> Entity {
+    fn answer_info_requests(&self) {
+        while let Ok(sender) = self.connection_handler.info_requester_receiver.try_recv() {
+            // can fail, e.g. due to timeout or network prob.
+            trace!("sending info to connection_handler");
+            let _ = sender.send(connection_handler::ServerInfoPacket {
+                info: self.get_server_info(),
+                time: self.state.get_time(),
+            });
+        }
+    }
+
+    fn initialize_client(&mut self, mut client: connection_handler::IncomingClient) -> Entity {
+        if let Some(recorder) = &mut self.recorder {
+            client.replay_tap = Some(recorder.tap(client.client_type));
+        }
         let entity = self
             .state
             .ecs_mut()
@@ -1314,14 +1458,7 @@ impl Server {
 
     /// Handle new client connections.
     fn handle_new_connections(&mut self, frontend_events: &mut Vec<Event>) {
-        while let Ok(sender) = self.connection_handler.info_requester_receiver.try_recv() {
-            // can fail, e.g. due to timeout or network prob.
-            trace!("sending info to connection_handler");
-            let _ = sender.send(connection_handler::ServerInfoPacket {
-                info: self.get_server_info(),
-                time: self.state.get_time(),
-            });
-        }
+        self.answer_info_requests();
 
         while let Ok(incoming) = self.connection_handler.client_receiver.try_recv() {
             let entity = self.initialize_client(incoming);
@@ -1361,6 +1498,42 @@ impl Server {
         );
     }
 
+    /// The calendar at the current time of day, including the season if
+    /// seasons are enabled.
+    fn calendar_now(&self) -> Calendar {
+        let settings = self.state.ecs().read_resource::<Settings>();
+        let time_of_day = self.state.ecs().read_resource::<TimeOfDay>();
+        settings.calendar_mode.calendar_now().with_seasons(
+            settings
+                .season_length
+                .map(|days| SeasonCycle::new(time_of_day.0, days)),
+        )
+    }
+
+    /// Unloads a chunk so that it gets regenerated when it's needed again.
+    /// Persisted terrain modifications are kept. Returns whether the chunk was
+    /// loaded.
+    pub fn reload_chunk(&mut self, key: Vec2<i32>) -> bool {
+        #[cfg(feature = "persistent_world")]
+        self.state
+            .ecs()
+            .try_fetch_mut::<TerrainPersistence>()
+            .map(|mut terrain_persistence| terrain_persistence.unload_chunk(key));
+        self.state.remove_chunk(key)
+    }
+
+    /// Unloads all terrain so that it gets regenerated as it's needed again.
+    /// Persisted terrain modifications are kept. Returns the number of chunks
+    /// unloaded.
+    pub fn reload_all_chunks(&mut self) -> usize {
+        #[cfg(feature = "persistent_world")]
+        self.state
+            .ecs()
+            .try_fetch_mut::<TerrainPersistence>()
+            .map(|mut terrain_persistence| terrain_persistence.unload_all());
+        self.state.clear_terrain()
+    }
+
     fn process_command(&mut self, entity: EcsEntity, name: String, args: Vec<String>) {
         // Find the command object and run its handler.
         if let Ok(command) = name.parse::<ServerChatCommand>() {
@@ -1511,6 +1684,71 @@ impl Server {
         };
     }
 
+    /// Validates and applies changes to the settings that can be changed while
+    /// the server is running, and saves them to the settings file. Only the
+    /// changed settings are written, other edits to the file are kept.
+    ///
+    /// NOTE: Do *not* allow this to be called from any command that doesn't go
+    /// through the CLI!
+    pub fn update_settings(
+        &mut self,
+        patch: RuntimeSettingsPatch,
+    ) -> Result<RuntimeSettings, InvalidSettingsError> {
+        self.check_max_view_distance(&patch)?;
+        self.settings_mut().apply_runtime(patch.clone())?;
+        if let Err(error) = Settings::save_runtime(&self.data_dir().path, patch) {
+            error!(%error, "Failed to save the settings file");
+        }
+        Ok(self.settings_changed())
+    }
+
+    /// Reloads the settings that can be changed while the server is running
+    /// from the settings file. Other settings only take effect on restart.
+    pub fn reload_settings(&mut self) -> Result<RuntimeSettings, InvalidSettingsError> {
+        let patch = Settings::load_runtime(&self.data_dir().path)?;
+        self.check_max_view_distance(&patch)?;
+        self.settings_mut().apply_runtime(patch)?;
+        Ok(self.settings_changed())
+    }
+
+    /// Instances are spaced for the max view distance at startup, so it can't
+    /// be raised while the server is running.
+    fn check_max_view_distance(
+        &self,
+        patch: &RuntimeSettingsPatch,
+    ) -> Result<(), InvalidSettingsError> {
+        if let Some(max_view_distance) = patch.max_view_distance
+            && sys::terrain::convert_to_loaded_vd(u32::MAX, max_view_distance.unwrap_or(u32::MAX))
+                > self
+                    .state
+                    .ecs()
+                    .read_resource::<instance::Instances>()
+                    .view_distance()
+        {
+            return Err(InvalidSettingsError::MaxViewDistanceRaised);
+        }
+        Ok(())
+    }
+
+    /// Updates the state derived from the runtime settings after they changed.
+    fn settings_changed(&mut self) -> RuntimeSettings {
+        let settings = self.settings().clone();
+        let ecs = self.state.ecs();
+        // NOTE: Clients that are already connected keep predicting the time of day with
+        // the old day length, but are corrected as it is synced.
+        ecs.write_resource::<ServerConstants>()
+            .day_cycle_coefficient = settings.day_cycle_coefficient();
+        ecs.write_resource::<AutoMod>()
+            .update_settings(&settings.moderation);
+        if let ServerBattleMode::Global(mode) = settings.gameplay.battle_mode {
+            for player in (&mut ecs.write_storage::<comp::Player>()).join() {
+                player.battle_mode = mode;
+            }
+        }
+        info!(settings = ?settings.runtime(), "Applied changed settings");
+        settings.runtime()
+    }
+
     /// Useful for testing without a client
     /// view_distance: distance in chunks that are persisted, this acts like the
     /// player view distance so it is actually a bit farther due to a buffer
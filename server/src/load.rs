@@ -0,0 +1,161 @@
+//! Finding the areas and players which put the most load on the server.
+//!
+//! Every [`REPORT_INTERVAL`] ticks the metrics system counts entities, agents
+//! and physics contacts per region and collects the chunk requests and the
+//! bytes sent to each player since the previous report. The report is shown by
+//! the `/profile` command and exported as labelled metrics when
+//! `labelled_metrics_limit` is set in the settings.
+
+use common::{region::RegionMap, terrain::CoordinateConversions};
+use hashbrown::HashMap;
+use std::{
+    sync::Mutex,
+    time::{Duration, Instant},
+};
+use vek::*;
+
+/// Number of ticks between two reports.
+pub const REPORT_INTERVAL: u64 = 100;
+
+#[derive(Clone, Debug, Default)]
+pub struct RegionLoad {
+    /// Key of the region, see [`RegionMap`].
+    pub key: Vec2<i32>,
+    pub entities: u64,
+    /// Entities with an agent, these run the NPC AI every tick.
+    pub agents: u64,
+    /// Entities touching other entities.
+    pub physics_contacts: u64,
+    /// Chunks requested by clients since the previous report.
+    pub chunk_requests: u64,
+}
+
+#[derive(Clone, Debug)]
+pub struct PlayerLoad {
+    pub alias: String,
+    /// Bytes sent to the player since the previous report.
+    pub sent_bytes: u64,
+}
+
+#[derive(Clone, Debug)]
+pub struct LoadReport {
+    /// Time covered by the counts since the previous report.
+    pub period: Duration,
+    pub regions: Vec<RegionLoad>,
+    pub players: Vec<PlayerLoad>,
+}
+
+impl LoadReport {
+    /// The `n` regions with the highest `value`, ignoring regions where it is
+    /// zero.
+    pub fn top_regions(&self, n: usize, value: fn(&RegionLoad) -> u64) -> Vec<&RegionLoad> {
+        let mut regions = self
+            .regions
+            .iter()
+            .filter(|region| value(region) > 0)
+            .collect::<Vec<_>>();
+        regions.sort_by_key(|region| std::cmp::Reverse(value(region)));
+        regions.truncate(n);
+        regions
+    }
+
+    /// The `n` players the most bytes were sent to.
+    pub fn top_players(&self, n: usize) -> Vec<&PlayerLoad> {
+        let mut players = self.players.iter().collect::<Vec<_>>();
+        players.sort_by_key(|player| std::cmp::Reverse(player.sent_bytes));
+        players.truncate(n);
+        players
+    }
+}
+
+/// Collects the counts which are accumulated between reports, and keeps the
+/// latest report.
+pub struct LoadTracker {
+    chunk_requests: Mutex<HashMap<Vec2<i32>, u64>>,
+    last_report: Instant,
+    report: Option<LoadReport>,
+}
+
+impl Default for LoadTracker {
+    fn default() -> Self {
+        Self {
+            chunk_requests: Mutex::default(),
+            last_report: Instant::now(),
+            report: None,
+        }
+    }
+}
+
+impl LoadTracker {
+    /// Count a chunk requested by a client, given by its key.
+    pub fn record_chunk_request(&self, chunk_key: Vec2<i32>) {
+        let region = RegionMap::pos_key(chunk_key.cpos_to_wpos());
+        *self
+            .chunk_requests
+            .lock()
+            .unwrap()
+            .entry(region)
+            .or_default() += 1;
+    }
+
+    /// Store a new report, with the chunk requests counted since the previous
+    /// one added to its regions.
+    pub fn set_report(&mut self, mut regions: Vec<RegionLoad>, players: Vec<PlayerLoad>) {
+        let mut chunk_requests = std::mem::take(self.chunk_requests.get_mut().unwrap());
+        for region in &mut regions {
+            region.chunk_requests = chunk_requests.remove(&region.key).unwrap_or(0);
+        }
+        // Chunks can be requested in regions without entities
+        regions.extend(
+            chunk_requests
+                .into_iter()
+                .map(|(key, chunk_requests)| RegionLoad {
+                    key,
+                    chunk_requests,
+                    ..Default::default()
+                }),
+        );
+        let now = Instant::now();
+        self.report = Some(LoadReport {
+            period: now.duration_since(self.last_report),
+            regions,
+            players,
+        });
+        self.last_report = now;
+    }
+
+    /// The latest report, if one has been made yet.
+    pub fn report(&self) -> Option<&LoadReport> { self.report.as_ref() }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn chunk_requests_by_region() {
+        let mut tracker = LoadTracker::default();
+        // Regions are 16 chunks wide
+        tracker.record_chunk_request(Vec2::new(0, 0));
+        tracker.record_chunk_request(Vec2::new(15, 3));
+        tracker.record_chunk_request(Vec2::new(16, 0));
+        tracker.record_chunk_request(Vec2::new(-1, 0));
+        tracker.set_report(
+            vec![RegionLoad {
+                key: Vec2::new(0, 0),
+                entities: 3,
+                ..Default::default()
+            }],
+            Vec::new(),
+        );
+
+        let report = tracker.report().unwrap();
+        let top = report.top_regions(2, |region| region.chunk_requests);
+        assert_eq!(top[0].key, Vec2::new(0, 0));
+        assert_eq!(top[0].chunk_requests, 2);
+        assert_eq!(top[0].entities, 3);
+        assert_eq!(top[1].chunk_requests, 1);
+        assert_eq!(report.regions.len(), 3);
+        assert!(report.top_regions(5, |region| region.agents).is_empty());
+    }
+}
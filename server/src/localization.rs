@@ -0,0 +1,146 @@
+//! Rendering [`Content`] on the server.
+//!
+//! Clients localize most of what the server sends them, but the server also
+//! has outputs of its own (the web chat export, the console and logs) and
+//! sends some text that clients show verbatim, such as kick and ban reasons.
+//! These are rendered here, in the language configured in the settings or the
+//! language a player reported when logging in.
+
+use client_i18n::{LocalizationHandle, REFERENCE_LANG, list_localizations};
+use common::comp::Content;
+use common_net::msg::{DisconnectReason, RegisterError, server::BanInfo};
+use hashbrown::HashSet;
+use tracing::warn;
+
+pub struct ServerLocalization {
+    language: String,
+    handle: LocalizationHandle,
+    /// Identifiers of all languages that can be loaded
+    available: HashSet<String>,
+}
+
+impl ServerLocalization {
+    /// Load the given language, falling back to the reference language if it
+    /// isn't available.
+    pub fn new(language: &str) -> Self {
+        let available = list_localizations()
+            .into_iter()
+            .map(|metadata| metadata.language_identifier)
+            .collect::<HashSet<_>>();
+        let language = if available.contains(language) {
+            language
+        } else {
+            warn!(
+                ?language,
+                "Language is not available, using {REFERENCE_LANG} instead"
+            );
+            REFERENCE_LANG
+        };
+        Self {
+            language: language.to_owned(),
+            handle: Self::load(language),
+            available,
+        }
+    }
+
+    fn load(language: &str) -> LocalizationHandle {
+        let mut handle = LocalizationHandle::load_expect(language);
+        handle.set_english_fallback(true);
+        handle
+    }
+
+    /// Identifier of the language of the server.
+    pub fn language(&self) -> &str { &self.language }
+
+    /// Render content in the language of the server.
+    pub fn localize(&self, content: &Content) -> String { self.handle.read().get_content(content) }
+
+    /// Render content in the language a player reported, or the language of
+    /// the server if the player's isn't available.
+    pub fn localize_for(&self, locale: Option<&str>, content: &Content) -> String {
+        match locale {
+            Some(locale) if locale != self.language && self.available.contains(locale) => {
+                Self::load(locale).read().get_content(content)
+            },
+            _ => self.localize(content),
+        }
+    }
+
+    /// Localize the reason of a disconnect for the player it is sent to.
+    pub fn localize_disconnect(
+        &self,
+        locale: Option<&str>,
+        reason: DisconnectReason,
+    ) -> DisconnectReason {
+        match reason {
+            DisconnectReason::Kicked(reason) => {
+                DisconnectReason::Kicked(self.localize_reason(locale, &reason))
+            },
+            DisconnectReason::Banned(info) => {
+                DisconnectReason::Banned(self.localize_ban(locale, info))
+            },
+            DisconnectReason::Shutdown => DisconnectReason::Shutdown,
+        }
+    }
+
+    /// Localize the reason of a refused login for the player it is sent to.
+    pub fn localize_register_error(
+        &self,
+        locale: Option<&str>,
+        error: RegisterError,
+    ) -> RegisterError {
+        match error {
+            RegisterError::Kicked(reason) => {
+                RegisterError::Kicked(self.localize_reason(locale, &reason))
+            },
+            RegisterError::Banned(info) => RegisterError::Banned(self.localize_ban(locale, info)),
+            error => error,
+        }
+    }
+
+    fn localize_ban(&self, locale: Option<&str>, info: BanInfo) -> BanInfo {
+        BanInfo {
+            reason: self.localize_reason(locale, &info.reason),
+            ..info
+        }
+    }
+
+    /// Clients show kick and ban reasons verbatim. Reasons naming a server
+    /// message, such as `server-ban-reason-griefing`, are localized, others
+    /// are kept as they were written and empty ones are replaced by a note
+    /// that no reason was given.
+    fn localize_reason(&self, locale: Option<&str>, reason: &str) -> String {
+        let key = if reason.is_empty() {
+            "server-reason-none"
+        } else if reason.starts_with("server-") && self.handle.read().try_msg(reason).is_some() {
+            reason
+        } else {
+            return reason.to_owned();
+        };
+        self.localize_for(locale, &Content::localized(key))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn kick_reasons() {
+        let localization = ServerLocalization::new(REFERENCE_LANG);
+        let kick = |reason: &str| match localization
+            .localize_disconnect(None, DisconnectReason::Kicked(reason.to_owned()))
+        {
+            DisconnectReason::Kicked(reason) => reason,
+            _ => unreachable!(),
+        };
+        assert_eq!(kick(""), "No reason given");
+        assert_eq!(kick("server-reason-none"), "No reason given");
+        assert_eq!(kick("Griefing"), "Griefing");
+        assert_eq!(
+            kick("server-ban-reason-harassment"),
+            "Harassing other players"
+        );
+        assert_eq!(kick("server-not-a-key"), "server-not-a-key");
+    }
+}
@@ -1,3 +1,4 @@
+use crate::load::{LoadReport, RegionLoad};
 use prometheus::{
     Gauge, GaugeVec, Histogram, HistogramOpts, HistogramVec, IntCounter, IntCounterVec, IntGauge,
     IntGaugeVec, Opts, Registry,
@@ -66,6 +67,17 @@ pub struct TickMetrics {
     pub light_count: IntGauge,
 }
 
+/// Metrics labelled by region or player, see [`crate::load`].
+pub struct LabelledMetrics {
+    /// Number of labels exported per metric.
+    pub limit: usize,
+    pub region_entities: IntGaugeVec,
+    pub region_agents: IntGaugeVec,
+    pub region_physics_contacts: IntGaugeVec,
+    pub region_chunk_requests: IntGaugeVec,
+    pub player_sent_bytes: IntGaugeVec,
+}
+
 pub struct ServerEventMetrics {
     pub event_count: IntCounterVec,
 }
@@ -427,6 +439,89 @@ impl TickMetrics {
     }
 }
 
+impl LabelledMetrics {
+    pub fn new(registry: &Registry, limit: usize) -> Result<Self, prometheus::Error> {
+        let region_entities = IntGaugeVec::new(
+            Opts::new(
+                "region_entities",
+                "number of entities in the regions with the most entities",
+            ),
+            &["region"],
+        )?;
+        let region_agents = IntGaugeVec::new(
+            Opts::new(
+                "region_agents",
+                "number of entities with an agent in the regions with the most agents",
+            ),
+            &["region"],
+        )?;
+        let region_physics_contacts = IntGaugeVec::new(
+            Opts::new(
+                "region_physics_contacts",
+                "number of entities touching other entities in the regions with the most contacts",
+            ),
+            &["region"],
+        )?;
+        let region_chunk_requests = IntGaugeVec::new(
+            Opts::new(
+                "region_chunk_requests",
+                "number of chunks requested since the last report in the regions with the most \
+                 requests",
+            ),
+            &["region"],
+        )?;
+        let player_sent_bytes = IntGaugeVec::new(
+            Opts::new(
+                "player_sent_bytes",
+                "bytes sent since the last report to the players receiving the most",
+            ),
+            &["player"],
+        )?;
+
+        registry.register(Box::new(region_entities.clone()))?;
+        registry.register(Box::new(region_agents.clone()))?;
+        registry.register(Box::new(region_physics_contacts.clone()))?;
+        registry.register(Box::new(region_chunk_requests.clone()))?;
+        registry.register(Box::new(player_sent_bytes.clone()))?;
+
+        Ok(Self {
+            limit,
+            region_entities,
+            region_agents,
+            region_physics_contacts,
+            region_chunk_requests,
+            player_sent_bytes,
+        })
+    }
+
+    /// Replace the exported values with the busiest regions and players of a
+    /// report.
+    pub fn apply(&self, report: &LoadReport) {
+        let regions: [(&IntGaugeVec, fn(&RegionLoad) -> u64); 4] = [
+            (&self.region_entities, |r| r.entities),
+            (&self.region_agents, |r| r.agents),
+            (&self.region_physics_contacts, |r| r.physics_contacts),
+            (&self.region_chunk_requests, |r| r.chunk_requests),
+        ];
+        for (gauge, value) in regions {
+            // Drop the regions which aren't among the busiest anymore
+            gauge.reset();
+            for region in report.top_regions(self.limit, value) {
+                gauge
+                    .with_label_values(&[&format!("{},{}", region.key.x, region.key.y)])
+                    .set(value(region) as i64);
+            }
+        }
+
+        self.player_sent_bytes.reset();
+        for player in report.top_players(self.limit) {
+            self.player_sent_bytes
+                .with_label_values(&[&player.alias])
+                .set(player.sent_bytes as i64);
+        }
+    }
+}
+
 impl ServerEventMetrics {
     pub fn new(registry: &Registry) -> Result<Self, prometheus::Error> {
         let event_count = IntCounterVec::new(
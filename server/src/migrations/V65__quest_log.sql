@@ -0,0 +1,12 @@
+-- Creates new quest_log table
+CREATE TABLE "quest_log" (
+      "entity_id" INT NOT NULL,
+      "quest_log" TEXT NOT NULL,
+      PRIMARY KEY("entity_id"),
+      FOREIGN KEY("entity_id") REFERENCES "character"("character_id")
+);
+
+-- Inserts empty quest logs for everyone
+INSERT INTO quest_log
+SELECT c.character_id, '{"active":[],"completed":0}'
+FROM character c
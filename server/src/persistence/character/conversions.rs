@@ -5,13 +5,13 @@ use crate::persistence::{
         self, CharacterPosition, DatabaseAbilitySet, DatabaseItemProperties, GenericBody,
         HumanoidBody,
     },
-    models::{AbilitySets, Character, Item, SkillGroup},
+    models::{AbilitySets, Character, Item, QuestLog, SkillGroup},
 };
 use common::{
     character::CharacterId,
     comp::{
-        ActiveAbilities, Body as CompBody, Content, Hardcore, Inventory, MapMarker, Stats,
-        Waypoint, body,
+        ActiveAbilities, Body as CompBody, Content, Hardcore, Inventory, MapMarker,
+        QuestLog as CompQuestLog, Stats, Waypoint, body,
         inventory::{
             item::{Item as VelorenItem, MaterialStatManifest, tool::AbilityMap},
             loadout::{Loadout, LoadoutError},
@@ -900,6 +900,25 @@ pub fn convert_active_abilities_from_database(ability_sets: &AbilitySets) -> Act
     json_models::active_abilities_from_db_model(ability_sets)
 }
 
+pub fn convert_quest_log_to_database(entity_id: CharacterId, quest_log: &CompQuestLog) -> QuestLog {
+    QuestLog {
+        entity_id: entity_id.0,
+        quest_log: serde_json::to_string(quest_log).unwrap_or_default(),
+    }
+}
+
+pub fn convert_quest_log_from_database(quest_log: &QuestLog) -> CompQuestLog {
+    // Quests reference content that may have changed since they were accepted, so
+    // rather than failing to load the character, start with an empty quest log
+    serde_json::from_str::<CompQuestLog>(&quest_log.quest_log).unwrap_or_else(|err| {
+        warn!(
+            "Failed to parse quest log, resetting it. Error: {:#?}\nQuest log:\n{:#?}",
+            err, quest_log.quest_log
+        );
+        CompQuestLog::default()
+    })
+}
+
 pub fn convert_recipe_book_from_database_items(
     database_items: &[Item],
 ) -> Result<RecipeBook, PersistenceError> {
@@ -17,6 +17,7 @@ use crate::{
             convert_character_from_database, convert_hardcore_from_database,
             convert_hardcore_to_database, convert_inventory_from_database_items,
             convert_items_to_database_items, convert_loadout_from_database_items,
+            convert_quest_log_from_database, convert_quest_log_to_database,
             convert_recipe_book_from_database_items, convert_skill_groups_to_database,
             convert_skill_set_from_database, convert_stats_from_database,
             convert_waypoint_from_database_json, convert_waypoint_to_database_json,
@@ -285,6 +286,20 @@ pub fn load_character_data(
         })
     })?;
 
+    let mut stmt = connection.prepare_cached(
+        "
+            SELECT  quest_log
+            FROM    quest_log
+            WHERE   entity_id = ?1",
+    )?;
+
+    let quest_log_data = stmt.query_row([char_id.0], |row| {
+        Ok(QuestLog {
+            entity_id: char_id.0,
+            quest_log: row.get(0)?,
+        })
+    })?;
+
     let (skill_set, skill_set_persistence_load_error) =
         convert_skill_set_from_database(&skill_group_data);
     let body = convert_body_from_database(&body_data.variant, &body_data.body_data)?;
@@ -308,6 +323,7 @@ pub fn load_character_data(
             pets,
             active_abilities: convert_active_abilities_from_database(&ability_set_data),
             map_marker: char_map_marker,
+            quest_log: convert_quest_log_from_database(&quest_log_data),
         },
         UpdateCharacterMetadata {
             skill_set_persistence_load_error,
@@ -425,6 +441,7 @@ pub fn create_character(
         pets: _,
         active_abilities,
         map_marker,
+        quest_log,
     } = persisted_components;
 
     // Fetch new entity IDs for character, inventory, loadout, overflow items, and
@@ -581,6 +598,21 @@ pub fn create_character(
     ])?;
     drop(stmt);
 
+    let quest_log = convert_quest_log_to_database(CharacterId(character_id), &quest_log);
+
+    let mut stmt = transaction.prepare_cached(
+        "
+        INSERT INTO quest_log (entity_id,
+                               quest_log)
+        VALUES (?1, ?2)",
+    )?;
+
+    stmt.execute([
+        &character_id as &dyn ToSql,
+        &quest_log.quest_log as &dyn ToSql,
+    ])?;
+    drop(stmt);
+
     // Insert default inventory and loadout item records
     let mut inserts = Vec::new();
 
@@ -731,6 +763,17 @@ pub fn delete_character(
     stmt.execute([&char_id.0])?;
     drop(stmt);
 
+    // Delete quest log
+    let mut stmt = transaction.prepare_cached(
+        "
+        DELETE
+        FROM    quest_log
+        WHERE   entity_id = ?1",
+    )?;
+
+    stmt.execute([&char_id.0])?;
+    drop(stmt);
+
     // Delete character
     let mut stmt = transaction.prepare_cached(
         "
@@ -1065,6 +1108,7 @@ pub fn update(
     char_waypoint: Option<comp::Waypoint>,
     active_abilities: comp::ability::ActiveAbilities,
     map_marker: Option<comp::MapMarker>,
+    quest_log: comp::QuestLog,
     transaction: &mut Transaction,
 ) -> Result<(), PersistenceError> {
     // Run pet persistence
@@ -1241,5 +1285,25 @@ pub fn update(
         )));
     }
 
+    let quest_log = convert_quest_log_to_database(char_id, &quest_log);
+
+    let mut stmt = transaction.prepare_cached(
+        "
+        UPDATE  quest_log
+        SET     quest_log = ?1
+        WHERE   entity_id = ?2
+    ",
+    )?;
+
+    let quest_log_count =
+        stmt.execute([&quest_log.quest_log as &dyn ToSql, &char_id.0 as &dyn ToSql])?;
+
+    if quest_log_count != 1 {
+        return Err(PersistenceError::OtherError(format!(
+            "Error updating quest_log table for char_id {}",
+            char_id.0,
+        )));
+    }
+
     Ok(())
 }
@@ -15,9 +15,10 @@ use specs::Entity;
 use std::{
     collections::HashMap,
     sync::{
-        Arc, RwLock,
+        Arc, Mutex, RwLock,
         atomic::{AtomicBool, Ordering},
     },
+    time::Instant,
 };
 use tracing::{debug, error, info, trace, warn};
 
@@ -29,6 +30,7 @@ pub type CharacterUpdateData = (
     Option<comp::Waypoint>,
     comp::ability::ActiveAbilities,
     Option<comp::MapMarker>,
+    comp::QuestLog,
 );
 
 pub type PetPersistenceData = (comp::Pet, comp::Body, comp::Stats);
@@ -82,6 +84,17 @@ enum DatabaseActionKind {
     },
 }
 
+/// Maximum number of batch timings kept until they are taken.
+const MAX_BATCH_TIMINGS: usize = 64;
+
+/// When a batch update was executed by the background thread.
+pub struct BatchTiming {
+    pub batch_id: u64,
+    pub updates: usize,
+    pub start: Instant,
+    pub end: Instant,
+}
+
 /// A unidirectional messaging resource for saving characters in a
 /// background thread.
 ///
@@ -98,6 +111,8 @@ pub struct CharacterUpdater {
     /// set to true
     disconnect_all_clients_requested: Arc<AtomicBool>,
     last_pending_database_event_id: u64,
+    /// Timings of the batches executed since they were last taken
+    batch_timings: Arc<Mutex<Vec<BatchTiming>>>,
 }
 
 impl CharacterUpdater {
@@ -107,6 +122,8 @@ impl CharacterUpdater {
 
         let disconnect_all_clients_requested = Arc::new(AtomicBool::new(false));
         let disconnect_all_clients_requested_clone = Arc::clone(&disconnect_all_clients_requested);
+        let batch_timings = Arc::new(Mutex::new(Vec::new()));
+        let batch_timings_clone = Arc::clone(&batch_timings);
 
         let builder = std::thread::Builder::new().name("persistence_updater".into());
         let handle = builder
@@ -127,6 +144,8 @@ impl CharacterUpdater {
                             }
                             conn.update_log_mode(&settings);
 
+                            let start = Instant::now();
+                            let update_count = updates.len();
                             if let Err(e) = execute_batch_update(updates.into_iter(), &mut conn) {
                                 error!(
                                     ?e,
@@ -136,6 +155,16 @@ impl CharacterUpdater {
                                 disconnect_all_clients_requested_clone
                                     .store(true, Ordering::Relaxed);
                             };
+                            let mut timings = batch_timings_clone.lock().unwrap();
+                            if timings.len() < MAX_BATCH_TIMINGS {
+                                timings.push(BatchTiming {
+                                    batch_id,
+                                    updates: update_count,
+                                    start,
+                                    end: Instant::now(),
+                                });
+                            }
+                            drop(timings);
 
                             if let Err(e) = response_tx
                                 .send(CharacterUpdaterMessage::DatabaseBatchCompletion(batch_id))
@@ -229,9 +258,15 @@ impl CharacterUpdater {
             pending_database_actions: HashMap::new(),
             disconnect_all_clients_requested,
             last_pending_database_event_id: 0,
+            batch_timings,
         })
     }
 
+    /// Timings of the batch updates executed since the last call.
+    pub fn take_batch_timings(&self) -> Vec<BatchTiming> {
+        std::mem::take(&mut *self.batch_timings.lock().unwrap())
+    }
+
     /// Adds a character to the list of characters that have recently logged out
     /// and will be persisted in the next batch update.
     pub fn add_pending_logout_update(&mut self, update_data: CharacterUpdateData) {
@@ -425,6 +460,7 @@ fn execute_batch_update(
             waypoint,
             active_abilities,
             map_marker,
+            quest_log,
         )) => super::character::update(
             character_id,
             stats,
@@ -433,6 +469,7 @@ fn execute_batch_update(
             waypoint,
             active_abilities,
             map_marker,
+            quest_log,
             &mut transaction,
         ),
         DatabaseActionKind::DeleteCharacter {
@@ -40,6 +40,7 @@ pub struct PersistedComponents {
     pub pets: Vec<PetPersistenceData>,
     pub active_abilities: comp::ActiveAbilities,
     pub map_marker: Option<comp::MapMarker>,
+    pub quest_log: comp::QuestLog,
 }
 
 pub type EditableComponents = (comp::Body,);
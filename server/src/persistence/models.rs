@@ -56,3 +56,9 @@ pub struct AbilitySets {
     pub entity_id: i64,
     pub ability_sets: String,
 }
+
+pub struct QuestLog {
+    #[expect(dead_code)]
+    pub entity_id: i64,
+    pub quest_log: String,
+}
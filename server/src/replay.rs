@@ -0,0 +1,645 @@
+//! Recording client inputs to replay the server tick by tick for debugging.
+//!
+//! When `record_replay` is enabled in the settings, the server copies its data
+//! directory to `replays/<start time>/backup` on startup. It then writes every
+//! message it handles from a client to `replays/<start time>/inputs`, tagged
+//! with the tick it was handled in, together with the frontend events and a
+//! hash of the ECS state at the end of each tick.
+//!
+//! [`replay`] starts a server from such a backup, feeds it the recorded
+//! messages tick by tick and reports the first tick where the state hash
+//! differs from the recording.
+//!
+//! Chat bridged in through the web API, server CLI commands and settings
+//! changed at runtime are not recorded. Authentication is disabled while
+//! replaying, so servers using an auth server should be recorded without it
+//! for players to log in to the same accounts.
+//!
+//! The world seed is recorded and restored, which also seeds the weather
+//! simulation, and rtsim restarts from its backed up state. NPC AI, rtsim
+//! rules, combat and loot draw from the thread RNG, whose seeds can't be
+//! recorded, so NPCs don't replay exactly and only the state of players'
+//! characters is hashed. A divergence is the first tick where a character
+//! didn't end up where its recorded inputs put it. When an NPC fought the
+//! character in that tick, the random rolls of the fight can explain it;
+//! otherwise it is a determinism bug.
+
+use crate::{
+    EditableSettings, Error, Event, MPSC_ADDR, Server, Settings,
+    input::Input,
+    persistence::{DatabaseSettings, SqlLogMode},
+};
+use common::comp::{CharacterState, Energy, Health, Ori, Player, Pos, Vel};
+use common_net::msg::{ClientType, ServerInfo};
+use crossbeam_channel::{Receiver, Sender, unbounded};
+use hashbrown::HashMap;
+use network::{ConnectAddr, Network, Participant, Pid, Stream, StreamError};
+use serde::{Deserialize, Serialize, de::DeserializeOwned};
+use specs::{Join, WorldExt};
+use std::{
+    collections::VecDeque,
+    fs,
+    hash::{DefaultHasher, Hash, Hasher},
+    io::{self, BufReader, BufWriter, Write},
+    mem,
+    path::{Path, PathBuf},
+    sync::{Arc, Mutex},
+    time::{Duration, SystemTime, UNIX_EPOCH},
+};
+use tokio::runtime::Runtime;
+use tracing::{info, warn};
+
+/// Directory in the server data that recordings are stored in.
+pub const REPLAYS_DIR: &str = "replays";
+const BACKUP_DIR: &str = "backup";
+/// Copy of the backup the replayed server runs in, replaced on every replay.
+const WORK_DIR: &str = "work";
+/// Directory of the database within the backup.
+const DATABASE_DIR: &str = "saves";
+const INPUTS_FILE: &str = "inputs";
+/// Version of the inputs file, bumped whenever [`Record`] changes.
+const FORMAT_VERSION: u32 = 1;
+/// Number of streams of a client, see `Client::recv`.
+const STREAM_COUNT: usize = 6;
+/// How long the replayed server may take to accept a recorded connection.
+const CONNECT_TIMEOUT: Duration = Duration::from_secs(10);
+
+#[derive(Serialize, Deserialize)]
+struct Header {
+    format_version: u32,
+    git_hash: String,
+    world_seed: u32,
+    /// Settings of the recorded server as RON, bincode can't deserialize them.
+    settings: String,
+}
+
+/// Numbers the connections of a recording in the order they were accepted.
+type ConnectionId = u64;
+
+#[derive(Serialize, Deserialize)]
+pub(crate) enum Record {
+    Connected {
+        connection: ConnectionId,
+        client_type: ClientType,
+    },
+    Message {
+        connection: ConnectionId,
+        stream: u8,
+        data: Vec<u8>,
+    },
+    /// Receiving from the stream failed, which disconnects the client.
+    StreamClosed {
+        connection: ConnectionId,
+        stream: u8,
+    },
+    /// End of a tick, the records since the previous one were handled in it.
+    Tick {
+        tick: u64,
+        dt: Duration,
+        hash: u64,
+        events: Vec<String>,
+    },
+}
+
+/// Hooks the streams of a client into a recording or a replay.
+pub(crate) enum ReplayTap {
+    Record {
+        connection: ConnectionId,
+        records: Sender<Record>,
+    },
+    Replay(Arc<Mutex<ReplayedStreams>>),
+}
+
+impl ReplayTap {
+    /// Messages are only taken from the recording while replaying.
+    pub(crate) fn replayed<M: DeserializeOwned>(
+        &self,
+        stream: u8,
+    ) -> Option<Result<Option<M>, StreamError>> {
+        match self {
+            Self::Record { .. } => None,
+            Self::Replay(streams) => Some(
+                match streams.lock().unwrap().0[stream as usize].pop_front() {
+                    None => Ok(None),
+                    Some(None) => Err(StreamError::StreamClosed),
+                    Some(Some(data)) => bincode::deserialize(&data)
+                        .map(Some)
+                        .map_err(StreamError::Deserialize),
+                },
+            ),
+        }
+    }
+
+    pub(crate) fn record<M: Serialize>(
+        &self,
+        stream: u8,
+        received: &Result<Option<M>, StreamError>,
+    ) {
+        let Self::Record {
+            connection,
+            records,
+        } = self
+        else {
+            return;
+        };
+        let connection = *connection;
+        let record = match received {
+            Ok(None) => return,
+            Ok(Some(msg)) => match bincode::serialize(msg) {
+                Ok(data) => Record::Message {
+                    connection,
+                    stream,
+                    data,
+                },
+                Err(e) => {
+                    warn!(?e, "Failed to serialize client message for the replay");
+                    return;
+                },
+            },
+            Err(_) => Record::StreamClosed { connection, stream },
+        };
+        // The recorder is gone if writing the recording failed
+        let _ = records.send(record);
+    }
+}
+
+/// Recorded messages of a client waiting to be received, `None` closes the
+/// stream.
+#[derive(Default)]
+pub(crate) struct ReplayedStreams([VecDeque<Option<Vec<u8>>>; STREAM_COUNT]);
+
+/// Writes the inputs of a running server to its replay directory.
+pub(crate) struct Recorder {
+    writer: BufWriter<fs::File>,
+    sender: Sender<Record>,
+    receiver: Receiver<Record>,
+    next_connection: ConnectionId,
+}
+
+impl Recorder {
+    /// Back up the server data and start a new recording, must be called
+    /// before the server touches the data.
+    pub(crate) fn start(
+        data_dir: &Path,
+        database_settings: &DatabaseSettings,
+        settings: &Settings,
+    ) -> io::Result<Self> {
+        let started = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .unwrap_or_default()
+            .as_secs();
+        let dir = data_dir.join(REPLAYS_DIR).join(started.to_string());
+        let backup_dir = dir.join(BACKUP_DIR);
+        copy_dir(data_dir, &backup_dir, &[
+            data_dir.join(REPLAYS_DIR).as_path(),
+            database_settings.db_dir.as_path(),
+        ])?;
+        copy_dir(
+            &database_settings.db_dir,
+            &backup_dir.join(DATABASE_DIR),
+            &[],
+        )?;
+
+        let mut writer = BufWriter::new(fs::File::create(dir.join(INPUTS_FILE))?);
+        let header = Header {
+            format_version: FORMAT_VERSION,
+            git_hash: common::util::GIT_HASH.to_string(),
+            world_seed: settings.world_seed,
+            settings: ron::to_string(settings).map_err(io::Error::other)?,
+        };
+        bincode::serialize_into(&mut writer, &header).map_err(io::Error::other)?;
+        writer.flush()?;
+        info!("Recording client inputs to {}", dir.display());
+
+        let (sender, receiver) = unbounded();
+        Ok(Self {
+            writer,
+            sender,
+            receiver,
+            next_connection: 0,
+        })
+    }
+
+    /// Start recording the messages of a newly connected client.
+    pub(crate) fn tap(&mut self, client_type: ClientType) -> ReplayTap {
+        let connection = self.next_connection;
+        self.next_connection += 1;
+        let _ = self.sender.send(Record::Connected {
+            connection,
+            client_type,
+        });
+        ReplayTap::Record {
+            connection,
+            records: self.sender.clone(),
+        }
+    }
+
+    /// Write the inputs handled during a tick and the state it ended with.
+    pub(crate) fn end_tick(
+        &mut self,
+        tick: u64,
+        dt: Duration,
+        ecs: &specs::World,
+        events: &[Event],
+    ) -> io::Result<()> {
+        let end = Record::Tick {
+            tick,
+            dt,
+            hash: state_hash(ecs),
+            events: events.iter().map(describe_event).collect(),
+        };
+        for record in self.receiver.try_iter().chain([end]) {
+            bincode::serialize_into(&mut self.writer, &record).map_err(io::Error::other)?;
+        }
+        // Flushed every tick so that the recording survives a crash
+        self.writer.flush()
+    }
+}
+
+/// Result of a replay.
+#[derive(Debug)]
+pub struct ReplayReport {
+    /// Number of ticks replayed.
+    pub ticks: u64,
+    /// First tick whose state differs from the recording, if any.
+    pub divergence: Option<Divergence>,
+}
+
+#[derive(Debug)]
+pub struct Divergence {
+    pub tick: u64,
+    pub recorded_hash: u64,
+    pub replayed_hash: u64,
+    pub recorded_events: Vec<String>,
+    pub replayed_events: Vec<String>,
+}
+
+/// A connection made by the replay in place of a recorded client, kept open
+/// until the replay ends.
+struct ReplayedConnection {
+    streams: Arc<Mutex<ReplayedStreams>>,
+    _participant: Participant,
+    _network_streams: Vec<Stream>,
+}
+
+/// Replay the recording in `dir`, a directory in [`REPLAYS_DIR`], until it
+/// ends or the state of the server diverges from it.
+pub fn replay(
+    dir: &Path,
+    runtime: Arc<Runtime>,
+    report_stage: &(dyn Fn(crate::ServerInitStage) + Send + Sync),
+) -> Result<ReplayReport, Error> {
+    let file = fs::File::open(dir.join(INPUTS_FILE)).map_err(other)?;
+    let mut reader = BufReader::new(file);
+    let header: Header = bincode::deserialize_from(&mut reader).map_err(other)?;
+    if header.format_version != FORMAT_VERSION {
+        return Err(Error::Other(format!(
+            "Replay format version {} is not supported, expected {FORMAT_VERSION}",
+            header.format_version
+        )));
+    }
+    if header.git_hash != *common::util::GIT_HASH {
+        warn!(
+            recorded = %header.git_hash,
+            "Replay was recorded by a different version of the server, it will diverge early"
+        );
+    }
+    info!(
+        world_seed = header.world_seed,
+        "Replaying {}",
+        dir.display()
+    );
+
+    let mut settings: Settings = ron::from_str(&header.settings).map_err(other)?;
+    settings.world_seed = header.world_seed;
+    settings.record_replay = false;
+    settings.gameserver_protocols = Vec::new();
+    settings.auth_server_address = None;
+    settings.query_address = None;
+
+    let work_dir = dir.join(WORK_DIR);
+    if work_dir.exists() {
+        fs::remove_dir_all(&work_dir).map_err(other)?;
+    }
+    copy_dir(&dir.join(BACKUP_DIR), &work_dir, &[]).map_err(other)?;
+    let database_settings = DatabaseSettings {
+        db_dir: work_dir.join(DATABASE_DIR),
+        sql_log_mode: SqlLogMode::Disabled,
+    };
+    let mut server = Server::new(
+        settings,
+        EditableSettings::load(&work_dir),
+        database_settings,
+        &work_dir,
+        report_stage,
+        Arc::clone(&runtime),
+    )?;
+
+    let network = Network::new(Pid::new(), &runtime);
+    let mut connections = HashMap::<ConnectionId, ReplayedConnection>::new();
+    let mut ticks = 0;
+    loop {
+        let record = match bincode::deserialize_from(&mut reader) {
+            Ok(record) => record,
+            // A recording ends wherever the server stopped, even mid-record
+            Err(e) => match *e {
+                bincode::ErrorKind::Io(ref e) if e.kind() == io::ErrorKind::UnexpectedEof => {
+                    break;
+                },
+                _ => return Err(other(e)),
+            },
+        };
+        match record {
+            Record::Connected {
+                connection,
+                client_type,
+            } => {
+                let connected = connect(&mut server, &network, &runtime, client_type)?;
+                connections.insert(connection, connected);
+            },
+            Record::Message {
+                connection,
+                stream,
+                data,
+            } => replayed_stream(&connections, connection, stream, |stream| {
+                stream.push_back(Some(data))
+            })?,
+            Record::StreamClosed { connection, stream } => {
+                replayed_stream(&connections, connection, stream, |stream| {
+                    stream.push_back(None)
+                })?
+            },
+            Record::Tick {
+                tick,
+                dt,
+                hash,
+                events,
+            } => {
+                let replayed_events = server.tick(Input::default(), dt)?;
+                let replayed_hash = state_hash(server.state.ecs());
+                server.cleanup();
+                ticks += 1;
+                if replayed_hash != hash {
+                    return Ok(ReplayReport {
+                        ticks,
+                        divergence: Some(Divergence {
+                            tick,
+                            recorded_hash: hash,
+                            replayed_hash,
+                            recorded_events: events,
+                            replayed_events: replayed_events.iter().map(describe_event).collect(),
+                        }),
+                    });
+                }
+            },
+        }
+    }
+
+    Ok(ReplayReport {
+        ticks,
+        divergence: None,
+    })
+}
+
+/// Connect to the replayed server like the recorded client did and add its
+/// client, which takes its messages from the recording.
+fn connect(
+    server: &mut Server,
+    network: &Network,
+    runtime: &Runtime,
+    client_type: ClientType,
+) -> Result<ReplayedConnection, Error> {
+    let mut participant = runtime.block_on(network.connect(ConnectAddr::Mpsc(MPSC_ADDR)))?;
+    // The connection handler needs the server info to finish the handshake,
+    // which is answered below while this runs.
+    let handshake = runtime.spawn(async move {
+        let mut streams = Vec::with_capacity(STREAM_COUNT);
+        for _ in 0..STREAM_COUNT {
+            streams.push(participant.opened().await?);
+        }
+        // The register stream is opened third, see `ConnectionHandler`
+        let register_stream = &mut streams[2];
+        register_stream.recv::<ServerInfo>().await?;
+        register_stream.send(client_type)?;
+        Ok::<_, Error>((participant, streams))
+    });
+
+    let started = std::time::Instant::now();
+    let mut client = loop {
+        server.answer_info_requests();
+        if let Ok(client) = server
+            .connection_handler
+            .client_receiver
+            .recv_timeout(Duration::from_millis(10))
+        {
+            break client;
+        }
+        if started.elapsed() > CONNECT_TIMEOUT {
+            return Err(Error::Other(
+                "Replayed server did not accept a recorded connection".into(),
+            ));
+        }
+    };
+    let (participant, network_streams) = runtime
+        .block_on(handshake)
+        .map_err(|e| Error::Other(e.to_string()))??;
+
+    let streams = Arc::new(Mutex::new(ReplayedStreams::default()));
+    client.replay_tap = Some(ReplayTap::Replay(Arc::clone(&streams)));
+    server.initialize_client(client);
+    Ok(ReplayedConnection {
+        streams,
+        _participant: participant,
+        _network_streams: network_streams,
+    })
+}
+
+/// Changes the recorded messages of a stream of a replayed client, rejecting
+/// connections and streams that don't exist.
+fn replayed_stream(
+    connections: &HashMap<ConnectionId, ReplayedConnection>,
+    connection: ConnectionId,
+    stream: u8,
+    f: impl FnOnce(&mut VecDeque<Option<Vec<u8>>>),
+) -> Result<(), Error> {
+    let connection = connections
+        .get(&connection)
+        .ok_or_else(|| Error::Other(format!("Recording uses unknown connection {connection}")))?;
+    let mut streams = connection.streams.lock().unwrap();
+    let stream = streams
+        .0
+        .get_mut(stream as usize)
+        .ok_or_else(|| Error::Other(format!("Recording uses unknown stream {stream}")))?;
+    f(stream);
+    Ok(())
+}
+
+/// Hash of the state of the players' characters, independent of the order of
+/// the entities in the ECS.
+///
+/// Characters are told apart by their account rather than their [`Uid`], as
+/// NPCs spawned at random take uids too.
+///
+/// [`Uid`]: common::uid::Uid
+pub fn state_hash(ecs: &specs::World) -> u64 {
+    let players = ecs.read_storage::<Player>();
+    let positions = ecs.read_storage::<Pos>();
+    let velocities = ecs.read_storage::<Vel>();
+    let orientations = ecs.read_storage::<Ori>();
+    let healths = ecs.read_storage::<Health>();
+    let energies = ecs.read_storage::<Energy>();
+    let character_states = ecs.read_storage::<CharacterState>();
+
+    let mut entities = (
+        &players,
+        positions.maybe(),
+        velocities.maybe(),
+        orientations.maybe(),
+        healths.maybe(),
+        energies.maybe(),
+        character_states.maybe(),
+    )
+        .join()
+        .collect::<Vec<_>>();
+    entities.sort_unstable_by_key(|(player, ..)| player.uuid());
+
+    let mut hasher = DefaultHasher::new();
+    for (player, pos, vel, ori, health, energy, character_state) in entities {
+        player.uuid().hash(&mut hasher);
+        pos.map(|pos| pos.0.into_array().map(f32::to_bits))
+            .hash(&mut hasher);
+        vel.map(|vel| vel.0.into_array().map(f32::to_bits))
+            .hash(&mut hasher);
+        ori.map(|ori| ori.to_quat().into_vec4().into_array().map(f32::to_bits))
+            .hash(&mut hasher);
+        health
+            .map(|health| health.current().to_bits())
+            .hash(&mut hasher);
+        energy
+            .map(|energy| energy.current().to_bits())
+            .hash(&mut hasher);
+        character_state.map(mem::discriminant).hash(&mut hasher);
+    }
+    hasher.finish()
+}
+
+fn describe_event(event: &Event) -> String {
+    match event {
+        Event::ClientConnected { entity } => format!("client connected: {entity:?}"),
+        Event::ClientDisconnected { entity } => format!("client disconnected: {entity:?}"),
+        Event::Chat { entity, msg } => format!("chat from {entity:?}: {msg}"),
+    }
+}
+
+/// Recursively copy `from` to `to`, leaving out the paths in `skip`.
+fn copy_dir(from: &Path, to: &Path, skip: &[&Path]) -> io::Result<()> {
+    fs::create_dir_all(to)?;
+    if !from.exists() {
+        return Ok(());
+    }
+    for entry in fs::read_dir(from)? {
+        let path = entry?.path();
+        if skip.contains(&path.as_path()) {
+            continue;
+        }
+        let target: PathBuf = to.join(path.file_name().unwrap_or_default());
+        if path.is_dir() {
+            copy_dir(&path, &target, skip)?;
+        } else {
+            fs::copy(&path, &target)?;
+        }
+    }
+    Ok(())
+}
+
+fn other(e: impl std::fmt::Display) -> Error { Error::Other(e.to_string()) }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use common::{comp::Content, resources::BattleMode};
+    use common_net::msg::ClientGeneral;
+    use specs::Builder;
+    use vek::Vec3;
+
+    #[test]
+    fn recorded_messages_are_replayed() {
+        let (records, receiver) = unbounded();
+        let recording = ReplayTap::Record {
+            connection: 3,
+            records,
+        };
+        let sent = ClientGeneral::ChatMsg(Content::Plain("hello".into()));
+        recording.record(3, &Ok(Some(sent.clone())));
+        recording.record::<ClientGeneral>(3, &Ok(None));
+        recording.record::<ClientGeneral>(3, &Err(StreamError::StreamClosed));
+
+        let streams = Arc::new(Mutex::new(ReplayedStreams::default()));
+        for record in receiver.try_iter() {
+            match record {
+                Record::Message {
+                    connection: 3,
+                    stream,
+                    data,
+                } => streams.lock().unwrap().0[stream as usize].push_back(Some(data)),
+                Record::StreamClosed {
+                    connection: 3,
+                    stream,
+                } => streams.lock().unwrap().0[stream as usize].push_back(None),
+                _ => panic!("Unexpected record"),
+            }
+        }
+
+        let replay = ReplayTap::Replay(streams);
+        assert!(
+            replay
+                .replayed::<ClientGeneral>(2)
+                .unwrap()
+                .unwrap()
+                .is_none()
+        );
+        let replayed = replay.replayed::<ClientGeneral>(3).unwrap().unwrap();
+        assert!(matches!(
+            replayed,
+            Some(ClientGeneral::ChatMsg(Content::Plain(msg))) if msg == "hello"
+        ));
+        assert!(replay.replayed::<ClientGeneral>(3).unwrap().is_err());
+        assert!(recording.replayed::<ClientGeneral>(3).is_none());
+    }
+
+    #[test]
+    fn state_hash_ignores_npcs() {
+        let mut ecs = specs::World::new();
+        ecs.register::<Player>();
+        ecs.register::<Pos>();
+        ecs.register::<Vel>();
+        ecs.register::<Ori>();
+        ecs.register::<Health>();
+        ecs.register::<Energy>();
+        ecs.register::<CharacterState>();
+
+        let player = Player::new(
+            "player".to_string(),
+            BattleMode::PvE,
+            authc::Uuid::from_u128(1),
+            None,
+        );
+        let character = ecs
+            .create_entity()
+            .with(player)
+            .with(Pos(Vec3::zero()))
+            .build();
+        let npc = ecs.create_entity().with(Pos(Vec3::zero())).build();
+        let hash = state_hash(&ecs);
+
+        ecs.write_storage::<Pos>()
+            .insert(npc, Pos(Vec3::unit_x()))
+            .unwrap();
+        assert_eq!(state_hash(&ecs), hash);
+
+        ecs.write_storage::<Pos>()
+            .insert(character, Pos(Vec3::unit_x()))
+            .unwrap();
+        assert_ne!(state_hash(&ecs), hash);
+    }
+}
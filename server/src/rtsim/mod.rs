@@ -217,6 +217,18 @@ impl RtSim {
         }
     }
 
+    /// Ends the hire of an NPC that was hired by `actor` to be escorted,
+    /// once the escort quest was completed or abandoned
+    pub fn hook_escort_ended(&mut self, npc: NpcId, actor: Actor) {
+        let data = self.state.get_data_mut();
+
+        if let Some(npc) = data.npcs.get_mut(npc)
+            && npc.hiring.is_some_and(|(hirer, _)| hirer == actor)
+        {
+            npc.hiring = None;
+        }
+    }
+
     pub fn hook_rtsim_actor_hp_change(
         &mut self,
         world: &World,
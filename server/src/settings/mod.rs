@@ -25,7 +25,7 @@ use common::{
 use core::time::Duration;
 use portpicker::pick_unused_port;
 use rand::prelude::SliceRandom;
-use serde::{Deserialize, Serialize};
+use serde::{Deserialize, Deserializer, Serialize};
 use std::{
     fmt::Display,
     fs,
@@ -157,6 +157,34 @@ impl Default for ModerationSettings {
     }
 }
 
+/// Where traces of slow ticks are exported to.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub enum TraceExport {
+    /// Append one OTLP JSON request per line to this file, relative to the
+    /// server data directory.
+    File(PathBuf),
+    /// Send the traces to an OpenTelemetry collector accepting OTLP over HTTP
+    /// with JSON, for example `http://localhost:4318`.
+    Otlp { endpoint: String },
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct TickTraceSettings {
+    pub export: TraceExport,
+    /// Only ticks taking at least this long are traced.
+    #[serde(default = "TickTraceSettings::default_slow_tick_threshold")]
+    pub slow_tick_threshold: Duration,
+    /// Fraction of the slow ticks which are traced, from 0 to 1.
+    #[serde(default = "TickTraceSettings::default_sample_rate")]
+    pub sample_rate: f32,
+}
+
+impl TickTraceSettings {
+    fn default_slow_tick_threshold() -> Duration { Duration::from_millis(50) }
+
+    fn default_sample_rate() -> f32 { 1.0 }
+}
+
 #[derive(Clone, Debug, Serialize, Deserialize)]
 pub enum CalendarMode {
     None,
@@ -192,6 +220,8 @@ pub struct Settings {
     pub start_time: f64,
     /// Length of a day in minutes.
     pub day_length: f64,
+    /// Length of a season in in-game days. Seasons are disabled when `None`.
+    pub season_length: Option<u32>,
     /// When set to None, loads the default map file (if available); otherwise,
     /// uses the value of the file options to decide how to proceed.
     pub map_file: Option<FileOpts>,
@@ -200,11 +230,29 @@ pub struct Settings {
     pub client_timeout: Duration,
     pub max_player_for_kill_broadcast: Option<usize>,
     pub calendar_mode: CalendarMode,
-
-    /// Experimental feature. No guaranteed forwards-compatibility, may be
-    /// removed at *any time* with no migration.
-    #[serde(default, skip_serializing)]
-    pub experimental_terrain_persistence: bool,
+    /// Language the server renders messages in for its own outputs, such as the
+    /// web chat export and logs, and for players whose language isn't
+    /// available.
+    pub language: String,
+
+    /// Whether changes made to the terrain, such as blocks placed or destroyed
+    /// by players, are saved in the `terrain` directory of the server data.
+    #[serde(alias = "experimental_terrain_persistence")]
+    pub terrain_persistence: bool,
+
+    /// Whether client inputs are recorded to the `replays` directory of the
+    /// server data, to be replayed with the `replay` server CLI command.
+    pub record_replay: bool,
+
+    /// Export entity counts, agents, physics contacts and chunk requests per
+    /// region and the bytes sent to each player as labelled metrics. Only this
+    /// many of the busiest regions and players are exported per metric, to
+    /// limit the number of time series. Disabled when `None`.
+    pub labelled_metrics_limit: Option<usize>,
+
+    /// Export traces of slow ticks, with the time spent in each phase, system,
+    /// slow job and database batch. Disabled when `None`.
+    pub tick_traces: Option<TickTraceSettings>,
 
     #[serde(default)]
     pub gameplay: GameplaySettings,
@@ -232,14 +280,19 @@ impl Default for Settings {
             server_name: "Veloren Server".into(),
             max_players: 100,
             day_length: DAY_LENGTH_DEFAULT,
+            season_length: None,
             start_time: 9.0 * 3600.0,
             map_file: None,
             max_view_distance: Some(65),
             max_player_group_size: 6,
             calendar_mode: CalendarMode::Auto,
+            language: client_i18n::REFERENCE_LANG.to_owned(),
             client_timeout: Duration::from_secs(40),
             max_player_for_kill_broadcast: None,
-            experimental_terrain_persistence: false,
+            terrain_persistence: false,
+            record_replay: false,
+            labelled_metrics_limit: None,
+            tick_traces: None,
             gameplay: GameplaySettings::default(),
             moderation: ModerationSettings::default(),
             world: WorldSettings::default(),
@@ -349,10 +402,195 @@ impl Settings {
     /// Derive a coefficient that is the relatively speed of the in-game
     /// day/night cycle compared to reality.
     pub fn day_cycle_coefficient(&self) -> f64 { 1440.0 / self.day_length }
+
+    /// The settings that can be changed while the server is running.
+    pub fn runtime(&self) -> RuntimeSettings {
+        RuntimeSettings {
+            max_players: self.max_players,
+            max_view_distance: self.max_view_distance,
+            day_length: self.day_length,
+            battle_mode: self.gameplay.battle_mode,
+            automod: self.moderation.automod,
+            automod_admins_exempt: self.moderation.admins_exempt,
+            max_player_for_kill_broadcast: self.max_player_for_kill_broadcast,
+            calendar_mode: self.calendar_mode.clone(),
+        }
+    }
+
+    /// Validates and applies changes to the settings that can be changed while
+    /// the server is running. Nothing is changed if any of them is invalid.
+    pub fn apply_runtime(
+        &mut self,
+        patch: RuntimeSettingsPatch,
+    ) -> Result<(), InvalidSettingsError> {
+        let mut settings = self.runtime();
+        let RuntimeSettingsPatch {
+            max_players,
+            max_view_distance,
+            day_length,
+            battle_mode,
+            automod,
+            automod_admins_exempt,
+            max_player_for_kill_broadcast,
+            calendar_mode,
+        } = patch;
+        if let Some(max_players) = max_players {
+            settings.max_players = max_players;
+        }
+        if let Some(max_view_distance) = max_view_distance {
+            settings.max_view_distance = max_view_distance;
+        }
+        if let Some(day_length) = day_length {
+            settings.day_length = day_length;
+        }
+        if let Some(battle_mode) = battle_mode {
+            settings.battle_mode = battle_mode;
+        }
+        if let Some(automod) = automod {
+            settings.automod = automod;
+        }
+        if let Some(admins_exempt) = automod_admins_exempt {
+            settings.automod_admins_exempt = admins_exempt;
+        }
+        if let Some(max_player_for_kill_broadcast) = max_player_for_kill_broadcast {
+            settings.max_player_for_kill_broadcast = max_player_for_kill_broadcast;
+        }
+        if let Some(calendar_mode) = calendar_mode {
+            settings.calendar_mode = calendar_mode;
+        }
+
+        if settings.day_length <= 0.0 {
+            return Err(InvalidSettingsError::InvalidDayDuration);
+        }
+        if settings.max_players == 0 {
+            return Err(InvalidSettingsError::InvalidMaxPlayers);
+        }
+        if settings
+            .max_view_distance
+            .is_some_and(|vd| vd < crate::MIN_VD)
+        {
+            return Err(InvalidSettingsError::InvalidMaxViewDistance);
+        }
+
+        self.max_players = settings.max_players;
+        self.max_view_distance = settings.max_view_distance;
+        self.day_length = settings.day_length;
+        self.gameplay.battle_mode = settings.battle_mode;
+        self.moderation.automod = settings.automod;
+        self.moderation.admins_exempt = settings.automod_admins_exempt;
+        self.max_player_for_kill_broadcast = settings.max_player_for_kill_broadcast;
+        self.calendar_mode = settings.calendar_mode;
+        Ok(())
+    }
+
+    /// Reads the settings that can be changed while the server is running from
+    /// the settings file, unlike [`Settings::load`] failing if it can't be
+    /// parsed.
+    ///
+    /// path: Directory that contains the server config directory
+    pub fn load_runtime(path: &Path) -> Result<RuntimeSettingsPatch, InvalidSettingsError> {
+        Ok(Self::read_file(&Self::get_settings_path(path))?
+            .runtime()
+            .into())
+    }
+
+    /// Applies changes to the settings that can be changed while the server is
+    /// running to the settings file. Everything else in the file is kept as it
+    /// is, so that edits made to it while the server is running aren't lost.
+    ///
+    /// path: Directory that contains the server config directory
+    pub fn save_runtime(
+        path: &Path,
+        patch: RuntimeSettingsPatch,
+    ) -> Result<(), InvalidSettingsError> {
+        let path = Self::get_settings_path(path);
+        let mut settings = Self::read_file(&path)?;
+        settings.apply_runtime(patch)?;
+        settings
+            .save_to_file(&path)
+            .map_err(|e| InvalidSettingsError::Unwritable(e.to_string()))
+    }
+
+    fn read_file(path: &Path) -> Result<Self, InvalidSettingsError> {
+        fs::File::open(path)
+            .map_err(|e| e.to_string())
+            .and_then(|file| ron::de::from_reader(file).map_err(|e| e.to_string()))
+            .map_err(InvalidSettingsError::Unreadable)
+    }
+
+    /// path: Directory that contains the server config directory
+    pub fn save(&self, path: &Path) -> std::io::Result<()> {
+        self.save_to_file(&Self::get_settings_path(path))
+    }
+}
+
+/// The settings that can be changed while the server is running
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct RuntimeSettings {
+    pub max_players: u16,
+    pub max_view_distance: Option<u32>,
+    /// Length of a day in minutes.
+    pub day_length: f64,
+    pub battle_mode: ServerBattleMode,
+    pub automod: bool,
+    pub automod_admins_exempt: bool,
+    pub max_player_for_kill_broadcast: Option<usize>,
+    pub calendar_mode: CalendarMode,
+}
+
+/// Changes to [`RuntimeSettings`], the settings left out are kept as they are
+#[derive(Clone, Debug, Default, Deserialize)]
+#[serde(default)]
+pub struct RuntimeSettingsPatch {
+    pub max_players: Option<u16>,
+    #[serde(deserialize_with = "present")]
+    pub max_view_distance: Option<Option<u32>>,
+    pub day_length: Option<f64>,
+    pub battle_mode: Option<ServerBattleMode>,
+    pub automod: Option<bool>,
+    pub automod_admins_exempt: Option<bool>,
+    #[serde(deserialize_with = "present")]
+    pub max_player_for_kill_broadcast: Option<Option<usize>>,
+    pub calendar_mode: Option<CalendarMode>,
+}
+
+impl From<RuntimeSettings> for RuntimeSettingsPatch {
+    fn from(settings: RuntimeSettings) -> Self {
+        Self {
+            max_players: Some(settings.max_players),
+            max_view_distance: Some(settings.max_view_distance),
+            day_length: Some(settings.day_length),
+            battle_mode: Some(settings.battle_mode),
+            automod: Some(settings.automod),
+            automod_admins_exempt: Some(settings.automod_admins_exempt),
+            max_player_for_kill_broadcast: Some(settings.max_player_for_kill_broadcast),
+            calendar_mode: Some(settings.calendar_mode),
+        }
+    }
+}
+
+/// Distinguishes a setting set to `null` from one left out of a
+/// [`RuntimeSettingsPatch`]
+fn present<'de, D, T>(de: D) -> Result<Option<T>, D::Error>
+where
+    D: Deserializer<'de>,
+    T: Deserialize<'de>,
+{
+    T::deserialize(de).map(Some)
 }
 
+#[derive(Debug)]
 pub enum InvalidSettingsError {
     InvalidDayDuration,
+    InvalidMaxPlayers,
+    InvalidMaxViewDistance,
+    /// The max view distance was raised above its value at startup, which
+    /// instances are spaced for
+    MaxViewDistanceRaised,
+    /// The settings file couldn't be read or parsed
+    Unreadable(String),
+    /// The settings file couldn't be written
+    Unwritable(String),
 }
 impl Display for InvalidSettingsError {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
@@ -360,6 +598,30 @@ impl Display for InvalidSettingsError {
             InvalidSettingsError::InvalidDayDuration => {
                 f.write_str("Invalid settings error: Day length was invalid (zero or negative).")
             },
+            InvalidSettingsError::InvalidMaxPlayers => {
+                f.write_str("Invalid settings error: Max players must be at least 1.")
+            },
+            InvalidSettingsError::InvalidMaxViewDistance => write!(
+                f,
+                "Invalid settings error: Max view distance must be at least {}.",
+                crate::MIN_VD
+            ),
+            InvalidSettingsError::MaxViewDistanceRaised => f.write_str(
+                "Invalid settings error: Max view distance can't be raised above its value at \
+                 startup while the server is running.",
+            ),
+            InvalidSettingsError::Unreadable(error) => {
+                write!(
+                    f,
+                    "Invalid settings error: Couldn't read the settings file: {error}"
+                )
+            },
+            InvalidSettingsError::Unwritable(error) => {
+                write!(
+                    f,
+                    "Invalid settings error: Couldn't write the settings file: {error}"
+                )
+            },
         }
     }
 }
@@ -439,3 +701,59 @@ impl EditableSettings {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn runtime_patch() {
+        let mut settings = Settings::default();
+        let patch: RuntimeSettingsPatch =
+            serde_json::from_str(r#"{"max_players": 10, "max_view_distance": null}"#).unwrap();
+        settings.apply_runtime(patch).unwrap();
+        assert_eq!(settings.max_players, 10);
+        assert_eq!(settings.max_view_distance, None);
+        assert_eq!(settings.day_length, DAY_LENGTH_DEFAULT);
+
+        // Invalid changes are rejected without changing anything
+        let patch: RuntimeSettingsPatch =
+            serde_json::from_str(r#"{"max_players": 20, "day_length": 0.0}"#).unwrap();
+        assert!(settings.apply_runtime(patch).is_err());
+        assert_eq!(settings.max_players, 10);
+    }
+
+    #[test]
+    fn save_runtime_keeps_other_settings() {
+        let dir =
+            std::env::temp_dir().join(format!("veloren-settings-test-{}", std::process::id()));
+        // Edited by hand while the server is running
+        let edited = Settings {
+            server_name: "Edited".to_string(),
+            day_length: 60.0,
+            ..Settings::default()
+        };
+        edited.save(&dir).unwrap();
+
+        let patch = RuntimeSettingsPatch {
+            max_players: Some(10),
+            ..RuntimeSettingsPatch::default()
+        };
+        Settings::save_runtime(&dir, patch).unwrap();
+        let saved = Settings::read_file(&Settings::get_settings_path(&dir)).unwrap();
+        assert_eq!(saved.max_players, 10);
+        assert_eq!(saved.server_name, "Edited");
+        assert_eq!(saved.day_length, 60.0);
+
+        // Invalid changes aren't saved
+        let patch = RuntimeSettingsPatch {
+            max_players: Some(0),
+            ..RuntimeSettingsPatch::default()
+        };
+        assert!(Settings::save_runtime(&dir, patch).is_err());
+        let saved = Settings::read_file(&Settings::get_settings_path(&dir)).unwrap();
+        assert_eq!(saved.max_players, 10);
+
+        fs::remove_dir_all(&dir).unwrap();
+    }
+}
@@ -603,6 +603,7 @@ impl StateExt for State {
             pets,
             active_abilities,
             map_marker,
+            quest_log,
         } = components;
 
         if let Some(player_uid) = self.read_component_copied::<Uid>(entity) {
@@ -657,6 +658,7 @@ impl StateExt for State {
             self.write_component_ignore_entity_dead(entity, stats);
             self.write_component_ignore_entity_dead(entity, active_abilities);
             self.write_component_ignore_entity_dead(entity, skill_set);
+            self.write_component_ignore_entity_dead(entity, quest_log);
             self.write_component_ignore_entity_dead(entity, inventory);
             self.write_component_ignore_entity_dead(
                 entity,
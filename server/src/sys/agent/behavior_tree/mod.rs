@@ -10,6 +10,7 @@ use common::{
         body, is_downed,
     },
     consts::MAX_INTERACT_RANGE,
+    event::{EmitExt, StartQuestEvent},
     interaction::InteractionKind,
     path::TraversalConfig,
     rtsim::{NpcAction, RtSimEntity},
@@ -653,6 +654,15 @@ fn handle_rtsim_actions(bdata: &mut BehaviorData) -> bool {
                     warn!("NPC dialogue sent to non-existent target entity");
                 }
             },
+            NpcAction::StartQuest(target, quest) => {
+                if let Some(target) = bdata.read_data.id_maps.actor_entity(target) {
+                    bdata
+                        .emitters
+                        .emit(StartQuestEvent(*bdata.agent_data.entity, target, quest));
+                } else {
+                    warn!("NPC quest given to non-existent target entity");
+                }
+            },
         }
         true
     } else {
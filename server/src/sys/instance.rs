@@ -0,0 +1,45 @@
+use common::{
+    comp::{Pos, Presence},
+    resources::Time,
+    terrain::TerrainGrid,
+};
+use common_ecs::{Origin, Phase, System};
+use specs::{Join, Read, ReadExpect, ReadStorage, WriteExpect};
+
+use crate::{Tick, chunk_generator::ChunkGenerator, instance::Instances};
+
+// Check for empty instances every 30 ticks
+const MAINTAIN_INTERVAL: u64 = 30;
+
+#[derive(Default)]
+pub struct Sys;
+
+impl<'a> System<'a> for Sys {
+    type SystemData = (
+        Read<'a, Tick>,
+        Read<'a, Time>,
+        ReadExpect<'a, TerrainGrid>,
+        WriteExpect<'a, Instances>,
+        WriteExpect<'a, ChunkGenerator>,
+        ReadStorage<'a, Pos>,
+        ReadStorage<'a, Presence>,
+    );
+
+    const NAME: &'static str = "instance";
+    const ORIGIN: Origin = Origin::Server;
+    const PHASE: Phase = Phase::Create;
+
+    fn run(
+        _job: &mut common_ecs::Job<Self>,
+        (tick, time, terrain, mut instances, mut chunk_generator, positions, presences): Self::SystemData,
+    ) {
+        if tick.0 % MAINTAIN_INTERVAL == 0 {
+            instances.maintain(
+                (&positions, &presences).join().map(|(pos, _)| pos.0),
+                *time,
+                &terrain,
+                &mut chunk_generator,
+            );
+        }
+    }
+}
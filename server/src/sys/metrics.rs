@@ -1,11 +1,22 @@
 use crate::{
     HwStats, Tick, TickStart,
     chunk_generator::ChunkGenerator,
-    metrics::{EcsSystemMetrics, JobMetrics, PhysicsMetrics, QueryServerMetrics, TickMetrics},
+    client::Client,
+    load::{self, LoadTracker, PlayerLoad, RegionLoad},
+    metrics::{
+        EcsSystemMetrics, JobMetrics, LabelledMetrics, PhysicsMetrics, QueryServerMetrics,
+        TickMetrics,
+    },
+};
+use common::{
+    comp::{Agent, PhysicsState, Player},
+    region::RegionMap,
+    resources::TimeOfDay,
+    slowjob::SlowJobPool,
+    terrain::TerrainGrid,
 };
-use common::{resources::TimeOfDay, slowjob::SlowJobPool, terrain::TerrainGrid};
 use common_ecs::{Job, Origin, Phase, SysMetrics, System};
-use specs::{Entities, Join, Read, ReadExpect};
+use specs::{Entities, Join, Read, ReadExpect, ReadStorage, WriteExpect};
 use std::{
     sync::{Arc, Mutex},
     time::Instant,
@@ -33,6 +44,13 @@ impl<'a> System<'a> for Sys {
         ReadExpect<'a, JobMetrics>,
         Option<Read<'a, Arc<Mutex<RawQueryServerMetrics>>>>,
         ReadExpect<'a, QueryServerMetrics>,
+        ReadExpect<'a, RegionMap>,
+        WriteExpect<'a, LoadTracker>,
+        Option<Read<'a, LabelledMetrics>>,
+        ReadStorage<'a, Agent>,
+        ReadStorage<'a, PhysicsState>,
+        ReadStorage<'a, Player>,
+        ReadStorage<'a, Client>,
     );
 
     const NAME: &'static str = "metrics";
@@ -58,6 +76,13 @@ impl<'a> System<'a> for Sys {
             export_jobs,
             raw_query_server,
             export_query_server,
+            region_map,
+            mut load_tracker,
+            export_labelled,
+            agents,
+            physics_states,
+            players,
+            clients,
         ): Self::SystemData,
     ) {
         const NANOSEC_PER_SEC: f64 = std::time::Duration::from_secs(1).as_nanos() as f64;
@@ -116,6 +141,34 @@ impl<'a> System<'a> for Sys {
             let entity_count = entities.join().count();
             export_tick.entity_count.set(entity_count as i64);
         }
+        if tick.0.rem_euclid(load::REPORT_INTERVAL) == 0 {
+            let regions = region_map
+                .iter()
+                .map(|(key, region)| RegionLoad {
+                    key,
+                    entities: region.entities().join().count() as u64,
+                    agents: (region.entities(), &agents).join().count() as u64,
+                    physics_contacts: (region.entities(), &physics_states)
+                        .join()
+                        .filter(|physics| !physics.touch_entities.is_empty())
+                        .count() as u64,
+                    chunk_requests: 0,
+                })
+                .collect();
+            let players = (&players, &clients)
+                .join()
+                .map(|(player, client)| PlayerLoad {
+                    alias: player.alias.clone(),
+                    sent_bytes: client.take_sent_bytes(),
+                })
+                .collect();
+            load_tracker.set_report(regions, players);
+            if let (Some(export_labelled), Some(report)) =
+                (export_labelled.as_ref(), load_tracker.report())
+            {
+                export_labelled.apply(report);
+            }
+        }
         common_base::plot!("entity count", entities.join().count() as f64);
         common_base::plot!(
             "pending chunks",
@@ -2,6 +2,7 @@ pub mod agent;
 pub mod chunk_send;
 pub mod chunk_serialize;
 pub mod entity_sync;
+pub mod instance;
 pub mod invite_timeout;
 pub mod item;
 pub mod loot;
@@ -10,6 +11,7 @@ pub mod msg;
 pub mod object;
 pub mod persistence;
 pub mod pets;
+pub mod quest;
 pub mod sentinel;
 pub mod server_info;
 pub mod subscription;
@@ -36,6 +38,7 @@ pub fn add_server_systems(dispatch_builder: &mut DispatcherBuilder) {
     dispatch::<terrain::Sys>(dispatch_builder, &[&msg::terrain::Sys::sys_name()]);
     dispatch::<waypoint::Sys>(dispatch_builder, &[]);
     dispatch::<teleporter::Sys>(dispatch_builder, &[]);
+    dispatch::<instance::Sys>(dispatch_builder, &[&terrain::Sys::sys_name()]);
     dispatch::<invite_timeout::Sys>(dispatch_builder, &[]);
     dispatch::<persistence::Sys>(dispatch_builder, &[]);
     dispatch::<object::Sys>(dispatch_builder, &[]);
@@ -45,6 +48,7 @@ pub fn add_server_systems(dispatch_builder: &mut DispatcherBuilder) {
     // don't depend on chunk_serialize, as we assume everything is done in a SlowJow
     dispatch::<chunk_send::Sys>(dispatch_builder, &[]);
     dispatch::<item::Sys>(dispatch_builder, &[]);
+    dispatch::<quest::Sys>(dispatch_builder, &[]);
     dispatch::<server_info::Sys>(dispatch_builder, &[]);
 }
 
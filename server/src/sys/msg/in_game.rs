@@ -75,6 +75,7 @@ impl Sys {
         player_physics_setting: Option<&mut PlayerPhysicsSetting>,
         server_physics_forced: bool,
         maybe_admin: &Option<&Admin>,
+        _maybe_player: Option<&Player>,
         time_for_vd_changes: Instant,
         msg: ClientGeneral,
         player_physics: &mut Option<(Pos, Vel, Ori)>,
@@ -179,6 +180,14 @@ impl Sys {
                                         guard._terrain_persistence.as_mut()
                                     {
                                         terrain_persistence.set_block(pos, new_block);
+                                        if let Some(player) = _maybe_player {
+                                            terrain_persistence.record_edit(
+                                                pos,
+                                                *old_block,
+                                                new_block,
+                                                player.uuid(),
+                                            );
+                                        }
                                     }
                                 }
                             }
@@ -208,6 +217,16 @@ impl Sys {
                                         guard._terrain_persistence.as_mut()
                                     {
                                         terrain_persistence.set_block(pos, new_block);
+                                        if let Some(player) = _maybe_player
+                                            && let Ok(old_block) = terrain.get(pos)
+                                        {
+                                            terrain_persistence.record_edit(
+                                                pos,
+                                                *old_block,
+                                                new_block,
+                                                player.uuid(),
+                                            );
+                                        }
                                     }
                                 }
                             }
@@ -427,6 +446,7 @@ impl<'a> System<'a> for Sys {
                             new_player_physics_setting.as_mut(),
                             is_server_physics_forced,
                             &maybe_admin,
+                            maybe_player,
                             time_for_vd_changes,
                             msg,
                             &mut player_physics,
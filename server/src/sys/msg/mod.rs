@@ -11,7 +11,7 @@ use crate::{
     sys::{loot, pets},
 };
 use common_ecs::{System, dispatch};
-use serde::de::DeserializeOwned;
+use serde::{Serialize, de::DeserializeOwned};
 use specs::DispatcherBuilder;
 
 pub fn add_server_systems(dispatch_builder: &mut DispatcherBuilder) {
@@ -36,7 +36,7 @@ pub(crate) fn try_recv_all<M, F>(
     mut f: F,
 ) -> Result<u64, crate::error::Error>
 where
-    M: DeserializeOwned,
+    M: Serialize + DeserializeOwned,
     F: FnMut(&Client, M) -> Result<(), crate::error::Error>,
 {
     let mut cnt = 0u64;
@@ -1,4 +1,6 @@
-use crate::{EditableSettings, client::Client, settings::banlist::NormalizedIpAddr};
+use crate::{
+    EditableSettings, ServerLocalization, client::Client, settings::banlist::NormalizedIpAddr,
+};
 use common::{
     comp::Player,
     event::{ClientDisconnectEvent, EventBus},
@@ -28,6 +30,7 @@ impl<'a> System<'a> for Sys {
         WriteStorage<'a, Client>,
         Read<'a, EventBus<ClientDisconnectEvent>>,
         ReadExpect<'a, EditableSettings>,
+        ReadExpect<'a, ServerLocalization>,
     );
 
     const NAME: &'static str = "msg::network_events";
@@ -36,7 +39,14 @@ impl<'a> System<'a> for Sys {
 
     fn run(
         _job: &mut Job<Self>,
-        (entities, players, mut clients, client_disconnect_event_bus, editable_settings): Self::SystemData,
+        (
+            entities,
+            players,
+            mut clients,
+            client_disconnect_event_bus,
+            editable_settings,
+            localization,
+        ): Self::SystemData,
     ) {
         let now = chrono::Utc::now();
         let mut client_disconnect_emitter = client_disconnect_event_bus.emitter();
@@ -74,7 +84,10 @@ impl<'a> System<'a> for Sys {
                                     common::comp::DisconnectReason::Kicked,
                                 ));
                                 let _ = client.send(ServerGeneral::Disconnect(
-                                    DisconnectReason::Banned(ban_info),
+                                    localization.localize_disconnect(
+                                        client.locale.as_deref(),
+                                        DisconnectReason::Banned(ban_info),
+                                    ),
                                 ));
                             }
                         }
@@ -1,12 +1,12 @@
 use crate::{
-    EditableSettings, Settings,
+    EditableSettings, ServerLocalization, Settings,
     client::Client,
     login_provider::{LoginProvider, PendingLogin},
     metrics::PlayerMetrics,
     sys::sentinel::TrackedStorages,
 };
 use common::{
-    comp::{self, Admin, Player, Stats},
+    comp::{self, Admin, Content, Player, Stats},
     event::{ClientDisconnectEvent, EventBus, MakeAdminEvent},
     recipe::{default_component_recipe_book, default_repair_recipe_book},
     resources::TimeOfDay,
@@ -41,6 +41,7 @@ pub struct ReadData<'a> {
     login_provider: ReadExpect<'a, LoginProvider>,
     player_metrics: ReadExpect<'a, PlayerMetrics>,
     settings: ReadExpect<'a, Settings>,
+    localization: ReadExpect<'a, ServerLocalization>,
     editable_settings: ReadExpect<'a, EditableSettings>,
     time_of_day: Read<'a, TimeOfDay>,
     material_stats: ReadExpect<'a, comp::item::MaterialStatManifest>,
@@ -244,7 +245,12 @@ impl<'a> System<'a> for Sys {
                                             entity,
                                             common::comp::DisconnectReason::Kicked,
                                         ));
-                                        client.send(Err(e))?;
+                                        client.send(Err(read_data
+                                            .localization
+                                            .localize_register_error(
+                                                client.locale.as_deref(),
+                                                e,
+                                            )))?;
                                         return Ok(());
                                     },
                                     // Swap the order of the tuple, so when it's destructured guard
@@ -297,9 +303,14 @@ impl<'a> System<'a> for Sys {
                                              joining on client",
                                         );
                                     let _ = old_client.send(ServerGeneral::Disconnect(
-                                        DisconnectReason::Kicked(String::from(
-                                            "You have logged in from another location.",
-                                        )),
+                                        DisconnectReason::Kicked(
+                                            read_data.localization.localize_for(
+                                                old_client.locale.as_deref(),
+                                                &Content::localized(
+                                                    "server-kick-logged_in_elsewhere",
+                                                ),
+                                            ),
+                                        ),
                                     ));
                                 } else {
                                     drop(new_players_guard);
@@ -1,5 +1,5 @@
 use crate::{
-    ChunkRequest, chunk_serialize::ChunkSendEntry, client::Client, lod::Lod,
+    ChunkRequest, chunk_serialize::ChunkSendEntry, client::Client, load::LoadTracker, lod::Lod,
     metrics::NetworkRequestMetrics,
 };
 use common::{
@@ -26,6 +26,7 @@ impl<'a> System<'a> for Sys {
         ReadExpect<'a, TerrainGrid>,
         ReadExpect<'a, Lod>,
         ReadExpect<'a, NetworkRequestMetrics>,
+        ReadExpect<'a, LoadTracker>,
         Write<'a, Vec<ChunkRequest>>,
         ReadStorage<'a, Pos>,
         ReadStorage<'a, Presence>,
@@ -45,6 +46,7 @@ impl<'a> System<'a> for Sys {
             terrain,
             lod,
             network_metrics,
+            load_tracker,
             mut chunk_requests,
             positions,
             presences,
@@ -81,6 +83,7 @@ impl<'a> System<'a> for Sys {
                             };
                             match msg {
                                 ClientGeneral::TerrainChunkRequest { key } => {
+                                    load_tracker.record_chunk_request(key);
                                     let in_vd = if let Some(pos) = positions.get(entity) {
                                         pos.0.xy().map(|e| e as f64).distance_squared(
                                             key.map(|e| e as f64 + 0.5)
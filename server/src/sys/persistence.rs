@@ -1,8 +1,8 @@
 use crate::{persistence::character_updater, sys::SysScheduler};
 use common::{
     comp::{
-        ActiveAbilities, Alignment, Body, Inventory, MapMarker, Presence, PresenceKind, SkillSet,
-        Stats, Waypoint,
+        ActiveAbilities, Alignment, Body, Inventory, MapMarker, Presence, PresenceKind, QuestLog,
+        SkillSet, Stats, Waypoint,
         pet::{Pet, is_tameable},
     },
     uid::Uid,
@@ -27,6 +27,7 @@ impl<'a> System<'a> for Sys {
         ReadStorage<'a, Pet>,
         ReadStorage<'a, Stats>,
         ReadStorage<'a, ActiveAbilities>,
+        ReadStorage<'a, QuestLog>,
         WriteExpect<'a, character_updater::CharacterUpdater>,
         Write<'a, SysScheduler<Self>>,
     );
@@ -49,6 +50,7 @@ impl<'a> System<'a> for Sys {
             pets,
             stats,
             active_abilities,
+            quest_logs,
             mut updater,
             mut scheduler,
         ): Self::SystemData,
@@ -63,6 +65,7 @@ impl<'a> System<'a> for Sys {
                     player_waypoints.maybe(),
                     &active_abilities,
                     map_markers.maybe(),
+                    &quest_logs,
                 )
                     .join()
                     .filter_map(
@@ -74,6 +77,7 @@ impl<'a> System<'a> for Sys {
                             waypoint,
                             active_abilities,
                             map_marker,
+                            quest_log,
                         )| match presence.kind {
                             PresenceKind::LoadingCharacter(_char_id) => {
                                 error!(
@@ -107,6 +111,7 @@ impl<'a> System<'a> for Sys {
                                     waypoint.cloned(),
                                     active_abilities.clone(),
                                     map_marker.cloned(),
+                                    quest_log.clone(),
                                 ))
                             },
                             PresenceKind::Spectator | PresenceKind::Possessor => None,
@@ -0,0 +1,66 @@
+use crate::{
+    Tick,
+    events::shared::{QuestHandInData, hand_in_quests},
+};
+use common::uid::IdMaps;
+use common_ecs::{Job, Origin, Phase, System};
+use specs::{Entities, Join, Read};
+
+/// This system tracks characters and escorted NPCs reaching quest locations,
+/// and pays out completed quests that don't need to be handed in
+#[derive(Default)]
+pub struct Sys;
+impl<'a> System<'a> for Sys {
+    type SystemData = (
+        Entities<'a>,
+        Read<'a, IdMaps>,
+        Read<'a, Tick>,
+        QuestHandInData<'a>,
+    );
+
+    const NAME: &'static str = "quest";
+    const ORIGIN: Origin = Origin::Server;
+    const PHASE: Phase = Phase::Create;
+
+    fn run(_job: &mut Job<Self>, (entities, id_maps, tick, mut data): Self::SystemData) {
+        // Only check about once per second
+        if tick.0.rem_euclid(30) != 0 {
+            return;
+        }
+
+        let positions = &data.positions;
+        let arrivals = (&entities, positions, &data.quest_logs)
+            .join()
+            .filter(|(_, _, quest_log)| quest_log.wants_arrivals())
+            .flat_map(|(entity, pos, quest_log)| {
+                let escorts = quest_log.escorted_npcs().filter_map(|npc| {
+                    let pos = positions.get(id_maps.actor_entity(npc)?)?;
+                    Some((Some(npc), pos.0.xy()))
+                });
+                std::iter::once((None, pos.0.xy()))
+                    .chain(escorts)
+                    .filter(|(escort, wpos)| quest_log.would_arrive(*escort, *wpos))
+                    .map(move |(escort, wpos)| (entity, escort, wpos))
+                    .collect::<Vec<_>>()
+            })
+            .collect::<Vec<_>>();
+
+        for (entity, escort, wpos) in arrivals {
+            if let Some(mut quest_log) = data.quest_logs.get_mut(entity) {
+                quest_log.record_arrival(escort, wpos);
+            }
+        }
+
+        let completed = (&entities, &data.quest_logs)
+            .join()
+            .filter(|(entity, quest_log)| {
+                quest_log.can_hand_in(None, data.inventories.get(*entity))
+            })
+            .map(|(entity, _)| entity)
+            .collect::<Vec<_>>();
+
+        for entity in completed {
+            hand_in_quests(&mut data, entity, None);
+        }
+    }
+}
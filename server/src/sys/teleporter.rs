@@ -1,8 +1,9 @@
+use crate::instance::InstanceEntrance;
 use common::{
     CachedSpatialGrid,
     comp::{Agent, Alignment, CharacterState, Object, Pos, Teleporting},
     consts::TELEPORTER_RADIUS,
-    event::{EventBus, TeleportToPositionEvent},
+    event::{EnterInstanceEvent, EventBus, TeleportToPositionEvent},
     outcome::Outcome,
     resources::Time,
     uid::Uid,
@@ -29,11 +30,13 @@ impl<'a> System<'a> for Sys {
         ReadStorage<'a, Alignment>,
         ReadStorage<'a, Agent>,
         ReadStorage<'a, Object>,
+        ReadStorage<'a, InstanceEntrance>,
         WriteStorage<'a, Teleporting>,
         ReadStorage<'a, CharacterState>,
         Read<'a, CachedSpatialGrid>,
         Read<'a, Time>,
         Read<'a, EventBus<TeleportToPositionEvent>>,
+        Read<'a, EventBus<EnterInstanceEvent>>,
         Read<'a, EventBus<Outcome>>,
     );
 
@@ -50,15 +53,18 @@ impl<'a> System<'a> for Sys {
             alignments,
             agent,
             objects,
+            instance_entrances,
             mut teleporting,
             character_states,
             spatial_grid,
             time,
             teleport_to_position_events,
+            enter_instance_events,
             outcome_bus,
         ): Self::SystemData,
     ) {
         let mut teleport_to_position_emitter = teleport_to_position_events.emitter();
+        let mut enter_instance_emitter = enter_instance_events.emitter();
         let mut outcome_emitter = outcome_bus.emitter();
         let check_aggro = |entity, pos: Vec3<f32>| {
             spatial_grid
@@ -125,6 +131,19 @@ impl<'a> System<'a> for Sys {
                             .then_some(nearby_entity)
                     });
 
+                if let Some(InstanceEntrance(kind)) = instance_entrances.get(teleporting.portal) {
+                    // Where the instance is only known once it is created
+                    let nearby = nearby.collect::<Vec<_>>();
+                    cancel_teleporting.extend(nearby.iter().copied());
+                    enter_instance_emitter.emit(EnterInstanceEvent {
+                        leader: entity,
+                        entities: nearby,
+                        kind: *kind,
+                        exit: portal_pos.map_or(position.0, |portal_pos| portal_pos.0),
+                    });
+                    continue;
+                }
+
                 for entity in nearby {
                     cancel_teleporting.push(entity);
                     teleport_to_position_emitter.emit(TeleportToPositionEvent {
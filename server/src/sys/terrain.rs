@@ -9,7 +9,7 @@ use world::{IndexOwned, World};
 #[cfg(feature = "worldgen")] use crate::rtsim;
 use crate::{
     ChunkRequest, Tick, chunk_generator::ChunkGenerator, chunk_serialize::ChunkSendEntry,
-    client::Client, presence::RepositionOnChunkLoad, settings::Settings,
+    client::Client, instance::Instances, presence::RepositionOnChunkLoad, settings::Settings,
 };
 use common::{
     SkillSetBuilder,
@@ -74,6 +74,7 @@ pub struct Data<'a> {
     world: ReadExpect<'a, Arc<World>>,
     chunk_send_bus: ReadExpect<'a, EventBus<ChunkSendEntry>>,
     chunk_generator: WriteExpect<'a, ChunkGenerator>,
+    instances: ReadExpect<'a, Instances>,
     terrain: WriteExpect<'a, TerrainGrid>,
     terrain_changes: Write<'a, TerrainChanges>,
     chunk_requests: Write<'a, Vec<ChunkRequest>>,
@@ -238,6 +239,7 @@ impl<'a> System<'a> for Sys {
         let world_size = data.world.map_size_lg().chunks().map(u32::from);
         let (presences_position_entities, presences_positions) = prepare_player_presences(
             world_size,
+            &data.instances,
             max_view_distance,
             &data.entities,
             &data.positions,
@@ -643,6 +645,7 @@ pub fn convert_to_loaded_vd(vd: u32, max_view_distance: u32) -> i32 {
 /// Returns: ((player_chunk_pos, player_vd_squared), entity, is_client)
 fn prepare_for_vd_check(
     world_aabr_in_chunks: &Aabr<i32>,
+    instances: &Instances,
     max_view_distance: u32,
     entity: Entity,
     presence: &Presence,
@@ -673,11 +676,15 @@ fn prepare_for_vd_check(
         min: player_chunk_pos - player_vd,
         max: player_chunk_pos + player_vd,
     };
-
-    (world_aabr_in_chunks.max.x >= player_aabr_in_chunks.min.x &&
-     world_aabr_in_chunks.min.x <= player_aabr_in_chunks.max.x &&
-     world_aabr_in_chunks.max.y >= player_aabr_in_chunks.min.y &&
-     world_aabr_in_chunks.min.y <= player_aabr_in_chunks.max.y)
+    // Players in an instance are within its slot east of the world instead.
+    let bounds_in_chunks = instances
+        .area_at(player_chunk_pos)
+        .unwrap_or(*world_aabr_in_chunks);
+
+    (bounds_in_chunks.max.x >= player_aabr_in_chunks.min.x &&
+     bounds_in_chunks.min.x <= player_aabr_in_chunks.max.x &&
+     bounds_in_chunks.max.y >= player_aabr_in_chunks.min.y &&
+     bounds_in_chunks.min.y <= player_aabr_in_chunks.max.y)
         // The cast to i32 here is definitely safe thanks to MAX_VD limiting us to fit
         // within i32^2.
         //
@@ -690,6 +697,7 @@ fn prepare_for_vd_check(
 
 pub fn prepare_player_presences<'a, P>(
     world_size: Vec2<u32>,
+    instances: &Instances,
     max_view_distance: u32,
     entities: &Entities<'a>,
     positions: P,
@@ -714,6 +722,7 @@ where
             .filter_map(|(entity, presence, position, client)| {
                 prepare_for_vd_check(
                     &world_aabr_in_chunks,
+                    instances,
                     max_view_distance,
                     entity,
                     presence,
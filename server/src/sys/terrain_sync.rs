@@ -1,6 +1,6 @@
 #[cfg(not(feature = "worldgen"))]
 use crate::test_world::World;
-use crate::{Settings, chunk_serialize::ChunkSendEntry, client::Client};
+use crate::{Settings, chunk_serialize::ChunkSendEntry, client::Client, instance::Instances};
 use common::{
     comp::{Pos, Presence},
     event::EventBus,
@@ -21,6 +21,7 @@ impl<'a> System<'a> for Sys {
     type SystemData = (
         Entities<'a>,
         ReadExpect<'a, Arc<World>>,
+        ReadExpect<'a, Instances>,
         Read<'a, Settings>,
         Read<'a, TerrainChanges>,
         ReadExpect<'a, EventBus<ChunkSendEntry>>,
@@ -38,6 +39,7 @@ impl<'a> System<'a> for Sys {
         (
             entities,
             world,
+            instances,
             server_settings,
             terrain_changes,
             chunk_send_bus,
@@ -53,6 +55,7 @@ impl<'a> System<'a> for Sys {
         let world_size = world.map_size_lg().chunks().as_();
         let (presences_position_entities, _) = super::terrain::prepare_player_presences(
             world_size,
+            &instances,
             max_view_distance,
             &entities,
             &positions,
@@ -0,0 +1,336 @@
+//! A log of the blocks changed by players, kept alongside the persisted
+//! terrain so that admins can find out who changed a block and undo griefing.
+//!
+//! Edits are appended to a file as they happen. Edits older than
+//! [`BlockHistory::RETENTION`] are dropped from memory every
+//! [`BlockHistory::PRUNE_INTERVAL`], and from the file when the log is loaded.
+
+use atomicwrites::{AtomicFile, OverwriteBehavior};
+use authc::Uuid;
+use common::{
+    terrain::{Block, TerrainChunk},
+    vol::RectRasterableVol,
+};
+use hashbrown::HashMap;
+use serde::{Deserialize, Serialize};
+use std::{
+    fs::{self, File, OpenOptions},
+    io::{self, BufWriter, Write},
+    path::{Path, PathBuf},
+    time::{Duration, SystemTime, UNIX_EPOCH},
+};
+use tracing::{debug, error, info};
+use vek::*;
+
+const FILE_NAME: &str = "block_history.dat";
+
+/// A block changed by a player
+#[derive(Clone, Copy, Debug)]
+pub struct BlockEdit {
+    pub pos: Vec3<i32>,
+    pub old: Block,
+    pub new: Block,
+    pub player: Uuid,
+    /// Seconds since the unix epoch
+    pub time: u64,
+}
+
+/// How edits are stored in the log file
+#[derive(Serialize, Deserialize)]
+struct RawEdit {
+    pos: (i32, i32, i32),
+    old: u32,
+    new: u32,
+    player: u128,
+    time: u64,
+}
+
+impl From<&BlockEdit> for RawEdit {
+    fn from(edit: &BlockEdit) -> Self {
+        Self {
+            pos: edit.pos.into_tuple(),
+            old: edit.old.to_u32(),
+            new: edit.new.to_u32(),
+            player: edit.player.as_u128(),
+            time: edit.time,
+        }
+    }
+}
+
+impl From<RawEdit> for BlockEdit {
+    fn from(raw: RawEdit) -> Self {
+        Self {
+            pos: raw.pos.into(),
+            old: Block::from_u32(raw.old).unwrap_or_else(Block::empty),
+            new: Block::from_u32(raw.new).unwrap_or_else(Block::empty),
+            player: Uuid::from_u128(raw.player),
+            time: raw.time,
+        }
+    }
+}
+
+pub struct BlockHistory {
+    path: PathBuf,
+    /// Edits of each chunk, oldest first
+    edits: HashMap<Vec2<i32>, Vec<BlockEdit>>,
+    writer: Option<BufWriter<File>>,
+    /// When expired edits were last dropped, in seconds since the unix epoch
+    last_pruned: u64,
+}
+
+impl BlockHistory {
+    /// How often expired edits are dropped while the server runs
+    pub const PRUNE_INTERVAL: Duration = Duration::from_secs(3600);
+    /// How long edits are kept for
+    pub const RETENTION: Duration = Duration::from_secs(30 * 24 * 3600);
+
+    pub fn load(dir: &Path) -> Self {
+        let path = dir.join(FILE_NAME);
+        let mut history = Self {
+            path,
+            edits: HashMap::default(),
+            writer: None,
+            last_pruned: now(),
+        };
+
+        let bytes = match fs::read(&history.path) {
+            Ok(bytes) => bytes,
+            Err(error) if error.kind() == io::ErrorKind::NotFound => Vec::new(),
+            Err(error) => {
+                error!(
+                    ?error,
+                    "Failed to read block history, it will not be recorded"
+                );
+                return history;
+            },
+        };
+        let mut reader = io::Cursor::new(&bytes);
+        let mut raw_edits = Vec::new();
+        // A partially written edit at the end of the file is dropped
+        while (reader.position() as usize) < bytes.len() {
+            match bincode::deserialize_from::<_, RawEdit>(&mut reader) {
+                Ok(raw) => raw_edits.push(raw),
+                Err(_) => break,
+            }
+        }
+
+        let oldest = now().saturating_sub(Self::RETENTION.as_secs());
+        let total = raw_edits.len();
+        raw_edits.retain(|raw| raw.time >= oldest);
+        let expired = total - raw_edits.len();
+        for raw in raw_edits {
+            let edit = BlockEdit::from(raw);
+            history
+                .edits
+                .entry(chunk_key(edit.pos))
+                .or_default()
+                .push(edit);
+        }
+
+        // Rewrite the log without the expired edits
+        if expired > 0 || (reader.position() as usize) < bytes.len() {
+            info!(?expired, "Removing expired edits from the block history");
+            if let Err(error) = history.rewrite() {
+                error!(?error, "Failed to rewrite block history");
+            }
+        }
+
+        match OpenOptions::new()
+            .create(true)
+            .append(true)
+            .open(&history.path)
+        {
+            Ok(file) => history.writer = Some(BufWriter::new(file)),
+            Err(error) => error!(
+                ?error,
+                "Failed to open block history, it will not be recorded"
+            ),
+        }
+        history
+    }
+
+    fn rewrite(&self) -> io::Result<()> {
+        let mut edits = self.edits.values().flatten().collect::<Vec<_>>();
+        edits.sort_by_key(|edit| edit.time);
+        let mut bytes = Vec::new();
+        for edit in edits {
+            bincode::serialize_into(&mut bytes, &RawEdit::from(edit)).map_err(io::Error::other)?;
+        }
+        AtomicFile::new(&self.path, OverwriteBehavior::AllowOverwrite)
+            .write(|file| file.write_all(&bytes))
+            .map_err(io::Error::other)
+    }
+
+    /// Records a block changed by a player.
+    pub fn record(&mut self, pos: Vec3<i32>, old: Block, new: Block, player: Uuid) {
+        let edit = BlockEdit {
+            pos,
+            old,
+            new,
+            player,
+            time: now(),
+        };
+        if let Some(writer) = &mut self.writer
+            && let Err(error) = bincode::serialize_into(writer, &RawEdit::from(&edit))
+        {
+            error!(?error, "Failed to write block history");
+        }
+        self.edits.entry(chunk_key(pos)).or_default().push(edit);
+    }
+
+    /// Drops expired edits from memory, at most once every
+    /// [`Self::PRUNE_INTERVAL`].
+    pub fn prune(&mut self) {
+        let now = now();
+        if now < self.last_pruned + Self::PRUNE_INTERVAL.as_secs() {
+            return;
+        }
+        self.last_pruned = now;
+        let expired = self.prune_before(now.saturating_sub(Self::RETENTION.as_secs()));
+        if expired > 0 {
+            debug!(?expired, "Removed expired edits from the block history");
+        }
+    }
+
+    /// Drops the edits made before `oldest`, returning how many were dropped.
+    fn prune_before(&mut self, oldest: u64) -> usize {
+        let mut expired = 0;
+        self.edits.retain(|_, edits| {
+            let len = edits.len();
+            edits.retain(|edit| edit.time >= oldest);
+            expired += len - edits.len();
+            !edits.is_empty()
+        });
+        expired
+    }
+
+    /// Writes recorded edits to the disk.
+    pub fn flush(&mut self) {
+        if let Some(writer) = &mut self.writer
+            && let Err(error) = writer.flush()
+        {
+            error!(?error, "Failed to write block history");
+        }
+    }
+
+    /// Edits of the blocks within an area, oldest first.
+    pub fn edits_in(&self, area: Aabb<i32>) -> Vec<BlockEdit> {
+        let (min, max) = (chunk_key(area.min), chunk_key(area.max));
+        let mut edits = (min.y..=max.y)
+            .flat_map(|y| (min.x..=max.x).map(move |x| Vec2::new(x, y)))
+            .filter_map(|key| self.edits.get(&key))
+            .flatten()
+            .filter(|edit| area.contains_point(edit.pos))
+            .copied()
+            .collect::<Vec<_>>();
+        edits.sort_by_key(|edit| edit.time);
+        edits
+    }
+
+    /// Works out which blocks to set to undo the edits a player made within an
+    /// area since a given time.
+    ///
+    /// Blocks that were changed again by someone else afterwards are left
+    /// alone, so rolling back a griefer doesn't undo the repairs made since.
+    pub fn rollback(&self, player: Uuid, area: Aabb<i32>, since: u64) -> Vec<(Vec3<i32>, Block)> {
+        let mut by_pos = HashMap::<_, Vec<_>>::new();
+        for edit in self.edits_in(area) {
+            by_pos.entry(edit.pos).or_default().push(edit);
+        }
+
+        by_pos
+            .into_iter()
+            .filter_map(|(pos, edits)| {
+                edits
+                    .iter()
+                    .rev()
+                    .take_while(|edit| edit.player == player && edit.time >= since)
+                    .last()
+                    .map(|edit| (pos, edit.old))
+            })
+            .collect()
+    }
+}
+
+fn chunk_key(pos: Vec3<i32>) -> Vec2<i32> {
+    pos.xy()
+        .map2(TerrainChunk::RECT_SIZE, |e, sz| e.div_euclid(sz as i32))
+}
+
+/// Seconds since the unix epoch
+pub fn now() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map_or(0, |time| time.as_secs())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use common::terrain::BlockKind;
+
+    #[test]
+    fn rollback_keeps_later_repairs() {
+        let dir = std::env::temp_dir().join(format!("veloren-history-test-{}", std::process::id()));
+        fs::create_dir_all(&dir).unwrap();
+        let mut history = BlockHistory::load(&dir);
+
+        let (griefer, builder) = (Uuid::from_u128(1), Uuid::from_u128(2));
+        let rock = Block::new(BlockKind::Rock, Rgb::zero());
+        let (a, b) = (Vec3::new(1, 1, 1), Vec3::new(40, 1, 1));
+        history.record(a, rock, Block::empty(), griefer);
+        history.record(b, rock, Block::empty(), griefer);
+        history.record(b, Block::empty(), Block::empty(), griefer);
+        history.record(a, Block::empty(), rock, builder);
+
+        let area = Aabb {
+            min: Vec3::zero(),
+            max: Vec3::new(64, 64, 64),
+        };
+        assert_eq!(history.rollback(griefer, area, 0), vec![(b, rock)]);
+
+        // Edits survive a restart
+        history.flush();
+        drop(history);
+        let history = BlockHistory::load(&dir);
+        assert_eq!(history.edits_in(area).len(), 4);
+
+        fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn prune_drops_expired_edits() {
+        let dir =
+            std::env::temp_dir().join(format!("veloren-history-prune-{}", std::process::id()));
+        fs::create_dir_all(&dir).unwrap();
+        let mut history = BlockHistory::load(&dir);
+
+        let player = Uuid::from_u128(1);
+        let (a, b) = (Vec3::new(1, 1, 1), Vec3::new(40, 1, 1));
+        history.record(a, Block::empty(), Block::empty(), player);
+        history.record(b, Block::empty(), Block::empty(), player);
+        // Make the edit at `a` old enough to expire
+        for edit in history.edits.values_mut().flatten() {
+            if edit.pos == a {
+                edit.time = 0;
+            }
+        }
+
+        assert_eq!(history.prune_before(1), 1);
+        assert_eq!(history.edits.len(), 1);
+        let area = Aabb {
+            min: Vec3::zero(),
+            max: Vec3::new(64, 64, 64),
+        };
+        assert_eq!(
+            history
+                .edits_in(area)
+                .iter()
+                .map(|edit| edit.pos)
+                .collect::<Vec<_>>(),
+            vec![b]
+        );
+
+        fs::remove_dir_all(&dir).unwrap();
+    }
+}
@@ -0,0 +1,701 @@
+//! Persistence of the changes made to the terrain, such as blocks placed or
+//! destroyed by players.
+//!
+//! Only the blocks that differ from the generated terrain are kept. They are
+//! stored in region files that each group the chunks of a square of the
+//! world, see [`region`] for the format. Instances are hosted east of the map
+//! and discarded once closed, so only the chunks of the map are persisted.
+
+pub mod history;
+pub mod region;
+
+use authc::Uuid;
+use common::{
+    slowjob::SlowJobPool,
+    terrain::{Block, MapSizeLg, TerrainChunk},
+    vol::{RectRasterableVol, WriteVol},
+};
+use hashbrown::HashMap;
+use history::BlockHistory;
+use region::RegionIndex;
+use schnellru::{Limiter, LruMap};
+use serde::{Deserialize, Serialize, de::DeserializeOwned};
+use std::{
+    any::{Any, type_name},
+    fs, io,
+    path::{Path, PathBuf},
+    time::{Duration, Instant},
+};
+use tracing::{debug, error, info, warn};
+use vek::*;
+
+const MAX_BLOCK_CACHE: usize = 64_000_000;
+/// How long to wait before compacting a region again after it failed
+const COMPACTION_RETRY_DELAY: Duration = Duration::from_secs(30 * 60);
+
+pub struct TerrainPersistence {
+    path: PathBuf,
+    map_size_lg: MapSizeLg,
+    chunks: HashMap<Vec2<i32>, LoadedChunk>,
+    /// A cache of recently unloaded chunks
+    cached_chunks: LruMap<Vec2<i32>, Chunk, ByBlockLimiter>,
+    /// Indices of the region files that have been read so far
+    regions: HashMap<Vec2<i32>, RegionIndex>,
+    /// The region currently being compacted in the background, if any
+    compaction: Option<Compaction>,
+    /// When the compaction of each region last failed
+    failed_compactions: HashMap<Vec2<i32>, Instant>,
+    history: BlockHistory,
+}
+
+/// Wrapper over a [`Chunk`] that keeps track of modifications
+#[derive(Default)]
+pub struct LoadedChunk {
+    chunk: Chunk,
+    modified: bool,
+}
+
+struct Compaction {
+    region: Vec2<i32>,
+    /// Length of the region file when compaction started
+    snapshot_len: u64,
+    result_rx: crossbeam_channel::Receiver<io::Result<PathBuf>>,
+}
+
+impl TerrainPersistence {
+    /// Create a new terrain persistence system using the given data directory,
+    /// for a map of the given size.
+    ///
+    /// If the `VELOREN_TERRAIN` environment variable is set, this will be used
+    /// as the persistence directory instead.
+    pub fn new(data_dir: PathBuf, map_size_lg: MapSizeLg) -> Self {
+        let path = Self::path_in(data_dir);
+
+        std::fs::create_dir_all(&path).expect("Failed to create terrain persistence directory");
+
+        info!("Using {:?} as the terrain persistence path", path);
+
+        let mut this = Self {
+            history: BlockHistory::load(&path),
+            path,
+            map_size_lg,
+            chunks: HashMap::default(),
+            cached_chunks: LruMap::new(ByBlockLimiter::new(MAX_BLOCK_CACHE)),
+            regions: HashMap::default(),
+            compaction: None,
+            failed_compactions: HashMap::default(),
+        };
+        this.remove_compaction_files();
+        this.migrate_chunk_files();
+        this
+    }
+
+    /// The terrain persistence directory within the given data directory.
+    pub fn path_in(mut data_dir: PathBuf) -> PathBuf {
+        std::env::var("VELOREN_TERRAIN")
+            .map(PathBuf::from)
+            .unwrap_or_else(|_| {
+                data_dir.push("terrain");
+                data_dir
+            })
+    }
+
+    /// Apply persistence changes to a newly generated chunk.
+    pub fn apply_changes(&mut self, key: Vec2<i32>, terrain_chunk: &mut TerrainChunk) {
+        if !self.map_size_lg.contains_chunk(key) {
+            return;
+        }
+        let loaded_chunk = self.load_chunk(key);
+
+        let mut resets = Vec::new();
+        for (rpos, new_block) in loaded_chunk.chunk.blocks() {
+            if let Err(e) = terrain_chunk.map(rpos, |block| {
+                if block == new_block {
+                    resets.push(rpos);
+                }
+                new_block
+            }) {
+                warn!(
+                    "Could not set block in chunk {:?} with position {:?} (out of bounds?): {:?}",
+                    key, rpos, e
+                );
+            }
+        }
+
+        // Reset any unchanged blocks, so that only the difference with the generated
+        // terrain gets stored
+        for rpos in resets {
+            loaded_chunk.chunk.reset_block(rpos);
+            loaded_chunk.modified = true;
+        }
+    }
+
+    /// Maintain terrain persistence: writes and prunes the block history,
+    /// finishes the compaction of region files in the background and starts
+    /// compacting regions that need it.
+    pub fn maintain(&mut self, slow_job_pool: &SlowJobPool) {
+        self.history.flush();
+        self.history.prune();
+
+        if let Some(compaction) = &self.compaction {
+            let result = match compaction.result_rx.try_recv() {
+                Ok(result) => result,
+                Err(crossbeam_channel::TryRecvError::Empty) => return,
+                Err(crossbeam_channel::TryRecvError::Disconnected) => {
+                    Err(io::Error::other("compaction job stopped without a result"))
+                },
+            };
+            let Compaction {
+                region,
+                snapshot_len,
+                ..
+            } = self.compaction.take().expect("compaction is in progress");
+            let len = self
+                .regions
+                .get(&region)
+                .map_or(snapshot_len, |index| index.len);
+            match result
+                .and_then(|_| region::finish_compaction(&self.path, region, snapshot_len, len))
+            {
+                Ok(()) => {
+                    debug!(?region, "Compacted terrain persistence region");
+                    // The index is read again from the compacted file when needed
+                    self.regions.remove(&region);
+                    self.failed_compactions.remove(&region);
+                },
+                Err(error) => {
+                    error!(
+                        ?error,
+                        ?region,
+                        "Failed to compact terrain persistence region, retrying in {:?}",
+                        COMPACTION_RETRY_DELAY
+                    );
+                    self.failed_compactions.insert(region, Instant::now());
+                },
+            }
+        }
+
+        if let Some((region, index)) = self.regions.iter().find(|(region, index)| {
+            index.needs_compaction()
+                && self
+                    .failed_compactions
+                    .get(*region)
+                    .is_none_or(|failed| failed.elapsed() > COMPACTION_RETRY_DELAY)
+        }) {
+            let (result_tx, result_rx) = crossbeam_channel::bounded(1);
+            let (dir, region, snapshot_len) = (self.path.clone(), *region, index.len);
+            slow_job_pool.spawn("TERRAIN_COMPACTION", move || {
+                let _ = result_tx.send(region::compact(&dir, region, snapshot_len));
+            });
+            self.compaction = Some(Compaction {
+                region,
+                snapshot_len,
+                result_rx,
+            });
+        }
+    }
+
+    /// Reads the index of a region file if it wasn't read yet.
+    fn region(&mut self, region: Vec2<i32>) -> &mut RegionIndex {
+        let path = region::path_for(&self.path, region);
+        self.regions.entry(region).or_insert_with(|| {
+            let bytes = match fs::read(&path) {
+                Ok(bytes) => bytes,
+                Err(error) if error.kind() == io::ErrorKind::NotFound => Vec::new(),
+                Err(error) => {
+                    error!(?error, ?path, "Failed to read region file");
+                    backup(&path, true);
+                    return RegionIndex::default();
+                },
+            };
+            match RegionIndex::scan(region, &bytes) {
+                Ok(index) => {
+                    if index.corrupt_tail {
+                        error!(
+                            ?path,
+                            "Region file ends with invalid data, which will be overwritten"
+                        );
+                        backup(&path, false);
+                    }
+                    index
+                },
+                Err(error) => {
+                    error!(?error, ?path, "Failed to load region file");
+                    backup(&path, true);
+                    RegionIndex::default()
+                },
+            }
+        })
+    }
+
+    fn load_chunk(&mut self, key: Vec2<i32>) -> &mut LoadedChunk {
+        if !self.chunks.contains_key(&key) {
+            // If the chunk has been recently unloaded and is still cached, dont read it
+            // from disk
+            let chunk = self
+                .cached_chunks
+                .remove(&key)
+                .unwrap_or_else(|| self.read_chunk(key));
+            self.chunks.insert(key, LoadedChunk {
+                chunk,
+                modified: false,
+            });
+        }
+        self.chunks.get_mut(&key).expect("chunk was just loaded")
+    }
+
+    fn read_chunk(&mut self, key: Vec2<i32>) -> Chunk {
+        let region = region::region_of(key);
+        let path = region::path_for(&self.path, region);
+        match self.region(region).read_payload(&path, key) {
+            Ok(Some(payload)) => region::decode_chunk(&payload).unwrap_or_else(|| {
+                error!(?key, ?path, "Failed to decode persisted chunk");
+                Chunk::default()
+            }),
+            Ok(None) => Chunk::default(),
+            Err(error) => {
+                error!(?error, ?key, ?path, "Failed to read persisted chunk");
+                Chunk::default()
+            },
+        }
+    }
+
+    fn write_chunk(&mut self, key: Vec2<i32>, chunk: &Chunk) -> io::Result<()> {
+        let region = region::region_of(key);
+        let path = region::path_for(&self.path, region);
+        self.region(region)
+            .append(&path, key, &region::encode_chunk(chunk))
+    }
+
+    pub fn unload_chunk(&mut self, key: Vec2<i32>) {
+        if let Some(LoadedChunk { chunk, modified }) = self.chunks.remove(&key) {
+            if modified || self.cached_chunks.peek(&key).is_none() {
+                self.cached_chunks.insert(key, chunk.clone());
+            }
+
+            // Prevent any uneccesarry IO when nothing in this chunk has changed
+            if !modified {
+                return;
+            }
+
+            if let Err(error) = self.write_chunk(key, &chunk) {
+                error!(?error, ?key, "Failed to write chunk data");
+            }
+        }
+    }
+
+    pub fn clear_chunk(&mut self, chunk: Vec2<i32>) {
+        if !self.map_size_lg.contains_chunk(chunk) {
+            return;
+        }
+        self.cached_chunks.remove(&chunk);
+        self.chunks.insert(chunk, LoadedChunk {
+            chunk: Chunk::default(),
+            modified: true,
+        });
+    }
+
+    pub fn unload_all(&mut self) {
+        for key in self.chunks.keys().copied().collect::<Vec<_>>() {
+            self.unload_chunk(key);
+        }
+    }
+
+    pub fn set_block(&mut self, pos: Vec3<i32>, block: Block) {
+        let key = pos
+            .xy()
+            .map2(TerrainChunk::RECT_SIZE, |e, sz| e.div_euclid(sz as i32));
+        if !self.map_size_lg.contains_chunk(key) {
+            return;
+        }
+        let loaded_chunk = self.load_chunk(key);
+        let old_block = loaded_chunk
+            .chunk
+            .blocks
+            .insert(pos - key * TerrainChunk::RECT_SIZE.map(|e| e as i32), block);
+        if old_block != Some(block) {
+            loaded_chunk.modified = true;
+        }
+    }
+
+    /// Records a block changed by a player in the block history.
+    pub fn record_edit(&mut self, pos: Vec3<i32>, old: Block, new: Block, player: Uuid) {
+        let key = pos
+            .xy()
+            .map2(TerrainChunk::RECT_SIZE, |e, sz| e.div_euclid(sz as i32));
+        if self.map_size_lg.contains_chunk(key) {
+            self.history.record(pos, old, new, player);
+        }
+    }
+
+    pub fn history(&self) -> &BlockHistory { &self.history }
+
+    /// Removes the persisted changes to the blocks within an area, so that it
+    /// goes back to the generated terrain once the chunks are reloaded.
+    /// Returns the keys of the chunks that were changed.
+    pub fn restore_area(&mut self, area: Aabb<i32>) -> Vec<Vec2<i32>> {
+        let chunk_size = TerrainChunk::RECT_SIZE.map(|e| e as i32);
+        let (min, max) = (
+            area.min.xy().map2(chunk_size, |e, sz| e.div_euclid(sz)),
+            area.max.xy().map2(chunk_size, |e, sz| e.div_euclid(sz)),
+        );
+        let mut changed = Vec::new();
+        for key in (min.y..=max.y).flat_map(|y| (min.x..=max.x).map(move |x| Vec2::new(x, y))) {
+            let chunk_wpos = (key * chunk_size).with_z(0);
+            let loaded_chunk = self.load_chunk(key);
+            let len = loaded_chunk.chunk.len();
+            loaded_chunk
+                .chunk
+                .blocks
+                .retain(|rpos, _| !area.contains_point(chunk_wpos + *rpos));
+            if loaded_chunk.chunk.len() != len {
+                loaded_chunk.modified = true;
+                changed.push(key);
+            }
+            self.unload_chunk(key);
+        }
+        changed
+    }
+
+    /// Removes compaction files left behind when the server stopped during
+    /// compaction.
+    fn remove_compaction_files(&self) {
+        let Ok(entries) = fs::read_dir(&self.path) else {
+            return;
+        };
+        for entry in entries.flatten() {
+            if entry
+                .file_name()
+                .to_string_lossy()
+                .ends_with(".vtr.compact")
+            {
+                let _ = fs::remove_file(entry.path());
+            }
+        }
+    }
+
+    /// Moves the modifications stored in the one file per chunk format used
+    /// before region files into region files. The old files are kept in a
+    /// `legacy_chunks` directory.
+    fn migrate_chunk_files(&mut self) {
+        let Ok(entries) = fs::read_dir(&self.path) else {
+            return;
+        };
+        let chunk_files = entries
+            .flatten()
+            .filter_map(|entry| {
+                let name = entry.file_name().into_string().ok()?;
+                let (x, y) = name
+                    .strip_prefix("chunk_")?
+                    .strip_suffix(".dat")?
+                    .split_once('_')?;
+                Some((Vec2::new(x.parse().ok()?, y.parse().ok()?), entry.path()))
+            })
+            .collect::<Vec<_>>();
+        if chunk_files.is_empty() {
+            return;
+        }
+
+        info!(
+            "Migrating {} terrain persistence chunk files to region files",
+            chunk_files.len()
+        );
+        let legacy_dir = self.path.join("legacy_chunks");
+        if let Err(error) = fs::create_dir_all(&legacy_dir) {
+            error!(?error, "Failed to create directory for legacy chunk files");
+            return;
+        }
+        for (key, path) in chunk_files {
+            let Some(chunk) = fs::read(&path)
+                .ok()
+                .and_then(|bytes| Chunk::deserialize_from(io::Cursor::new(bytes)))
+            else {
+                error!(?path, "Failed to load chunk file, it will not be migrated");
+                continue;
+            };
+            if let Err(error) = self.write_chunk(key, &chunk) {
+                error!(?error, ?path, "Failed to migrate chunk file");
+                continue;
+            }
+            if let Some(name) = path.file_name()
+                && let Err(error) = fs::rename(&path, legacy_dir.join(name))
+            {
+                error!(?error, ?path, "Failed to move migrated chunk file");
+            }
+        }
+    }
+}
+
+impl Drop for TerrainPersistence {
+    fn drop(&mut self) { self.unload_all(); }
+}
+
+/// Keeps a copy of a file that failed to load for an admin to repair, moving
+/// it out of the way if `remove` is set.
+fn backup(path: &Path, remove: bool) {
+    // Find an untaken name for a backup
+    let mut i = 0;
+    let backup_path = loop {
+        let backup_path = path.with_extension(format!("vtr_backup_{}", i));
+        if !backup_path.exists() {
+            break backup_path;
+        }
+        i += 1;
+    };
+
+    error!(
+        "Backing up possibly corrupt (or too new) data to {:?} for you to repair.",
+        backup_path
+    );
+    let result = if remove {
+        fs::rename(path, backup_path)
+    } else {
+        fs::copy(path, backup_path).map(|_| ())
+    };
+    if let Err(err) = result {
+        error!("Failed to back up invalid region file: {:?}", err);
+    }
+}
+
+#[derive(Default, Serialize, Deserialize, Clone)]
+pub struct Chunk {
+    blocks: HashMap<Vec3<i32>, Block>,
+}
+
+impl Chunk {
+    fn deserialize_from<R: io::Read + Clone>(reader: R) -> Option<Self> {
+        version::try_load(reader)
+    }
+
+    fn blocks(&self) -> impl Iterator<Item = (Vec3<i32>, Block)> + '_ {
+        self.blocks.iter().map(|(k, b)| (*k, *b))
+    }
+
+    fn reset_block(&mut self, rpos: Vec3<i32>) { self.blocks.remove(&rpos); }
+
+    /// Get the number of blocks this chunk contains
+    fn len(&self) -> usize { self.blocks.len() }
+}
+
+/// LRU limiter that limits by the number of blocks
+struct ByBlockLimiter {
+    /// Maximum number of blocks that can be contained
+    block_limit: usize,
+    /// Total number of blocks that are currently contained in the LRU
+    counted_blocks: usize,
+}
+
+impl Limiter<Vec2<i32>, Chunk> for ByBlockLimiter {
+    type KeyToInsert<'a> = Vec2<i32>;
+    type LinkType = u32;
+
+    fn is_over_the_limit(&self, _length: usize) -> bool { self.counted_blocks > self.block_limit }
+
+    fn on_insert(
+        &mut self,
+        _length: usize,
+        key: Self::KeyToInsert<'_>,
+        chunk: Chunk,
+    ) -> Option<(Vec2<i32>, Chunk)> {
+        let chunk_size = chunk.len();
+
+        if self.counted_blocks + chunk_size > self.block_limit {
+            None
+        } else {
+            self.counted_blocks += chunk_size;
+            Some((key, chunk))
+        }
+    }
+
+    fn on_replace(
+        &mut self,
+        _length: usize,
+        _old_key: &mut Vec2<i32>,
+        _new_key: Self::KeyToInsert<'_>,
+        old_chunk: &mut Chunk,
+        new_chunk: &mut Chunk,
+    ) -> bool {
+        let old_size = old_chunk.len() as isize; // I assume chunks are never larger than a few thousand blocks anyways, cast should be OK
+        let new_size = new_chunk.len() as isize;
+        let new_total = self
+            .counted_blocks
+            .saturating_add_signed(new_size - old_size);
+
+        if new_total > self.block_limit {
+            false
+        } else {
+            self.counted_blocks = new_total;
+            true
+        }
+    }
+
+    fn on_removed(&mut self, _key: &mut Vec2<i32>, chunk: &mut Chunk) {
+        self.counted_blocks = self.counted_blocks.saturating_sub(chunk.len());
+    }
+
+    fn on_cleared(&mut self) { self.counted_blocks = 0; }
+
+    fn on_grow(&mut self, _new_memory_usage: usize) -> bool { true }
+}
+
+impl ByBlockLimiter {
+    /// Creates a new by-block limit
+    fn new(block_limit: usize) -> Self {
+        Self {
+            block_limit,
+            counted_blocks: 0,
+        }
+    }
+}
+
+/// Formats of the one file per chunk storage used before region files. These
+/// are only loaded to migrate old terrain persistence directories, see
+/// [`region`] for the current format.
+mod version {
+    use super::*;
+
+    type LoadChunkFn<R> = fn(R) -> Result<Chunk, (&'static str, bincode::Error)>;
+    fn loaders<'a, R: io::Read + Clone>() -> &'a [LoadChunkFn<R>] {
+        &[load_raw::<V3, _>, load_raw::<V2, _>, load_raw::<V1, _>]
+    }
+
+    /// Version 3 of the raw chunk format.
+    #[derive(Deserialize)]
+    pub struct V3 {
+        #[serde(deserialize_with = "version::<_, 3>")]
+        pub version: u64,
+        pub blocks: Vec<(u8, u8, i16, u32)>,
+    }
+
+    impl From<V3> for Chunk {
+        fn from(v3: V3) -> Self {
+            Self {
+                blocks: v3
+                    .blocks
+                    .into_iter()
+                    .map(|(x, y, z, b)| {
+                        (
+                            Vec3::new(x as i32, y as i32, z as i32),
+                            Block::from_u32(b).unwrap_or_else(Block::empty),
+                        )
+                    })
+                    .collect(),
+            }
+        }
+    }
+
+    /// Version 2 of the raw chunk format.
+    #[derive(Deserialize)]
+    pub struct V2 {
+        #[serde(deserialize_with = "version::<_, 2>")]
+        pub version: u64,
+        pub blocks: Vec<(u8, u8, i16, Block)>,
+    }
+
+    impl From<V2> for Chunk {
+        fn from(v2: V2) -> Self {
+            Self {
+                blocks: v2
+                    .blocks
+                    .into_iter()
+                    .map(|(x, y, z, b)| (Vec3::new(x as i32, y as i32, z as i32), b))
+                    .collect(),
+            }
+        }
+    }
+
+    /// Version 1 of the raw chunk format.
+    #[derive(Deserialize)]
+    pub struct V1 {
+        pub blocks: HashMap<Vec3<i32>, Block>,
+    }
+
+    impl From<V1> for Chunk {
+        fn from(v1: V1) -> Self { Self { blocks: v1.blocks } }
+    }
+
+    // Utility things
+
+    fn version_magic(n: u16) -> u64 { (n as u64) | (0x3352ACEEA789 << 16) }
+
+    fn version<'de, D: serde::Deserializer<'de>, const V: u16>(de: D) -> Result<u64, D::Error> {
+        u64::deserialize(de).and_then(|x| {
+            if x == version_magic(V) {
+                Ok(x)
+            } else {
+                Err(serde::de::Error::invalid_value(
+                    serde::de::Unexpected::Unsigned(x),
+                    &"incorrect magic/version bytes",
+                ))
+            }
+        })
+    }
+
+    fn load_raw<RawChunk: Any + Into<Chunk> + DeserializeOwned, R: io::Read + Clone>(
+        reader: R,
+    ) -> Result<Chunk, (&'static str, bincode::Error)> {
+        bincode::deserialize_from::<_, RawChunk>(reader)
+            .map(Into::into)
+            .map_err(|e| (type_name::<RawChunk>(), e))
+    }
+
+    pub fn try_load<R: io::Read + Clone>(reader: R) -> Option<Chunk> {
+        loaders()
+            .iter()
+            .find_map(|load_raw| match load_raw(reader.clone()) {
+                Ok(chunk) => Some(chunk),
+                Err((raw_name, e)) => {
+                    debug!(
+                        "Attempt to load chunk with raw format `{}` failed: {:?}",
+                        raw_name, e
+                    );
+                    None
+                },
+            })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use common::{
+        terrain::{BlockKind, SpriteKind, TerrainChunkMeta},
+        vol::ReadVol,
+    };
+
+    #[test]
+    fn instance_chunks_are_not_persisted() {
+        let dir =
+            std::env::temp_dir().join(format!("veloren-persistence-test-{}", std::process::id()));
+        let map_size_lg = MapSizeLg::new(Vec2::new(4, 4)).unwrap();
+        let mut persistence = TerrainPersistence::new(dir.clone(), map_size_lg);
+
+        let player = Uuid::from_u128(1);
+        let rock = Block::new(BlockKind::Rock, Rgb::zero());
+        let chunk_size = TerrainChunk::RECT_SIZE.as_::<i32>();
+        // The instances are east of the map
+        let (map_key, instance_key) = (Vec2::new(1, 1), Vec2::new(20, 1));
+        for key in [map_key, instance_key] {
+            let pos = (key * chunk_size).with_z(1);
+            persistence.set_block(pos, rock);
+            persistence.record_edit(pos, Block::empty(), rock, player);
+        }
+
+        let generated = |key: Vec2<i32>, persistence: &mut TerrainPersistence| {
+            let air = Block::air(SpriteKind::Empty);
+            let mut chunk = TerrainChunk::new(0, air, air, TerrainChunkMeta::void());
+            persistence.apply_changes(key, &mut chunk);
+            *chunk.get(Vec3::new(0, 0, 1)).unwrap()
+        };
+        assert_eq!(generated(map_key, &mut persistence), rock);
+        assert!(generated(instance_key, &mut persistence).is_air());
+
+        let edits = persistence.history().edits_in(Aabb {
+            min: Vec3::zero(),
+            max: (Vec2::new(32, 2) * chunk_size).with_z(64),
+        });
+        assert_eq!(edits.len(), 1);
+
+        fs::remove_dir_all(&dir).unwrap();
+    }
+}
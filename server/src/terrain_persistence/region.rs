@@ -0,0 +1,513 @@
+//! Region files store the persisted modifications of a square of
+//! [`REGION_SIZE`]² chunks.
+//!
+//! # Format
+//!
+//! A region file starts with an 8 byte header ([`REGION_MAGIC`] with the
+//! format version in its lowest bits), followed by any number of records:
+//!
+//! | Size | Content                                                  |
+//! |------|----------------------------------------------------------|
+//! | 1    | x coordinate of the chunk within the region              |
+//! | 1    | y coordinate of the chunk within the region              |
+//! | 4    | length of the payload in bytes (little endian)           |
+//! | 4    | Adler-32 checksum of the position and payload            |
+//! | len  | payload, as written by [`encode_chunk`]                  |
+//!
+//! Records are only ever appended: the last record of a chunk is the one that
+//! counts, and an empty payload means the chunk has no modifications left.
+//! Superseded records are removed by compaction, which rewrites the file with
+//! only the live records.
+//!
+//! A payload only contains the blocks that differ from the generated terrain.
+//! Blocks are sorted by position and stored as runs of identical blocks, each
+//! prefixed by the distance from the end of the previous run, so that
+//! neighbouring modifications (walls, floors, tunnels...) take very little
+//! space.
+
+use super::Chunk;
+use common::{
+    terrain::{Block, TerrainChunk},
+    vol::RectRasterableVol,
+};
+use hashbrown::HashMap;
+use std::{
+    fs::{self, File, OpenOptions},
+    io::{self, Read, Seek, SeekFrom, Write},
+    path::{Path, PathBuf},
+};
+use vek::*;
+
+/// Width of a region, in chunks
+pub const REGION_SIZE: i32 = 16;
+/// Magic bytes at the start of region files, the lowest 16 bits are the
+/// version.
+pub const REGION_MAGIC: u64 = 0x5645_4C52_4547_0000;
+const REGION_VERSION: u16 = 1;
+const HEADER_LEN: u64 = 8;
+const RECORD_HEADER_LEN: u64 = 10;
+/// Version of the payload encoding, stored in the first byte of payloads
+const PAYLOAD_VERSION: u8 = 1;
+const FILE_EXTENSION: &str = "vtr";
+const COMPACTION_EXTENSION: &str = "vtr.compact";
+
+pub fn region_of(key: Vec2<i32>) -> Vec2<i32> { key.map(|e| e.div_euclid(REGION_SIZE)) }
+
+pub fn path_for(dir: &Path, region: Vec2<i32>) -> PathBuf {
+    dir.join(format!("region_{}_{}.{FILE_EXTENSION}", region.x, region.y))
+}
+
+/// Temporary file that a compacted region is written to before replacing the
+/// region file.
+pub fn compaction_path_for(dir: &Path, region: Vec2<i32>) -> PathBuf {
+    dir.join(format!(
+        "region_{}_{}.{COMPACTION_EXTENSION}",
+        region.x, region.y
+    ))
+}
+
+/// Parses the region position out of a region file name.
+pub fn parse_file_name(name: &str) -> Option<Vec2<i32>> {
+    let (x, y) = name
+        .strip_prefix("region_")?
+        .strip_suffix(&format!(".{FILE_EXTENSION}"))?
+        .split_once('_')?;
+    Some(Vec2::new(x.parse().ok()?, y.parse().ok()?))
+}
+
+/// Where the latest record of each chunk of a region file is.
+#[derive(Default)]
+pub struct RegionIndex {
+    /// Offset and length of the payload of the latest record of each chunk.
+    /// Chunks without modifications are left out.
+    pub records: HashMap<Vec2<i32>, (u64, u32)>,
+    /// Bytes taken up by the latest records
+    pub live_bytes: u64,
+    /// Bytes taken up by records that have since been superseded
+    pub stale_bytes: u64,
+    /// Length of the valid part of the file, new records go here
+    pub len: u64,
+    /// Whether the file had trailing data that isn't a valid record, such as a
+    /// record that was only partially written when the server stopped.
+    pub corrupt_tail: bool,
+}
+
+impl RegionIndex {
+    /// Reads the index of a region file out of its contents, checking the
+    /// checksum of every record. Reading stops at the first invalid record.
+    pub fn scan(region: Vec2<i32>, bytes: &[u8]) -> io::Result<Self> {
+        if bytes.is_empty() {
+            return Ok(Self::default());
+        }
+        let magic = bytes
+            .get(..HEADER_LEN as usize)
+            .map(|header| u64::from_le_bytes(header.try_into().unwrap()))
+            .ok_or_else(|| invalid_data("region file too short"))?;
+        if magic & !0xFFFF != REGION_MAGIC {
+            return Err(invalid_data("not a region file"));
+        }
+        if magic & 0xFFFF != REGION_VERSION as u64 {
+            return Err(invalid_data("unsupported region file version"));
+        }
+
+        let mut index = Self {
+            len: HEADER_LEN,
+            ..Self::default()
+        };
+        let mut offset = HEADER_LEN as usize;
+        while offset < bytes.len() {
+            let Some(header) = bytes.get(offset..offset + RECORD_HEADER_LEN as usize) else {
+                index.corrupt_tail = true;
+                break;
+            };
+            let rpos = Vec2::new(header[0] as i32, header[1] as i32);
+            let len = u32::from_le_bytes(header[2..6].try_into().unwrap());
+            let checksum = u32::from_le_bytes(header[6..10].try_into().unwrap());
+            let payload_start = offset + RECORD_HEADER_LEN as usize;
+            let Some(payload) = bytes.get(payload_start..payload_start + len as usize) else {
+                index.corrupt_tail = true;
+                break;
+            };
+            if rpos.reduce_max() >= REGION_SIZE || record_checksum(rpos, payload) != checksum {
+                index.corrupt_tail = true;
+                break;
+            }
+
+            let key = region * REGION_SIZE + rpos;
+            let record_len = RECORD_HEADER_LEN + len as u64;
+            if let Some((_, old_len)) = index.records.remove(&key) {
+                index.live_bytes -= RECORD_HEADER_LEN + old_len as u64;
+                index.stale_bytes += RECORD_HEADER_LEN + old_len as u64;
+            }
+            if len > 0 {
+                index.records.insert(key, (payload_start as u64, len));
+                index.live_bytes += record_len;
+            } else {
+                // Empty records only exist to remove older ones
+                index.stale_bytes += record_len;
+            }
+            offset = payload_start + len as usize;
+            index.len = offset as u64;
+        }
+
+        Ok(index)
+    }
+
+    /// Reads the payload of a chunk's latest record, if it has one.
+    pub fn read_payload(&self, path: &Path, key: Vec2<i32>) -> io::Result<Option<Vec<u8>>> {
+        let Some((offset, len)) = self.records.get(&key) else {
+            return Ok(None);
+        };
+        let mut file = File::open(path)?;
+        file.seek(SeekFrom::Start(*offset))?;
+        let mut payload = vec![0; *len as usize];
+        file.read_exact(&mut payload)?;
+        Ok(Some(payload))
+    }
+
+    /// Appends a record for a chunk to the region file. An empty payload
+    /// removes the chunk's modifications.
+    pub fn append(&mut self, path: &Path, key: Vec2<i32>, payload: &[u8]) -> io::Result<()> {
+        if payload.is_empty() && !self.records.contains_key(&key) {
+            return Ok(());
+        }
+
+        let mut bytes = Vec::new();
+        if self.len == 0 {
+            push_header(&mut bytes);
+        }
+        push_record(&mut bytes, key, payload);
+
+        // Records are written after the last valid one, overwriting anything that
+        // was only partially written
+        let mut file = OpenOptions::new()
+            .create(true)
+            .truncate(false)
+            .write(true)
+            .open(path)?;
+        file.seek(SeekFrom::Start(self.len))?;
+        file.write_all(&bytes)?;
+        let end = self.len + bytes.len() as u64;
+        if self.corrupt_tail {
+            file.set_len(end)?;
+            self.corrupt_tail = false;
+        }
+
+        let payload_start = end - payload.len() as u64;
+        let record_len = RECORD_HEADER_LEN + payload.len() as u64;
+        if let Some((_, old_len)) = self.records.remove(&key) {
+            self.live_bytes -= RECORD_HEADER_LEN + old_len as u64;
+            self.stale_bytes += RECORD_HEADER_LEN + old_len as u64;
+        }
+        if payload.is_empty() {
+            self.stale_bytes += record_len;
+        } else {
+            self.records
+                .insert(key, (payload_start, payload.len() as u32));
+            self.live_bytes += record_len;
+        }
+        self.len = end;
+        Ok(())
+    }
+
+    /// Whether enough of the file is taken up by superseded records for
+    /// compacting it to be worthwhile.
+    pub fn needs_compaction(&self) -> bool {
+        const MIN_STALE_BYTES: u64 = 64 * 1024;
+        self.stale_bytes > MIN_STALE_BYTES && self.stale_bytes > self.live_bytes
+    }
+}
+
+/// Writes the live records among the first `len` bytes of a region file to
+/// its compaction file, returning the path of the compaction file.
+///
+/// This only reads the region file, so it may run in the background while
+/// more records are appended to the region.
+pub fn compact(dir: &Path, region: Vec2<i32>, len: u64) -> io::Result<PathBuf> {
+    let mut bytes = Vec::new();
+    File::open(path_for(dir, region))?
+        .take(len)
+        .read_to_end(&mut bytes)?;
+    let index = RegionIndex::scan(region, &bytes)?;
+
+    let mut records = index.records.iter().collect::<Vec<_>>();
+    // Keep the file deterministic
+    records.sort_by_key(|(key, _)| (key.y, key.x));
+
+    let mut compacted = Vec::new();
+    push_header(&mut compacted);
+    for (key, (offset, len)) in records {
+        push_record(
+            &mut compacted,
+            *key,
+            &bytes[*offset as usize..*offset as usize + *len as usize],
+        );
+    }
+    let path = compaction_path_for(dir, region);
+    fs::write(&path, compacted)?;
+    Ok(path)
+}
+
+/// Replaces a region file with its compacted version, first copying over the
+/// records that were appended to the region file since compaction started,
+/// between `snapshot_len` and `len`.
+pub fn finish_compaction(
+    dir: &Path,
+    region: Vec2<i32>,
+    snapshot_len: u64,
+    len: u64,
+) -> io::Result<()> {
+    let path = path_for(dir, region);
+    let compaction_path = compaction_path_for(dir, region);
+
+    let mut tail = Vec::new();
+    let mut file = File::open(&path)?;
+    file.seek(SeekFrom::Start(snapshot_len))?;
+    file.take(len.saturating_sub(snapshot_len))
+        .read_to_end(&mut tail)?;
+
+    let mut compacted = OpenOptions::new().append(true).open(&compaction_path)?;
+    compacted.write_all(&tail)?;
+    compacted.sync_all()?;
+    fs::rename(compaction_path, path)
+}
+
+/// Serializes the modified blocks of a chunk.
+pub fn encode_chunk(chunk: &Chunk) -> Vec<u8> {
+    if chunk.blocks.is_empty() {
+        return Vec::new();
+    }
+
+    let mut blocks = chunk
+        .blocks
+        .iter()
+        .map(|(pos, block)| (block_index(*pos), block.to_u32()))
+        .collect::<Vec<_>>();
+    blocks.sort_unstable_by_key(|(index, _)| *index);
+
+    let mut bytes = vec![PAYLOAD_VERSION];
+    let mut last_end = 0;
+    let mut blocks = blocks.into_iter().peekable();
+    while let Some((start, block)) = blocks.next() {
+        let mut run = 1;
+        while blocks
+            .next_if(|(index, b)| *index == start + run && *b == block)
+            .is_some()
+        {
+            run += 1;
+        }
+        write_varint(&mut bytes, zigzag(start - last_end));
+        write_varint(&mut bytes, run as u64);
+        bytes.extend_from_slice(&block.to_le_bytes());
+        last_end = start + run;
+    }
+    bytes
+}
+
+/// Deserializes a payload written by [`encode_chunk`].
+pub fn decode_chunk(bytes: &[u8]) -> Option<Chunk> {
+    let mut chunk = Chunk::default();
+    let Some((&version, mut bytes)) = bytes.split_first() else {
+        return Some(chunk);
+    };
+    if version != PAYLOAD_VERSION {
+        return None;
+    }
+
+    let mut last_end = 0;
+    while !bytes.is_empty() {
+        let start = last_end + unzigzag(read_varint(&mut bytes)?);
+        let run = read_varint(&mut bytes)? as i64;
+        let (block, rest) = bytes.split_first_chunk::<4>()?;
+        bytes = rest;
+        let block = Block::from_u32(u32::from_le_bytes(*block)).unwrap_or_else(Block::empty);
+        for index in start..start + run {
+            chunk.blocks.insert(block_pos(index), block);
+        }
+        last_end = start + run;
+    }
+    Some(chunk)
+}
+
+/// Summary of the contents of a region file, used to inspect persisted
+/// terrain offline.
+#[derive(Debug)]
+pub struct RegionReport {
+    pub region: Vec2<i32>,
+    pub file_len: u64,
+    pub chunks: usize,
+    pub blocks: usize,
+    pub live_bytes: u64,
+    pub stale_bytes: u64,
+    /// Chunks whose payload could not be decoded
+    pub invalid_chunks: usize,
+    pub corrupt_tail: bool,
+}
+
+/// Reads every region file in a terrain persistence directory, without
+/// modifying anything.
+pub fn inspect(dir: &Path) -> io::Result<Vec<RegionReport>> {
+    let mut reports = Vec::new();
+    for entry in fs::read_dir(dir)? {
+        let entry = entry?;
+        let Some(region) = entry.file_name().to_str().and_then(parse_file_name) else {
+            continue;
+        };
+        let bytes = fs::read(entry.path())?;
+        let index = RegionIndex::scan(region, &bytes)?;
+
+        let mut blocks = 0;
+        let mut invalid_chunks = 0;
+        for (offset, len) in index.records.values() {
+            match decode_chunk(&bytes[*offset as usize..*offset as usize + *len as usize]) {
+                Some(chunk) => blocks += chunk.len(),
+                None => invalid_chunks += 1,
+            }
+        }
+
+        reports.push(RegionReport {
+            region,
+            file_len: bytes.len() as u64,
+            chunks: index.records.len(),
+            blocks,
+            live_bytes: index.live_bytes,
+            stale_bytes: index.stale_bytes,
+            invalid_chunks,
+            corrupt_tail: index.corrupt_tail,
+        });
+    }
+    reports.sort_by_key(|report| (report.region.y, report.region.x));
+    Ok(reports)
+}
+
+fn push_header(bytes: &mut Vec<u8>) {
+    bytes.extend_from_slice(&(REGION_MAGIC | REGION_VERSION as u64).to_le_bytes());
+}
+
+fn push_record(bytes: &mut Vec<u8>, key: Vec2<i32>, payload: &[u8]) {
+    let rpos = key.map(|e| e.rem_euclid(REGION_SIZE));
+    bytes.extend_from_slice(&[rpos.x as u8, rpos.y as u8]);
+    bytes.extend_from_slice(&(payload.len() as u32).to_le_bytes());
+    bytes.extend_from_slice(&record_checksum(rpos, payload).to_le_bytes());
+    bytes.extend_from_slice(payload);
+}
+
+fn invalid_data(msg: &str) -> io::Error { io::Error::new(io::ErrorKind::InvalidData, msg) }
+
+/// Adler-32 checksum of a record
+fn record_checksum(rpos: Vec2<i32>, payload: &[u8]) -> u32 {
+    const MOD: u32 = 65521;
+    let (a, b) =
+        [rpos.x as u8, rpos.y as u8]
+            .iter()
+            .chain(payload)
+            .fold((1u32, 0u32), |(a, b), byte| {
+                let a = (a + *byte as u32) % MOD;
+                (a, (b + a) % MOD)
+            });
+    (b << 16) | a
+}
+
+fn block_index(pos: Vec3<i32>) -> i64 {
+    let size = TerrainChunk::RECT_SIZE.map(|e| e as i64);
+    pos.z as i64 * size.product() + pos.y as i64 * size.x + pos.x as i64
+}
+
+fn block_pos(index: i64) -> Vec3<i32> {
+    let size = TerrainChunk::RECT_SIZE.map(|e| e as i64);
+    let z = index.div_euclid(size.product());
+    let xy = index.rem_euclid(size.product());
+    Vec3::new(xy % size.x, xy / size.x, z).map(|e| e as i32)
+}
+
+fn zigzag(n: i64) -> u64 { ((n << 1) ^ (n >> 63)) as u64 }
+
+fn unzigzag(n: u64) -> i64 { (n >> 1) as i64 ^ -((n & 1) as i64) }
+
+fn write_varint(bytes: &mut Vec<u8>, mut n: u64) {
+    while n >= 0x80 {
+        bytes.push(n as u8 | 0x80);
+        n >>= 7;
+    }
+    bytes.push(n as u8);
+}
+
+fn read_varint(bytes: &mut &[u8]) -> Option<u64> {
+    let mut n = 0;
+    for shift in (0..64).step_by(7) {
+        let (&byte, rest) = bytes.split_first()?;
+        *bytes = rest;
+        n |= ((byte & 0x7F) as u64) << shift;
+        if byte & 0x80 == 0 {
+            return Some(n);
+        }
+    }
+    None
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use common::terrain::BlockKind;
+
+    fn test_chunk() -> Chunk {
+        let mut chunk = Chunk::default();
+        for x in 0..8 {
+            chunk.blocks.insert(
+                Vec3::new(x, 3, -20),
+                Block::new(BlockKind::Rock, Rgb::zero()),
+            );
+        }
+        chunk.blocks.insert(Vec3::new(31, 31, 900), Block::empty());
+        chunk
+    }
+
+    #[test]
+    fn chunk_roundtrip() {
+        let chunk = test_chunk();
+        let decoded = decode_chunk(&encode_chunk(&chunk)).unwrap();
+        assert_eq!(decoded.blocks, chunk.blocks);
+        assert!(
+            decode_chunk(&encode_chunk(&Chunk::default()))
+                .unwrap()
+                .blocks
+                .is_empty()
+        );
+    }
+
+    #[test]
+    fn region_keeps_latest_record() {
+        let dir = std::env::temp_dir().join(format!("veloren-region-test-{}", std::process::id()));
+        fs::create_dir_all(&dir).unwrap();
+        let region = Vec2::new(-1, 2);
+        let path = path_for(&dir, region);
+        let key = region * REGION_SIZE + Vec2::new(3, 4);
+
+        let mut index = RegionIndex::default();
+        index
+            .append(&path, key, &encode_chunk(&Chunk::default()))
+            .unwrap();
+        index.append(&path, key, &[PAYLOAD_VERSION]).unwrap();
+        index
+            .append(&path, key, &encode_chunk(&test_chunk()))
+            .unwrap();
+
+        // A record that was cut off while being written is ignored
+        let mut bytes = fs::read(&path).unwrap();
+        bytes.extend_from_slice(&[1, 2, 3]);
+        let scanned = RegionIndex::scan(region, &bytes).unwrap();
+        assert!(scanned.corrupt_tail);
+        assert_eq!(scanned.records, index.records);
+        assert!(scanned.stale_bytes > 0);
+
+        let payload = index.read_payload(&path, key).unwrap().unwrap();
+        assert_eq!(decode_chunk(&payload).unwrap().blocks, test_chunk().blocks);
+
+        let compacted = compact(&dir, region, index.len).unwrap();
+        let compacted = RegionIndex::scan(region, &fs::read(compacted).unwrap()).unwrap();
+        assert_eq!(compacted.stale_bytes, 0);
+        assert_eq!(compacted.records.len(), 1);
+
+        fs::remove_dir_all(&dir).unwrap();
+    }
+}
@@ -0,0 +1,473 @@
+//! Exporting traces of slow server ticks.
+//!
+//! When `tick_traces` is set in the settings, every tick taking longer than the
+//! configured threshold is turned into a trace in the OpenTelemetry format.
+//! The root span covers the tick, with a child span per phase of the tick and
+//! the ECS systems and event handlers nested in the phase they started in. The
+//! slow jobs and database batches which finished since the previous tick are
+//! added too, these run on other threads and can start before the tick.
+//!
+//! Traces are exported on a background thread, either appended to a file as
+//! OTLP JSON, one request per line (the format of the collector's
+//! `otlpjsonfile` receiver), or sent to a collector with OTLP over HTTP.
+
+use crate::{
+    Tick,
+    persistence::character_updater::CharacterUpdater,
+    settings::{TickTraceSettings, TraceExport},
+};
+use common::slowjob::SlowJobPool;
+use common_ecs::SysMetrics;
+use crossbeam_channel::{Receiver, Sender, TrySendError, bounded};
+use serde::Serialize;
+use specs::{Join, WorldExt};
+use std::{
+    fs,
+    io::{self, BufRead, BufReader, Write},
+    net::{TcpStream, ToSocketAddrs},
+    path::Path,
+    time::{Duration, Instant, SystemTime, UNIX_EPOCH},
+};
+use tracing::{debug, error, info, warn};
+
+const SERVICE_NAME: &str = "veloren-server";
+/// Number of traces waiting to be exported before new ones are dropped.
+const MAX_QUEUED_TRACES: usize = 16;
+const DEFAULT_OTLP_PORT: u16 = 4318;
+const OTLP_TIMEOUT: Duration = Duration::from_secs(5);
+
+/// Name of a phase of the tick, with its start and end.
+pub type Phase = (&'static str, Instant, Instant);
+
+pub struct TickTracer {
+    slow_tick_threshold: Duration,
+    sample_rate: f32,
+    /// The same moment as a system time and an instant, to convert instants to
+    /// timestamps.
+    reference: (SystemTime, Instant),
+    traces: Sender<String>,
+}
+
+impl TickTracer {
+    /// Start the export thread, fails if the export target is invalid.
+    pub fn new(settings: &TickTraceSettings, data_dir: &Path) -> io::Result<Self> {
+        let exporter = match &settings.export {
+            TraceExport::File(path) => {
+                let path = data_dir.join(path);
+                if let Some(dir) = path.parent() {
+                    fs::create_dir_all(dir)?;
+                }
+                let file = fs::OpenOptions::new()
+                    .create(true)
+                    .append(true)
+                    .open(&path)?;
+                info!("Exporting traces of slow ticks to {}", path.display());
+                Exporter::File(file)
+            },
+            TraceExport::Otlp { endpoint } => {
+                let endpoint = OtlpEndpoint::parse(endpoint)?;
+                info!(
+                    "Exporting traces of slow ticks to http://{}:{}",
+                    endpoint.host, endpoint.port
+                );
+                Exporter::Otlp(endpoint)
+            },
+        };
+
+        let (traces, traces_rx) = bounded(MAX_QUEUED_TRACES);
+        std::thread::Builder::new()
+            .name("tick_trace_export".into())
+            .spawn(move || exporter.run(traces_rx))?;
+
+        Ok(Self {
+            slow_tick_threshold: settings.slow_tick_threshold,
+            sample_rate: settings.sample_rate,
+            reference: (SystemTime::now(), Instant::now()),
+            traces,
+        })
+    }
+
+    /// Trace the tick if it was slow. Has to run before the metrics system,
+    /// which takes the metrics of the slow jobs.
+    pub fn end_tick(&mut self, ecs: &specs::World, phases: &[Phase]) {
+        // Always taken, so that a traced tick only gets its own batches
+        let batches = ecs.read_resource::<CharacterUpdater>().take_batch_timings();
+
+        let (Some((_, tick_start, _)), Some((_, _, tick_end))) = (phases.first(), phases.last())
+        else {
+            return;
+        };
+        if tick_end.duration_since(*tick_start) < self.slow_tick_threshold
+            || rand::random::<f32>() >= self.sample_rate
+        {
+            return;
+        }
+
+        let mut trace = TraceBuilder::new(self.reference);
+        let tick = ecs.read_resource::<Tick>().0;
+        let root = trace.span("tick", None, *tick_start, *tick_end, vec![
+            KeyValue::int("tick", tick),
+            KeyValue::int("entities", ecs.entities().join().count() as u64),
+        ]);
+
+        let phase_spans = phases
+            .iter()
+            .map(|(name, start, end)| {
+                (
+                    trace.span(name, Some(root), *start, *end, Vec::new()),
+                    start,
+                    end,
+                )
+            })
+            .collect::<Vec<_>>();
+        let parent_of = |start: Instant| {
+            phase_spans
+                .iter()
+                .find(|(_, phase_start, phase_end)| (**phase_start..**phase_end).contains(&start))
+                .map_or(root, |(span, _, _)| *span)
+        };
+
+        for (name, timeline) in ecs
+            .read_resource::<SysMetrics>()
+            .stats
+            .lock()
+            .unwrap()
+            .iter()
+        {
+            // Systems which didn't run during this tick keep their last run
+            if let Some((start, end)) = timeline.span()
+                && start >= *tick_start
+            {
+                trace.span(name, Some(parent_of(start)), start, end, Vec::new());
+            }
+        }
+
+        for (name, jobs) in ecs.read_resource::<SlowJobPool>().peek_metrics() {
+            for job in jobs {
+                trace.span(
+                    "slow job",
+                    Some(root),
+                    job.execution_start,
+                    job.execution_end,
+                    vec![
+                        KeyValue::string("job", name.clone()),
+                        KeyValue::int(
+                            "queued_ns",
+                            job.execution_start
+                                .duration_since(job.queue_created)
+                                .as_nanos() as u64,
+                        ),
+                    ],
+                );
+            }
+        }
+
+        for batch in batches {
+            trace.span(
+                "persistence batch",
+                Some(root),
+                batch.start,
+                batch.end,
+                vec![
+                    KeyValue::int("batch_id", batch.batch_id),
+                    KeyValue::int("updates", batch.updates as u64),
+                ],
+            );
+        }
+
+        match serde_json::to_string(&trace.finish()) {
+            Ok(json) => match self.traces.try_send(json) {
+                Ok(()) => {},
+                Err(TrySendError::Full(_)) => {
+                    debug!(?tick, "Trace export is behind, dropping trace")
+                },
+                Err(TrySendError::Disconnected(_)) => {
+                    error!("Trace export thread stopped")
+                },
+            },
+            Err(e) => error!(?e, "Failed to serialize trace"),
+        }
+    }
+}
+
+enum Exporter {
+    File(fs::File),
+    Otlp(OtlpEndpoint),
+}
+
+impl Exporter {
+    fn run(mut self, traces: Receiver<String>) {
+        for trace in traces {
+            let result = match &mut self {
+                Exporter::File(file) => writeln!(file, "{trace}"),
+                Exporter::Otlp(endpoint) => endpoint.send(&trace),
+            };
+            if let Err(e) = result {
+                warn!(?e, "Failed to export trace of a slow tick");
+            }
+        }
+    }
+}
+
+/// Collector accepting OTLP over HTTP, only plain HTTP is supported.
+#[derive(Debug, PartialEq)]
+struct OtlpEndpoint {
+    host: String,
+    port: u16,
+    /// Path of the traces endpoint, `/v1/traces` below the configured path.
+    path: String,
+}
+
+impl OtlpEndpoint {
+    fn parse(endpoint: &str) -> io::Result<Self> {
+        let invalid = |msg| io::Error::new(io::ErrorKind::InvalidInput, msg);
+        let rest = endpoint
+            .strip_prefix("http://")
+            .ok_or_else(|| invalid("OTLP endpoint has to start with http://"))?;
+        let (authority, path) = rest.split_once('/').unwrap_or((rest, ""));
+        let (host, port) = match authority.rsplit_once(':') {
+            Some((host, port)) => (
+                host,
+                port.parse()
+                    .map_err(|_| invalid("invalid port in OTLP endpoint"))?,
+            ),
+            None => (authority, DEFAULT_OTLP_PORT),
+        };
+        if host.is_empty() {
+            return Err(invalid("OTLP endpoint has no host"));
+        }
+        let path = path.trim_end_matches('/');
+        Ok(Self {
+            host: host.to_owned(),
+            port,
+            path: if path.is_empty() {
+                "/v1/traces".to_owned()
+            } else {
+                format!("/{path}/v1/traces")
+            },
+        })
+    }
+
+    fn send(&self, trace: &str) -> io::Result<()> {
+        let addr = (self.host.as_str(), self.port)
+            .to_socket_addrs()?
+            .next()
+            .ok_or_else(|| io::Error::new(io::ErrorKind::NotFound, "OTLP host not found"))?;
+        let mut stream = TcpStream::connect_timeout(&addr, OTLP_TIMEOUT)?;
+        stream.set_read_timeout(Some(OTLP_TIMEOUT))?;
+        stream.set_write_timeout(Some(OTLP_TIMEOUT))?;
+        write!(
+            stream,
+            "POST {} HTTP/1.1\r\nHost: {}:{}\r\nContent-Type: application/json\r\nContent-Length: \
+             {}\r\nConnection: close\r\n\r\n{trace}",
+            self.path,
+            self.host,
+            self.port,
+            trace.len(),
+        )?;
+        let mut status = String::new();
+        BufReader::new(stream).read_line(&mut status)?;
+        match status.split_whitespace().nth(1) {
+            Some(code) if code.starts_with('2') => Ok(()),
+            _ => Err(io::Error::other(format!(
+                "collector responded with {}",
+                status.trim_end()
+            ))),
+        }
+    }
+}
+
+/// Collects the spans of one trace.
+struct TraceBuilder {
+    trace_id: String,
+    reference: (SystemTime, Instant),
+    spans: Vec<Span>,
+}
+
+impl TraceBuilder {
+    fn new(reference: (SystemTime, Instant)) -> Self {
+        Self {
+            trace_id: format!("{:032x}", rand::random::<u128>()),
+            reference,
+            spans: Vec::new(),
+        }
+    }
+
+    /// Add a span and return its id, to be used as the parent of other spans.
+    fn span(
+        &mut self,
+        name: &str,
+        parent: Option<u64>,
+        start: Instant,
+        end: Instant,
+        attributes: Vec<KeyValue>,
+    ) -> u64 {
+        let id = rand::random::<u64>();
+        self.spans.push(Span {
+            trace_id: self.trace_id.clone(),
+            span_id: format!("{id:016x}"),
+            parent_span_id: parent.map(|parent| format!("{parent:016x}")),
+            name: name.to_owned(),
+            kind: SPAN_KIND_INTERNAL,
+            start_time_unix_nano: self.unix_nanos(start).to_string(),
+            end_time_unix_nano: self.unix_nanos(end).to_string(),
+            attributes,
+        });
+        id
+    }
+
+    fn unix_nanos(&self, instant: Instant) -> u128 {
+        let (system_time, reference) = self.reference;
+        let time = match instant.checked_duration_since(reference) {
+            Some(since) => system_time + since,
+            None => system_time - reference.duration_since(instant),
+        };
+        time.duration_since(UNIX_EPOCH)
+            .unwrap_or_default()
+            .as_nanos()
+    }
+
+    fn finish(self) -> ExportTraceServiceRequest {
+        ExportTraceServiceRequest {
+            resource_spans: vec![ResourceSpans {
+                resource: Resource {
+                    attributes: vec![
+                        KeyValue::string("service.name", SERVICE_NAME.to_owned()),
+                        KeyValue::string("service.version", common::util::GIT_HASH.to_string()),
+                    ],
+                },
+                scope_spans: vec![ScopeSpans {
+                    scope: Scope { name: SERVICE_NAME },
+                    spans: self.spans,
+                }],
+            }],
+        }
+    }
+}
+
+// The OTLP JSON encoding, see
+// https://opentelemetry.io/docs/specs/otlp/#json-protobuf-encoding.
+// 64 bit integers are encoded as strings.
+
+const SPAN_KIND_INTERNAL: u8 = 1;
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+struct ExportTraceServiceRequest {
+    resource_spans: Vec<ResourceSpans>,
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+struct ResourceSpans {
+    resource: Resource,
+    scope_spans: Vec<ScopeSpans>,
+}
+
+#[derive(Serialize)]
+struct Resource {
+    attributes: Vec<KeyValue>,
+}
+
+#[derive(Serialize)]
+struct ScopeSpans {
+    scope: Scope,
+    spans: Vec<Span>,
+}
+
+#[derive(Serialize)]
+struct Scope {
+    name: &'static str,
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+struct Span {
+    trace_id: String,
+    span_id: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    parent_span_id: Option<String>,
+    name: String,
+    kind: u8,
+    start_time_unix_nano: String,
+    end_time_unix_nano: String,
+    attributes: Vec<KeyValue>,
+}
+
+#[derive(Serialize)]
+struct KeyValue {
+    key: &'static str,
+    value: AnyValue,
+}
+
+impl KeyValue {
+    fn string(key: &'static str, value: String) -> Self {
+        Self {
+            key,
+            value: AnyValue::StringValue(value),
+        }
+    }
+
+    fn int(key: &'static str, value: u64) -> Self {
+        Self {
+            key,
+            value: AnyValue::IntValue(value.to_string()),
+        }
+    }
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+enum AnyValue {
+    StringValue(String),
+    IntValue(String),
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn otlp_json() {
+        let now = Instant::now();
+        let mut trace = TraceBuilder::new((UNIX_EPOCH + Duration::from_secs(10), now));
+        let root = trace.span("tick", None, now, now + Duration::from_millis(60), vec![
+            KeyValue::int("tick", 42),
+        ]);
+        trace.span(
+            "agent",
+            Some(root),
+            now,
+            now + Duration::from_millis(5),
+            Vec::new(),
+        );
+
+        let json = serde_json::to_value(trace.finish()).unwrap();
+        let spans = &json["resourceSpans"][0]["scopeSpans"][0]["spans"];
+        assert_eq!(spans[0]["name"], "tick");
+        assert_eq!(spans[0]["startTimeUnixNano"], "10000000000");
+        assert_eq!(spans[0]["endTimeUnixNano"], "10060000000");
+        assert_eq!(spans[0]["attributes"][0]["value"]["intValue"], "42");
+        assert!(spans[0].get("parentSpanId").is_none());
+        assert_eq!(spans[1]["parentSpanId"], spans[0]["spanId"]);
+        assert_eq!(spans[1]["traceId"], spans[0]["traceId"]);
+        assert_eq!(spans[1]["traceId"].as_str().unwrap().len(), 32);
+    }
+
+    #[test]
+    fn otlp_endpoint() {
+        assert_eq!(
+            OtlpEndpoint::parse("http://localhost:4318").unwrap(),
+            OtlpEndpoint {
+                host: "localhost".to_owned(),
+                port: 4318,
+                path: "/v1/traces".to_owned(),
+            }
+        );
+        assert_eq!(
+            OtlpEndpoint::parse("http://collector/otlp/").unwrap().path,
+            "/otlp/v1/traces"
+        );
+        assert!(OtlpEndpoint::parse("https://collector").is_err());
+    }
+}
@@ -18,7 +18,9 @@ pub fn add_server_systems(dispatch_builder: &mut DispatcherBuilder) {
 #[cfg(feature = "worldgen")]
 pub fn init(state: &mut State) {
     use crate::weather::sim::LightningCells;
+    use common::weather::WeatherForecast;
 
     state.ecs_mut().insert(None::<WeatherJob>);
     state.ecs_mut().insert(LightningCells::default());
+    state.ecs_mut().insert(WeatherForecast::default());
 }
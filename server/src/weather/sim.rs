@@ -1,9 +1,11 @@
 use common::{
+    calendar::Calendar,
     grid::Grid,
     resources::TimeOfDay,
-    weather::{CELL_SIZE, CHUNKS_PER_CELL, Weather, WeatherGrid},
+    weather::{CELL_SIZE, CHUNKS_PER_CELL, Weather, WeatherForecast, WeatherGrid},
 };
 use noise::{NoiseFn, Perlin, SuperSimplex, Turbulence};
+use rand::{Rng, SeedableRng, rngs::SmallRng};
 use vek::*;
 use world::World;
 
@@ -11,6 +13,12 @@ use crate::weather::WEATHER_DT;
 
 fn cell_to_wpos_center(p: Vec2<i32>) -> Vec2<i32> { p * CELL_SIZE as i32 + CELL_SIZE as i32 / 2 }
 
+/// Degrees C per unit of worldgen temperature, which is roughly between -1 and
+/// 1.
+const WORLDGEN_TEMP_SCALE: f32 = 25.0;
+
+fn worldgen_temp_to_celsius(temp: f32) -> f32 { temp * WORLDGEN_TEMP_SCALE + 12.0 }
+
 #[derive(Clone)]
 struct WeatherZone {
     weather: Weather,
@@ -18,14 +26,46 @@ struct WeatherZone {
     time_to_live: f32,
 }
 
+#[derive(Clone)]
 struct CellConsts {
     humidity: f32,
+    /// Average temperature of the cell's terrain in degrees C
+    temperature: f32,
+}
+
+/// A thunderstorm drifting across the weather grid with the wind.
+#[derive(Clone)]
+struct StormCell {
+    /// Position in weather cell units
+    pos: Vec2<f32>,
+    /// Velocity in weather cells per second
+    vel: Vec2<f32>,
+    /// Radius in weather cell units
+    radius: f32,
+    /// Intensity at the centre of the storm, between 0 and 1
+    strength: f32,
+    /// Time, in seconds, until the storm dies out
+    time_to_live: f32,
 }
 
+impl StormCell {
+    /// Storms fade out over the last minute of their life
+    const FADE_TIME: f32 = 60.0;
+
+    fn intensity_at(&self, pos: Vec2<f32>) -> f32 {
+        (1.0 - pos.distance(self.pos) / self.radius).max(0.0)
+            * self.strength
+            * (self.time_to_live / Self::FADE_TIME).min(1.0)
+    }
+}
+
+#[derive(Clone)]
 pub struct WeatherSim {
     size: Vec2<u32>,
     consts: Grid<CellConsts>,
     zones: Grid<Option<WeatherZone>>,
+    storms: Vec<StormCell>,
+    rng: SmallRng,
 }
 
 /// A list of weather cells where lightning has a chance to strike.
@@ -44,24 +84,30 @@ impl WeatherSim {
                     .map(|i| Vec2::new(i % size.x, i / size.x))
                     .map(|p| {
                         let mut humid_sum = 0.0;
+                        let mut temp_sum = 0.0;
 
                         for y in 0..CHUNKS_PER_CELL {
                             for x in 0..CHUNKS_PER_CELL {
                                 let chunk_pos = p * CHUNKS_PER_CELL + Vec2::new(x, y);
                                 if let Some(chunk) = world.sim().get(chunk_pos.as_()) {
-                                    let env = chunk.get_environment();
+                                    let env = chunk.get_environment(&world.sim().config);
                                     humid_sum += env.humid;
+                                    temp_sum += env.temp;
                                 }
                             }
                         }
                         let average_humid = humid_sum / (CHUNKS_PER_CELL * CHUNKS_PER_CELL) as f32;
+                        let average_temp = temp_sum / (CHUNKS_PER_CELL * CHUNKS_PER_CELL) as f32;
                         CellConsts {
                             humidity: average_humid.powf(0.2).min(1.0),
+                            temperature: worldgen_temp_to_celsius(average_temp),
                         }
                     })
                     .collect::<Vec<_>>(),
             ),
             zones: Grid::new(size.as_(), None),
+            storms: Vec::new(),
+            rng: SmallRng::seed_from_u64(world.sim().seed as u64),
         }
     }
 
@@ -89,8 +135,18 @@ impl WeatherSim {
     }
 
     // Time step is cell size / maximum wind speed.
-    pub fn tick(&mut self, time_of_day: TimeOfDay, out: &mut WeatherGrid) -> LightningCells {
+    pub fn tick(
+        &mut self,
+        time_of_day: TimeOfDay,
+        calendar: &Calendar,
+        out: &mut WeatherGrid,
+    ) -> LightningCells {
         let time = time_of_day.0;
+        // Colder seasons bring lower pressure, and so more precipitation
+        let season_pressure = calendar.temperature_offset() * 0.3;
+        let season_temp = calendar.temperature_offset() * WORLDGEN_TEMP_SCALE;
+        // 1 at midnight, -1 at noon
+        let night = time_of_day.get_sun_dir().z;
 
         let base_nz: Turbulence<Turbulence<SuperSimplex, Perlin>, Perlin> = Turbulence::new(
             Turbulence::new(SuperSimplex::new(0))
@@ -101,53 +157,89 @@ impl WeatherSim {
         .set_power(0.2);
 
         let rain_nz = SuperSimplex::new(0);
+        let fog_nz = SuperSimplex::new(1);
+
+        for (point, cell) in out.iter_mut() {
+            let consts = &self.consts[point];
+            let wpos = cell_to_wpos_center(point);
+
+            let pos = wpos.as_::<f64>() + time * 0.1;
+
+            let space_scale = 7_500.0;
+            let time_scale = 100_000.0;
+            let spos = (pos / space_scale).with_z(time / time_scale);
+
+            let avg_scale = 30_000.0;
+            let avg_delay = 250_000.0;
+            let pressure = ((base_nz.get((pos / avg_scale).with_z(time / avg_delay).into_array())
+                + base_nz.get(
+                    (pos / (avg_scale * 0.25))
+                        .with_z(time / (avg_delay * 0.25))
+                        .into_array(),
+                ) * 0.5)
+                * 0.5
+                + 1.0)
+                .clamped(0.0, 1.0) as f32
+                + 0.55
+                + season_pressure
+                - consts.humidity * 0.6;
+
+            const RAIN_CLOUD_THRESHOLD: f32 = 0.25;
+            cell.cloud = (1.0 - pressure).max(0.0).powi(2) * 4.0;
+            // Split into rain and snow once storms have been added
+            cell.rain = ((1.0 - pressure - RAIN_CLOUD_THRESHOLD).max(0.0) * consts.humidity * 2.5)
+                .powf(0.75);
+            cell.wind = Vec2::new(
+                rain_nz.get(spos.into_array()).powi(3) as f32,
+                rain_nz.get((spos + 1.0).into_array()).powi(3) as f32,
+            ) * 200.0
+                * (1.0 - pressure);
+            // Nights are colder, and cloud cover keeps the sun off during the day
+            cell.temperature =
+                consts.temperature + season_temp - night * 4.0 - cell.cloud.min(1.0) * 2.0;
+            // Fog settles in still, humid air, mostly at night and in the early morning
+            cell.fog = ((consts.humidity - 0.7) * 3.0).clamp(0.0, 1.0)
+                * (1.0 - cell.wind.magnitude() / 20.0).max(0.0)
+                * (night + 0.5).clamp(0.2, 1.0)
+                * (fog_nz.get((spos * 4.0).into_array()) as f32 + 0.5).clamp(0.0, 1.0);
+        }
+
+        self.tick_storms(out);
 
         let mut lightning_cells = Vec::new();
         for (point, cell) in out.iter_mut() {
-            if let Some(zone) = &mut self.zones[point] {
-                *cell = zone.weather;
+            let storm = self
+                .storms
+                .iter()
+                .map(|storm| storm.intensity_at(point.as_()))
+                .fold(0.0, f32::max);
+            if storm > 0.0 {
+                cell.cloud = cell.cloud.max(storm);
+                cell.rain += storm * 0.6;
+                cell.wind *= 1.0 + storm;
+                cell.fog *= 1.0 - storm;
+            }
+            (cell.rain, cell.snow) =
+                Weather::split_precipitation(cell.rain.min(1.0), cell.temperature);
+
+            // Zones override storms, so lightning only strikes in zones that are stormy
+            // themselves
+            let lightning = if let Some(zone) = &mut self.zones[point] {
+                // Zones set the weather, but not the temperature
+                *cell = Weather {
+                    temperature: cell.temperature,
+                    ..zone.weather
+                };
                 zone.time_to_live -= WEATHER_DT;
                 if zone.time_to_live <= 0.0 {
                     self.zones[point] = None;
                 }
+                cell.rain > 0.2 && cell.cloud > 0.15
             } else {
-                let wpos = cell_to_wpos_center(point);
-
-                let pos = wpos.as_::<f64>() + time * 0.1;
-
-                let space_scale = 7_500.0;
-                let time_scale = 100_000.0;
-                let spos = (pos / space_scale).with_z(time / time_scale);
-
-                let avg_scale = 30_000.0;
-                let avg_delay = 250_000.0;
-                let pressure = ((base_nz
-                    .get((pos / avg_scale).with_z(time / avg_delay).into_array())
-                    + base_nz.get(
-                        (pos / (avg_scale * 0.25))
-                            .with_z(time / (avg_delay * 0.25))
-                            .into_array(),
-                    ) * 0.5)
-                    * 0.5
-                    + 1.0)
-                    .clamped(0.0, 1.0) as f32
-                    + 0.55
-                    - self.consts[point].humidity * 0.6;
-
-                const RAIN_CLOUD_THRESHOLD: f32 = 0.25;
-                cell.cloud = (1.0 - pressure).max(0.0).powi(2) * 4.0;
-                cell.rain = ((1.0 - pressure - RAIN_CLOUD_THRESHOLD).max(0.0)
-                    * self.consts[point].humidity
-                    * 2.5)
-                    .powf(0.75);
-                cell.wind = Vec2::new(
-                    rain_nz.get(spos.into_array()).powi(3) as f32,
-                    rain_nz.get((spos + 1.0).into_array()).powi(3) as f32,
-                ) * 200.0
-                    * (1.0 - pressure);
-            }
-
-            if cell.rain > 0.2 && cell.cloud > 0.15 {
+                // Lightning strikes near the core of storms, but not in snowstorms
+                storm > 0.3 && cell.temperature > Weather::FREEZING_POINT
+            };
+            if lightning {
                 lightning_cells.push(point);
             }
         }
@@ -156,5 +248,133 @@ impl WeatherSim {
         }
     }
 
+    /// Moves storms along with the wind, lets old ones die out and spawns new
+    /// ones over warm, wet cells.
+    fn tick_storms(&mut self, grid: &WeatherGrid) {
+        const SPAWN_CHANCE: f64 = 0.05;
+        const MIN_SPAWN_TEMPERATURE: f32 = 15.0;
+
+        for storm in self.storms.iter_mut() {
+            let wind = grid.get(storm.pos.map(|e| e.round().max(0.0) as u32)).wind;
+            storm.vel = Lerp::lerp(storm.vel, wind / CELL_SIZE as f32, 0.1);
+            storm.pos += storm.vel * WEATHER_DT;
+            storm.time_to_live -= WEATHER_DT;
+        }
+        let size = self.size.as_::<f32>();
+        self.storms.retain(|storm| {
+            storm.time_to_live > 0.0
+                && storm.pos.x > -storm.radius
+                && storm.pos.y > -storm.radius
+                && storm.pos.x < size.x + storm.radius
+                && storm.pos.y < size.y + storm.radius
+        });
+
+        let max_storms = (self.size.product() / 128).max(1) as usize;
+        for _ in 0..(self.size.product() / 64).max(1) {
+            if self.storms.len() >= max_storms {
+                break;
+            }
+            let point = self.size.map(|e| self.rng.gen_range(0..e));
+            let cell = grid.get(point);
+            if cell.rain > 0.3
+                && cell.temperature > MIN_SPAWN_TEMPERATURE
+                && self.rng.gen_bool(SPAWN_CHANCE)
+            {
+                self.storms.push(StormCell {
+                    pos: point.as_(),
+                    vel: cell.wind / CELL_SIZE as f32,
+                    radius: self.rng.gen_range(1.0..3.0),
+                    strength: self.rng.gen_range(0.5..1.0),
+                    time_to_live: self.rng.gen_range(600.0..1800.0),
+                });
+            }
+        }
+    }
+
+    /// Predicts the weather for the next few in-game hours by running a copy
+    /// of the simulation ahead of time, ticking it on the same schedule as the
+    /// live weather. The copy draws storms from the same random state, so the
+    /// forecast only turns out wrong when weather zones are added, or when the
+    /// live weather isn't ticked exactly every `time_per_tick`, e.g. because
+    /// the server lags or time is sped up.
+    ///
+    /// `time_per_tick` is how many in-game seconds pass between two ticks.
+    pub fn forecast(
+        &self,
+        time_of_day: TimeOfDay,
+        calendar: &Calendar,
+        time_per_tick: f64,
+    ) -> WeatherForecast {
+        // Avoid ticking forever when time is slowed down or frozen
+        let time_per_tick = time_per_tick.max(60.0);
+        let mut sim = self.clone();
+        let mut grid = WeatherGrid::new(self.size);
+        let mut time = time_of_day.0 + time_per_tick;
+        let steps = (1..=WeatherForecast::STEPS)
+            .map(|step| {
+                // The weather at each step is that of the last tick before it
+                let step_time = time_of_day.0 + step as f64 * WeatherForecast::STEP;
+                while time <= step_time {
+                    sim.tick(TimeOfDay(time), calendar, &mut grid);
+                    time += time_per_tick;
+                }
+                grid.clone()
+            })
+            .collect();
+
+        WeatherForecast {
+            time_of_day: time_of_day.0,
+            steps,
+        }
+    }
+
     pub fn size(&self) -> Vec2<u32> { self.size }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    impl WeatherSim {
+        /// A warm, humid world where storms spawn easily
+        fn humid(size: Vec2<u32>) -> Self {
+            Self {
+                size,
+                consts: Grid::new(size.as_(), CellConsts {
+                    humidity: 1.0,
+                    temperature: 25.0,
+                }),
+                zones: Grid::new(size.as_(), None),
+                storms: Vec::new(),
+                rng: SmallRng::seed_from_u64(0),
+            }
+        }
+    }
+
+    #[test]
+    fn forecast_matches_simulation() {
+        let calendar = Calendar::default();
+        let time_per_tick = 150.0;
+        let start = TimeOfDay(40_000.0);
+        let mut sim = WeatherSim::humid(Vec2::new(16, 16));
+        let mut grid = WeatherGrid::new(sim.size());
+        sim.tick(start, &calendar, &mut grid);
+
+        let forecast = sim.forecast(start, &calendar, time_per_tick);
+        assert_eq!(forecast.steps.len(), WeatherForecast::STEPS);
+
+        let mut time = start.0 + time_per_tick;
+        for (step, predicted) in forecast.steps.iter().enumerate() {
+            let step_time = start.0 + (step + 1) as f64 * WeatherForecast::STEP;
+            while time <= step_time {
+                sim.tick(TimeOfDay(time), &calendar, &mut grid);
+                time += time_per_tick;
+            }
+            assert!(
+                grid.iter().zip(predicted.iter()).all(|(a, b)| a == b),
+                "Forecast for hour {} differs from the simulated weather",
+                step + 1
+            );
+        }
+    }
+}
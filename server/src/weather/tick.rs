@@ -1,10 +1,12 @@
 use common::{
+    calendar::Calendar,
     comp,
     event::EventBus,
     outcome::Outcome,
-    resources::{DeltaTime, ProgramTime, TimeOfDay},
+    resources::{DeltaTime, ProgramTime, TimeOfDay, TimeScale},
+    shared_server_config::ServerConstants,
     slowjob::{SlowJob, SlowJobPool},
-    weather::{SharedWeatherGrid, Weather, WeatherGrid},
+    weather::{SharedWeatherGrid, Weather, WeatherForecast, WeatherGrid},
 };
 use common_ecs::{Origin, Phase, System};
 use common_net::msg::ServerGeneral;
@@ -28,10 +30,17 @@ enum WeatherJobState {
     None,
 }
 
+type WeatherJobResult = (
+    WeatherGrid,
+    LightningCells,
+    WeatherSim,
+    Option<WeatherForecast>,
+);
+
 pub struct WeatherJob {
     last_update: ProgramTime,
-    weather_tx: crossbeam_channel::Sender<(WeatherGrid, LightningCells, WeatherSim)>,
-    weather_rx: crossbeam_channel::Receiver<(WeatherGrid, LightningCells, WeatherSim)>,
+    weather_tx: crossbeam_channel::Sender<WeatherJobResult>,
+    weather_rx: crossbeam_channel::Receiver<WeatherJobResult>,
     state: WeatherJobState,
     qeued_zones: Vec<(Weather, Vec2<f32>, f32, f32)>,
 }
@@ -49,11 +58,15 @@ impl<'a> System<'a> for Sys {
     type SystemData = (
         Entities<'a>,
         Read<'a, TimeOfDay>,
+        Read<'a, Calendar>,
+        Read<'a, TimeScale>,
+        ReadExpect<'a, ServerConstants>,
         Read<'a, ProgramTime>,
         Read<'a, Tick>,
         Read<'a, DeltaTime>,
         Write<'a, LightningCells>,
         Write<'a, Option<WeatherJob>>,
+        Write<'a, WeatherForecast>,
         WriteExpect<'a, WeatherGrid>,
         WriteExpect<'a, SlowJobPool>,
         Read<'a, EventBus<Outcome>>,
@@ -71,11 +84,15 @@ impl<'a> System<'a> for Sys {
         (
             entities,
             game_time,
+            calendar,
+            time_scale,
+            server_constants,
             program_time,
             tick,
             delta_time,
             mut lightning_cells,
             mut weather_job,
+            mut forecast,
             mut grid,
             slow_job_pool,
             outcomes,
@@ -93,7 +110,7 @@ impl<'a> System<'a> for Sys {
                 let weather_size = world.sim().get_size() / common::weather::CHUNKS_PER_CELL;
                 let mut sim = WeatherSim::new(weather_size, &world);
                 *grid = WeatherGrid::new(sim.size());
-                *lightning_cells = sim.tick(*game_time, &mut grid);
+                *lightning_cells = sim.tick(*game_time, &calendar, &mut grid);
 
                 *weather_job = Some(WeatherJob {
                     last_update: *program_time,
@@ -109,10 +126,14 @@ impl<'a> System<'a> for Sys {
 
         if let Some(weather_job) = to_update {
             if matches!(weather_job.state, WeatherJobState::Working(_))
-                && let Ok((new_grid, new_lightning_cells, sim)) = weather_job.weather_rx.try_recv()
+                && let Ok((new_grid, new_lightning_cells, sim, new_forecast)) =
+                    weather_job.weather_rx.try_recv()
             {
                 *grid = new_grid;
                 *lightning_cells = new_lightning_cells;
+                if let Some(new_forecast) = new_forecast {
+                    *forecast = new_forecast;
+                }
                 let mut lazy_msg = None;
                 for client in clients.join() {
                     if lazy_msg.is_none() {
@@ -135,13 +156,21 @@ impl<'a> System<'a> for Sys {
 
                 let weather_tx = weather_job.weather_tx.clone();
                 let game_time = *game_time;
+                let calendar = calendar.clone();
+                // New zones change the weather to come, so the forecast has to be remade
+                let forecast_stale =
+                    forecast.is_stale(game_time.0) || !weather_job.qeued_zones.is_empty();
+                let time_per_tick =
+                    WEATHER_DT as f64 * server_constants.day_cycle_coefficient * time_scale.0;
                 for (weather, pos, radius, time) in weather_job.qeued_zones.drain(..) {
                     sim.add_zone(weather, pos, radius, time)
                 }
                 let job = slow_job_pool.spawn("WEATHER", move || {
                     let mut grid = WeatherGrid::new(sim.size());
-                    let lightning_cells = sim.tick(game_time, &mut grid);
-                    let _ = weather_tx.send((grid, lightning_cells, sim));
+                    let lightning_cells = sim.tick(game_time, &calendar, &mut grid);
+                    let forecast =
+                        forecast_stale.then(|| sim.forecast(game_time, &calendar, time_per_tick));
+                    let _ = weather_tx.send((grid, lightning_cells, sim, forecast));
                 });
 
                 weather_job.state = WeatherJobState::Working(job);
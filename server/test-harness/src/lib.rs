@@ -0,0 +1,458 @@
+//! In-process harness for end-to-end tests of the server and clients.
+//!
+//! [`TestServer`] runs a [`Server`] with a throwaway data directory and a tiny
+//! world, reachable only over the in-process mpsc transport. Clients connect
+//! with [`TestServer::connect`], after which the server and all clients are
+//! advanced together, one fixed-length tick at a time, with
+//! [`TestServer::tick`]. The ECS of the server and of each client can be
+//! inspected between ticks.
+//!
+//! Messages between the server and the clients (and results of the database
+//! threads) are delivered in the background, so tests should wait for their
+//! effects with [`TestServer::tick_until`] instead of assuming they arrive
+//! within a fixed number of ticks.
+//!
+//! ```no_run
+//! use veloren_test_harness::TestServer;
+//!
+//! let mut server = TestServer::new();
+//! let alice = server.connect("alice");
+//! server.send_chat(alice, "hello");
+//! server.tick_until("the message to arrive", |server| {
+//!     server.chat_messages(alice).any(|msg| msg == "hello")
+//! });
+//! ```
+
+use client::{Client, ClientType, Event, addr::ConnectionArgs};
+use common::{
+    ViewDistances,
+    comp::{self, AdminRole, Content, ControllerInputs},
+    uid::{IdMaps, Uid},
+};
+use server::{
+    EditableSettings, Input, MPSC_ADDR, Server, Settings,
+    persistence::{DatabaseSettings, SqlLogMode},
+};
+use specs::{Entity, WorldExt};
+use std::{
+    env, fs,
+    path::{Path, PathBuf},
+    process,
+    sync::{
+        Arc, Mutex, MutexGuard, PoisonError,
+        atomic::{AtomicU32, Ordering},
+    },
+    thread,
+    time::Duration,
+};
+use tokio::runtime::Runtime;
+use world::sim::{FileOpts, GenOpts};
+
+/// Length of a single tick of the server and the clients.
+pub const TICK: Duration = Duration::from_nanos(1_000_000_000 / 30);
+/// Number of ticks [`TestServer::tick_until`] waits before giving up.
+pub const MAX_WAIT_TICKS: u32 = 30 * 300;
+/// Wall clock time given to background threads between waiting ticks.
+const WAIT_INTERVAL: Duration = Duration::from_millis(1);
+/// Terrain and entity view distance of the server and the clients.
+const VIEW_DISTANCE: u32 = 2;
+/// Base two logarithm of the size of the generated world, in chunks.
+const WORLD_SIZE_LG: u32 = 6;
+/// Weapon given to the characters of test clients.
+const STARTER_WEAPON: &str = "common.items.weapons.sword.starter";
+
+/// Only one server can listen on the mpsc channel at a time, so tests using
+/// the harness run one after another even when the test runner uses several
+/// threads.
+static SERVER_LOCK: Mutex<()> = Mutex::new(());
+
+/// A client connected to a [`TestServer`].
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct TestClient(usize);
+
+struct ConnectedClient {
+    username: String,
+    client: Client,
+    inputs: ControllerInputs,
+    /// Events returned by the client since they were last taken.
+    events: Vec<Event>,
+}
+
+/// A server and the clients connected to it, ticked in lockstep.
+pub struct TestServer {
+    // NOTE: Fields are dropped in order, the clients and server have to be shut
+    // down before the data directory is removed and the next test may start a
+    // server.
+    clients: Vec<Option<ConnectedClient>>,
+    server: Server,
+    runtime: Arc<Runtime>,
+    ticks: u64,
+    data_dir: DataDir,
+    _lock: MutexGuard<'static, ()>,
+}
+
+impl Default for TestServer {
+    fn default() -> Self { Self::new() }
+}
+
+impl TestServer {
+    /// Start a server with the default test settings.
+    pub fn new() -> Self { Self::with_settings(|_| {}) }
+
+    /// Start a server, allowing the test settings to be adjusted first.
+    pub fn with_settings(configure: impl FnOnce(&mut Settings)) -> Self {
+        let lock = SERVER_LOCK.lock().unwrap_or_else(PoisonError::into_inner);
+        let data_dir = DataDir::new();
+
+        let mut settings = Settings {
+            gameserver_protocols: Vec::new(),
+            auth_server_address: None,
+            query_address: None,
+            max_view_distance: Some(VIEW_DISTANCE),
+            map_file: Some(FileOpts::Generate(GenOpts {
+                x_lg: WORLD_SIZE_LG,
+                y_lg: WORLD_SIZE_LG,
+                ..GenOpts::default()
+            })),
+            ..Settings::default()
+        };
+        configure(&mut settings);
+
+        let editable_settings = EditableSettings::load(data_dir.path());
+        let database_settings = DatabaseSettings {
+            db_dir: data_dir.path().join("saves"),
+            sql_log_mode: SqlLogMode::Disabled,
+        };
+        let runtime = Arc::new(Runtime::new().expect("Failed to create tokio runtime"));
+        let server = Server::new(
+            settings,
+            editable_settings,
+            database_settings,
+            data_dir.path(),
+            &|_| {},
+            Arc::clone(&runtime),
+        )
+        .expect("Failed to create server");
+
+        Self {
+            clients: Vec::new(),
+            server,
+            runtime,
+            ticks: 0,
+            data_dir,
+            _lock: lock,
+        }
+    }
+
+    pub fn server(&self) -> &Server { &self.server }
+
+    pub fn server_mut(&mut self) -> &mut Server { &mut self.server }
+
+    /// The ECS of the server.
+    pub fn ecs(&self) -> &specs::World { self.server.state().ecs() }
+
+    /// Data directory of the server, removed when the server is dropped.
+    pub fn data_dir(&self) -> &Path { self.data_dir.path() }
+
+    /// Number of ticks the harness has advanced so far.
+    pub fn ticks(&self) -> u64 { self.ticks }
+
+    /// Make `username` an admin. Only takes effect for clients connecting
+    /// afterwards.
+    pub fn add_admin(&mut self, username: &str, role: AdminRole) {
+        self.server.add_admin(username, role);
+    }
+
+    /// Connect a new client and spawn a character for it into the world.
+    pub fn connect(&mut self, username: &str) -> TestClient {
+        let client = self.login(username);
+        self.enter_world(client);
+        client
+    }
+
+    /// Connect a new client, leaving it in the character selection.
+    pub fn login(&mut self, username: &str) -> TestClient {
+        let runtime = Arc::clone(&self.runtime);
+        let name = username.to_owned();
+        // The server only answers the registration while it ticks, so the
+        // connection is made in the background meanwhile.
+        let connecting = self.runtime.spawn(async move {
+            Client::new(
+                ConnectionArgs::Mpsc(MPSC_ADDR),
+                runtime,
+                &mut None,
+                &name,
+                "",
+                None,
+                |_| false,
+                &|_| {},
+                |_| {},
+                PathBuf::new(),
+                ClientType::Game,
+            )
+            .await
+        });
+        let mut waited = 0;
+        while !connecting.is_finished() {
+            assert!(
+                waited < MAX_WAIT_TICKS,
+                "Timed out waiting for {username} to connect"
+            );
+            self.tick_server();
+            thread::sleep(WAIT_INTERVAL);
+            waited += 1;
+        }
+        let client = self
+            .runtime
+            .block_on(connecting)
+            .expect("Connecting client panicked")
+            .unwrap_or_else(|e| panic!("Failed to connect {username}: {e:?}"));
+
+        let id = TestClient(self.clients.len());
+        self.clients.push(Some(ConnectedClient {
+            username: username.to_owned(),
+            client,
+            inputs: ControllerInputs::default(),
+            events: Vec::new(),
+        }));
+        id
+    }
+
+    /// Spawn the character of a client in character selection into the
+    /// world, creating the character first if it has none.
+    pub fn enter_world(&mut self, client: TestClient) {
+        self.client_mut(client).load_character_list();
+        self.tick_until("the character list", |server| {
+            !server.client(client).character_list().loading
+        });
+
+        if self.client(client).character_list().characters.is_empty() {
+            let alias = self.connected(client).username.clone();
+            let client_mut = self.client_mut(client);
+            client_mut.create_character(
+                alias,
+                Some(STARTER_WEAPON.into()),
+                None,
+                body(),
+                false,
+                None,
+            );
+            client_mut.load_character_list();
+            self.tick_until("the character to be created", |server| {
+                let list = server.client(client).character_list();
+                !list.loading && !list.characters.is_empty()
+            });
+        }
+
+        let character_id = self.client(client).character_list().characters[0]
+            .character
+            .id
+            .expect("Listed characters are stored");
+        self.client_mut(client)
+            .request_character(character_id, ViewDistances {
+                terrain: VIEW_DISTANCE,
+                entity: VIEW_DISTANCE,
+            });
+        self.tick_until("the character to spawn", |server| {
+            server.client(client).position().is_some() && server.entity(client).is_some()
+        });
+    }
+
+    /// Return the character of a client to the character selection, which
+    /// saves it.
+    pub fn leave_world(&mut self, client: TestClient) {
+        self.client_mut(client).request_remove_character();
+        self.tick_until("the character to leave the world", |server| {
+            server.client(client).presence().is_none()
+        });
+    }
+
+    /// Disconnect a client, waiting for the server to remove it.
+    pub fn disconnect(&mut self, client: TestClient) {
+        let entity = self.entity(client);
+        let mut connected = self.clients[client.0]
+            .take()
+            .expect("Client is already disconnected");
+        connected.client.logout();
+        if let Some(entity) = entity {
+            self.tick_until("the server to remove the client", |server| {
+                !server.ecs().is_alive(entity)
+            });
+        }
+    }
+
+    /// The client of a connected test client.
+    pub fn client(&self, client: TestClient) -> &Client { &self.connected(client).client }
+
+    pub fn client_mut(&mut self, client: TestClient) -> &mut Client {
+        &mut self.connected_mut(client).client
+    }
+
+    /// Controller inputs the client applies every tick until changed.
+    pub fn inputs_mut(&mut self, client: TestClient) -> &mut ControllerInputs {
+        &mut self.connected_mut(client).inputs
+    }
+
+    /// Uid of the character (or spectator) entity of a client.
+    pub fn uid(&self, client: TestClient) -> Uid {
+        self.client(client)
+            .uid()
+            .expect("Client has an entity once registered")
+    }
+
+    /// Server side entity of a client, if it still exists.
+    pub fn entity(&self, client: TestClient) -> Option<Entity> {
+        let uid = self.client(client).uid()?;
+        self.ecs().read_resource::<IdMaps>().uid_entity(uid)
+    }
+
+    /// Read a component of the server side entity of a client.
+    pub fn component<C: specs::Component + Clone>(&self, client: TestClient) -> Option<C> {
+        self.server
+            .state()
+            .read_component_cloned(self.entity(client)?)
+    }
+
+    /// Number of items with the given definition in the inventory of the
+    /// character of a client, as seen by the server.
+    pub fn item_count(&self, client: TestClient, item_definition_id: &str) -> u64 {
+        self.component::<comp::Inventory>(client)
+            .map_or(0, |inventory| {
+                inventory
+                    .slots()
+                    .flatten()
+                    .filter(|item| {
+                        item.item_definition_id().itemdef_id() == Some(item_definition_id)
+                    })
+                    .map(|item| u64::from(item.amount()))
+                    .sum()
+            })
+    }
+
+    /// Send a chat message from a client.
+    pub fn send_chat(&mut self, client: TestClient, message: &str) {
+        self.client_mut(client).send_chat(message.to_owned());
+    }
+
+    /// Send a chat command from a client, without the leading `/`.
+    pub fn send_command(&mut self, client: TestClient, name: &str, args: &[&str]) {
+        self.client_mut(client).send_command(
+            name.to_owned(),
+            args.iter().map(|arg| (*arg).to_owned()).collect(),
+        );
+    }
+
+    /// Events returned by a client since they were last taken.
+    pub fn events(&self, client: TestClient) -> &[Event] { &self.connected(client).events }
+
+    pub fn take_events(&mut self, client: TestClient) -> Vec<Event> {
+        std::mem::take(&mut self.connected_mut(client).events)
+    }
+
+    /// Plain text of the chat messages a client has received and not yet
+    /// taken. Localized messages are represented by their key.
+    pub fn chat_messages(&self, client: TestClient) -> impl Iterator<Item = &str> {
+        self.events(client).iter().filter_map(|event| match event {
+            Event::Chat(msg) => match msg.content() {
+                Content::Plain(text) => Some(text.as_str()),
+                Content::Localized { key, .. } => Some(key.as_str()),
+                _ => None,
+            },
+            _ => None,
+        })
+    }
+
+    /// Advance the server and then every connected client by one tick.
+    pub fn tick(&mut self) {
+        self.tick_server();
+        for connected in self.clients.iter_mut().flatten() {
+            let events = connected
+                .client
+                .tick(connected.inputs.clone(), TICK)
+                .unwrap_or_else(|e| panic!("{} failed to tick: {e:?}", connected.username));
+            connected.events.extend(events);
+            connected.client.cleanup();
+        }
+    }
+
+    /// Advance by the given number of ticks.
+    pub fn tick_n(&mut self, ticks: u32) {
+        for _ in 0..ticks {
+            self.tick();
+        }
+    }
+
+    /// Tick until `condition` holds, giving background threads a moment
+    /// between ticks. Panics naming `what` was waited for if this takes more
+    /// than [`MAX_WAIT_TICKS`] ticks.
+    pub fn tick_until(&mut self, what: &str, mut condition: impl FnMut(&Self) -> bool) {
+        for _ in 0..MAX_WAIT_TICKS {
+            if condition(self) {
+                return;
+            }
+            self.tick();
+            thread::sleep(WAIT_INTERVAL);
+        }
+        panic!("Timed out after {MAX_WAIT_TICKS} ticks waiting for {what}");
+    }
+
+    fn tick_server(&mut self) {
+        self.server
+            .tick(Input::default(), TICK)
+            .expect("Server failed to tick");
+        self.server.cleanup();
+        self.ticks += 1;
+    }
+
+    fn connected(&self, client: TestClient) -> &ConnectedClient {
+        self.clients[client.0]
+            .as_ref()
+            .expect("Client is disconnected")
+    }
+
+    fn connected_mut(&mut self, client: TestClient) -> &mut ConnectedClient {
+        self.clients[client.0]
+            .as_mut()
+            .expect("Client is disconnected")
+    }
+}
+
+/// Body of the characters of test clients, fixed to keep runs reproducible.
+fn body() -> comp::Body {
+    comp::body::humanoid::Body {
+        species: comp::body::humanoid::Species::Human,
+        body_type: comp::body::humanoid::BodyType::Female,
+        hair_style: 0,
+        beard: 0,
+        eyes: 0,
+        accessory: 0,
+        hair_color: 0,
+        skin: 0,
+        eye_color: 0,
+    }
+    .into()
+}
+
+/// Temporary data directory of a test server, removed again when dropped.
+struct DataDir(PathBuf);
+
+impl DataDir {
+    fn new() -> Self {
+        static NEXT: AtomicU32 = AtomicU32::new(0);
+
+        let path = env::temp_dir().join(format!(
+            "veloren-test-harness-{}-{}",
+            process::id(),
+            NEXT.fetch_add(1, Ordering::Relaxed)
+        ));
+        let _ = fs::remove_dir_all(&path);
+        fs::create_dir_all(&path).expect("Failed to create server data directory");
+        Self(path)
+    }
+
+    fn path(&self) -> &Path { &self.0 }
+}
+
+impl Drop for DataDir {
+    fn drop(&mut self) { let _ = fs::remove_dir_all(&self.0); }
+}
@@ -0,0 +1,37 @@
+use client::Event;
+use common::comp::{AdminRole, ChatType};
+use veloren_test_harness::TestServer;
+
+#[test]
+fn chat_message_reaches_other_players() {
+    let mut server = TestServer::new();
+    let alice = server.connect("alice");
+    let bob = server.connect("bob");
+
+    server.send_chat(alice, "hello bob");
+    server.tick_until("bob to receive the message", |server| {
+        server.chat_messages(bob).any(|msg| msg == "hello bob")
+    });
+}
+
+#[test]
+fn give_item_requires_admin() {
+    let mut server = TestServer::new();
+    server.add_admin("admin", AdminRole::Admin);
+    let admin = server.connect("admin");
+    let player = server.connect("player");
+    server.take_events(player);
+
+    server.send_command(player, "give_item", &["common.items.food.apple", "3"]);
+    server.tick_until("the command to be rejected", |server| {
+        server.events(player).iter().any(|event| {
+            matches!(event, Event::Chat(msg) if matches!(msg.chat_type, ChatType::CommandError))
+        })
+    });
+    assert_eq!(server.item_count(player, "common.items.food.apple"), 0);
+
+    server.send_command(admin, "give_item", &["common.items.food.apple", "3"]);
+    server.tick_until("the admin to receive the apples", |server| {
+        server.item_count(admin, "common.items.food.apple") == 3
+    });
+}
@@ -0,0 +1,29 @@
+use common::comp::{Group, invite::InviteKind};
+use veloren_test_harness::TestServer;
+
+#[test]
+fn accepted_invite_forms_group() {
+    let mut server = TestServer::new();
+    let leader = server.connect("leader");
+    let member = server.connect("member");
+
+    let member_uid = server.uid(member);
+    server
+        .client_mut(leader)
+        .send_invite(member_uid, InviteKind::Group);
+    server.tick_until("the invite to arrive", |server| {
+        server.client(member).invite().is_some()
+    });
+
+    server.client_mut(member).accept_invite();
+    server.tick_until("the group to form", |server| {
+        let group = server.component::<Group>(leader);
+        group.is_some() && group == server.component::<Group>(member)
+    });
+    server.tick_until("the member to know the leader", |server| {
+        server
+            .client(member)
+            .group_members()
+            .contains_key(&server.uid(leader))
+    });
+}
@@ -0,0 +1,151 @@
+use common::{
+    comp::{AdminRole, Group, Pos, invite::InviteKind, misc::InstanceKind},
+    event::{EnterInstanceEvent, EventBus, TeleportToPositionEvent},
+    terrain::{BlockKind, TerrainGrid},
+    uid::IdMaps,
+};
+use server::instance::Instances;
+use specs::WorldExt;
+use veloren_test_harness::{TestClient, TestServer};
+
+fn position(server: &TestServer, client: TestClient) -> Pos {
+    server
+        .component::<Pos>(client)
+        .expect("Characters have a position")
+}
+
+/// Send the clients into an arena, led by the first one, and wait for it to
+/// load on both sides.
+fn enter_arena(server: &mut TestServer, clients: &[TestClient]) -> Pos {
+    let client = clients[0];
+    let entities = clients
+        .iter()
+        .map(|client| server.entity(*client).expect("Client is in the world"))
+        .collect::<Vec<_>>();
+    let exit = position(server, client);
+    server
+        .ecs()
+        .read_resource::<EventBus<EnterInstanceEvent>>()
+        .emit_now(EnterInstanceEvent {
+            leader: entities[0],
+            entities,
+            kind: InstanceKind::Arena,
+            exit: exit.0,
+        });
+    server.tick_until("the character to enter the arena", |server| {
+        position(server, client).0.distance(exit.0) > 100.0
+    });
+
+    let key = TerrainGrid::chunk_key(position(server, client).0.xy().as_::<i32>());
+    server.tick_until("the arena to load", |server| {
+        server
+            .ecs()
+            .read_resource::<TerrainGrid>()
+            .get_key_real(key)
+            .is_some()
+            && server
+                .client(client)
+                .state()
+                .terrain()
+                .get_key_real(key)
+                .is_some()
+    });
+    position(server, client)
+}
+
+#[test]
+fn closed_instance_slot_is_reused() {
+    let mut server = TestServer::with_settings(|settings| settings.terrain_persistence = true);
+    server.add_admin("explorer", AdminRole::Admin);
+    let explorer = server.connect("explorer");
+    let exit = position(&server, explorer);
+
+    let spawn = enter_arena(&mut server, &[explorer]);
+    let edit = spawn.0.map(|e| e.floor() as i32);
+    server.send_command(explorer, "make_block", &["Rock"]);
+    server.tick_until("the block to be placed", |server| {
+        server
+            .server()
+            .state()
+            .get_block(edit)
+            .is_some_and(|block| block.kind() == BlockKind::Rock)
+    });
+
+    // Leaving the arena closes it, and the slot is freed once its chunks unload
+    let entity = server.entity(explorer).expect("Client is in the world");
+    server
+        .ecs()
+        .read_resource::<EventBus<TeleportToPositionEvent>>()
+        .emit_now(TeleportToPositionEvent {
+            entity,
+            position: exit.0,
+        });
+    let key = TerrainGrid::chunk_key(spawn.0.xy().as_::<i32>());
+    server.tick_until("the arena to close", |server| {
+        server
+            .ecs()
+            .read_resource::<Instances>()
+            .area_at(key)
+            .is_none()
+    });
+
+    // The next arena takes the same slot, without the edits made in the last one
+    let reused = enter_arena(&mut server, &[explorer]);
+    assert_eq!(reused.0.xy(), spawn.0.xy());
+    assert!(
+        server
+            .server()
+            .state()
+            .get_block(edit)
+            .is_some_and(|block| block.kind() != BlockKind::Rock)
+    );
+}
+
+/// Whether the client has been sent the entity of `other`.
+fn knows(server: &TestServer, client: TestClient, other: TestClient) -> bool {
+    server
+        .client(client)
+        .state()
+        .ecs()
+        .read_resource::<IdMaps>()
+        .uid_entity(server.uid(other))
+        .is_some()
+}
+
+#[test]
+fn groups_in_other_instances_are_not_synced() {
+    let mut server = TestServer::new();
+    let leader = server.connect("leader");
+    let member = server.connect("member");
+    let stranger = server.connect("stranger");
+
+    let member_uid = server.uid(member);
+    server
+        .client_mut(leader)
+        .send_invite(member_uid, InviteKind::Group);
+    server.tick_until("the invite to arrive", |server| {
+        server.client(member).invite().is_some()
+    });
+    server.client_mut(member).accept_invite();
+    server.tick_until("the group to form", |server| {
+        let group = server.component::<Group>(leader);
+        group.is_some() && group == server.component::<Group>(member)
+    });
+
+    let group_arena = enter_arena(&mut server, &[leader, member]);
+    let stranger_arena = enter_arena(&mut server, &[stranger]);
+    assert_ne!(group_arena.0.xy(), stranger_arena.0.xy());
+    server.tick_until("the group to be out of sight", |server| {
+        knows(server, member, leader)
+            && !knows(server, stranger, leader)
+            && !knows(server, stranger, member)
+    });
+
+    // The group keeps seeing each other, and the stranger never sees them
+    for _ in 0..100 {
+        server.tick();
+        assert!(knows(&server, member, leader));
+        assert!(!knows(&server, stranger, leader));
+        assert!(!knows(&server, stranger, member));
+    }
+}
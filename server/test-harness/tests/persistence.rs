@@ -0,0 +1,23 @@
+use common::comp::AdminRole;
+use veloren_test_harness::TestServer;
+
+#[test]
+fn inventory_persists_across_sessions() {
+    let mut server = TestServer::new();
+    server.add_admin("hoarder", AdminRole::Admin);
+    let hoarder = server.connect("hoarder");
+
+    server.send_command(hoarder, "give_item", &["common.items.food.apple", "2"]);
+    server.tick_until("the apples to arrive", |server| {
+        server.item_count(hoarder, "common.items.food.apple") == 2
+    });
+
+    server.leave_world(hoarder);
+    server.enter_world(hoarder);
+    assert_eq!(server.item_count(hoarder, "common.items.food.apple"), 2);
+
+    server.disconnect(hoarder);
+    let hoarder = server.login("hoarder");
+    server.enter_world(hoarder);
+    assert_eq!(server.item_count(hoarder, "common.items.food.apple"), 2);
+}
@@ -26,7 +26,8 @@ pub fn criterion_benchmark(c: &mut Criterion) {
         },
         &pool,
         &|_| {},
-    );
+    )
+    .unwrap();
     let mut terrain = TerrainGrid::new(
         world.sim().map_size_lg(),
         Arc::new(world.sim().generate_oob_chunk()),
@@ -87,6 +87,7 @@ fn maps_basic_melee() {
                     angle: 15.0,
                     damage_effect: None,
                     attack_effect: None,
+                    elemental_damage: None,
                     multi_target: None,
                     simultaneous_hits: 1,
                     custom_combo: melee::CustomCombo {
@@ -22,7 +22,7 @@ use client::{self, Client};
 use common::{
     combat,
     comp::{
-        self, Body, CharacterState, Energy, Health, Inventory, Poise, Stats,
+        self, Body, CharacterState, Energy, Health, Inventory, Poise, QuestLog, Stats,
         ability::{Ability, ActiveAbilities, AuxiliaryAbility, BASE_ABILITY_LIMIT},
         inventory::{
             item::{
@@ -32,6 +32,7 @@ use common::{
             },
             slot::EquipSlot,
         },
+        quest::{Objective, QuestId},
         skills::{
             self, AxeSkill, BowSkill, ClimbSkill, HammerSkill, MiningSkill, SKILL_MODIFIERS,
             SceptreSkill, Skill, StaffSkill, SwimSkill, SwordSkill,
@@ -186,6 +187,11 @@ widget_ids! {
         stat_values[],
         // Recipes
         recipe_groups[],
+        // Quests
+        quest_titles[],
+        quest_objectives[],
+        quest_abandon_btns[],
+        quests_completed_txt,
     }
 }
 
@@ -214,6 +220,7 @@ pub struct Diary<'a> {
     pulse: f32,
     context: &'a AbilityContext,
     stats: Option<&'a Stats>,
+    quest_log: Option<&'a QuestLog>,
 
     #[conrod(common_builder)]
     common: widget::CommonBuilder,
@@ -263,6 +270,7 @@ impl<'a> Diary<'a> {
         pulse: f32,
         context: &'a AbilityContext,
         stats: Option<&'a Stats>,
+        quest_log: Option<&'a QuestLog>,
     ) -> Self {
         Self {
             show,
@@ -288,6 +296,7 @@ impl<'a> Diary<'a> {
             pulse,
             context,
             stats,
+            quest_log,
             common: widget::CommonBuilder::default(),
             created_btns_top_l: 0,
             created_btns_top_r: 0,
@@ -313,7 +322,7 @@ const TREES: [&str; 8] = [
 
 // Possible future sections: Bestiary ("Pokedex" of fought enemies), Weapon and
 // armour catalogue, Achievements...
-const SECTIONS: [&str; 4] = ["Skill-Trees", "Abilities", "Stats", "Recipes"];
+const SECTIONS: [&str; 5] = ["Skill-Trees", "Abilities", "Stats", "Recipes", "Quests"];
 
 pub enum Event {
     Close,
@@ -321,6 +330,7 @@ pub enum Event {
     UnlockSkill(Skill),
     ChangeSection(DiarySection),
     SelectExpBar(Option<SkillGroupKind>),
+    AbandonQuest(QuestId),
 }
 
 #[derive(PartialEq, Eq)]
@@ -329,6 +339,7 @@ pub enum DiarySection {
     AbilitySelection,
     Stats,
     Recipes,
+    Quests,
 }
 
 pub struct DiaryState {
@@ -451,6 +462,7 @@ impl Widget for Diary<'_> {
                 DiarySection::AbilitySelection => "hud-diary-sections-abilities-title",
                 DiarySection::Stats => "hud-diary-sections-stats-title",
                 DiarySection::Recipes => "hud-diary-sections-recipes-title",
+                DiarySection::Quests => "hud-diary-sections-quests-title",
             };
 
             let section_name = self.localized_strings.get_msg(section_name_key);
@@ -461,6 +473,7 @@ impl Widget for Diary<'_> {
                     DiarySection::SkillTrees => self.imgs.skilltree_ico,
                     DiarySection::Stats => self.imgs.stats_ico,
                     DiarySection::Recipes => self.imgs.crafting_icon,
+                    DiarySection::Quests => self.imgs.quest_ico,
                 };
 
                 if i == 0 {
@@ -1149,7 +1162,7 @@ impl Widget for Diary<'_> {
                 events
             },
             DiarySection::Stats => {
-                const STATS: [&str; 12] = [
+                const STATS: [&str; 13] = [
                     "Hitpoints",
                     "Energy",
                     "Poise",
@@ -1162,6 +1175,7 @@ impl Widget for Diary<'_> {
                     "Weapon Power",
                     "Weapon Speed",
                     "Weapon Effect Power",
+                    "Set Bonuses",
                 ];
 
                 // Background Art
@@ -1306,6 +1320,21 @@ impl Widget for Diary<'_> {
                             },
                             (None, None) => String::new(),
                         },
+                        "Set Bonuses" => {
+                            let set_pieces = self.inventory.armor_set_pieces();
+                            let mut active_sets = set_pieces
+                                .iter()
+                                .filter(|(set, pieces)| {
+                                    self.msm
+                                        .armor_set_bonuses(set)
+                                        .iter()
+                                        .any(|bonus| bonus.is_active(**pieces))
+                                })
+                                .map(|(set, pieces)| format!("{} ({})", set, pieces))
+                                .collect::<Vec<_>>();
+                            active_sets.sort();
+                            active_sets.join(", ")
+                        },
                         unknown => unreachable!("{}", unknown),
                     };
 
@@ -1429,7 +1458,160 @@ impl Widget for Diary<'_> {
 
                 events
             },
+            DiarySection::Quests => {
+                // Background Art
+                Image::new(self.imgs.book_bg)
+                    .w_h(299.0 * 4.0, 184.0 * 4.0)
+                    .mid_top_with_margin_on(state.ids.content_align, 4.0)
+                    .set(state.ids.spellbook_art, ui);
+
+                Rectangle::fill_with([299.0 * 2.0, 184.0 * 4.0], color::TRANSPARENT)
+                    .top_left_with_margins_on(state.ids.spellbook_art, 0.0, 0.0)
+                    .set(state.ids.sb_page_left_align, ui);
+                Rectangle::fill_with([299.0 * 2.0, 184.0 * 4.0], color::TRANSPARENT)
+                    .top_right_with_margins_on(state.ids.spellbook_art, 0.0, 0.0)
+                    .set(state.ids.sb_page_right_align, ui);
+
+                // Half of the quests on each page
+                const QUESTS_PER_PAGE: usize = comp::quest::MAX_ACTIVE_QUESTS.div_ceil(2);
+
+                let quests = self
+                    .quest_log
+                    .into_iter()
+                    .flat_map(|log| log.quests())
+                    .collect::<Vec<_>>();
+
+                state.update(|s| {
+                    s.ids
+                        .quest_titles
+                        .resize(quests.len(), &mut ui.widget_id_generator());
+                    s.ids
+                        .quest_objectives
+                        .resize(quests.len(), &mut ui.widget_id_generator());
+                    s.ids
+                        .quest_abandon_btns
+                        .resize(quests.len(), &mut ui.widget_id_generator());
+                });
+
+                for (i, quest) in quests.iter().enumerate() {
+                    let title = Text::new(&self.localized_strings.get_content(&quest.quest.title))
+                        .font_id(self.fonts.cyri.conrod_id)
+                        .font_size(self.fonts.cyri.scale(24))
+                        .color(BLACK);
+                    let title = if i == 0 {
+                        title.top_left_with_margins_on(state.ids.sb_page_left_align, 20.0, 20.0)
+                    } else if i == QUESTS_PER_PAGE {
+                        title.top_left_with_margins_on(state.ids.sb_page_right_align, 20.0, 20.0)
+                    } else {
+                        title.down_from(state.ids.quest_objectives[i - 1], 20.0)
+                    };
+                    title.set(state.ids.quest_titles[i], ui);
+
+                    if Button::image(self.imgs.button)
+                        .w_h(105.0, 25.0)
+                        .hover_image(self.imgs.button_hover)
+                        .press_image(self.imgs.button_press)
+                        .label(&self.localized_strings.get_msg("hud-diary-quests-abandon"))
+                        .label_y(conrod_core::position::Relative::Scalar(1.0))
+                        .label_color(TEXT_COLOR)
+                        .label_font_size(self.fonts.cyri.scale(12))
+                        .label_font_id(self.fonts.cyri.conrod_id)
+                        .right_from(state.ids.quest_titles[i], 20.0)
+                        .set(state.ids.quest_abandon_btns[i], ui)
+                        .was_clicked()
+                    {
+                        events.push(Event::AbandonQuest(quest.quest.id));
+                    }
+
+                    let objectives = quest
+                        .quest
+                        .objectives
+                        .iter()
+                        .enumerate()
+                        .map(|(idx, objective)| {
+                            let progress = quest.objective_progress(idx, Some(self.inventory));
+                            self.objective_text(objective, progress)
+                        })
+                        .collect::<Vec<_>>()
+                        .join("\n");
+                    Text::new(&objectives)
+                        .font_id(self.fonts.cyri.conrod_id)
+                        .font_size(self.fonts.cyri.scale(18))
+                        .color(BLACK)
+                        .down_from(state.ids.quest_titles[i], 8.0)
+                        .set(state.ids.quest_objectives[i], ui);
+                }
+
+                let completed = self.quest_log.map_or(0, |log| log.completed());
+                let summary_key = if quests.is_empty() {
+                    "hud-diary-quests-none"
+                } else {
+                    "hud-diary-quests-completed"
+                };
+                let summary =
+                    self.localized_strings
+                        .get_msg_ctx(summary_key, &i18n::fluent_args! {
+                            "completed" => completed,
+                        });
+                Text::new(&summary)
+                    .font_id(self.fonts.cyri.conrod_id)
+                    .font_size(self.fonts.cyri.scale(18))
+                    .color(BLACK)
+                    .bottom_left_with_margins_on(state.ids.sb_page_left_align, 20.0, 20.0)
+                    .set(state.ids.quests_completed_txt, ui);
+
+                events
+            },
+        }
+    }
+}
+
+impl Diary<'_> {
+    fn objective_text(&self, objective: &Objective, progress: u32) -> String {
+        let strings = self.localized_strings;
+        match objective {
+            Objective::Kill { body, count } => {
+                let name = common::npc::NPC_NAMES
+                    .read()
+                    .get_default_name(body)
+                    .map(|name| strings.get_content(&name))
+                    .unwrap_or_default();
+                strings.get_msg_ctx("hud-diary-quests-kill", &i18n::fluent_args! {
+                    "body" => name,
+                    "progress" => progress,
+                    "count" => count,
+                })
+            },
+            Objective::Fetch { amount, .. } => {
+                let name = objective
+                    .item_def()
+                    .map(|item_def| util::item_text(&*item_def, strings, self.item_i18n).0)
+                    .unwrap_or_default();
+                strings.get_msg_ctx("hud-diary-quests-fetch", &i18n::fluent_args! {
+                    "item" => name,
+                    "progress" => progress,
+                    "amount" => amount,
+                })
+            },
+            Objective::Escort { name, .. } => {
+                strings.get_msg_ctx("hud-diary-quests-escort", &i18n::fluent_args! {
+                    "site" => strings.get_content(name),
+                    "done" => progress.min(1),
+                })
+            },
+            Objective::Visit { name, .. } => {
+                strings.get_msg_ctx("hud-diary-quests-visit", &i18n::fluent_args! {
+                    "site" => strings.get_content(name),
+                    "done" => progress.min(1),
+                })
+            },
+            Objective::Slay { name, .. } => {
+                strings.get_msg_ctx("hud-diary-quests-slay", &i18n::fluent_args! {
+                    "target" => strings.get_content(name),
+                })
+            },
         }
+        .into_owned()
     }
 }
 
@@ -1453,6 +1635,7 @@ fn section_from_str(string: &str) -> Option<DiarySection> {
         "Skill-Trees" => Some(DiarySection::SkillTrees),
         "Stats" => Some(DiarySection::Stats),
         "Recipes" => Some(DiarySection::Recipes),
+        "Quests" => Some(DiarySection::Quests),
         _ => None,
     }
 }
@@ -88,7 +88,7 @@ use crate::{
 };
 use client::Client;
 use common::{
-    combat,
+    combat::{self, DamageKind},
     comp::{
         self, BuffData, BuffKind, Content, Health, Item, MapMarkerChange, PickupItem, PresenceKind,
         ability::{AuxiliaryAbility, Stance},
@@ -103,6 +103,7 @@ use common::{
             tool::{AbilityContext, ToolKind},
         },
         loot_owner::LootOwnerKind,
+        quest::QuestId,
         skillset::{SkillGroupKind, SkillsPersistenceError, skills::Skill},
     },
     consts::MAX_PICKUP_RANGE,
@@ -753,6 +754,7 @@ pub enum Event {
     LeaveStance,
     UnlockSkill(Skill),
     SelectExpBar(Option<SkillGroupKind>),
+    AbandonQuest(QuestId),
 
     RequestSiteInfo(SiteId),
     ChangeAbility(usize, AuxiliaryAbility),
@@ -1662,9 +1664,11 @@ impl Hud {
                         .filter(|fl| !fl.floaters.is_empty()),
                     healths.get(me),
                 ) {
-                    let player_font_col = |precise: bool| {
+                    let player_font_col = |precise: bool, kind: Option<DamageKind>| {
                         if precise {
                             Rgb::new(1.0, 0.9, 0.0)
+                        } else if let Some(col) = kind.and_then(elemental_damage_col) {
+                            col
                         } else {
                             Rgb::new(1.0, 0.1, 0.0)
                         }
@@ -1723,7 +1727,7 @@ impl Hud {
                             } else {
                                 0
                             };
-                        let font_col = player_font_col(precise);
+                        let font_col = player_font_col(precise, floater.info.kind);
                         // Timer sets the widget offset
                         let y = if floater.info.amount < 0.0 {
                             floater.timer as f64
@@ -2539,9 +2543,11 @@ impl Hud {
                     ];
                     // Largest value that select the first color is 40, then it shifts colors
                     // every 5
-                    let font_col = |font_size: u32, precise: bool| {
+                    let font_col = |font_size: u32, precise: bool, kind: Option<DamageKind>| {
                         if precise {
                             Rgb::new(1.0, 0.9, 0.0)
+                        } else if let Some(col) = kind.and_then(elemental_damage_col) {
+                            col
                         } else {
                             DAMAGE_COLORS[(font_size.saturating_sub(36) / 5).min(5) as usize]
                         }
@@ -2588,7 +2594,7 @@ impl Hud {
                             } else {
                                 0
                             };
-                        let font_col = font_col(font_size, precise);
+                        let font_col = font_col(font_size, precise, floater.info.kind);
                         // Timer sets the widget offset
                         let y = if precise {
                             ui_widgets.win_h * (floater.rand as f64 % 0.075)
@@ -2824,11 +2830,14 @@ impl Hud {
             // Weather
             let weather = client.weather_at_player();
             Text::new(&format!(
-                "Weather({kind}): {{cloud: {cloud:.2}, rain: {rain:.2}, wind: <{wind_x:.0}, \
-                 {wind_y:.0}>}}",
+                "Weather({kind}): {{cloud: {cloud:.2}, rain: {rain:.2}, snow: {snow:.2}, fog: \
+                 {fog:.2}, temperature: {temperature:.0}°C, wind: <{wind_x:.0}, {wind_y:.0}>}}",
                 kind = weather.get_kind(),
                 cloud = weather.cloud,
                 rain = weather.rain,
+                snow = weather.snow,
+                fog = weather.fog,
+                temperature = weather.temperature,
                 wind_x = weather.wind.x,
                 wind_y = weather.wind.y
             ))
@@ -3725,6 +3734,7 @@ impl Hud {
         if self.show.diary {
             let entity = info.viewpoint_entity;
             let skill_sets = ecs.read_storage::<comp::SkillSet>();
+            let quest_logs = ecs.read_storage::<comp::QuestLog>();
             if let (
                 Some(skill_set),
                 Some(inventory),
@@ -3767,6 +3777,7 @@ impl Hud {
                     self.pulse,
                     &context,
                     stats.get(entity),
+                    quest_logs.get(entity),
                 )
                 .set(self.ids.diary, ui_widgets)
                 {
@@ -3786,6 +3797,9 @@ impl Hud {
                         diary::Event::SelectExpBar(xp_bar) => {
                             events.push(Event::SelectExpBar(xp_bar))
                         },
+                        diary::Event::AbandonQuest(quest_id) => {
+                            events.push(Event::AbandonQuest(quest_id))
+                        },
                     }
                 }
             }
@@ -5223,6 +5237,8 @@ impl Hud {
                                     break;
                                 }
                                 if floater.info.instance == info.instance
+                                    // Keep elemental damage separate so the breakdown stays visible
+                                    && floater.info.kind == info.kind
                                     // Group up precision hits and regular attacks for incoming damage
                                     && (hit_me
                                         || floater.info.precise
@@ -5252,6 +5268,7 @@ impl Hud {
                                         }
                                     // Ignore precise floaters, unless the damage is incoming
                                     && (hit_me || !f.info.precise)
+                                    && f.info.kind == info.kind
                                 })
                             } else {
                                 None
@@ -5281,6 +5298,21 @@ impl Hud {
         }
     }
 }
+
+/// Combat text color of elemental damage, `None` for other damage kinds
+fn elemental_damage_col(kind: DamageKind) -> Option<Rgb<f32>> {
+    match kind {
+        DamageKind::Fire => Some(Rgb::new(1.0, 0.45, 0.1)),
+        DamageKind::Frost => Some(Rgb::new(0.5, 0.85, 1.0)),
+        DamageKind::Poison => Some(Rgb::new(0.45, 0.9, 0.2)),
+        DamageKind::Lightning => Some(Rgb::new(0.85, 0.85, 1.0)),
+        DamageKind::Arcane => Some(Rgb::new(0.75, 0.4, 1.0)),
+        DamageKind::Piercing | DamageKind::Slashing | DamageKind::Crushing | DamageKind::Energy => {
+            None
+        },
+    }
+}
+
 // Get item qualities of equipped items and assign a tooltip title/frame color
 pub fn get_quality_col(quality: Quality) -> Color {
     match quality {
@@ -1,12 +1,13 @@
 use super::img_ids;
 use common::{
+    combat::Resistances,
     comp::{
         BuffData, BuffKind,
         inventory::trade_pricing::TradePricing,
         item::{
             Effects, Item, ItemDefinitionId, ItemDesc, ItemI18n, ItemKind, MaterialKind,
             MaterialStatManifest,
-            armor::{Armor, ArmorKind, Protection},
+            armor::{Armor, ArmorKind, Protection, SetBonus, SetBonusEffect},
             tool::{Hands, Tool, ToolKind},
         },
     },
@@ -136,7 +137,11 @@ pub fn stats_count(item: &dyn ItemDesc, msm: &MaterialStatManifest) -> usize {
                 + armor_stats.precision_power.is_some() as usize
                 + armor_stats.poise_resilience.is_some() as usize
                 + armor_stats.protection.is_some() as usize
+                + armor_stats.resistances.is_some() as usize
                 + (item.num_slots() > 0) as usize
+                + armor
+                    .set()
+                    .map_or(0, |set| msm.armor_set_bonuses(set).len())
         },
         ItemKind::Tool(_) => 6,
         ItemKind::Consumable { effects, .. } => match effects {
@@ -510,6 +515,86 @@ pub fn protec2string(stat: Protection) -> String {
     }
 }
 
+/// Describes the non-zero elemental resistances, e.g. "Fire 60, Frost 30"
+pub fn resistances_desc(resistances: &Resistances, i18n: &Localization) -> String {
+    [
+        ("common-stats-res-fire", resistances.fire),
+        ("common-stats-res-frost", resistances.frost),
+        ("common-stats-res-poison", resistances.poison),
+        ("common-stats-res-lightning", resistances.lightning),
+        ("common-stats-res-arcane", resistances.arcane),
+    ]
+    .into_iter()
+    .filter(|(_, res)| *res != 0.0)
+    .map(|(key, res)| format!("{} {:.0}", i18n.get_msg(key), res))
+    .collect::<Vec<_>>()
+    .join(", ")
+}
+
+/// Describes an armour set bonus, e.g. "Set (4) : Armor 20.0"
+pub fn set_bonus_desc(bonus: &SetBonus, i18n: &Localization) -> String {
+    let effect = match bonus.effect {
+        SetBonusEffect::Stats(stats) => {
+            let mut descs = Vec::new();
+            if let Some(protection) = stats.protection {
+                descs.push(format!(
+                    "{} {}",
+                    i18n.get_msg("common-stats-armor"),
+                    protec2string(protection)
+                ));
+            }
+            if let Some(poise_resilience) = stats.poise_resilience {
+                descs.push(format!(
+                    "{} {}",
+                    i18n.get_msg("common-stats-poise_res"),
+                    protec2string(poise_resilience)
+                ));
+            }
+            if let Some(energy_max) = stats.energy_max {
+                descs.push(format!(
+                    "{} {:.1}",
+                    i18n.get_msg("common-stats-energy_max"),
+                    energy_max
+                ));
+            }
+            if let Some(energy_reward) = stats.energy_reward {
+                descs.push(format!(
+                    "{} {:.1}%",
+                    i18n.get_msg("common-stats-energy_reward"),
+                    energy_reward * 100.0
+                ));
+            }
+            if let Some(precision_power) = stats.precision_power {
+                descs.push(format!(
+                    "{} {:.3}",
+                    i18n.get_msg("common-stats-precision_power"),
+                    precision_power
+                ));
+            }
+            if let Some(stealth) = stats.stealth {
+                descs.push(format!(
+                    "{} {:.3}",
+                    i18n.get_msg("common-stats-stealth"),
+                    stealth
+                ));
+            }
+            if let Some(resistances) = stats.resistances {
+                descs.push(resistances_desc(&resistances, i18n));
+            }
+            descs.join(", ")
+        },
+        SetBonusEffect::Buff { kind, .. } => get_buff_title(kind, i18n).into_owned(),
+    };
+
+    format!(
+        "{} : {}",
+        i18n.get_msg_ctx("common-stats-set_bonus", &fluent_args! {
+            "pieces" => bonus.pieces,
+        }),
+        effect
+    )
+}
+
 /// Gets the durability of an item in a format more intuitive for UI
 pub fn item_durability(item: &dyn ItemDesc) -> Option<u32> {
     let durability = item
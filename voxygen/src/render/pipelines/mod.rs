@@ -106,6 +106,7 @@ impl Globals {
         tgt_detail: f32,
         map_bounds: Vec2<f32>,
         time_of_day: f64,
+        fog_density: f32,
         tick: f64,
         client_tick: f64,
         screen_res: Vec2<u16>,
@@ -141,7 +142,7 @@ impl Globals {
                 // per tick on defualt server settings. So those per tick changes can't
                 // really be fully represented at a value above `50.0`.
                 (time_of_day / (3600.0 * 24.0) % 1000.0) as f32,
-                0.0,
+                fog_density,
                 0.0,
             ],
             sun_dir: Vec4::from_direction(TimeOfDay::new(time_of_day).get_sun_dir()).into_array(),
@@ -210,6 +211,7 @@ impl Default for Globals {
             0.0,
             0.0,
             0.0,
+            0.0,
             Vec2::new(800, 500),
             Vec2::new(1.0, 25.0),
             0,
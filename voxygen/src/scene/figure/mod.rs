@@ -1025,7 +1025,7 @@ impl FigureMgr {
                 can_shadow(sun_dir, can_shadow_sun, visible_psr_bounds),
                 can_shadow(
                     weather.rain_vel(),
-                    weather.rain > RAIN_THRESHOLD,
+                    weather.precipitation() > RAIN_THRESHOLD,
                     visible_por_bounds,
                 ),
             )
@@ -251,7 +251,14 @@ impl Lod {
             [size.x, size.y],
             &weather
                 .iter()
-                .map(|(_, w)| [(w.cloud * 255.0) as u8, (w.rain * 255.0) as u8, 0, 0])
+                .map(|(_, w)| {
+                    [
+                        (w.cloud * 255.0) as u8,
+                        (w.precipitation() * 255.0) as u8,
+                        0,
+                        0,
+                    ]
+                })
                 .collect::<Vec<_>>(),
         );
     }
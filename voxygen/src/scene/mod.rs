@@ -901,6 +901,7 @@ impl Scene {
             self.lod.get_data().tgt_detail as f32,
             self.map_bounds,
             time_of_day,
+            client.weather_at_player().fog,
             scene_data.state.get_time(),
             self.local_time,
             renderer.resolution().as_(),
@@ -1244,7 +1245,7 @@ impl Scene {
             .state()
             .max_weather_near(focus_off.xy() + cam_pos.xy());
         self.wind_vel = weather.wind_vel();
-        if weather.rain > RAIN_THRESHOLD {
+        if weather.precipitation() > RAIN_THRESHOLD {
             let weather = client.weather_at_player();
             let rain_vel = weather.rain_vel();
             let rain_view_mat = math::Mat4::look_at_rh(look_at, look_at + rain_vel, up);
@@ -1260,6 +1261,7 @@ impl Scene {
                 texture_mat,
                 rain_dir_mat,
                 weather.rain,
+                weather.snow,
                 self.integrated_rain_vel,
             );
 
@@ -1384,7 +1386,7 @@ impl Scene {
         let is_daylight = sun_dir.z < 0.0;
         let focus_pos = self.camera.get_focus_pos();
         let cam_pos = self.camera.dependents().cam_pos + focus_pos.map(|e| e.trunc());
-        let is_rain = state.max_weather_near(cam_pos.xy()).rain > RAIN_THRESHOLD;
+        let is_rain = state.max_weather_near(cam_pos.xy()).precipitation() > RAIN_THRESHOLD;
         let culling_mode = if scene_data
             .state
             .terrain()
@@ -10,6 +10,7 @@ use crate::{
 };
 use common::{
     assets::{AssetExt, DotVoxAsset},
+    calendar::{Calendar, Season},
     comp::{
         self, Beam, Body, CharacterActivity, CharacterState, Fluid, Inventory, Ori, PhysicsState,
         Pos, Scale, Shockwave, Vel, ability::Dodgeable, aura, beam, body, buff, item::Reagent,
@@ -2392,6 +2393,17 @@ impl ParticleMgr {
                 mode: ParticleMode::Leaf,
                 cond: |_| true,
             },
+            // Leaves fall much more often in autumn
+            BlockParticles {
+                blocks: |boi| BlockParticleSlice::Positions(&boi.leaves),
+                range: 4,
+                rate: 0.05,
+                lifetime: 30.0,
+                mode: ParticleMode::Leaf,
+                cond: |sd| {
+                    sd.state.ecs().read_resource::<Calendar>().season() == Some(Season::Autumn)
+                },
+            },
             BlockParticles {
                 blocks: |boi| BlockParticleSlice::Positions(&boi.drip),
                 range: 4,
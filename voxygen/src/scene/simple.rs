@@ -239,6 +239,7 @@ impl Scene {
             self.lod.get_data().tgt_detail as f32,
             self.map_bounds,
             TIME,
+            0.0,
             scene_data.time,
             0.0,
             renderer.resolution().as_(),
@@ -1466,10 +1466,11 @@ impl<V: RectRasterableVol> Terrain<V> {
         drop(guard);
         span!(guard, "Rain occlusion magic");
         // Check if there is rain near the camera
-        let max_weather = scene_data
+        let max_precipitation = scene_data
             .state
-            .max_weather_near(focus_off.xy() + cam_pos.xy());
-        let (visible_occlusion_volume, visible_por_bounds) = if max_weather.rain > RAIN_THRESHOLD {
+            .max_weather_near(focus_off.xy() + cam_pos.xy())
+            .precipitation();
+        let (visible_occlusion_volume, visible_por_bounds) = if max_precipitation > RAIN_THRESHOLD {
             let visible_bounding_box = math::Aabb::<f32> {
                 min: (visible_bounding_box.min - focus_off),
                 max: (visible_bounding_box.max - focus_off),
@@ -1746,6 +1746,9 @@ impl PlayState for SessionState {
                     HudEvent::UnlockSkill(skill) => {
                         self.client.borrow_mut().unlock_skill(skill);
                     },
+                    HudEvent::AbandonQuest(quest_id) => {
+                        self.client.borrow_mut().abandon_quest(quest_id);
+                    },
                     HudEvent::UseSlot {
                         slot,
                         bypass_dialog,
@@ -94,6 +94,7 @@ fn migrate_old_singleplayer(from: &Path, to: &Path) {
                     FileOpts::Load(path) => (Some(path), None),
                     FileOpts::LoadAsset(asset) => (Some(asset_path(&asset)), None),
                     FileOpts::Save(_, gen_opts) => (None, Some(gen_opts)),
+                    FileOpts::Heightmap(heightmap) => (None, Some(heightmap.opts)),
                 })
             })
             .unwrap_or((Some(asset_path(DEFAULT_WORLD_MAP)), None));
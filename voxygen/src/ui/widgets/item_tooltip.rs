@@ -279,6 +279,8 @@ const IMAGE_W_FRAC: f64 = 0.3;
 const ICON_SIZE: [f64; 2] = [64.0, 64.0];
 /// Total item tooltip width
 const WIDTH: f64 = 320.0;
+/// Text color of set bonuses that are not currently active
+const INACTIVE_SET_BONUS_COLOR: Color = Color::Rgba(0.5, 0.5, 0.5, 1.0);
 
 /// A widget for displaying tooltips
 #[derive(Clone, WidgetCommon)]
@@ -908,6 +910,19 @@ impl Widget for ItemTooltip<'_> {
                     index += 1;
                 }
 
+                // Elemental resistances
+                if let Some(resistances) = armor_stats.resistances {
+                    stat_text(
+                        format!(
+                            "{} : {}",
+                            i18n.get_msg("common-stats-resistances"),
+                            util::resistances_desc(&resistances, i18n)
+                        ),
+                        index,
+                    );
+                    index += 1;
+                }
+
                 // Slots
                 if item.num_slots() > 0 {
                     stat_text(
@@ -931,6 +946,40 @@ impl Widget for ItemTooltip<'_> {
                         ),
                         index,
                     );
+                    index += 1;
+                }
+
+                // Set bonuses, greyed out while not enough pieces of the set are equipped
+                if let Some(set) = armor.set() {
+                    let equipped_pieces = self
+                        .inventory
+                        .and_then(|inv| inv.armor_set_pieces().get(set).copied())
+                        .unwrap_or(0);
+                    for bonus in self.msm.armor_set_bonuses(set) {
+                        let color = if bonus.is_active(equipped_pieces) {
+                            text_color
+                        } else {
+                            INACTIVE_SET_BONUS_COLOR
+                        };
+                        widget::Text::new(&util::set_bonus_desc(bonus, i18n))
+                            .graphics_for(id)
+                            .parent(id)
+                            .with_style(self.style.desc)
+                            .color(color)
+                            .and(|t| {
+                                if index == 0 {
+                                    t.x_align_to(
+                                        state.ids.item_frame,
+                                        conrod_core::position::Align::Start,
+                                    )
+                                    .down_from(state.ids.item_frame, V_PAD)
+                                } else {
+                                    t.down_from(state.ids.stats[index - 1], V_PAD_STATS)
+                                }
+                            })
+                            .set(state.ids.stats[index], ui);
+                        index += 1;
+                    }
                 }
 
                 if let Some(equipped_item) = equipped_item {
@@ -1034,6 +1083,7 @@ impl Widget for ItemTooltip<'_> {
                             }
                         }
                         index += armor_stats.stealth.is_some() as usize;
+                        index += armor_stats.resistances.is_some() as usize;
 
                         if armor_durability != equipped_durability && item.has_durability() {
                             let diff = armor_durability.unwrap_or(Item::MAX_DURABILITY) as i32
@@ -17,7 +17,8 @@ fn cave(c: &mut Criterion) {
         },
         &pool,
         &|_| {},
-    );
+    )
+    .unwrap();
     let land = Land::from_sim(world.sim());
     let mut group = c.benchmark_group("cave");
     group.sample_size(10);
@@ -26,7 +26,7 @@ use tracing::{Level, Span, debug, error, info, info_span};
 use tracing_subscriber::EnvFilter;
 use vek::{Aabr, Rgb, Vec2};
 use veloren_world::{
-    CONFIG, IndexOwned, World, WorldGenerateStage,
+    IndexOwned, World, WorldGenerateStage,
     sim::{FileOpts, GenOpts, WorldOpts, WorldSimStage, get_horizon_map, sample_pos, sample_wpos},
 };
 
@@ -80,6 +80,7 @@ impl BatchGenerateConfig {
             scale: thread_rng().gen_range(self.scale.clone()),
             map_kind: self.kind,
             erosion_quality: thread_rng().gen_range(self.erosion_quality.clone()),
+            ..GenOpts::default()
         }
     }
 }
@@ -161,7 +162,8 @@ fn generate_one(
                 })
             }
         },
-    );
+    )
+    .unwrap();
 
     if save_image {
         let index_ref = index.as_index_ref();
@@ -174,8 +176,8 @@ fn generate_one(
                 min: Vec2::zero(),
                 max: map_size_lg.chunks().map(|e| e as i32),
             },
-            CONFIG.sea_level,
-            CONFIG.sea_level + sampler.max_height,
+            sampler.config.sea_level,
+            sampler.config.sea_level + sampler.max_height,
             |posi| {
                 let sample = sampler.get(uniform_idx_as_vec2(map_size_lg, posi)).unwrap();
 
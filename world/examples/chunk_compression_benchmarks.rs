@@ -707,7 +707,8 @@ fn main() {
         },
         &pool,
         &|_| {},
-    );
+    )
+    .unwrap();
     println!("Loaded world");
     const HISTOGRAMS: bool = false;
     let mut histogram: HashMap<Vec<u8>, usize> = HashMap::new();
@@ -0,0 +1,114 @@
+use std::{fs, path::PathBuf};
+
+use clap::{Parser, Subcommand};
+use tracing::{Level, info};
+use tracing_subscriber::EnvFilter;
+use vek::{Aabb, Vec3};
+use veloren_world::{
+    World,
+    export::{ExportError, RasterFormat, RasterLayer},
+    sim::{DEFAULT_WORLD_MAP, DEFAULT_WORLD_SEED, FileOpts, WorldOpts},
+};
+
+/// Exports a world's terrain to formats understood by other tools.
+#[derive(Parser)]
+struct Cli {
+    #[command(subcommand)]
+    subcommand: Action,
+    /// World file to load, defaults to the default world map
+    #[arg(long)]
+    map: Option<PathBuf>,
+    /// Seed the world was generated with
+    #[arg(long, default_value_t = DEFAULT_WORLD_SEED)]
+    seed: u32,
+    /// Directory the exported files are written to
+    #[arg(short, long, default_value = "export")]
+    out: PathBuf,
+}
+
+#[derive(Subcommand)]
+enum Action {
+    /// Per-chunk rasters of altitude, water, biome, temperature or humidity
+    Raster {
+        /// Layers to export, defaults to all of them
+        layers: Vec<RasterLayer>,
+        /// `png16` or `raw`
+        #[arg(short, long, default_value = "png16")]
+        format: RasterFormat,
+    },
+    /// Sites, landmarks, roads and cave entrances as GeoJSON
+    Overlays,
+    /// A region of blocks as a MagicaVoxel .vox file
+    Region {
+        /// One corner of the region, as `x,y,z`
+        #[arg(value_parser = parse_pos)]
+        min: Vec3<i32>,
+        /// The opposite corner of the region, as `x,y,z`
+        #[arg(value_parser = parse_pos)]
+        max: Vec3<i32>,
+    },
+}
+
+fn parse_pos(s: &str) -> Result<Vec3<i32>, String> {
+    let coords = s
+        .split(',')
+        .map(|e| e.trim().parse::<i32>().map_err(|err| err.to_string()))
+        .collect::<Result<Vec<_>, _>>()?;
+    match coords[..] {
+        [x, y, z] => Ok(Vec3::new(x, y, z)),
+        _ => Err(format!("expected a position as x,y,z, got '{s}'")),
+    }
+}
+
+fn main() -> Result<(), ExportError> {
+    tracing_subscriber::fmt()
+        .with_max_level(Level::INFO)
+        .with_env_filter(EnvFilter::from_default_env())
+        .init();
+
+    let cli = Cli::parse();
+    let threadpool = rayon::ThreadPoolBuilder::new().build().unwrap();
+    let (world, index) = World::generate(
+        cli.seed,
+        WorldOpts {
+            seed_elements: true,
+            world_file: match cli.map {
+                Some(path) => FileOpts::Load(path),
+                None => FileOpts::LoadAsset(DEFAULT_WORLD_MAP.into()),
+            },
+            calendar: None,
+        },
+        &threadpool,
+        &|_| {},
+    )
+    .unwrap();
+    let index = index.as_index_ref();
+
+    fs::create_dir_all(&cli.out)?;
+    match cli.subcommand {
+        Action::Raster { layers, format } => {
+            let layers = if layers.is_empty() {
+                RasterLayer::ALL.to_vec()
+            } else {
+                layers
+            };
+            for layer in layers {
+                let path = world.export_raster(layer).save(&cli.out, format)?;
+                info!(?path, "Exported raster");
+            }
+        },
+        Action::Overlays => {
+            let path = cli.out.join("overlays.geojson");
+            let file = fs::File::create(&path)?;
+            serde_json::to_writer_pretty(file, &world.export_overlays(index))?;
+            info!(?path, "Exported overlays");
+        },
+        Action::Region { min, max } => {
+            let path = cli.out.join(format!("{}_{}_{}.vox", min.x, min.y, min.z));
+            world.export_region(index, Aabb { min, max })?.save(&path)?;
+            info!(?path, "Exported region");
+        },
+    }
+
+    Ok(())
+}
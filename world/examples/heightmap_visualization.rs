@@ -128,7 +128,8 @@ fn main() {
         },
         &pool,
         &|_| {},
-    );
+    )
+    .unwrap();
     println!("Loaded world");
 
     let land = Land::from_sim(world.sim());
@@ -170,7 +170,8 @@ fn main() {
         },
         &pool,
         &|_| {},
-    );
+    )
+    .unwrap();
     println!("Loaded world");
 
     if let Err(e) = good_pricing_csv(&world, &index) {
@@ -15,7 +15,8 @@ fn main() {
         },
         &threadpool,
         &|_| {},
-    );
+    )
+    .unwrap();
 
     let index = index.as_index_ref();
 
@@ -151,7 +151,8 @@ fn generate(db_path: &str, ymin: Option<i32>, ymax: Option<i32>) -> Result<(), B
         },
         &pool,
         &|_| {},
-    );
+    )
+    .unwrap();
     println!("Loaded world");
 
     let conn = block_statistics_db(db_path)?;
@@ -18,7 +18,8 @@ fn main() {
         },
         &threadpool,
         &|_| {},
-    );
+    )
+    .unwrap();
     core::hint::black_box((world, index));
     println!("{} ms", start.elapsed().as_nanos() / 1_000_000);
 }
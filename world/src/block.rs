@@ -1,10 +1,10 @@
 use crate::{
-    CONFIG, IndexRef,
+    IndexRef,
     column::{ColumnGen, ColumnSample},
     util::{FastNoise, RandomField, Sampler, SmallCache},
 };
 use common::{
-    calendar::{Calendar, CalendarEvent},
+    calendar::{Calendar, CalendarEvent, Season},
     comp::item::ItemDefinitionIdOwned,
     terrain::{
         Block, BlockKind, SpriteCfg, SpriteKind, UnlockKind,
@@ -144,7 +144,7 @@ impl<'a> BlockGen<'a> {
             let over_water = alt < water_level;
             // Water
             if over_water && (wposf.z as f32 - water_level).abs() < ice_depth {
-                Some(Block::new(BlockKind::Ice, CONFIG.ice_color))
+                Some(Block::new(BlockKind::Ice, sample.config.ice_color))
             } else if (wposf.z as f32) < water_level {
                 // Ocean
                 Some(water)
@@ -286,8 +286,14 @@ pub fn block_from_structure(
                 && field.chance(pos + structure_pos, 0.025)
             {
                 Some(Block::new(BlockKind::GlowingWeakRock, Rgb::new(255, 0, 0)))
-            } else if calendar.is_some_and(|c| c.is_event(CalendarEvent::Halloween))
-                && (*sblock == StructureBlock::TemperateLeaves
+            } else if calendar.is_some_and(|c| {
+                c.is_event(CalendarEvent::Halloween)
+                    // Trees turn one by one as autumn goes on
+                    || c.seasons().is_some_and(|s| {
+                        s.season() == Season::Autumn
+                            && (structure_seed % 64) as f32 / 64.0 < s.season_progress() * 1.5 + 0.25
+                    })
+            }) && (*sblock == StructureBlock::TemperateLeaves
                     || *sblock == StructureBlock::Chestnut
                     || *sblock == StructureBlock::CherryLeaves)
             {
@@ -122,6 +122,7 @@ impl<'a> CanvasInfo<'a> {
             cliff_height: 0.0,
             contains_waypoint: false,
             spot: None,
+            landmark: None,
         };
         f(&CanvasInfo {
             chunk_pos: Vec2::zero(),
@@ -6,7 +6,7 @@ mod econ;
 use crate::{
     Index, IndexRef, Land,
     civ::airship_travel::Airships,
-    config::CONFIG,
+    config::SiteRule,
     sim::WorldSim,
     site::{Castle, Settlement, Site as WorldSite, Tree, namegen::NameGen},
     site2,
@@ -30,6 +30,7 @@ use core::{fmt, hash::BuildHasherDefault, ops::Range};
 use fxhash::FxHasher64;
 use rand::prelude::*;
 use rand_chacha::ChaChaRng;
+use serde::{Deserialize, Serialize};
 use tracing::{debug, info, warn};
 use vek::*;
 
@@ -59,6 +60,34 @@ pub struct Civs {
 
     pub sites: Store<Site>,
     pub airships: Airships,
+
+    /// What the site distribution of the world preset actually placed
+    pub site_report: SitePlacementReport,
+}
+
+/// The number of sites of each kind placed by the world preset's site
+/// distribution
+#[derive(Debug, Default)]
+pub struct SitePlacementReport {
+    pub placed: Vec<(SiteKind, u32)>,
+    /// Kinds that couldn't be placed as many times as their minimum requires,
+    /// along with how many are missing
+    pub shortfalls: Vec<(SiteKind, u32)>,
+}
+
+impl SitePlacementReport {
+    fn log(&self) {
+        for (kind, count) in &self.placed {
+            info!(?kind, ?count, "placed sites");
+        }
+        for (kind, missing) in &self.shortfalls {
+            warn!(
+                ?kind,
+                ?missing,
+                "couldn't find enough locations for the minimum number of sites"
+            );
+        }
+    }
 }
 
 // Change this to get rid of particularly horrid seeds
@@ -239,6 +268,7 @@ impl Civs {
     ) -> Self {
         prof_span!("Civs::generate");
         let mut this = Self::default();
+        let config = sim.config.clone();
         let rng = ChaChaRng::from_seed(seed_expan::rng_state(seed));
         let name_rng = rng.clone();
         let mut name_ctx = GenCtx { sim, rng: name_rng };
@@ -251,7 +281,9 @@ impl Civs {
             this.name_biomes(&mut name_ctx);
         }
 
-        let initial_civ_count = initial_civ_count(sim.map_size_lg());
+        let initial_civ_count =
+            ((initial_civ_count(sim.map_size_lg()) as f32 * config.sites.civ_scale).round() as u32)
+                .max(1);
         let mut ctx = GenCtx { sim, rng };
 
         // info!("starting cave generation");
@@ -273,214 +305,57 @@ impl Civs {
         report_stage(WorldCivStage::SiteGeneration);
         prof_span!(guard, "find locations and establish sites");
         let world_dims = ctx.sim.get_aabr();
-        for _ in 0..initial_civ_count * 3 {
-            attempt(5, || {
-                let (loc, kind) = match ctx.rng.gen_range(0..116) {
-                    0..=4 => {
-                        if index.features().site2_giant_trees {
-                            (
-                                find_site_loc(
-                                    &mut ctx,
-                                    &ProximityRequirementsBuilder::new()
-                                        .avoid_all_of(this.tree_enemies(), 40)
-                                        .finalize(&world_dims),
-                                    &SiteKind::GiantTree,
-                                )?,
-                                SiteKind::GiantTree,
-                            )
-                        } else {
-                            (
-                                find_site_loc(
-                                    &mut ctx,
-                                    &ProximityRequirementsBuilder::new()
-                                        .avoid_all_of(this.tree_enemies(), 40)
-                                        .finalize(&world_dims),
-                                    &SiteKind::Tree,
-                                )?,
-                                SiteKind::Tree,
-                            )
-                        }
-                    },
-                    5..=15 => (
-                        find_site_loc(
-                            &mut ctx,
-                            &ProximityRequirementsBuilder::new()
-                                .avoid_all_of(this.gnarling_enemies(), 40)
-                                .finalize(&world_dims),
-                            &SiteKind::Gnarling,
-                        )?,
-                        SiteKind::Gnarling,
-                    ),
-                    16..=20 => (
-                        find_site_loc(
-                            &mut ctx,
-                            &ProximityRequirementsBuilder::new()
-                                .avoid_all_of(this.chapel_site_enemies(), 40)
-                                .finalize(&world_dims),
-                            &SiteKind::ChapelSite,
-                        )?,
-                        SiteKind::ChapelSite,
-                    ),
-                    21..=27 => (
-                        find_site_loc(
-                            &mut ctx,
-                            &ProximityRequirementsBuilder::new()
-                                .avoid_all_of(this.gnarling_enemies(), 40)
-                                .finalize(&world_dims),
-                            &SiteKind::Adlet,
-                        )?,
-                        SiteKind::Adlet,
-                    ),
-                    28..=38 => (
-                        find_site_loc(
-                            &mut ctx,
-                            &ProximityRequirementsBuilder::new()
-                                .avoid_all_of(this.pirate_hideout_enemies(), 40)
-                                .finalize(&world_dims),
-                            &SiteKind::PirateHideout,
-                        )?,
-                        SiteKind::PirateHideout,
-                    ),
-                    39..=45 => (
-                        find_site_loc(
-                            &mut ctx,
-                            &ProximityRequirementsBuilder::new()
-                                .avoid_all_of(this.jungle_ruin_enemies(), 40)
-                                .finalize(&world_dims),
-                            &SiteKind::JungleRuin,
-                        )?,
-                        SiteKind::JungleRuin,
-                    ),
-                    46..=55 => (
-                        find_site_loc(
-                            &mut ctx,
-                            &ProximityRequirementsBuilder::new()
-                                .avoid_all_of(this.rock_circle_enemies(), 40)
-                                .finalize(&world_dims),
-                            &SiteKind::RockCircle,
-                        )?,
-                        SiteKind::RockCircle,
-                    ),
-                    56..=66 => (
-                        find_site_loc(
-                            &mut ctx,
-                            &ProximityRequirementsBuilder::new()
-                                .avoid_all_of(this.troll_cave_enemies(), 40)
-                                .finalize(&world_dims),
-                            &SiteKind::TrollCave,
-                        )?,
-                        SiteKind::TrollCave,
-                    ),
-                    67..=72 => (
-                        find_site_loc(
-                            &mut ctx,
-                            &ProximityRequirementsBuilder::new()
-                                .avoid_all_of(this.camp_enemies(), 40)
-                                .finalize(&world_dims),
-                            &SiteKind::Camp,
-                        )?,
-                        SiteKind::Camp,
-                    ),
-                    73..=76 => (
-                        find_site_loc(
-                            &mut ctx,
-                            &ProximityRequirementsBuilder::new()
-                                .avoid_all_of(this.mine_site_enemies(), 40)
-                                .finalize(&world_dims),
-                            &SiteKind::Haniwa,
-                        )?,
-                        SiteKind::Haniwa,
-                    ),
-                    77..=81 => (
-                        find_site_loc(
-                            &mut ctx,
-                            &ProximityRequirementsBuilder::new()
-                                .avoid_all_of(this.terracotta_enemies(), 40)
-                                .finalize(&world_dims),
-                            &SiteKind::Terracotta,
-                        )?,
-                        SiteKind::Terracotta,
-                    ),
-                    82..=87 => (
-                        find_site_loc(
-                            &mut ctx,
-                            &ProximityRequirementsBuilder::new()
-                                .avoid_all_of(this.mine_site_enemies(), 40)
-                                .finalize(&world_dims),
-                            &SiteKind::DwarvenMine,
-                        )?,
-                        SiteKind::DwarvenMine,
-                    ),
-                    88..=91 => (
-                        find_site_loc(
-                            &mut ctx,
-                            &ProximityRequirementsBuilder::new()
-                                .avoid_all_of(this.cultist_enemies(), 40)
-                                .finalize(&world_dims),
-                            &SiteKind::Cultist,
-                        )?,
-                        SiteKind::Cultist,
-                    ),
-                    92..=96 => (
-                        find_site_loc(
-                            &mut ctx,
-                            &ProximityRequirementsBuilder::new()
-                                .avoid_all_of(this.sahagin_enemies(), 40)
-                                .finalize(&world_dims),
-                            &SiteKind::Sahagin,
-                        )?,
-                        SiteKind::Sahagin,
-                    ),
-                    97..=102 => (
-                        find_site_loc(
-                            &mut ctx,
-                            &ProximityRequirementsBuilder::new()
-                                .avoid_all_of(this.vampire_castle_enemies(), 40)
-                                .finalize(&world_dims),
-                            &SiteKind::VampireCastle,
-                        )?,
-                        SiteKind::VampireCastle,
-                    ),
-                    103..108 => (
-                        find_site_loc(
-                            &mut ctx,
-                            &ProximityRequirementsBuilder::new().finalize(&world_dims),
-                            &SiteKind::GliderCourse,
-                        )?,
-                        SiteKind::GliderCourse,
-                    ),
-                    /*103..=108 => (
-                        find_site_loc(
-                            &mut ctx,
-                            &ProximityRequirementsBuilder::new()
-                                .avoid_all_of(this.castle_enemies(), 40)
-                                .close_to_one_of(this.towns(), 20)
-                                .finalize(&world_dims),
-                            &SiteKind::Castle,
-                        )?,
-                        SiteKind::Castle,
-                    ),
-                    109..=114 => (SiteKind::Citadel, (&castle_enemies, 20)),
-                    */
-                    _ => (
-                        find_site_loc(
-                            &mut ctx,
-                            &ProximityRequirementsBuilder::new()
-                                .avoid_all_of(this.myrmidon_enemies(), 40)
-                                .finalize(&world_dims),
-                            &SiteKind::Myrmidon,
-                        )?,
-                        SiteKind::Myrmidon,
-                    ),
-                };
-                Some(this.establish_site(&mut ctx.reseed(), loc, |place| Site {
-                    kind,
-                    center: loc,
-                    place,
-                    site_tmp: None,
-                }))
-            });
+        let rules = config.sites.enabled_rules().collect::<Vec<_>>();
+        let total_weight = rules.iter().map(|rule| rule.weight).sum::<u32>();
+        let mut placed = vec![0; rules.len()];
+        if total_weight > 0 {
+            for _ in 0..initial_civ_count * config.sites.sites_per_civ {
+                attempt(5, || {
+                    let mut roll = ctx.rng.gen_range(0..total_weight);
+                    let idx = rules.iter().position(|rule| {
+                        roll = match roll.checked_sub(rule.weight) {
+                            Some(roll) => roll,
+                            None => return true,
+                        };
+                        false
+                    })?;
+                    if rules[idx].max.is_some_and(|max| placed[idx] >= max) {
+                        return None;
+                    }
+                    let site = this.place_site(&mut ctx, index, &world_dims, rules[idx])?;
+                    placed[idx] += 1;
+                    Some(site)
+                });
+            }
+        }
+        // Top up the kinds that the random rolls didn't place often enough
+        for (idx, rule) in rules.iter().enumerate() {
+            for _ in 0..rule.min.saturating_sub(placed[idx]) * 5 {
+                if placed[idx] >= rule.min {
+                    break;
+                }
+                if this
+                    .place_site(&mut ctx, index, &world_dims, rule)
+                    .is_some()
+                {
+                    placed[idx] += 1;
+                }
+            }
         }
+        this.site_report = SitePlacementReport {
+            placed: rules
+                .iter()
+                .zip(&placed)
+                .map(|(rule, count)| (rule.kind, *count))
+                .collect(),
+            shortfalls: rules
+                .iter()
+                .zip(&placed)
+                .filter(|(rule, count)| **count < rule.min)
+                .map(|(rule, count)| (rule.kind, rule.min - *count))
+                .collect(),
+        };
+        this.site_report.log();
         drop(guard);
 
         // Tick
@@ -553,7 +428,7 @@ impl Civs {
                             // to worry about the case where water_alt is already set to a correct
                             // value higher than alt, since this chunk should have been filtered
                             // out in that case).
-                            chunk.water_alt = CONFIG.sea_level.max(chunk.water_alt + diff);
+                            chunk.water_alt = config.sea_level.max(chunk.water_alt + diff);
                             chunk.alt += diff;
                             chunk.basement += diff;
                             chunk.rockiness = 0.0;
@@ -996,7 +871,7 @@ impl Civs {
             }
             to_floodfill.push(exploring);
             // Should always be a chunk on the map
-            let biome = ctx.sim.chunks[exploring].get_biome();
+            let biome = ctx.sim.chunks[exploring].get_biome(&ctx.sim.config);
             let mut filled = Vec::new();
 
             while let Some(filling) = to_floodfill.pop() {
@@ -1006,7 +881,7 @@ impl Civs {
                     if explored[neighbour] {
                         continue;
                     }
-                    let n_biome = ctx.sim.chunks[neighbour].get_biome();
+                    let n_biome = ctx.sim.chunks[neighbour].get_biome(&ctx.sim.config);
                     if n_biome == biome {
                         to_floodfill.push(neighbour);
                     } else {
@@ -1268,6 +1143,7 @@ impl Civs {
         const MIN_MOUNTAIN_CHAOS: f32 = 0.35;
         let rng = &mut ctx.rng;
         let sim_chunks = &ctx.sim.chunks;
+        let sea_level = ctx.sim.config.sea_level;
         let peaks = sim_chunks
             .iter()
             .enumerate()
@@ -1283,7 +1159,7 @@ impl Civs {
                 (
                     posi,
                     uniform_idx_as_vec2(map_size_lg, posi),
-                    (chunk.alt - CONFIG.sea_level) as u32,
+                    (chunk.alt - sea_level) as u32,
                 )
             })
             .collect::<Vec<(usize, Vec2<i32>, u32)>>();
@@ -1336,6 +1212,68 @@ impl Civs {
         info!(?num_peaks, "all peaks named");
     }
 
+    /// Finds a location for a site following `rule` and establishes it there
+    fn place_site(
+        &mut self,
+        ctx: &mut GenCtx<impl Rng>,
+        index: &Index,
+        world_dims: &Aabr<i32>,
+        rule: &SiteRule,
+    ) -> Option<Id<Site>> {
+        let kind = match rule.kind {
+            SiteKind::GiantTree if !index.features().site2_giant_trees => SiteKind::Tree,
+            kind => kind,
+        };
+        let mut proximity = ProximityRequirementsBuilder::new();
+        if rule.avoid_radius > 0 {
+            proximity = self.avoid_enemies(proximity, &kind, rule.avoid_radius);
+        }
+        if let Some(radius) = rule.near_town {
+            proximity = proximity.close_to_one_of(self.towns(), radius);
+        }
+        let loc = find_site_loc(ctx, &proximity.finalize(world_dims), &kind)?;
+        Some(self.establish_site(&mut ctx.reseed(), loc, |place| Site {
+            kind,
+            center: loc,
+            place,
+            site_tmp: None,
+        }))
+    }
+
+    fn avoid_enemies(
+        &self,
+        proximity: ProximityRequirementsBuilder,
+        kind: &SiteKind,
+        radius: i32,
+    ) -> ProximityRequirementsBuilder {
+        match kind {
+            SiteKind::Tree | SiteKind::GiantTree => {
+                proximity.avoid_all_of(self.tree_enemies(), radius)
+            },
+            SiteKind::Gnarling => proximity.avoid_all_of(self.gnarling_enemies(), radius),
+            SiteKind::ChapelSite => proximity.avoid_all_of(self.chapel_site_enemies(), radius),
+            SiteKind::Adlet => proximity.avoid_all_of(self.adlet_enemies(), radius),
+            SiteKind::PirateHideout => {
+                proximity.avoid_all_of(self.pirate_hideout_enemies(), radius)
+            },
+            SiteKind::JungleRuin => proximity.avoid_all_of(self.jungle_ruin_enemies(), radius),
+            SiteKind::RockCircle => proximity.avoid_all_of(self.rock_circle_enemies(), radius),
+            SiteKind::TrollCave => proximity.avoid_all_of(self.troll_cave_enemies(), radius),
+            SiteKind::Camp => proximity.avoid_all_of(self.camp_enemies(), radius),
+            SiteKind::Haniwa | SiteKind::DwarvenMine => {
+                proximity.avoid_all_of(self.mine_site_enemies(), radius)
+            },
+            SiteKind::Terracotta => proximity.avoid_all_of(self.terracotta_enemies(), radius),
+            SiteKind::Cultist => proximity.avoid_all_of(self.cultist_enemies(), radius),
+            SiteKind::Sahagin => proximity.avoid_all_of(self.sahagin_enemies(), radius),
+            SiteKind::VampireCastle => {
+                proximity.avoid_all_of(self.vampire_castle_enemies(), radius)
+            },
+            SiteKind::Myrmidon => proximity.avoid_all_of(self.myrmidon_enemies(), radius),
+            _ => proximity.avoid_all_of(self.sites().map(|s| s.center), radius),
+        }
+    }
+
     fn establish_site(
         &mut self,
         ctx: &mut GenCtx<impl Rng>,
@@ -1811,11 +1749,11 @@ fn town_attributes_of_site(loc: Vec2<i32>, sim: &WorldSim) -> Option<TownSiteAtt
                         if c.tree_density > 0.7 {
                             tree_chunks += 1;
                         }
-                        if c.rockiness < 0.3 && c.temp > CONFIG.snow_temp {
+                        if c.rockiness < 0.3 && c.temp > sim.config.snow_temp {
                             if c.surface_veg > 0.5 {
                                 farmable_chunks += 1;
                             } else {
-                                match c.get_biome() {
+                                match c.get_biome(&sim.config) {
                                     common::terrain::BiomeKind::Savannah => {
                                         farmable_needs_irrigation_chunks += 1
                                     },
@@ -1840,18 +1778,21 @@ fn town_attributes_of_site(loc: Vec2<i32>, sim: &WorldSim) -> Option<TownSiteAtt
         let has_river = river_chunks > 1;
         let has_lake = lake_chunks > 1;
         let vegetation_implies_potable_water = chunk.tree_density > 0.4
-            && !matches!(chunk.get_biome(), common::terrain::BiomeKind::Swamp);
+            && !matches!(
+                chunk.get_biome(&sim.config),
+                common::terrain::BiomeKind::Swamp
+            );
         let has_many_rocks = chunk.rockiness > 1.2;
-        let warm_or_firewood = chunk.temp > CONFIG.snow_temp || tree_chunks > 2;
+        let warm_or_firewood = chunk.temp > sim.config.snow_temp || tree_chunks > 2;
         let has_potable_water =
             { has_river || (has_lake && chunk.alt > 100.0) || vegetation_implies_potable_water };
         let has_building_materials = tree_chunks > 0
             || rock_chunks > 0
-            || chunk.temp > CONFIG.tropical_temp && (has_river || has_lake);
+            || chunk.temp > sim.config.tropical_temp && (has_river || has_lake);
         let water_rich = lake_chunks + river_chunks > 2;
         let can_grow_rice = water_rich
-            && chunk.humidity + 1.0 > CONFIG.jungle_hum
-            && chunk.temp + 1.0 > CONFIG.tropical_temp;
+            && chunk.humidity + 1.0 > sim.config.jungle_hum
+            && chunk.temp + 1.0 > sim.config.tropical_temp;
         let farming_score = if can_grow_rice {
             farmable_chunks * 2
         } else {
@@ -1942,7 +1883,7 @@ impl fmt::Display for Site {
     }
 }
 
-#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
 pub enum SiteKind {
     Settlement,
     Castle,
@@ -1974,6 +1915,32 @@ pub enum SiteKind {
 }
 
 impl SiteKind {
+    /// Whether sites of this kind are placed by the world preset's site
+    /// distribution, rather than by civilisations
+    pub fn is_distributed(&self) -> bool {
+        matches!(
+            self,
+            SiteKind::Tree
+                | SiteKind::GiantTree
+                | SiteKind::Gnarling
+                | SiteKind::ChapelSite
+                | SiteKind::Adlet
+                | SiteKind::PirateHideout
+                | SiteKind::JungleRuin
+                | SiteKind::RockCircle
+                | SiteKind::TrollCave
+                | SiteKind::Camp
+                | SiteKind::Haniwa
+                | SiteKind::Terracotta
+                | SiteKind::DwarvenMine
+                | SiteKind::Cultist
+                | SiteKind::Sahagin
+                | SiteKind::VampireCastle
+                | SiteKind::GliderCourse
+                | SiteKind::Myrmidon
+        )
+    }
+
     pub fn is_suitable_loc(&self, loc: Vec2<i32>, sim: &WorldSim) -> bool {
         let on_land = || -> bool {
             if let Some(chunk) = sim.get(loc) {
@@ -1982,7 +1949,7 @@ impl SiteKind {
                     && !chunk.river.is_river()
                     && !chunk.is_underwater()
                     && !matches!(
-                        chunk.get_biome(),
+                        chunk.get_biome(&sim.config),
                         common::terrain::BiomeKind::Lake | common::terrain::BiomeKind::Ocean
                     )
             } else {
@@ -2016,7 +1983,7 @@ impl SiteKind {
                 },
                 SiteKind::Adlet => chunk.temp < -0.2 && chunk.cliff_height > 25.0,
                 SiteKind::DwarvenMine => {
-                    matches!(chunk.get_biome(), BiomeKind::Forest | BiomeKind::Desert)
+                    matches!(chunk.get_biome(&sim.config), BiomeKind::Forest | BiomeKind::Desert)
                         && !chunk.near_cliffs()
                         && !chunk.river.near_water()
                         && on_flat_terrain()
@@ -2034,7 +2001,7 @@ impl SiteKind {
                 },
                 SiteKind::Citadel => true,
                 SiteKind::CliffTown => {
-                    chunk.temp >= CONFIG.desert_temp
+                    chunk.temp >= sim.config.desert_temp
                         && chunk.cliff_height > 40.0
                         && chunk.rockiness > 1.2
                         && suitable_for_town()
@@ -2043,24 +2010,24 @@ impl SiteKind {
                     chunk.alt > 1400.0
                 },
                 SiteKind::SavannahTown => {
-                    matches!(chunk.get_biome(), BiomeKind::Savannah)
+                    matches!(chunk.get_biome(&sim.config), BiomeKind::Savannah)
                         && !chunk.near_cliffs()
                         && !chunk.river.near_water()
                         && suitable_for_town()
                 },
                 SiteKind::CoastalTown => {
-                    (2.0..3.5).contains(&(chunk.water_alt - CONFIG.sea_level))
+                    (2.0..3.5).contains(&(chunk.water_alt - sim.config.sea_level))
                         && suitable_for_town()
                 },
                 SiteKind::PirateHideout => {
-                    (0.5..3.5).contains(&(chunk.water_alt - CONFIG.sea_level))
+                    (0.5..3.5).contains(&(chunk.water_alt - sim.config.sea_level))
                 },
                 SiteKind::Sahagin => {
-                    matches!(chunk.get_biome(), BiomeKind::Ocean)
-                    && (40.0..45.0).contains(&(CONFIG.sea_level - chunk.alt))
+                    matches!(chunk.get_biome(&sim.config), BiomeKind::Ocean)
+                    && (40.0..45.0).contains(&(sim.config.sea_level - chunk.alt))
                 },
                 SiteKind::JungleRuin => {
-                    matches!(chunk.get_biome(), BiomeKind::Jungle)
+                    matches!(chunk.get_biome(&sim.config), BiomeKind::Jungle)
                 },
                 SiteKind::RockCircle => !chunk.near_cliffs() && !chunk.river.near_water(),
                 SiteKind::TrollCave => {
@@ -2078,13 +2045,13 @@ impl SiteKind {
                         && !chunk.river.near_water()
                 },
                 SiteKind::ChapelSite => {
-                    matches!(chunk.get_biome(), BiomeKind::Ocean)
-                        && CONFIG.sea_level < chunk.alt + 1.0
+                    matches!(chunk.get_biome(&sim.config), BiomeKind::Ocean)
+                        && sim.config.sea_level < chunk.alt + 1.0
                 },
                 SiteKind::Terracotta => {
                     (0.9..1.0).contains(&chunk.temp)
                         && on_land()
-                        && (chunk.water_alt - CONFIG.sea_level) > 50.0
+                        && (chunk.water_alt - sim.config.sea_level) > 50.0
                         && on_flat_terrain()
                         && !chunk.river.near_water()
                         && !chunk.near_cliffs()
@@ -2092,7 +2059,7 @@ impl SiteKind {
                 SiteKind::Myrmidon => {
                     (0.9..1.0).contains(&chunk.temp)
                         && on_land()
-                        && (chunk.water_alt - CONFIG.sea_level) > 50.0
+                        && (chunk.water_alt - sim.config.sea_level) > 50.0
                         && on_flat_terrain()
                         && !chunk.river.near_water()
                         && !chunk.near_cliffs()
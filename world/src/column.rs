@@ -1,12 +1,12 @@
 use crate::{
-    CONFIG, IndexRef,
+    Config, IndexRef,
     all::ForestKind,
     sim::{Path, RiverKind, SimChunk, WorldSim, local_cells},
     site::SpawnRules,
     util::{RandomField, RandomPerm, Sampler},
 };
 use common::{
-    calendar::{Calendar, CalendarEvent},
+    calendar::{Calendar, CalendarEvent, Season},
     terrain::{
         CoordinateConversions, TerrainChunkSize, quadratic_nearest_point, river_spline_coeffs,
         uniform_idx_as_vec2, vec2_as_uniform_idx,
@@ -81,7 +81,9 @@ impl<'a> Sampler<'a> for ColumnGen<'a> {
         let wposf_turb = wposf; // + turb.map(|e| e as f64);
 
         let chaos = sim.get_interpolated(wpos, |chunk| chunk.chaos)?;
-        let temp = sim.get_interpolated(wpos, |chunk| chunk.temp)?;
+        // Seasons shift the temperature of the whole world
+        let temp = sim.get_interpolated(wpos, |chunk| chunk.temp)?
+            + calendar.map_or(0.0, |c| c.temperature_offset());
         let humidity = sim.get_interpolated(wpos, |chunk| chunk.humidity)?;
         let rockiness = sim.get_interpolated(wpos, |chunk| chunk.rockiness)?;
         let tree_density = sim.get_interpolated(wpos, |chunk| chunk.tree_density)?;
@@ -113,6 +115,11 @@ impl<'a> Sampler<'a> for ColumnGen<'a> {
             .sites
             .iter()
             .map(|site| index.sites[*site].spawn_rules(wpos))
+            .chain(
+                sim_chunk
+                    .landmark
+                    .map(|landmark| index.landmarks[landmark].spawn_rules(wpos)),
+            )
             .fold(SpawnRules::default(), |a, b| a.combine(b));
 
         const SAMP_RES: i32 = 8;
@@ -375,7 +382,7 @@ impl<'a> Sampler<'a> for ColumnGen<'a> {
             })
             .collect::<Vec<_>>();
 
-        debug_assert!(sim_chunk.water_alt >= CONFIG.sea_level);
+        debug_assert!(sim_chunk.water_alt >= sim.config.sea_level);
 
         /// A type that makes managing surface altitude weighting much simpler.
         #[derive(Default)]
@@ -467,7 +474,7 @@ impl<'a> Sampler<'a> for ColumnGen<'a> {
         }
 
         // Use this to temporarily alter the sea level
-        let base_sea_level = CONFIG.sea_level - 1.0 + 0.01;
+        let base_sea_level = sim.config.sea_level - 1.0 + 0.01;
 
         // What's going on here?
         //
@@ -988,14 +995,14 @@ impl<'a> Sampler<'a> for ColumnGen<'a> {
             Lerp::lerp(
                 dead_tundra,
                 sand,
-                temp.sub(CONFIG.snow_temp)
-                    .div(CONFIG.desert_temp.sub(CONFIG.snow_temp))
+                temp.sub(sim.config.snow_temp)
+                    .div(sim.config.desert_temp.sub(sim.config.snow_temp))
                     .mul(0.5),
             ),
             dirt,
             humidity
-                .sub(CONFIG.desert_hum)
-                .div(CONFIG.forest_hum.sub(CONFIG.desert_hum))
+                .sub(sim.config.desert_hum)
+                .div(sim.config.forest_hum.sub(sim.config.desert_hum))
                 .mul(1.0),
         );
 
@@ -1012,33 +1019,33 @@ impl<'a> Sampler<'a> for ColumnGen<'a> {
                             tundra,
                             // snow_temp to temperate_temp
                             dirt,
-                            temp.sub(CONFIG.snow_temp)
-                                .div(CONFIG.temperate_temp.sub(CONFIG.snow_temp))
+                            temp.sub(sim.config.snow_temp)
+                                .div(sim.config.temperate_temp.sub(sim.config.snow_temp))
                                 /*.sub((marble - 0.5) * 0.05)
                                 .mul(256.0)*/
                                 .mul(1.0),
                         ),
                         // temperate_temp to tropical_temp
                         grass,
-                        temp.sub(CONFIG.temperate_temp)
-                            .div(CONFIG.tropical_temp.sub(CONFIG.temperate_temp))
+                        temp.sub(sim.config.temperate_temp)
+                            .div(sim.config.tropical_temp.sub(sim.config.temperate_temp))
                             .mul(4.0),
                     ),
                     // tropical_temp to desert_temp
                     moss,
-                    temp.sub(CONFIG.tropical_temp)
-                        .div(CONFIG.desert_temp.sub(CONFIG.tropical_temp))
+                    temp.sub(sim.config.tropical_temp)
+                        .div(sim.config.desert_temp.sub(sim.config.tropical_temp))
                         .mul(1.0),
                 ),
                 // above desert_temp
                 sand,
-                temp.sub(CONFIG.desert_temp)
-                    .div(1.0 - CONFIG.desert_temp)
+                temp.sub(sim.config.desert_temp)
+                    .div(1.0 - sim.config.desert_temp)
                     .mul(4.0),
             ),
             humidity
-                .sub(CONFIG.desert_hum)
-                .div(CONFIG.forest_hum.sub(CONFIG.desert_hum))
+                .sub(sim.config.desert_hum)
+                .div(sim.config.forest_hum.sub(sim.config.desert_hum))
                 .mul(1.25),
         );
         // From forest to jungle humidity, we go from snow to dark grass to grass to
@@ -1051,25 +1058,25 @@ impl<'a> Sampler<'a> for ColumnGen<'a> {
                         snow_moss,
                         // temperate_temp to tropical_temp
                         grass,
-                        temp.sub(CONFIG.temperate_temp)
-                            .div(CONFIG.tropical_temp.sub(CONFIG.temperate_temp))
+                        temp.sub(sim.config.temperate_temp)
+                            .div(sim.config.tropical_temp.sub(sim.config.temperate_temp))
                             .mul(4.0),
                     ),
                     // tropical_temp to desert_temp
                     tropical,
-                    temp.sub(CONFIG.tropical_temp)
-                        .div(CONFIG.desert_temp.sub(CONFIG.tropical_temp))
+                    temp.sub(sim.config.tropical_temp)
+                        .div(sim.config.desert_temp.sub(sim.config.tropical_temp))
                         .mul(1.0),
                 ),
                 // above desert_temp
                 sand,
-                temp.sub(CONFIG.desert_temp)
-                    .div(1.0 - CONFIG.desert_temp)
+                temp.sub(sim.config.desert_temp)
+                    .div(1.0 - sim.config.desert_temp)
                     .mul(4.0),
             ),
             humidity
-                .sub(CONFIG.forest_hum)
-                .div(CONFIG.jungle_hum.sub(CONFIG.forest_hum))
+                .sub(sim.config.forest_hum)
+                .div(sim.config.jungle_hum.sub(sim.config.forest_hum))
                 .mul(1.0),
         );
         // From jungle humidity upwards, we go from snow to grass to rainforest to
@@ -1082,34 +1089,34 @@ impl<'a> Sampler<'a> for ColumnGen<'a> {
                         snow_moss,
                         // temperate_temp to tropical_temp
                         rainforest,
-                        temp.sub(CONFIG.temperate_temp)
-                            .div(CONFIG.tropical_temp.sub(CONFIG.temperate_temp))
+                        temp.sub(sim.config.temperate_temp)
+                            .div(sim.config.tropical_temp.sub(sim.config.temperate_temp))
                             .mul(4.0),
                     ),
                     // tropical_temp to desert_temp
                     tropical,
-                    temp.sub(CONFIG.tropical_temp)
-                        .div(CONFIG.desert_temp.sub(CONFIG.tropical_temp))
+                    temp.sub(sim.config.tropical_temp)
+                        .div(sim.config.desert_temp.sub(sim.config.tropical_temp))
                         .mul(4.0),
                 ),
                 // above desert_temp
                 sand,
-                temp.sub(CONFIG.desert_temp)
-                    .div(1.0 - CONFIG.desert_temp)
+                temp.sub(sim.config.desert_temp)
+                    .div(1.0 - sim.config.desert_temp)
                     .mul(4.0),
             ),
-            humidity.sub(CONFIG.jungle_hum).mul(1.0),
+            humidity.sub(sim.config.jungle_hum).mul(1.0),
         );
 
         // Snow covering
         let thematic_snow = calendar.is_some_and(|c| c.is_event(CalendarEvent::Christmas));
         let snow_factor = temp
             .sub(if thematic_snow {
-                CONFIG.tropical_temp
+                sim.config.tropical_temp
             } else {
-                CONFIG.snow_temp
+                sim.config.snow_temp
             })
-            .max(-humidity.sub(CONFIG.desert_hum))
+            .max(-humidity.sub(sim.config.desert_hum))
             .mul(4.0)
             .max(-0.25)
             // 'Simulate' avalanches moving snow from areas with high gradients to areas with high flux
@@ -1252,7 +1259,11 @@ impl<'a> Sampler<'a> for ColumnGen<'a> {
             rock_density: if spawn_rules.trees { rock_density } else { 0.0 },
             temp,
             humidity,
-            spawn_rate,
+            spawn_rate: if spawn_rules.wildlife {
+                spawn_rate
+            } else {
+                0.0
+            },
             stone_col,
             water_dist,
             gradient,
@@ -1262,8 +1273,10 @@ impl<'a> Sampler<'a> for ColumnGen<'a> {
             cliff_height,
             water_vel,
             ice_depth,
+            season: calendar.and_then(|c| c.season()),
 
             chunk: sim_chunk,
+            config: &self.sim.config,
         })
     }
 }
@@ -1296,8 +1309,12 @@ pub struct ColumnSample<'a> {
     pub cliff_height: f32,
     pub water_vel: Vec3<f32>,
     pub ice_depth: f32,
+    /// `None` if seasons are disabled.
+    pub season: Option<Season>,
 
     pub chunk: &'a SimChunk,
+    /// The preset of the world
+    pub config: &'a Config,
 }
 
 impl ColumnSample<'_> {
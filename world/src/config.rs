@@ -1,7 +1,19 @@
-use common::assets;
-use serde::Deserialize;
+use crate::civ::SiteKind;
+use common::assets::{self, AssetExt};
+use serde::{Deserialize, Serialize};
+use std::{borrow::Cow, fmt};
 use vek::*;
 
+/// The asset specifier of the world preset used when none is given
+pub const DEFAULT_WORLD_PRESET: &str = "world.preset.default";
+
+/// Climate, terrain and river parameters of a world, loaded from a world
+/// preset.
+///
+/// The preset a world was generated with is stored in its world file, so that
+/// loading the world again produces the same terrain.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
 pub struct Config {
     pub sea_level: f32,
     pub mountain_scale: f32,
@@ -54,9 +66,93 @@ pub struct Config {
     pub river_width_to_depth: f32,
     /// TODO: Move to colors.ron when blockgen can access it
     pub ice_color: Rgb<u8>,
+    /// Which sites are placed outside of civilisations, and how often.
+    pub sites: SiteDistribution,
+}
+
+/// How the sites that don't belong to a civilisation (dungeons, camps, trees,
+/// ...) are distributed over the world.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct SiteDistribution {
+    /// Multiplier for the number of civilisations, which scales both the
+    /// number of towns and the number of other sites.
+    pub civ_scale: f32,
+    /// How many sites are attempted to be placed per civilisation.
+    pub sites_per_civ: u32,
+    /// Site kinds that are never placed, even if they have a rule.
+    pub disabled: Vec<SiteKind>,
+    /// The kinds of site that can be placed. Replaces the built-in rules
+    /// entirely when given.
+    pub rules: Cow<'static, [SiteRule]>,
+}
+
+impl Default for SiteDistribution {
+    fn default() -> Self { DEFAULT_SITES }
+}
+
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct SiteRule {
+    pub kind: SiteKind,
+    /// Relative chance of this kind being picked for each placement attempt.
+    pub weight: u32,
+    /// Additional attempts are made to place at least this many.
+    #[serde(default)]
+    pub min: u32,
+    #[serde(default)]
+    pub max: Option<u32>,
+    /// Minimum distance to other sites, in chunks.
+    #[serde(default = "default_avoid_radius")]
+    pub avoid_radius: i32,
+    /// If set, the site must be within this many chunks of a town.
+    #[serde(default)]
+    pub near_town: Option<i32>,
+}
+
+fn default_avoid_radius() -> i32 { 40 }
+
+const fn site_rule(kind: SiteKind, weight: u32, avoid_radius: i32) -> SiteRule {
+    SiteRule {
+        kind,
+        weight,
+        min: 0,
+        max: None,
+        avoid_radius,
+        near_town: None,
+    }
 }
 
-pub const CONFIG: Config = Config {
+/// The built-in site rules. Their order matters, since it decides which kind
+/// each random roll picks.
+const DEFAULT_SITE_RULES: &[SiteRule] = &[
+    site_rule(SiteKind::GiantTree, 5, 40),
+    site_rule(SiteKind::Gnarling, 11, 40),
+    site_rule(SiteKind::ChapelSite, 5, 40),
+    site_rule(SiteKind::Adlet, 7, 40),
+    site_rule(SiteKind::PirateHideout, 11, 40),
+    site_rule(SiteKind::JungleRuin, 7, 40),
+    site_rule(SiteKind::RockCircle, 10, 40),
+    site_rule(SiteKind::TrollCave, 11, 40),
+    site_rule(SiteKind::Camp, 6, 40),
+    site_rule(SiteKind::Haniwa, 4, 40),
+    site_rule(SiteKind::Terracotta, 5, 40),
+    site_rule(SiteKind::DwarvenMine, 6, 40),
+    site_rule(SiteKind::Cultist, 4, 40),
+    site_rule(SiteKind::Sahagin, 5, 40),
+    site_rule(SiteKind::VampireCastle, 6, 40),
+    site_rule(SiteKind::GliderCourse, 5, 0),
+    site_rule(SiteKind::Myrmidon, 8, 40),
+];
+
+const DEFAULT_SITES: SiteDistribution = SiteDistribution {
+    civ_scale: 1.0,
+    sites_per_civ: 3,
+    disabled: Vec::new(),
+    rules: Cow::Borrowed(DEFAULT_SITE_RULES),
+};
+
+/// The built-in preset, matching `assets/world/preset/default.ron`
+pub const DEFAULT_CONFIG: Config = Config {
     sea_level: 140.0,
     mountain_scale: 2048.0,
     // temperature
@@ -75,8 +171,208 @@ pub const CONFIG: Config = Config {
     river_min_height: 0.25,
     river_width_to_depth: 8.0,
     ice_color: Rgb::new(140, 175, 255),
+    sites: DEFAULT_SITES,
 };
 
+impl Default for Config {
+    fn default() -> Self { DEFAULT_CONFIG }
+}
+
+impl assets::Asset for Config {
+    type Loader = assets::RonLoader;
+
+    const EXTENSION: &'static str = "ron";
+}
+
+#[derive(Debug)]
+pub enum ConfigError {
+    /// The preset asset could not be loaded or parsed.
+    Load(String, assets::Error),
+    /// A parameter lies outside of the range that world generation can handle.
+    OutOfRange {
+        field: &'static str,
+        value: f32,
+        min: f32,
+        max: f32,
+    },
+    /// Two thresholds that must be increasing are out of order.
+    Unordered {
+        lower: &'static str,
+        upper: &'static str,
+    },
+    /// A site rule is given for a kind of site that is only placed by
+    /// civilisations.
+    UnplaceableSite(SiteKind),
+    /// More than one site rule is given for the same kind of site.
+    DuplicateSite(SiteKind),
+    /// A site rule requires more sites than it allows.
+    SiteCount { kind: SiteKind, min: u32, max: u32 },
+}
+
+impl fmt::Display for ConfigError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Load(specifier, err) => {
+                write!(f, "failed to load world preset '{specifier}': {err}")
+            },
+            Self::OutOfRange {
+                field,
+                value,
+                min,
+                max,
+            } => write!(
+                f,
+                "`{field}` is {value}, but must be between {min} and {max}"
+            ),
+            Self::Unordered { lower, upper } => {
+                write!(f, "`{lower}` must be lower than `{upper}`")
+            },
+            Self::UnplaceableSite(kind) => {
+                write!(f, "{kind:?} sites are only placed by civilisations")
+            },
+            Self::DuplicateSite(kind) => write!(f, "{kind:?} sites have more than one rule"),
+            Self::SiteCount { kind, min, max } => write!(
+                f,
+                "{kind:?} sites have a minimum count of {min}, above their maximum of {max}"
+            ),
+        }
+    }
+}
+
+impl std::error::Error for ConfigError {}
+
+impl Config {
+    /// Loads and validates the world preset with the given asset specifier.
+    pub fn load_preset(specifier: &str) -> Result<Self, ConfigError> {
+        let config = Self::load_owned(specifier)
+            .map_err(|err| ConfigError::Load(specifier.to_string(), err))?;
+        config.validate()?;
+        Ok(config)
+    }
+
+    /// Checks that the parameters can be used to generate a world.
+    pub fn validate(&self) -> Result<(), ConfigError> {
+        let in_range = |field, value: f32, min, max| {
+            if value.is_finite() && (min..=max).contains(&value) {
+                Ok(())
+            } else {
+                Err(ConfigError::OutOfRange {
+                    field,
+                    value,
+                    min,
+                    max,
+                })
+            }
+        };
+        let ordered = |lower: (&'static str, f32), upper: (&'static str, f32)| {
+            if lower.1 < upper.1 {
+                Ok(())
+            } else {
+                Err(ConfigError::Unordered {
+                    lower: lower.0,
+                    upper: upper.0,
+                })
+            }
+        };
+
+        in_range("sea_level", self.sea_level, 0.0, 2048.0)?;
+        in_range("mountain_scale", self.mountain_scale, 1.0, 8192.0)?;
+        in_range("snow_temp", self.snow_temp, -1.0, 1.0)?;
+        in_range("temperate_temp", self.temperate_temp, -1.0, 1.0)?;
+        in_range("tropical_temp", self.tropical_temp, -1.0, 1.0)?;
+        in_range("desert_temp", self.desert_temp, -1.0, 1.0)?;
+        in_range("desert_hum", self.desert_hum, 0.0, 1.0)?;
+        in_range("forest_hum", self.forest_hum, 0.0, 1.0)?;
+        in_range("jungle_hum", self.jungle_hum, 0.0, 1.0)?;
+        in_range(
+            "rainfall_chunk_rate",
+            self.rainfall_chunk_rate,
+            f32::EPSILON,
+            1.0,
+        )?;
+        in_range("river_roughness", self.river_roughness, f32::EPSILON, 1.0)?;
+        in_range("river_max_width", self.river_max_width, f32::EPSILON, 16.0)?;
+        in_range("river_min_height", self.river_min_height, 0.0, 16.0)?;
+        in_range(
+            "river_width_to_depth",
+            self.river_width_to_depth,
+            f32::EPSILON,
+            64.0,
+        )?;
+
+        ordered(
+            ("snow_temp", self.snow_temp),
+            ("temperate_temp", self.temperate_temp),
+        )?;
+        ordered(
+            ("temperate_temp", self.temperate_temp),
+            ("tropical_temp", self.tropical_temp),
+        )?;
+        ordered(
+            ("tropical_temp", self.tropical_temp),
+            ("desert_temp", self.desert_temp),
+        )?;
+        ordered(
+            ("desert_hum", self.desert_hum),
+            ("forest_hum", self.forest_hum),
+        )?;
+        ordered(
+            ("forest_hum", self.forest_hum),
+            ("jungle_hum", self.jungle_hum),
+        )?;
+
+        self.sites.validate()
+    }
+}
+
+impl SiteDistribution {
+    fn validate(&self) -> Result<(), ConfigError> {
+        if !(self.civ_scale.is_finite() && (0.0..=16.0).contains(&self.civ_scale)) {
+            return Err(ConfigError::OutOfRange {
+                field: "sites.civ_scale",
+                value: self.civ_scale,
+                min: 0.0,
+                max: 16.0,
+            });
+        }
+
+        for (i, rule) in self.rules.iter().enumerate() {
+            if !rule.kind.is_distributed() {
+                return Err(ConfigError::UnplaceableSite(rule.kind));
+            }
+            if self.rules[..i].iter().any(|r| r.kind == rule.kind) {
+                return Err(ConfigError::DuplicateSite(rule.kind));
+            }
+            if let Some(max) = rule.max
+                && rule.min > max
+            {
+                return Err(ConfigError::SiteCount {
+                    kind: rule.kind,
+                    min: rule.min,
+                    max,
+                });
+            }
+            if rule.avoid_radius < 0 {
+                return Err(ConfigError::OutOfRange {
+                    field: "sites.rules.avoid_radius",
+                    value: rule.avoid_radius as f32,
+                    min: 0.0,
+                    max: i32::MAX as f32,
+                });
+            }
+        }
+
+        Ok(())
+    }
+
+    /// The rules of the kinds of site that can be placed
+    pub fn enabled_rules(&self) -> impl Iterator<Item = &SiteRule> {
+        self.rules
+            .iter()
+            .filter(|rule| !self.disabled.contains(&rule.kind))
+    }
+}
+
 #[derive(Deserialize)]
 pub struct Features {
     pub caverns: bool,
@@ -87,6 +383,7 @@ pub struct Features {
     pub scatter: bool,
     pub paths: bool,
     pub spots: bool,
+    pub landmarks: bool,
     pub site2_towns: bool,
     pub site2_giant_trees: bool,
     // 1.0 is the default wildlife density
@@ -101,3 +398,86 @@ impl assets::Asset for Features {
 
     const EXTENSION: &'static str = "ron";
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn default_preset_matches_builtin() {
+        let config = Config::load_preset(DEFAULT_WORLD_PRESET).unwrap();
+        assert_eq!(config, DEFAULT_CONFIG);
+    }
+
+    #[test]
+    fn validate_presets() {
+        for preset in [
+            "world.preset.archipelago",
+            "world.preset.frozen",
+            "world.preset.dungeons",
+        ] {
+            Config::load_preset(preset).unwrap();
+        }
+    }
+
+    #[test]
+    fn validate_site_rules() {
+        let mut config = DEFAULT_CONFIG;
+        config.sites.rules = vec![SiteRule {
+            min: 3,
+            max: Some(1),
+            ..site_rule(SiteKind::Camp, 1, 40)
+        }]
+        .into();
+        assert!(matches!(
+            config.validate(),
+            Err(ConfigError::SiteCount {
+                kind: SiteKind::Camp,
+                ..
+            })
+        ));
+
+        config.sites.rules = vec![site_rule(SiteKind::Settlement, 1, 40)].into();
+        assert!(matches!(
+            config.validate(),
+            Err(ConfigError::UnplaceableSite(SiteKind::Settlement))
+        ));
+
+        config.sites.rules = vec![
+            site_rule(SiteKind::Camp, 1, 40),
+            site_rule(SiteKind::Camp, 2, 40),
+        ]
+        .into();
+        assert!(matches!(
+            config.validate(),
+            Err(ConfigError::DuplicateSite(SiteKind::Camp))
+        ));
+    }
+
+    #[test]
+    fn validate_ranges() {
+        let config = Config {
+            sea_level: -5.0,
+            ..DEFAULT_CONFIG
+        };
+        assert!(matches!(
+            config.validate(),
+            Err(ConfigError::OutOfRange {
+                field: "sea_level",
+                ..
+            })
+        ));
+
+        let config = Config {
+            snow_temp: 0.5,
+            ..DEFAULT_CONFIG
+        };
+        assert!(matches!(
+            config.validate(),
+            Err(ConfigError::Unordered {
+                lower: "snow_temp",
+                upper: "temperate_temp",
+            })
+        ));
+    }
+}
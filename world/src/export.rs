@@ -0,0 +1,645 @@
+//! Exporting generated worlds to formats understood by other tools: rasters of
+//! per-chunk data (16-bit PNG, or raw `f32` with a JSON sidecar), GeoJSON
+//! overlays of sites, roads and caves, and block regions as MagicaVoxel
+//! `.vox` files.
+//!
+//! All positions are world block coordinates, with `+y` pointing north.
+
+use crate::{IndexRef, World, civ::Civs, land::Land, layer, sim::WorldSim, util::NEIGHBORS};
+use common::{
+    terrain::{BiomeKind, CoordinateConversions, TerrainChunkSize},
+    vol::RectVolSize,
+};
+use hashbrown::HashMap;
+use image::{ImageBuffer, Luma};
+use serde::Serialize;
+use serde_json::{Value, json};
+use std::{
+    fmt,
+    fs::File,
+    io::{self, BufWriter, Write},
+    path::{Path, PathBuf},
+    str::FromStr,
+};
+use strum::IntoEnumIterator;
+use vek::*;
+
+#[derive(Debug)]
+pub enum ExportError {
+    Io(io::Error),
+    Image(image::ImageError),
+    Json(serde_json::Error),
+    /// The requested block region holds more than [`VoxRegion::MAX_VOLUME`]
+    /// blocks.
+    RegionTooLarge(Vec3<u32>),
+}
+
+impl fmt::Display for ExportError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Io(err) => write!(f, "i/o error: {err}"),
+            Self::Image(err) => write!(f, "failed to encode image: {err}"),
+            Self::Json(err) => write!(f, "failed to encode json: {err}"),
+            Self::RegionTooLarge(size) => write!(
+                f,
+                "region of {}x{}x{} blocks is larger than the maximum of {} blocks",
+                size.x,
+                size.y,
+                size.z,
+                VoxRegion::MAX_VOLUME
+            ),
+        }
+    }
+}
+
+impl std::error::Error for ExportError {}
+
+impl From<io::Error> for ExportError {
+    fn from(err: io::Error) -> Self { Self::Io(err) }
+}
+
+impl From<image::ImageError> for ExportError {
+    fn from(err: image::ImageError) -> Self { Self::Image(err) }
+}
+
+impl From<serde_json::Error> for ExportError {
+    fn from(err: serde_json::Error) -> Self { Self::Json(err) }
+}
+
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum RasterLayer {
+    Altitude,
+    WaterAltitude,
+    Biome,
+    Temperature,
+    Humidity,
+}
+
+impl RasterLayer {
+    pub const ALL: [Self; 5] = [
+        Self::Altitude,
+        Self::WaterAltitude,
+        Self::Biome,
+        Self::Temperature,
+        Self::Humidity,
+    ];
+
+    pub fn name(&self) -> &'static str {
+        match self {
+            Self::Altitude => "altitude",
+            Self::WaterAltitude => "water_altitude",
+            Self::Biome => "biome",
+            Self::Temperature => "temperature",
+            Self::Humidity => "humidity",
+        }
+    }
+
+    fn units(&self) -> &'static str {
+        match self {
+            Self::Altitude | Self::WaterAltitude => "blocks",
+            Self::Biome => "category",
+            // Both are normalised to -1..1 and 0..1 respectively by worldgen
+            Self::Temperature | Self::Humidity => "relative",
+        }
+    }
+
+    /// Biomes are stored as the index of the biome in [`BiomeKind`], rather
+    /// than being rescaled.
+    fn is_categorical(&self) -> bool { matches!(self, Self::Biome) }
+}
+
+impl FromStr for RasterLayer {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Self::ALL
+            .into_iter()
+            .find(|layer| layer.name() == s)
+            .ok_or_else(|| {
+                let names = Self::ALL.map(|layer| layer.name()).join(", ");
+                format!("unknown layer '{s}', expected one of: {names}")
+            })
+    }
+}
+
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum RasterFormat {
+    /// Grayscale 16-bit PNG. Continuous layers are rescaled from `min..=max`
+    /// (recorded in the metadata) to `0..=65535`.
+    Png16,
+    /// Little-endian `f32` samples without any header.
+    Raw,
+}
+
+impl RasterFormat {
+    fn extension(&self) -> &'static str {
+        match self {
+            Self::Png16 => "png",
+            Self::Raw => "raw",
+        }
+    }
+}
+
+impl FromStr for RasterFormat {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "png" | "png16" => Ok(Self::Png16),
+            "raw" => Ok(Self::Raw),
+            _ => Err(format!("unknown format '{s}', expected one of: png16, raw")),
+        }
+    }
+}
+
+/// Describes how to read a raster back, written next to it as
+/// `<layer>.json`.
+#[derive(Debug, Serialize)]
+pub struct RasterMetadata {
+    pub layer: &'static str,
+    pub format: &'static str,
+    pub width: u32,
+    pub height: u32,
+    /// The width of the area covered by one sample, in blocks.
+    pub sample_size: u32,
+    /// Rows are stored from north to south, and samples within a row from west
+    /// to east, so the first sample covers the chunk at `(0, height - 1)`.
+    pub row_order: &'static str,
+    pub units: &'static str,
+    pub min: f32,
+    pub max: f32,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub categories: Option<Vec<String>>,
+}
+
+/// One sample per chunk of a [`RasterLayer`].
+pub struct Raster {
+    pub layer: RasterLayer,
+    /// Width and height in samples.
+    pub size: Vec2<u32>,
+    /// Samples in the order described by [`RasterMetadata::row_order`].
+    pub samples: Vec<f32>,
+}
+
+impl Raster {
+    pub fn new(sim: &WorldSim, layer: RasterLayer) -> Self {
+        let size = sim.get_size();
+        let samples = (0..size.y as i32)
+            .rev()
+            .flat_map(|y| (0..size.x as i32).map(move |x| Vec2::new(x, y)))
+            .map(|pos| {
+                sim.get(pos).map_or(0.0, |chunk| match layer {
+                    RasterLayer::Altitude => chunk.alt,
+                    RasterLayer::WaterAltitude => chunk.water_alt,
+                    RasterLayer::Biome => biome_index(chunk.get_biome(&sim.config)) as f32,
+                    RasterLayer::Temperature => chunk.temp,
+                    RasterLayer::Humidity => chunk.humidity,
+                })
+            })
+            .collect();
+
+        Self {
+            layer,
+            size,
+            samples,
+        }
+    }
+
+    /// The smallest and largest sample.
+    pub fn range(&self) -> (f32, f32) {
+        self.samples
+            .iter()
+            .fold((f32::INFINITY, f32::NEG_INFINITY), |(min, max), s| {
+                (min.min(*s), max.max(*s))
+            })
+    }
+
+    pub fn metadata(&self, format: RasterFormat) -> RasterMetadata {
+        let (min, max) = self.range();
+        RasterMetadata {
+            layer: self.layer.name(),
+            format: match format {
+                RasterFormat::Png16 => "png16",
+                RasterFormat::Raw => "f32le",
+            },
+            width: self.size.x,
+            height: self.size.y,
+            sample_size: TerrainChunkSize::RECT_SIZE.x,
+            row_order: "north_to_south",
+            units: self.layer.units(),
+            min,
+            max,
+            categories: self
+                .layer
+                .is_categorical()
+                .then(|| BiomeKind::iter().map(|b| format!("{b:?}")).collect()),
+        }
+    }
+
+    pub fn to_png16(&self) -> ImageBuffer<Luma<u16>, Vec<u16>> {
+        let (min, max) = self.range();
+        let scale = if max > min { 1.0 / (max - min) } else { 0.0 };
+        let pixels = self
+            .samples
+            .iter()
+            .map(|s| {
+                if self.layer.is_categorical() {
+                    *s as u16
+                } else {
+                    ((s - min) * scale * u16::MAX as f32).round() as u16
+                }
+            })
+            .collect();
+        ImageBuffer::from_raw(self.size.x, self.size.y, pixels)
+            .expect("One sample is generated per pixel")
+    }
+
+    pub fn write_raw(&self, writer: &mut impl Write) -> io::Result<()> {
+        self.samples
+            .iter()
+            .try_for_each(|s| writer.write_all(&s.to_le_bytes()))
+    }
+
+    /// Writes the raster and its metadata into `dir`, returning the path of
+    /// the raster.
+    pub fn save(&self, dir: &Path, format: RasterFormat) -> Result<PathBuf, ExportError> {
+        let path = dir.join(format!("{}.{}", self.layer.name(), format.extension()));
+        match format {
+            RasterFormat::Png16 => self.to_png16().save(&path)?,
+            RasterFormat::Raw => {
+                let mut writer = BufWriter::new(File::create(&path)?);
+                self.write_raw(&mut writer)?;
+                writer.flush()?;
+            },
+        }
+        let metadata = File::create(dir.join(format!("{}.json", self.layer.name())))?;
+        serde_json::to_writer_pretty(metadata, &self.metadata(format))?;
+        Ok(path)
+    }
+}
+
+fn biome_index(biome: BiomeKind) -> usize {
+    BiomeKind::iter().position(|b| b == biome).unwrap_or(0)
+}
+
+/// A GeoJSON `FeatureCollection` of sites, landmarks, roads and cave
+/// entrances. Each feature has a `layer` property naming which of these it is.
+pub fn overlays(sim: &WorldSim, civs: &Civs, index: IndexRef) -> Value {
+    let point = |layer: &str, wpos: Vec2<i32>, mut properties: Value| {
+        properties["layer"] = json!(layer);
+        json!({
+            "type": "Feature",
+            "geometry": { "type": "Point", "coordinates": [wpos.x, wpos.y] },
+            "properties": properties,
+        })
+    };
+
+    let sites = civs.sites().map(|site| {
+        let name = site.site_tmp.map(|id| index.sites[id].name().to_string());
+        point(
+            "site",
+            site.center.cpos_to_wpos_center(),
+            json!({ "kind": format!("{:?}", site.kind), "name": name }),
+        )
+    });
+
+    let landmarks = index.landmarks.values().map(|landmark| {
+        point(
+            "landmark",
+            landmark.origin.xy(),
+            json!({ "name": landmark.spec.name }),
+        )
+    });
+
+    let caves = layer::cave::surface_entrances(&Land::from_sim(sim))
+        .map(|wpos| point("cave_entrance", wpos, json!({})));
+
+    // Each chunk on a road is joined to the neighbours it connects to. Only
+    // half of the neighbours are checked so that every segment appears once.
+    let way_pos = |cpos: Vec2<i32>| {
+        let chunk = sim.get(cpos)?;
+        Some(cpos.cpos_to_wpos_center() + chunk.path.0.offset.map(i32::from))
+    };
+    let size = sim.get_size().as_::<i32>();
+    let roads = (0..size.x)
+        .flat_map(|x| (0..size.y).map(move |y| Vec2::new(x, y)))
+        .filter_map(|cpos| Some((cpos, sim.get(cpos)?.path.0.neighbors)))
+        .flat_map(|(cpos, neighbors)| {
+            NEIGHBORS[..4]
+                .iter()
+                .enumerate()
+                .filter(move |(i, _)| neighbors & (1 << *i) != 0)
+                .filter_map(move |(_, dir)| Some((way_pos(cpos)?, way_pos(cpos + *dir)?)))
+        })
+        .map(|(a, b)| {
+            json!({
+                "type": "Feature",
+                "geometry": { "type": "LineString", "coordinates": [[a.x, a.y], [b.x, b.y]] },
+                "properties": { "layer": "road" },
+            })
+        });
+
+    json!({
+        "type": "FeatureCollection",
+        "features": sites
+            .chain(landmarks)
+            .chain(caves)
+            .chain(roads)
+            .collect::<Vec<_>>(),
+    })
+}
+
+/// The solid blocks of a region of generated terrain.
+///
+/// Only the base terrain produced by [`World::sample_blocks`] is included:
+/// trees, sites and other structures are not.
+pub struct VoxRegion {
+    pub size: Vec3<u32>,
+    /// Colours of the blocks in x, then y, then z order, or `None` for blocks
+    /// that are not solid.
+    blocks: Vec<Option<Rgb<u8>>>,
+}
+
+impl VoxRegion {
+    /// The maximum number of blocks in a region, to keep memory usage sane.
+    pub const MAX_VOLUME: u64 = 1 << 26;
+    /// MagicaVoxel can't load models larger than this along any axis, so
+    /// larger regions are split into several models.
+    const MODEL_SIZE: u32 = 256;
+
+    pub fn sample(world: &World, index: IndexRef, aabb: Aabb<i32>) -> Result<Self, ExportError> {
+        let aabb = aabb.made_valid();
+        let size = (aabb.max - aabb.min).map(|e| e as u32 + 1);
+        if size.map(u64::from).product() > Self::MAX_VOLUME {
+            return Err(ExportError::RegionTooLarge(size));
+        }
+
+        let mut blocks = vec![None; size.product() as usize];
+        let mut sampler = world.sample_blocks();
+        for y in 0..size.y as i32 {
+            for x in 0..size.x as i32 {
+                let wpos2d = aabb.min.xy() + Vec2::new(x, y);
+                let Some(z_cache) = sampler.get_z_cache(wpos2d, index, None) else {
+                    continue;
+                };
+                let (min_z, max_z) = z_cache.get_z_limits();
+                for z in 0..size.z as i32 {
+                    let wpos = wpos2d.with_z(aabb.min.z + z);
+                    let color = if wpos.z < min_z as i32 {
+                        Some(z_cache.sample.stone_col)
+                    } else if wpos.z < max_z as i32 {
+                        sampler
+                            .get_with_z_cache(wpos, Some(&z_cache))
+                            .filter(|block| block.is_filled())
+                            .and_then(|block| block.get_color())
+                    } else {
+                        None
+                    };
+                    blocks[Self::idx(size, Vec3::new(x, y, z).as_())] = color;
+                }
+            }
+        }
+
+        Ok(Self { size, blocks })
+    }
+
+    fn idx(size: Vec3<u32>, pos: Vec3<u32>) -> usize {
+        (pos.x + pos.y * size.x + pos.z * size.x * size.y) as usize
+    }
+
+    /// Writes the region as a MagicaVoxel `.vox` file.
+    pub fn write_vox(&self, writer: &mut impl Write) -> io::Result<()> {
+        let palette = Palette::new(self.blocks.iter().flatten().copied());
+
+        // Split the region into models that MagicaVoxel can load
+        let tiles = self.size.map(|e| e.div_ceil(Self::MODEL_SIZE));
+        let mut models = Vec::new();
+        let mut shapes = Vec::new();
+        for tz in 0..tiles.z {
+            for ty in 0..tiles.y {
+                for tx in 0..tiles.x {
+                    let min = Vec3::new(tx, ty, tz) * Self::MODEL_SIZE;
+                    let size = (self.size - min).map(|e| e.min(Self::MODEL_SIZE));
+                    let voxels = (0..size.z)
+                        .flat_map(|z| {
+                            (0..size.y)
+                                .flat_map(move |y| (0..size.x).map(move |x| Vec3::new(x, y, z)))
+                        })
+                        .filter_map(|pos| {
+                            let color = self.blocks[Self::idx(self.size, min + pos)]?;
+                            Some([
+                                pos.x as u8,
+                                pos.y as u8,
+                                pos.z as u8,
+                                palette.index_of(color),
+                            ])
+                        })
+                        .collect::<Vec<_>>();
+                    if voxels.is_empty() {
+                        continue;
+                    }
+                    // Models are positioned by their centre
+                    shapes.push((min + size / 2).as_::<i32>());
+                    models.push((size, voxels));
+                }
+            }
+        }
+
+        let mut main = Vec::new();
+        for (size, voxels) in &models {
+            let mut content = Vec::new();
+            size.map(|e| e as i32)
+                .into_iter()
+                .for_each(|e| content.extend(e.to_le_bytes()));
+            write_chunk(&mut main, b"SIZE", &content);
+
+            let mut content = (voxels.len() as i32).to_le_bytes().to_vec();
+            voxels.iter().for_each(|v| content.extend(v));
+            write_chunk(&mut main, b"XYZI", &content);
+        }
+
+        // Scene graph: a root transform and group, then a transform and shape
+        // for each model.
+        let mut content = Vec::new();
+        write_transform(&mut content, 0, 1, -1, None);
+        write_chunk(&mut main, b"nTRN", &content);
+        let mut content = Vec::new();
+        content.extend(1i32.to_le_bytes());
+        write_dict(&mut content, &[]);
+        content.extend((shapes.len() as i32).to_le_bytes());
+        (0..shapes.len() as i32).for_each(|i| content.extend((2 + i * 2).to_le_bytes()));
+        write_chunk(&mut main, b"nGRP", &content);
+        for (i, translation) in shapes.iter().enumerate() {
+            let node = 2 + i as i32 * 2;
+            let mut content = Vec::new();
+            write_transform(&mut content, node, node + 1, 0, Some(*translation));
+            write_chunk(&mut main, b"nTRN", &content);
+
+            let mut content = Vec::new();
+            content.extend((node + 1).to_le_bytes());
+            write_dict(&mut content, &[]);
+            content.extend(1i32.to_le_bytes());
+            content.extend((i as i32).to_le_bytes());
+            write_dict(&mut content, &[]);
+            write_chunk(&mut main, b"nSHP", &content);
+        }
+
+        // Palette entry `i` is used by voxels with colour index `i + 1`
+        let mut content = Vec::with_capacity(256 * 4);
+        for i in 0..256 {
+            let color = palette.colors.get(i).copied().unwrap_or_default();
+            content.extend([color.r, color.g, color.b, 255]);
+        }
+        write_chunk(&mut main, b"RGBA", &content);
+
+        writer.write_all(b"VOX ")?;
+        writer.write_all(&150i32.to_le_bytes())?;
+        writer.write_all(b"MAIN")?;
+        writer.write_all(&0i32.to_le_bytes())?;
+        writer.write_all(&(main.len() as i32).to_le_bytes())?;
+        writer.write_all(&main)
+    }
+
+    pub fn save(&self, path: &Path) -> Result<(), ExportError> {
+        let mut writer = BufWriter::new(File::create(path)?);
+        self.write_vox(&mut writer)?;
+        Ok(writer.flush()?)
+    }
+}
+
+/// `.vox` files can only hold 255 colours, so when there are more than that
+/// colours are merged by dropping their lowest bits.
+struct Palette {
+    colors: Vec<Rgb<u8>>,
+    shift: u32,
+    indices: HashMap<Rgb<u8>, u8>,
+}
+
+impl Palette {
+    fn new(colors: impl Iterator<Item = Rgb<u8>> + Clone) -> Self {
+        let quantize = |c: Rgb<u8>, shift: u32| c.map(|e| (e >> shift) << shift);
+        let (shift, mut colors) = (0..8)
+            .map(|shift| {
+                let mut unique = colors
+                    .clone()
+                    .map(|c| quantize(c, shift))
+                    .collect::<Vec<_>>();
+                unique.sort_by_key(|c| (c.r, c.g, c.b));
+                unique.dedup();
+                (shift, unique)
+            })
+            .find(|(_, unique)| unique.len() <= 255)
+            .expect("Quantizing to 1 bit per channel leaves at most 8 colours");
+        colors.truncate(255);
+        let indices = colors
+            .iter()
+            .enumerate()
+            .map(|(i, c)| (*c, i as u8 + 1))
+            .collect();
+
+        Self {
+            colors,
+            shift,
+            indices,
+        }
+    }
+
+    fn index_of(&self, color: Rgb<u8>) -> u8 {
+        self.indices[&color.map(|e| (e >> self.shift) << self.shift)]
+    }
+}
+
+fn write_chunk(out: &mut Vec<u8>, id: &[u8; 4], content: &[u8]) {
+    out.extend(id);
+    out.extend((content.len() as i32).to_le_bytes());
+    out.extend(0i32.to_le_bytes());
+    out.extend(content);
+}
+
+fn write_dict(out: &mut Vec<u8>, entries: &[(&str, &str)]) {
+    out.extend((entries.len() as i32).to_le_bytes());
+    for s in entries.iter().flat_map(|(k, v)| [k, v]) {
+        out.extend((s.len() as i32).to_le_bytes());
+        out.extend(s.as_bytes());
+    }
+}
+
+fn write_transform(
+    out: &mut Vec<u8>,
+    node: i32,
+    child: i32,
+    layer: i32,
+    translation: Option<Vec3<i32>>,
+) {
+    out.extend(node.to_le_bytes());
+    write_dict(out, &[]);
+    out.extend(child.to_le_bytes());
+    // Reserved
+    out.extend((-1i32).to_le_bytes());
+    out.extend(layer.to_le_bytes());
+    // Frames
+    out.extend(1i32.to_le_bytes());
+    match translation {
+        Some(t) => write_dict(out, &[("_t", &format!("{} {} {}", t.x, t.y, t.z))]),
+        None => write_dict(out, &[]),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn palette_fits_in_vox() {
+        let colors =
+            (0..=255u8).flat_map(|r| (0..=255u8).step_by(15).map(move |g| Rgb::new(r, g, 7)));
+        let palette = Palette::new(colors.clone());
+        assert!(palette.colors.len() <= 255);
+        for color in colors {
+            assert_ne!(palette.index_of(color), 0);
+        }
+
+        // Few colours are kept exactly
+        let palette = Palette::new([Rgb::new(1, 2, 3), Rgb::new(4, 5, 6)].into_iter());
+        assert_eq!(palette.shift, 0);
+        assert_eq!(palette.index_of(Rgb::new(4, 5, 6)), 2);
+    }
+
+    #[test]
+    fn vox_layout() {
+        let size = Vec3::new(300, 2, 1);
+        let mut blocks = vec![None; 600];
+        blocks[0] = Some(Rgb::new(255, 0, 0));
+        blocks[299] = Some(Rgb::new(0, 255, 0));
+        let region = VoxRegion { size, blocks };
+
+        let mut bytes = Vec::new();
+        region.write_vox(&mut bytes).unwrap();
+        assert_eq!(&bytes[..4], b"VOX ");
+        let main_children = i32::from_le_bytes(bytes[16..20].try_into().unwrap());
+        assert_eq!(main_children as usize, bytes.len() - 20);
+
+        // The region is split into two models along x, each with one voxel
+        let chunk_ids = {
+            let mut ids = Vec::new();
+            let mut i = 20;
+            while i < bytes.len() {
+                ids.push(&bytes[i..i + 4]);
+                let len = i32::from_le_bytes(bytes[i + 4..i + 8].try_into().unwrap());
+                i += 12 + len as usize;
+            }
+            ids
+        };
+        assert_eq!(chunk_ids.iter().filter(|id| *id == b"XYZI").count(), 2);
+        assert_eq!(chunk_ids.iter().filter(|id| *id == b"nSHP").count(), 2);
+        assert_eq!(chunk_ids.last(), Some(&&b"RGBA"[..]));
+    }
+
+    #[test]
+    fn parse_layers() {
+        for layer in RasterLayer::ALL {
+            assert_eq!(layer.name().parse(), Ok(layer));
+        }
+        assert!("height".parse::<RasterLayer>().is_err());
+    }
+}
@@ -1,6 +1,9 @@
 use crate::{
     Colors, Features,
-    layer::wildlife::{self, DensityFn, SpawnEntry},
+    layer::{
+        landmark::Landmark,
+        wildlife::{self, DensityFn, SpawnEntry},
+    },
     site::{Site, economy::TradeInformation},
 };
 use common::{
@@ -20,6 +23,7 @@ pub struct Index {
     pub time: f32,
     pub noise: Noise,
     pub sites: Store<Site>,
+    pub landmarks: Store<Landmark>,
     pub trade: TradeInformation,
     pub wildlife_spawns: Vec<(AssetHandle<SpawnEntry>, DensityFn)>,
     colors: AssetHandle<Arc<Colors>>,
@@ -75,6 +79,7 @@ impl Index {
             time: 0.0,
             noise: Noise::new(seed),
             sites: Store::default(),
+            landmarks: Store::default(),
             trade: Default::default(),
             wildlife_spawns,
             colors,
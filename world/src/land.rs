@@ -1,12 +1,15 @@
 use crate::{
     ColumnSample, IndexRef,
     column::ColumnGen,
+    config::{Config, DEFAULT_CONFIG},
     sim::{self, SimChunk},
     util::Sampler,
 };
 use common::{terrain::TerrainChunkSize, vol::RectVolSize};
 use vek::*;
 
+static DEFAULT: Config = DEFAULT_CONFIG;
+
 /// A wrapper type that may contain a reference to a generated world. If not,
 /// default values will be provided.
 pub struct Land<'a> {
@@ -20,6 +23,9 @@ impl<'a> Land<'a> {
 
     pub fn from_sim(sim: &'a sim::WorldSim) -> Self { Self { sim: Some(sim) } }
 
+    /// The preset of the world, or the built-in one if there is no world.
+    pub fn config(&self) -> &'a Config { self.sim.map_or(&DEFAULT, |sim| &*sim.config) }
+
     pub fn get_interpolated<T>(&self, wpos: Vec2<i32>, f: impl FnMut(&SimChunk) -> T) -> T
     where
         T: Copy + Default + std::ops::Add<Output = T> + std::ops::Mul<f32, Output = T>,
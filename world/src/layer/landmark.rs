@@ -0,0 +1,350 @@
+//! Hand-made landmarks, such as event arenas or spawn towns, stamped into the
+//! generated world at fixed locations or near sites of a given kind.
+
+use crate::{
+    Canvas, Index,
+    civ::{Civs, SiteKind},
+    sim::WorldSim,
+    site::SpawnRules,
+    util::{attempt, seed_expan},
+};
+use common::{
+    assets::{self, AssetExt},
+    spiral::Spiral2d,
+    store::Id,
+    terrain::{CoordinateConversions, Structure, TerrainChunkSize},
+    vol::RectVolSize,
+};
+use common_net::msg::world_msg::MarkerKind;
+use rand::prelude::*;
+use rand_chacha::ChaChaRng;
+use serde::Deserialize;
+use tracing::{info, warn};
+use vek::*;
+
+const LANDMARKS_MANIFEST: &str = "world.manifests.landmarks";
+
+#[derive(Clone, Debug, Deserialize)]
+pub struct LandmarkSpec {
+    /// The name the landmark is shown with on the map.
+    pub name: String,
+    /// The structure group (under `world.manifests`) to place. The first
+    /// structure of the group is used.
+    pub structure: String,
+    pub placement: Placement,
+    /// How many chunks around the structure the terrain is blended over.
+    #[serde(default = "default_blend_radius")]
+    pub blend_radius: i32,
+    /// How far the structure is sunk into the ground, in blocks.
+    #[serde(default)]
+    pub depth: i32,
+    #[serde(default = "default_marker")]
+    pub marker: MarkerKind,
+}
+
+fn default_blend_radius() -> i32 { 2 }
+
+fn default_marker() -> MarkerKind { MarkerKind::Unknown }
+
+#[derive(Clone, Debug, Deserialize)]
+pub enum Placement {
+    /// Centred on this world position.
+    At(Vec2<i32>),
+    /// Within `max_distance` chunks of a site of this kind.
+    NearSite { kind: SiteKind, max_distance: i32 },
+}
+
+#[derive(Clone, Deserialize)]
+pub struct LandmarkSpecs(pub Vec<LandmarkSpec>);
+
+impl assets::Asset for LandmarkSpecs {
+    type Loader = assets::RonLoader;
+
+    const EXTENSION: &'static str = "ron";
+}
+
+pub struct Landmark {
+    pub spec: LandmarkSpec,
+    /// The world position of the structure's centre.
+    pub origin: Vec3<i32>,
+    /// The area covered by the structure.
+    pub bounds: Aabr<i32>,
+    structure: Structure,
+    seed: u32,
+}
+
+impl Landmark {
+    /// Keeps trees, wildlife and paths off the landmark, and stops the terrain
+    /// around it from warping.
+    pub fn spawn_rules(&self, wpos: Vec2<i32>) -> SpawnRules {
+        let nearest = self.bounds.projected_point(wpos);
+        let dist = nearest.as_::<f32>().distance(wpos.as_());
+        let blend = (self.spec.blend_radius.max(1) * TerrainChunkSize::RECT_SIZE.x as i32) as f32;
+
+        SpawnRules {
+            trees: dist > blend,
+            wildlife: dist > blend,
+            max_warp: (dist / blend).clamp(0.0, 1.0),
+            paths: dist > 0.0,
+            waypoints: true,
+        }
+    }
+}
+
+/// Places the landmarks from the manifest, flattening the terrain they're
+/// placed on.
+pub fn generate(sim: &mut WorldSim, civs: &Civs, index: &mut Index) {
+    let specs = match LandmarkSpecs::load_cloned(LANDMARKS_MANIFEST) {
+        Ok(specs) => specs,
+        Err(err) => {
+            warn!(?err, "Failed to load landmarks, none will be placed");
+            return;
+        },
+    };
+    let mut rng = ChaChaRng::from_seed(seed_expan::rng_state(index.seed ^ 0x1A2D_3A4C));
+
+    for spec in specs.0 {
+        place(sim, civs, index, spec, &mut rng);
+    }
+}
+
+/// Places a single landmark, returning its id if a location was found
+fn place(
+    sim: &mut WorldSim,
+    civs: &Civs,
+    index: &mut Index,
+    spec: LandmarkSpec,
+    rng: &mut impl Rng,
+) -> Option<Id<Landmark>> {
+    let Some(structure) = Structure::load_group(&spec.structure)
+        .read()
+        .first()
+        .cloned()
+    else {
+        warn!(name = ?spec.name, "Landmark structure group is empty");
+        return None;
+    };
+    let bounds = structure.get_bounds();
+    // Radius of the structure's footprint, in chunks
+    let radius = (-bounds.min.xy())
+        .map2(bounds.max.xy(), i32::max)
+        .map2(TerrainChunkSize::RECT_SIZE, |e, sz| e / sz as i32 + 1)
+        .reduce_max();
+
+    let Some(center) = find_location(sim, civs, &spec.placement, radius, rng) else {
+        warn!(name = ?spec.name, "Couldn't find a location for landmark");
+        return None;
+    };
+    let outer = radius + spec.blend_radius.max(0);
+    let alt = flatten(sim, center, radius, outer)?;
+
+    let origin = center.cpos_to_wpos_center().with_z(alt as i32 - spec.depth);
+    info!(name = ?spec.name, ?origin, "Placed landmark");
+    let id = index.landmarks.insert(Landmark {
+        bounds: Aabr {
+            min: origin.xy() + bounds.min.xy(),
+            max: origin.xy() + bounds.max.xy(),
+        },
+        origin,
+        structure,
+        seed: rng.gen(),
+        spec,
+    });
+    // The structure is blitted into every chunk that has the landmark, and chunks
+    // in the blended area get its spawn rules too
+    for offs in Spiral2d::new().take((outer * 2 + 1).pow(2) as usize) {
+        if let Some(chunk) = sim.get_mut(center + offs) {
+            chunk.landmark = Some(id);
+        }
+    }
+    Some(id)
+}
+
+/// The chunk at the centre of a landmark following `placement`
+fn find_location(
+    sim: &WorldSim,
+    civs: &Civs,
+    placement: &Placement,
+    radius: i32,
+    rng: &mut impl Rng,
+) -> Option<Vec2<i32>> {
+    // Landmarks don't replace sites or other landmarks, and aren't placed next to
+    // water since the terrain there isn't flattened
+    let is_free = |center: Vec2<i32>| {
+        Spiral2d::new()
+            .take((radius * 2 + 1).pow(2) as usize)
+            .all(|offs| {
+                sim.get(center + offs).is_some_and(|chunk| {
+                    chunk.sites.is_empty()
+                        && chunk.landmark.is_none()
+                        && !chunk.is_underwater()
+                        && !chunk.river.near_water()
+                })
+            })
+    };
+
+    match placement {
+        Placement::At(wpos) => {
+            let center = wpos.wpos_to_cpos();
+            if is_free(center) {
+                Some(center)
+            } else {
+                warn!(
+                    ?wpos,
+                    "Landmark position is outside of the world, or overlaps a site, another \
+                     landmark or water"
+                );
+                None
+            }
+        },
+        Placement::NearSite { kind, max_distance } => {
+            let mut sites = civs
+                .sites()
+                .filter(|site| site.kind == *kind)
+                .map(|site| site.center)
+                .collect::<Vec<_>>();
+            sites.shuffle(rng);
+            sites.into_iter().find_map(|site| {
+                attempt(16, || {
+                    let offs = Vec2::new(
+                        rng.gen_range(-max_distance..=*max_distance),
+                        rng.gen_range(-max_distance..=*max_distance),
+                    );
+                    Some(site + offs).filter(|center| is_free(*center))
+                })
+            })
+        },
+    }
+}
+
+/// Flattens the terrain under the landmark, blending it into the surrounding
+/// terrain between `inner` and `outer` chunks from the centre. Returns the
+/// altitude of the flattened ground.
+fn flatten(sim: &mut WorldSim, center: Vec2<i32>, inner: i32, outer: i32) -> Option<f32> {
+    let center_alt = sim.get_alt_approx(center.cpos_to_wpos_center())?;
+    let sea_level = sim.config.sea_level;
+    for offs in Spiral2d::new().take((outer * 2 + 1).pow(2) as usize) {
+        let dist = offs.as_::<f32>().magnitude();
+        let factor = if dist <= inner as f32 {
+            1.0
+        } else {
+            (1.0 - (dist - inner as f32) / (outer - inner).max(1) as f32).max(0.0)
+        };
+        // Don't disrupt chunks that are near water
+        if let Some(chunk) = sim
+            .get_mut(center + offs)
+            .filter(|chunk| !chunk.river.near_water())
+        {
+            let diff = Lerp::lerp_precise(chunk.alt, center_alt, factor) - chunk.alt;
+            chunk.water_alt = sea_level.max(chunk.water_alt + diff);
+            chunk.alt += diff;
+            chunk.basement += diff;
+            chunk.rockiness *= 1.0 - factor;
+            chunk.surface_veg *= 1.0 - factor * 0.5;
+        }
+    }
+    Some(center_alt)
+}
+
+pub fn apply_landmarks_to(canvas: &mut Canvas) {
+    let Some(id) = canvas.chunk().landmark else {
+        return;
+    };
+    let index = canvas.index();
+    let landmark: &Landmark = &index.landmarks[id];
+    // Landmarks are placed exactly as they were authored, without rotation
+    let units = Vec2::new(Vec2::unit_x(), Vec2::unit_y());
+    canvas.blit_structure(
+        landmark.origin,
+        &landmark.structure,
+        landmark.seed,
+        units,
+        true,
+    );
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{site::Site, site2};
+    use common::terrain::MapSizeLg;
+
+    fn tower(placement: Placement) -> LandmarkSpec {
+        LandmarkSpec {
+            name: "Tower of the Wandering Mages".to_string(),
+            structure: "spots_general.mage_tower".to_string(),
+            placement,
+            blend_radius: 2,
+            depth: 1,
+            marker: MarkerKind::Castle,
+        }
+    }
+
+    fn flat_world() -> (WorldSim, Civs, Index, ChaChaRng) {
+        let sim = WorldSim::flat(MapSizeLg::new(Vec2::broadcast(4)).unwrap());
+        (
+            sim,
+            Civs::default(),
+            Index::new(0),
+            ChaChaRng::from_seed([0; 32]),
+        )
+    }
+
+    fn centre(sim: &WorldSim) -> Vec2<i32> {
+        (sim.map_size_lg().chunks().as_::<i32>() / 2).cpos_to_wpos_center()
+    }
+
+    // Checks that the manifest parses and each landmark's structure is loadable
+    #[test]
+    fn test_load_landmarks() {
+        let specs = LandmarkSpecs::load_expect(LANDMARKS_MANIFEST).read();
+        for spec in specs.0.iter() {
+            assert!(
+                !Structure::load_group(&spec.structure).read().is_empty(),
+                "{}: structure group is empty",
+                spec.name
+            );
+        }
+    }
+
+    // Every chunk the structure covers must have the landmark, since only those
+    // chunks are blitted
+    #[test]
+    fn test_place_landmark() {
+        let (mut sim, civs, mut index, mut rng) = flat_world();
+        let spec = tower(Placement::At(centre(&sim)));
+        let id = place(&mut sim, &civs, &mut index, spec.clone(), &mut rng)
+            .expect("Landmark wasn't placed on flat land");
+
+        let bounds = index.landmarks[id].bounds;
+        let min = bounds.min.wpos_to_cpos();
+        let max = bounds.max.wpos_to_cpos();
+        for x in min.x..=max.x {
+            for y in min.y..=max.y {
+                let chunk = sim.get(Vec2::new(x, y)).unwrap();
+                assert_eq!(chunk.landmark, Some(id), "Chunk ({x}, {y}) isn't blitted");
+            }
+        }
+
+        // A second landmark can't be placed on top of the first one
+        assert!(place(&mut sim, &civs, &mut index, spec, &mut rng).is_none());
+        assert_eq!(index.landmarks.values().len(), 1);
+    }
+
+    #[test]
+    fn test_landmark_avoids_sites() {
+        let (mut sim, civs, mut index, mut rng) = flat_world();
+        let wpos = centre(&sim);
+        let site = index
+            .sites
+            .insert(Site::glider_course(site2::Site::default()));
+        sim.get_mut(wpos.wpos_to_cpos() + 1)
+            .unwrap()
+            .sites
+            .push(site);
+
+        let spec = tower(Placement::At(wpos));
+        assert!(place(&mut sim, &civs, &mut index, spec, &mut rng).is_none());
+        assert!(sim.get(wpos.wpos_to_cpos()).unwrap().landmark.is_none());
+    }
+}
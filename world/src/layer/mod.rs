@@ -1,4 +1,5 @@
 pub mod cave;
+pub mod landmark;
 pub mod rock;
 pub mod scatter;
 pub mod shrub;
@@ -7,14 +8,13 @@ pub mod tree;
 pub mod wildlife;
 
 pub use self::{
-    cave::apply_caves_to, rock::apply_rocks_to, scatter::apply_scatter_to, shrub::apply_shrubs_to,
-    spot::apply_spots_to, tree::apply_trees_to,
+    cave::apply_caves_to, landmark::apply_landmarks_to, rock::apply_rocks_to,
+    scatter::apply_scatter_to, shrub::apply_shrubs_to, spot::apply_spots_to, tree::apply_trees_to,
 };
 
 use crate::{
     Canvas, CanvasInfo,
     column::ColumnSample,
-    config::CONFIG,
     sim,
     util::{FastNoise, RandomPerm, Sampler},
 };
@@ -290,7 +290,7 @@ pub fn apply_caverns_to<R: Rng>(canvas: &mut Canvas, dynamic_rng: &mut R) {
         );
 
         let cavern_avg_alt =
-            CONFIG.sea_level.min(alt * 0.25) - height_range.end - surface_clearance;
+            info.chunks().config.sea_level.min(alt * 0.25) - height_range.end - surface_clearance;
 
         let cavern = canvern_nz_at(wpos2d);
         let cavern_height = cavern * cavern_avg_height;
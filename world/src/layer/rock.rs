@@ -1,5 +1,5 @@
 use crate::{
-    CONFIG, Canvas, ColumnSample,
+    Canvas, ColumnSample,
     util::{
         NEIGHBORS, NEIGHBORS3, RandomField, Sampler, StructureGen2d, UnitChooser,
         gen_cache::StructureGenCache, seed_expan,
@@ -33,7 +33,7 @@ pub fn apply_rocks_to(canvas: &mut Canvas, _dynamic_rng: &mut impl Rng) {
                 && col.path.is_none_or(|(d, _, _, _)| d > 6.0)
             {
                 match (
-                    (col.alt - CONFIG.sea_level) as i32,
+                    (col.alt - col.config.sea_level) as i32,
                     (col.alt - col.water_level) as i32,
                     col.water_dist.map_or(i32::MAX, |d| d as i32),
                 ) {
@@ -52,8 +52,8 @@ pub fn apply_rocks_to(canvas: &mut Canvas, _dynamic_rng: &mut impl Rng) {
                         &mut rng,
                     ))),
                     (5..=i32::MAX, _, 0..=i32::MAX) => {
-                        if col.temp > CONFIG.desert_temp - 0.1
-                            && col.humidity < CONFIG.desert_hum + 0.1
+                        if col.temp > col.config.desert_temp - 0.1
+                            && col.humidity < col.config.desert_hum + 0.1
                         {
                             Some(RockKind::Sandstone(VoronoiCell::generate(
                                 rng.gen_range(2.0..20.0 - 10.0 * col.tree_density),
@@ -1,5 +1,5 @@
 use crate::{
-    CONFIG, Canvas,
+    Canvas,
     column::ColumnSample,
     sim::SimChunk,
     util::{RandomField, close},
@@ -59,9 +59,9 @@ pub fn apply_scatter_to(canvas: &mut Canvas, _rng: &mut impl Rng, calendar: Opti
             permit: |b| matches!(b, BlockKind::Grass),
             f: |_, col| {
                 (
-                    close(col.temp, CONFIG.temperate_temp, 0.7).min(close(
+                    close(col.temp, col.config.temperate_temp, 0.7).min(close(
                         col.humidity,
-                        CONFIG.jungle_hum,
+                        col.config.jungle_hum,
                         0.4,
                     )) * col.tree_density
                         * MUSH_FACT
@@ -76,7 +76,7 @@ pub fn apply_scatter_to(canvas: &mut Canvas, _rng: &mut impl Rng, calendar: Opti
             permit: |b| matches!(b, BlockKind::Grass),
             f: |_, col| {
                 (
-                    close(col.temp, 0.0, 0.7).min(close(col.humidity, CONFIG.jungle_hum, 0.4))
+                    close(col.temp, 0.0, 0.7).min(close(col.humidity, col.config.jungle_hum, 0.4))
                         * col.tree_density
                         * MUSH_FACT
                         * 350.0,
@@ -90,11 +90,11 @@ pub fn apply_scatter_to(canvas: &mut Canvas, _rng: &mut impl Rng, calendar: Opti
             permit: |b| matches!(b, BlockKind::Grass | BlockKind::Snow),
             f: |_, col| {
                 (
-                    close(col.temp, CONFIG.temperate_temp, 0.7)
-                        .max(close(col.temp, CONFIG.snow_temp, 0.7))
-                        .min(close(col.humidity, CONFIG.jungle_hum, 0.4).max(close(
+                    close(col.temp, col.config.temperate_temp, 0.7)
+                        .max(close(col.temp, col.config.snow_temp, 0.7))
+                        .min(close(col.humidity, col.config.jungle_hum, 0.4).max(close(
                             col.humidity,
-                            CONFIG.forest_hum,
+                            col.config.forest_hum,
                             0.5,
                         )))
                         * col.tree_density
@@ -110,11 +110,11 @@ pub fn apply_scatter_to(canvas: &mut Canvas, _rng: &mut impl Rng, calendar: Opti
             permit: |b| matches!(b, BlockKind::Grass | BlockKind::Snow),
             f: |_, col| {
                 (
-                    close(col.temp, CONFIG.tropical_temp, 0.7)
-                        .max(close(col.temp, CONFIG.snow_temp, 0.7))
-                        .min(close(col.humidity, CONFIG.jungle_hum, 0.4).max(close(
+                    close(col.temp, col.config.tropical_temp, 0.7)
+                        .max(close(col.temp, col.config.snow_temp, 0.7))
+                        .min(close(col.humidity, col.config.jungle_hum, 0.4).max(close(
                             col.humidity,
-                            CONFIG.forest_hum,
+                            col.config.forest_hum,
                             0.5,
                         )))
                         * col.tree_density
@@ -130,7 +130,7 @@ pub fn apply_scatter_to(canvas: &mut Canvas, _rng: &mut impl Rng, calendar: Opti
             permit: |b| matches!(b, BlockKind::Grass),
             f: |_, col| {
                 (
-                    close(col.temp, 0.0, 0.7).min(close(col.humidity, CONFIG.jungle_hum, 0.4))
+                    close(col.temp, 0.0, 0.7).min(close(col.humidity, col.config.jungle_hum, 0.4))
                         * col.tree_density
                         * MUSH_FACT
                         * 350.0,
@@ -145,10 +145,10 @@ pub fn apply_scatter_to(canvas: &mut Canvas, _rng: &mut impl Rng, calendar: Opti
             f: |_, col| {
                 (
                     close(col.temp, 0.0, 0.7)
-                        .max(close(col.temp, CONFIG.snow_temp, 0.7))
-                        .min(close(col.humidity, CONFIG.jungle_hum, 0.4).max(close(
+                        .max(close(col.temp, col.config.snow_temp, 0.7))
+                        .min(close(col.humidity, col.config.jungle_hum, 0.4).max(close(
                             col.humidity,
-                            CONFIG.forest_hum,
+                            col.config.forest_hum,
                             0.5,
                         )))
                         * col.tree_density
@@ -164,9 +164,9 @@ pub fn apply_scatter_to(canvas: &mut Canvas, _rng: &mut impl Rng, calendar: Opti
             permit: |b| matches!(b, BlockKind::Earth | BlockKind::Grass),
             f: |_, col| {
                 (
-                    close(col.temp, CONFIG.tropical_temp, 0.7).min(close(
+                    close(col.temp, col.config.tropical_temp, 0.7).min(close(
                         col.humidity,
-                        CONFIG.jungle_hum,
+                        col.config.jungle_hum,
                         0.4,
                     )) * col.tree_density
                     * MUSH_FACT
@@ -185,7 +185,7 @@ pub fn apply_scatter_to(canvas: &mut Canvas, _rng: &mut impl Rng, calendar: Opti
             permit: |b| matches!(b, BlockKind::Grass),
             f: |_, col| {
                 (
-                    close(col.temp, 0.0, 0.7).min(close(col.humidity, CONFIG.jungle_hum, 0.4))
+                    close(col.temp, 0.0, 0.7).min(close(col.humidity, col.config.jungle_hum, 0.4))
                         * col.tree_density
                         * MUSH_FACT
                         * 350.0,
@@ -199,9 +199,9 @@ pub fn apply_scatter_to(canvas: &mut Canvas, _rng: &mut impl Rng, calendar: Opti
             permit: |b| matches!(b, BlockKind::Grass),
             f: |_, col| {
                 (
-                    close(col.temp, CONFIG.temperate_temp, 0.7).min(close(
+                    close(col.temp, col.config.temperate_temp, 0.7).min(close(
                         col.humidity,
-                        CONFIG.forest_hum,
+                        col.config.forest_hum,
                         0.4,
                     )) * col.tree_density
                         * MUSH_FACT
@@ -220,7 +220,7 @@ pub fn apply_scatter_to(canvas: &mut Canvas, _rng: &mut impl Rng, calendar: Opti
             permit: |b| matches!(b, BlockKind::Grass),
             f: |_, col| {
                 (
-                    close(col.temp, 0.3, 0.4).min(close(col.humidity, CONFIG.jungle_hum, 0.5))
+                    close(col.temp, 0.3, 0.4).min(close(col.humidity, col.config.jungle_hum, 0.5))
                         * MUSH_FACT
                         * 2.5,
                     None,
@@ -233,7 +233,7 @@ pub fn apply_scatter_to(canvas: &mut Canvas, _rng: &mut impl Rng, calendar: Opti
             permit: |b| matches!(b, BlockKind::Grass),
             f: |_, col| {
                 (
-                    close(col.temp, 0.3, 0.4).min(close(col.humidity, CONFIG.jungle_hum, 0.3))
+                    close(col.temp, 0.3, 0.4).min(close(col.humidity, col.config.jungle_hum, 0.3))
                         * GRASS_FACT
                         * 4.0,
                     None,
@@ -246,7 +246,7 @@ pub fn apply_scatter_to(canvas: &mut Canvas, _rng: &mut impl Rng, calendar: Opti
             permit: |b| matches!(b, BlockKind::Grass),
             f: |_, col| {
                 (
-                    close(col.temp, 0.3, 0.4).min(close(col.humidity, CONFIG.jungle_hum, 0.4))
+                    close(col.temp, 0.3, 0.4).min(close(col.humidity, col.config.jungle_hum, 0.4))
                         * GRASS_FACT
                         * 32.0,
                     Some((0.15, 64.0, 0.2)),
@@ -259,7 +259,7 @@ pub fn apply_scatter_to(canvas: &mut Canvas, _rng: &mut impl Rng, calendar: Opti
             permit: |b| matches!(b, BlockKind::Grass),
             f: |_, col| {
                 (
-                    close(col.temp, 0.3, 0.4).min(close(col.humidity, CONFIG.forest_hum, 0.5))
+                    close(col.temp, 0.3, 0.4).min(close(col.humidity, col.config.forest_hum, 0.5))
                         * GRASS_FACT
                         * 0.25,
                     Some((0.0, 64.0, 0.2)),
@@ -272,7 +272,7 @@ pub fn apply_scatter_to(canvas: &mut Canvas, _rng: &mut impl Rng, calendar: Opti
             permit: |b| matches!(b, BlockKind::Grass),
             f: |_, col| {
                 (
-                    close(col.temp, 0.3, 0.4).min(close(col.humidity, CONFIG.jungle_hum, 0.4))
+                    close(col.temp, 0.3, 0.4).min(close(col.humidity, col.config.jungle_hum, 0.4))
                         * col.tree_density
                         * MUSH_FACT
                         * 200.0,
@@ -286,9 +286,9 @@ pub fn apply_scatter_to(canvas: &mut Canvas, _rng: &mut impl Rng, calendar: Opti
             permit: |b| matches!(b, BlockKind::Grass),
             f: |_, col| {
                 (
-                    close(col.temp, CONFIG.temperate_temp, 0.5).min(close(
+                    close(col.temp, col.config.temperate_temp, 0.5).min(close(
                         col.humidity,
-                        CONFIG.forest_hum,
+                        col.config.forest_hum,
                         0.5,
                     )) * MUSH_FACT
                         * 0.3,
@@ -305,9 +305,9 @@ pub fn apply_scatter_to(canvas: &mut Canvas, _rng: &mut impl Rng, calendar: Opti
             } else {
                 |_, col| {
                     (
-                        close(col.temp, CONFIG.temperate_temp, 0.5).min(close(
+                        close(col.temp, col.config.temperate_temp, 0.5).min(close(
                             col.humidity,
-                            CONFIG.forest_hum,
+                            col.config.forest_hum,
                             0.5,
                         )) * MUSH_FACT
                             * 500.0,
@@ -347,9 +347,9 @@ pub fn apply_scatter_to(canvas: &mut Canvas, _rng: &mut impl Rng, calendar: Opti
             permit: |b| matches!(b, BlockKind::Grass),
             f: |_, col| {
                 (
-                    ((close(col.temp, CONFIG.tropical_temp + 0.1, 0.3).min(close(
+                    ((close(col.temp, col.config.tropical_temp + 0.1, 0.3).min(close(
                         col.humidity,
-                        CONFIG.jungle_hum,
+                        col.config.jungle_hum,
                         0.4,
                     )) > 0.0) as i32) as f32
                         * (col.tree_density * 1.25 - 0.25).powf(0.5).max(0.0)
@@ -424,7 +424,7 @@ pub fn apply_scatter_to(canvas: &mut Canvas, _rng: &mut impl Rng, calendar: Opti
             permit: |b| matches!(b, BlockKind::Grass),
             f: |_, col| {
                 (
-                    close(col.temp, 0.3, 0.4).min(close(col.humidity, CONFIG.forest_hum, 0.35))
+                    close(col.temp, 0.3, 0.4).min(close(col.humidity, col.config.forest_hum, 0.35))
                         * MUSH_FACT,
                     None,
                 )
@@ -437,7 +437,7 @@ pub fn apply_scatter_to(canvas: &mut Canvas, _rng: &mut impl Rng, calendar: Opti
             permit: |b| matches!(b, BlockKind::Grass),
             f: |_, col| {
                 (
-                    close(col.temp, 0.2, 0.75).min(close(col.humidity, CONFIG.jungle_hum, 0.4))
+                    close(col.temp, 0.2, 0.75).min(close(col.humidity, col.config.jungle_hum, 0.4))
                         * GRASS_FACT
                         * 150.0,
                     Some((0.3, 64.0, 0.3)),
@@ -450,9 +450,9 @@ pub fn apply_scatter_to(canvas: &mut Canvas, _rng: &mut impl Rng, calendar: Opti
             permit: |b| matches!(b, BlockKind::Snow),
             f: |_, col| {
                 (
-                    close(col.temp, CONFIG.snow_temp - 0.2, 0.4).min(close(
+                    close(col.temp, col.config.snow_temp - 0.2, 0.4).min(close(
                         col.humidity,
-                        CONFIG.forest_hum,
+                        col.config.forest_hum,
                         0.5,
                     )) * GRASS_FACT
                         * 50.0,
@@ -466,7 +466,7 @@ pub fn apply_scatter_to(canvas: &mut Canvas, _rng: &mut impl Rng, calendar: Opti
             permit: |b| matches!(b, BlockKind::Grass),
             f: |_, col| {
                 (
-                    close(col.temp, 0.2, 0.6).min(close(col.humidity, CONFIG.jungle_hum, 0.4))
+                    close(col.temp, 0.2, 0.6).min(close(col.humidity, col.config.jungle_hum, 0.4))
                         * GRASS_FACT
                         * 120.0,
                     Some((0.3, 64.0, 0.3)),
@@ -479,7 +479,7 @@ pub fn apply_scatter_to(canvas: &mut Canvas, _rng: &mut impl Rng, calendar: Opti
             permit: |b| matches!(b, BlockKind::Grass),
             f: |_, col| {
                 (
-                    close(col.temp, 0.3, 0.35).min(close(col.humidity, CONFIG.jungle_hum, 0.3))
+                    close(col.temp, 0.3, 0.35).min(close(col.humidity, col.config.jungle_hum, 0.3))
                         * GRASS_FACT
                         * 150.0,
                     Some((0.1, 48.0, 0.3)),
@@ -492,9 +492,9 @@ pub fn apply_scatter_to(canvas: &mut Canvas, _rng: &mut impl Rng, calendar: Opti
             permit: |b| matches!(b, BlockKind::Snow),
             f: |_, col| {
                 (
-                    close(col.temp, CONFIG.snow_temp - 0.2, 0.4).min(close(
+                    close(col.temp, col.config.snow_temp - 0.2, 0.4).min(close(
                         col.humidity,
-                        CONFIG.forest_hum,
+                        col.config.forest_hum,
                         0.5,
                     )) * GRASS_FACT
                         * 25.0,
@@ -508,7 +508,7 @@ pub fn apply_scatter_to(canvas: &mut Canvas, _rng: &mut impl Rng, calendar: Opti
             permit: |b| matches!(b, BlockKind::Grass),
             f: |_, col| {
                 (
-                    close(col.temp, 0.3, 0.4).min(close(col.humidity, CONFIG.jungle_hum, 0.4))
+                    close(col.temp, 0.3, 0.4).min(close(col.humidity, col.config.jungle_hum, 0.4))
                         * col.tree_density
                         * MUSH_FACT
                         * 350.0,
@@ -519,9 +519,9 @@ pub fn apply_scatter_to(canvas: &mut Canvas, _rng: &mut impl Rng, calendar: Opti
         // Jungle Sprites
         // (LongGrass, Ground, |c, col| {
         //     (
-        //         close(col.temp, CONFIG.tropical_temp, 0.4).min(close(
+        //         close(col.temp, col.config.tropical_temp, 0.4).min(close(
         //             col.humidity,
-        //             CONFIG.jungle_hum,
+        //             col.config.jungle_hum,
         //             0.6,
         //         )) * 0.08,
         //         Some((0.0, 60.0, 5.0)),
@@ -529,7 +529,7 @@ pub fn apply_scatter_to(canvas: &mut Canvas, _rng: &mut impl Rng, calendar: Opti
         // }),
         /*(WheatGreen, Ground, |c, col| {
             (
-                close(col.temp, 0.4, 0.2).min(close(col.humidity, CONFIG.forest_hum, 0.1))
+                close(col.temp, 0.4, 0.2).min(close(col.humidity, col.config.forest_hum, 0.1))
                     * MUSH_FACT
                     * 0.001,
                 None,
@@ -541,9 +541,9 @@ pub fn apply_scatter_to(canvas: &mut Canvas, _rng: &mut impl Rng, calendar: Opti
             permit: |b| matches!(b, BlockKind::Grass),
             f: |_, col| {
                 (
-                    close(col.temp, CONFIG.snow_temp - 0.2, 0.4).min(close(
+                    close(col.temp, col.config.snow_temp - 0.2, 0.4).min(close(
                         col.humidity,
-                        CONFIG.forest_hum,
+                        col.config.forest_hum,
                         0.5,
                     )) * GRASS_FACT
                         * 100.0,
@@ -557,9 +557,9 @@ pub fn apply_scatter_to(canvas: &mut Canvas, _rng: &mut impl Rng, calendar: Opti
             permit: |b| matches!(b, BlockKind::Grass),
             f: |_, col| {
                 (
-                    close(col.temp, CONFIG.snow_temp - 0.2, 0.4).min(close(
+                    close(col.temp, col.config.snow_temp - 0.2, 0.4).min(close(
                         col.humidity,
-                        CONFIG.forest_hum,
+                        col.config.forest_hum,
                         0.5,
                     )) * 0.003,
                     Some((0.0, 48.0, 0.2)),
@@ -635,10 +635,10 @@ pub fn apply_scatter_to(canvas: &mut Canvas, _rng: &mut impl Rng, calendar: Opti
             f: |_, col| {
                 (
                     close(col.temp, 1.0, 0.95)
-                        .max(close(col.temp, CONFIG.snow_temp, 0.95))
+                        .max(close(col.temp, col.config.snow_temp, 0.95))
                         .min(close(col.humidity, 0.0, 0.3).max(close(
                             col.humidity,
-                            CONFIG.forest_hum,
+                            col.config.forest_hum,
                             0.3,
                         )))
                         * MUSH_FACT
@@ -653,8 +653,11 @@ pub fn apply_scatter_to(canvas: &mut Canvas, _rng: &mut impl Rng, calendar: Opti
             permit: |b| matches!(b, BlockKind::Grass),
             f: |_, col| {
                 (
-                    close(col.temp, CONFIG.desert_temp, 0.25).min(close(col.humidity, 0.0, 0.2))
-                        * MUSH_FACT
+                    close(col.temp, col.config.desert_temp, 0.25).min(close(
+                        col.humidity,
+                        0.0,
+                        0.2,
+                    )) * MUSH_FACT
                         * 0.1,
                     None,
                 )
@@ -666,8 +669,11 @@ pub fn apply_scatter_to(canvas: &mut Canvas, _rng: &mut impl Rng, calendar: Opti
             permit: |b| matches!(b, BlockKind::Grass),
             f: |_, col| {
                 (
-                    close(col.temp, CONFIG.desert_temp, 0.25).min(close(col.humidity, 0.0, 0.2))
-                        * MUSH_FACT
+                    close(col.temp, col.config.desert_temp, 0.25).min(close(
+                        col.humidity,
+                        0.0,
+                        0.2,
+                    )) * MUSH_FACT
                         * 1.5,
                     None,
                 )
@@ -679,8 +685,11 @@ pub fn apply_scatter_to(canvas: &mut Canvas, _rng: &mut impl Rng, calendar: Opti
             permit: |b| matches!(b, BlockKind::Grass),
             f: |_, col| {
                 (
-                    close(col.temp, CONFIG.desert_temp, 0.25).min(close(col.humidity, 0.0, 0.2))
-                        * MUSH_FACT
+                    close(col.temp, col.config.desert_temp, 0.25).min(close(
+                        col.humidity,
+                        0.0,
+                        0.2,
+                    )) * MUSH_FACT
                         * 2.0,
                     None,
                 )
@@ -692,8 +701,11 @@ pub fn apply_scatter_to(canvas: &mut Canvas, _rng: &mut impl Rng, calendar: Opti
             permit: |b| matches!(b, BlockKind::Grass),
             f: |_, col| {
                 (
-                    close(col.temp, CONFIG.desert_temp, 0.25).min(close(col.humidity, 0.0, 0.2))
-                        * MUSH_FACT
+                    close(col.temp, col.config.desert_temp, 0.25).min(close(
+                        col.humidity,
+                        0.0,
+                        0.2,
+                    )) * MUSH_FACT
                         * 1.5,
                     None,
                 )
@@ -705,8 +717,11 @@ pub fn apply_scatter_to(canvas: &mut Canvas, _rng: &mut impl Rng, calendar: Opti
             permit: |b| matches!(b, BlockKind::Grass),
             f: |_, col| {
                 (
-                    close(col.temp, CONFIG.desert_temp, 0.25).min(close(col.humidity, 0.0, 0.2))
-                        * MUSH_FACT
+                    close(col.temp, col.config.desert_temp, 0.25).min(close(
+                        col.humidity,
+                        0.0,
+                        0.2,
+                    )) * MUSH_FACT
                         * 2.0,
                     None,
                 )
@@ -718,8 +733,11 @@ pub fn apply_scatter_to(canvas: &mut Canvas, _rng: &mut impl Rng, calendar: Opti
             permit: |b| matches!(b, BlockKind::Grass),
             f: |_, col| {
                 (
-                    close(col.temp, CONFIG.desert_temp, 0.25).min(close(col.humidity, 0.0, 0.2))
-                        * MUSH_FACT
+                    close(col.temp, col.config.desert_temp, 0.25).min(close(
+                        col.humidity,
+                        0.0,
+                        0.2,
+                    )) * MUSH_FACT
                         * 2.0,
                     None,
                 )
@@ -731,8 +749,11 @@ pub fn apply_scatter_to(canvas: &mut Canvas, _rng: &mut impl Rng, calendar: Opti
             permit: |b| matches!(b, BlockKind::Grass),
             f: |_, col| {
                 (
-                    close(col.temp, CONFIG.desert_temp, 0.25).min(close(col.humidity, 0.0, 0.2))
-                        * MUSH_FACT
+                    close(col.temp, col.config.desert_temp, 0.25).min(close(
+                        col.humidity,
+                        0.0,
+                        0.2,
+                    )) * MUSH_FACT
                         * 2.0,
                     None,
                 )
@@ -744,8 +765,11 @@ pub fn apply_scatter_to(canvas: &mut Canvas, _rng: &mut impl Rng, calendar: Opti
             permit: |b| matches!(b, BlockKind::Grass),
             f: |_, col| {
                 (
-                    close(col.temp, CONFIG.desert_temp, 0.25).min(close(col.humidity, 0.0, 0.2))
-                        * MUSH_FACT
+                    close(col.temp, col.config.desert_temp, 0.25).min(close(
+                        col.humidity,
+                        0.0,
+                        0.2,
+                    )) * MUSH_FACT
                         * 2.0,
                     None,
                 )
@@ -812,10 +836,10 @@ pub fn apply_scatter_to(canvas: &mut Canvas, _rng: &mut impl Rng, calendar: Opti
             permit: |b| matches!(b, BlockKind::Grass | BlockKind::Sand),
             f: |_, col| {
                 (
-                    close(col.temp, CONFIG.temperate_temp, 0.8)
+                    close(col.temp, col.config.temperate_temp, 0.8)
                         * MUSH_FACT
                         * 300.0
-                        * if col.water_level <= CONFIG.sea_level
+                        * if col.water_level <= col.config.sea_level
                             && col.alt < col.water_level - DEPTH_WATER_NORM + 18.0
                         {
                             1.0
@@ -835,7 +859,7 @@ pub fn apply_scatter_to(canvas: &mut Canvas, _rng: &mut impl Rng, calendar: Opti
                 (
                     MUSH_FACT
                         * 600.0
-                        * if col.water_level <= CONFIG.sea_level
+                        * if col.water_level <= col.config.sea_level
                             && (col.water_level - col.alt) < 3.0
                         {
                             1.0
@@ -853,10 +877,10 @@ pub fn apply_scatter_to(canvas: &mut Canvas, _rng: &mut impl Rng, calendar: Opti
             permit: |b| matches!(b, BlockKind::Grass | BlockKind::Sand),
             f: |_, col| {
                 (
-                    close(col.temp, CONFIG.temperate_temp, 0.8)
+                    close(col.temp, col.config.temperate_temp, 0.8)
                         * MUSH_FACT
                         * 50.0
-                        * if col.water_level <= CONFIG.sea_level
+                        * if col.water_level <= col.config.sea_level
                             && col.alt < col.water_level - DEPTH_WATER_NORM + 11.0
                         {
                             1.0
@@ -877,7 +901,7 @@ pub fn apply_scatter_to(canvas: &mut Canvas, _rng: &mut impl Rng, calendar: Opti
                     close(col.temp, 1.0, 0.95)
                         * MUSH_FACT
                         * 50.0
-                        * if col.water_level <= CONFIG.sea_level
+                        * if col.water_level <= col.config.sea_level
                             && col.alt < col.water_level - DEPTH_WATER_NORM + 11.0
                         {
                             1.0
@@ -897,7 +921,7 @@ pub fn apply_scatter_to(canvas: &mut Canvas, _rng: &mut impl Rng, calendar: Opti
                 (
                     MUSH_FACT
                         * 250.0
-                        * if col.water_level <= CONFIG.sea_level
+                        * if col.water_level <= col.config.sea_level
                             && col.alt < col.water_level - DEPTH_WATER_NORM + 10.0
                         {
                             1.0
@@ -917,7 +941,7 @@ pub fn apply_scatter_to(canvas: &mut Canvas, _rng: &mut impl Rng, calendar: Opti
                 (
                     MUSH_FACT
                         * 250.0
-                        * if col.water_level <= CONFIG.sea_level
+                        * if col.water_level <= col.config.sea_level
                             && col.alt < col.water_level - DEPTH_WATER_NORM + 10.0
                         {
                             1.0
@@ -938,7 +962,7 @@ pub fn apply_scatter_to(canvas: &mut Canvas, _rng: &mut impl Rng, calendar: Opti
                     close(col.temp, 1.0, 0.95)
                         * MUSH_FACT
                         * 500.0
-                        * if col.water_level <= CONFIG.sea_level
+                        * if col.water_level <= col.config.sea_level
                             && col.alt < col.water_level - DEPTH_WATER_NORM + 10.0
                         {
                             1.0
@@ -956,10 +980,10 @@ pub fn apply_scatter_to(canvas: &mut Canvas, _rng: &mut impl Rng, calendar: Opti
             permit: |b| matches!(b, BlockKind::Earth | BlockKind::Sand),
             f: |_, col| {
                 (
-                    close(col.temp, CONFIG.temperate_temp, 0.8)
+                    close(col.temp, col.config.temperate_temp, 0.8)
                         * MUSH_FACT
                         * 125.0
-                        * if col.water_level <= CONFIG.sea_level
+                        * if col.water_level <= col.config.sea_level
                             && col.alt < col.water_level - DEPTH_WATER_NORM - 9.0
                         {
                             1.0
@@ -977,10 +1001,10 @@ pub fn apply_scatter_to(canvas: &mut Canvas, _rng: &mut impl Rng, calendar: Opti
             permit: |b| matches!(b, BlockKind::Earth | BlockKind::Sand),
             f: |_, col| {
                 (
-                    close(col.temp, CONFIG.temperate_temp, 0.8)
+                    close(col.temp, col.config.temperate_temp, 0.8)
                         * MUSH_FACT
                         * 220.0
-                        * if col.water_level <= CONFIG.sea_level
+                        * if col.water_level <= col.config.sea_level
                             && col.alt < col.water_level - DEPTH_WATER_NORM - 9.0
                         {
                             1.0
@@ -998,10 +1022,10 @@ pub fn apply_scatter_to(canvas: &mut Canvas, _rng: &mut impl Rng, calendar: Opti
             permit: |b| matches!(b, BlockKind::Earth | BlockKind::Sand),
             f: |_, col| {
                 (
-                    close(col.temp, CONFIG.temperate_temp, 0.7)
+                    close(col.temp, col.config.temperate_temp, 0.7)
                         * MUSH_FACT
                         * 300.0
-                        * if col.water_level <= CONFIG.sea_level
+                        * if col.water_level <= col.config.sea_level
                             && col.alt < col.water_level - DEPTH_WATER_NORM + 3.0
                         {
                             1.0
@@ -1022,7 +1046,7 @@ pub fn apply_scatter_to(canvas: &mut Canvas, _rng: &mut impl Rng, calendar: Opti
                     close(col.temp, 1.0, 0.9)
                         * MUSH_FACT
                         * 160.0
-                        * if col.water_level <= CONFIG.sea_level
+                        * if col.water_level <= col.config.sea_level
                             && col.alt < col.water_level - DEPTH_WATER_NORM + 10.0
                         {
                             1.0
@@ -1043,7 +1067,7 @@ pub fn apply_scatter_to(canvas: &mut Canvas, _rng: &mut impl Rng, calendar: Opti
                     close(col.temp, 1.0, 0.9)
                         * MUSH_FACT
                         * 120.0
-                        * if col.water_level <= CONFIG.sea_level
+                        * if col.water_level <= col.config.sea_level
                             && col.alt < col.water_level - DEPTH_WATER_NORM + 10.0
                         {
                             1.0
@@ -1063,7 +1087,7 @@ pub fn apply_scatter_to(canvas: &mut Canvas, _rng: &mut impl Rng, calendar: Opti
                 (
                     (c.rockiness - 0.5).max(0.0)
                         * 1.0e-3
-                        * if col.water_level <= CONFIG.sea_level
+                        * if col.water_level <= col.config.sea_level
                             && col.alt < col.water_level - DEPTH_WATER_NORM + 20.0
                         {
                             1.0
@@ -1098,10 +1122,10 @@ pub fn apply_scatter_to(canvas: &mut Canvas, _rng: &mut impl Rng, calendar: Opti
             permit: |_| true,
             f: |_, col| {
                 (
-                    close(col.temp, 0.2, 0.6).min(close(col.humidity, CONFIG.jungle_hum, 0.4))
+                    close(col.temp, 0.2, 0.6).min(close(col.humidity, col.config.jungle_hum, 0.4))
                         * GRASS_FACT
                         * 100.0
-                        * ((col.alt - CONFIG.sea_level) / 12.0).clamped(0.0, 1.0)
+                        * ((col.alt - col.config.sea_level) / 12.0).clamped(0.0, 1.0)
                         * col
                             .water_dist
                             .map_or(0.0, |d| 1.0 / (1.0 + (d.abs() * 0.4).powi(2))),
@@ -1115,10 +1139,10 @@ pub fn apply_scatter_to(canvas: &mut Canvas, _rng: &mut impl Rng, calendar: Opti
             permit: |b| matches!(b, BlockKind::Grass),
             f: |_, col| {
                 (
-                    close(col.temp, 0.2, 0.6).min(close(col.humidity, CONFIG.jungle_hum, 0.4))
+                    close(col.temp, 0.2, 0.6).min(close(col.humidity, col.config.jungle_hum, 0.4))
                         * GRASS_FACT
                         * 100.0
-                        * ((col.alt - CONFIG.sea_level) / 12.0).clamped(0.0, 1.0)
+                        * ((col.alt - col.config.sea_level) / 12.0).clamped(0.0, 1.0)
                         * col
                             .water_dist
                             .map_or(0.0, |d| 1.0 / (1.0 + (d.abs() * 0.40).powi(2))),
@@ -1132,12 +1156,12 @@ pub fn apply_scatter_to(canvas: &mut Canvas, _rng: &mut impl Rng, calendar: Opti
             permit: |b| matches!(b, BlockKind::Grass),
             f: |_, col| {
                 (
-                    close(col.humidity, CONFIG.jungle_hum, 0.9)
+                    close(col.humidity, col.config.jungle_hum, 0.9)
                         * col
                             .water_dist
                             .map(|wd| Lerp::lerp(0.2, 0.0, (wd / 8.0).clamped(0.0, 1.0)))
                             .unwrap_or(0.0)
-                        * ((col.alt - CONFIG.sea_level) / 12.0).clamped(0.0, 1.0),
+                        * ((col.alt - col.config.sea_level) / 12.0).clamped(0.0, 1.0),
                     Some((0.2, 128.0, 0.5)),
                 )
             },
@@ -1149,12 +1173,12 @@ pub fn apply_scatter_to(canvas: &mut Canvas, _rng: &mut impl Rng, calendar: Opti
             f: |_, col| {
                 (
                     0.014
-                        * close(col.humidity, CONFIG.jungle_hum, 0.9)
+                        * close(col.humidity, col.config.jungle_hum, 0.9)
                         * col
                             .water_dist
                             .map(|wd| Lerp::lerp(0.2, 0.0, (wd / 8.0).clamped(0.0, 1.0)))
                             .unwrap_or(0.0)
-                        * ((col.alt - CONFIG.sea_level) / 12.0).clamped(0.0, 1.0),
+                        * ((col.alt - col.config.sea_level) / 12.0).clamped(0.0, 1.0),
                     Some((0.2, 128.0, 0.5)),
                 )
             },
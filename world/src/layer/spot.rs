@@ -1,5 +1,5 @@
 use crate::{
-    Canvas,
+    Canvas, Config,
     sim::{SimChunk, WorldSim},
     util::{Sampler, UnitChooser, seed_expan},
 };
@@ -29,6 +29,8 @@ pub trait SpotGenerate {
 impl SpotGenerate for Spot {
     fn generate(world: &mut WorldSim) {
         use BiomeKind::*;
+        // Spot conditions are checked while the world is borrowed mutably
+        let config = world.config.clone();
         // Trees/spawn: false => *No* trees around the spot
         // Themed Spots -> Act as an introduction to themes of sites
         for s in RON_SPOT_PROPERTIES.0.iter() {
@@ -36,7 +38,7 @@ impl SpotGenerate for Spot {
                 Spot::RonFile(s),
                 world,
                 s.freq,
-                |g, c| is_valid(&s.condition, g, c),
+                |g, c| is_valid(&s.condition, g, c, &config),
                 s.spawn,
             );
         }
@@ -51,7 +53,7 @@ impl SpotGenerate for Spot {
                     && !c.path.0.is_way()
                     && c.sites.is_empty()
                     && matches!(
-                        c.get_biome(),
+                        c.get_biome(&config),
                         Grassland | Forest | Taiga | Snowland | Jungle
                     )
             },
@@ -67,7 +69,7 @@ impl SpotGenerate for Spot {
                     && !c.river.near_water()
                     && !c.path.0.is_way()
                     && c.sites.is_empty()
-                    && matches!(c.get_biome(), Snowland)
+                    && matches!(c.get_biome(&config), Snowland)
             },
             false,
         );
@@ -81,7 +83,7 @@ impl SpotGenerate for Spot {
                     && !c.river.near_water()
                     && !c.path.0.is_way()
                     && c.sites.is_empty()
-                    && matches!(c.get_biome(), Jungle | Forest)
+                    && matches!(c.get_biome(&config), Jungle | Forest)
             },
             false,
         );
@@ -95,7 +97,7 @@ impl SpotGenerate for Spot {
                     && !c.river.near_water()
                     && !c.path.0.is_way()
                     && c.sites.is_empty()
-                    && matches!(c.get_biome(), Jungle | Forest)
+                    && matches!(c.get_biome(&config), Jungle | Forest)
             },
             false,
         );
@@ -109,7 +111,7 @@ impl SpotGenerate for Spot {
                     && !c.river.near_water()
                     && !c.path.0.is_way()
                     && c.sites.is_empty()
-                    && matches!(c.get_biome(), Jungle | Forest)
+                    && matches!(c.get_biome(&config), Jungle | Forest)
             },
             false,
         );
@@ -123,7 +125,7 @@ impl SpotGenerate for Spot {
                     && !c.river.near_water()
                     && !c.path.0.is_way()
                     && c.sites.is_empty()
-                    && matches!(c.get_biome(), Jungle | Forest)
+                    && matches!(c.get_biome(&config), Jungle | Forest)
             },
             false,
         );
@@ -137,7 +139,7 @@ impl SpotGenerate for Spot {
                     && !c.river.near_water()
                     && !c.path.0.is_way()
                     && c.sites.is_empty()
-                    && matches!(c.get_biome(), Desert | Jungle)
+                    && matches!(c.get_biome(&config), Desert | Jungle)
             },
             false,
         );
@@ -151,7 +153,7 @@ impl SpotGenerate for Spot {
                     && !c.river.near_water()
                     && !c.path.0.is_way()
                     && c.sites.is_empty()
-                    && matches!(c.get_biome(), Forest | Grassland)
+                    && matches!(c.get_biome(&config), Forest | Grassland)
             },
             false,
         );
@@ -165,7 +167,7 @@ impl SpotGenerate for Spot {
                     && !c.river.near_water()
                     && !c.path.0.is_way()
                     && c.sites.is_empty()
-                    && matches!(c.get_biome(), Forest | Grassland)
+                    && matches!(c.get_biome(&config), Forest | Grassland)
             },
             false,
         );
@@ -181,7 +183,7 @@ impl SpotGenerate for Spot {
                     && !c.river.near_water()
                     && !c.path.0.is_way()
                     && c.sites.is_empty()
-                    && matches!(c.get_biome(), Savannah)
+                    && matches!(c.get_biome(&config), Savannah)
             },
             false,
         );
@@ -195,7 +197,7 @@ impl SpotGenerate for Spot {
                     && !c.river.near_water()
                     && !c.path.0.is_way()
                     && c.sites.is_empty()
-                    && matches!(c.get_biome(), Forest | Grassland)
+                    && matches!(c.get_biome(&config), Forest | Grassland)
             },
             false,
         );
@@ -209,7 +211,7 @@ impl SpotGenerate for Spot {
                     && !c.river.near_water()
                     && !c.path.0.is_way()
                     && c.sites.is_empty()
-                    && matches!(c.get_biome(), Jungle | Forest)
+                    && matches!(c.get_biome(&config), Jungle | Forest)
             },
             true,
         );
@@ -223,7 +225,7 @@ impl SpotGenerate for Spot {
                     && !c.river.near_water()
                     && !c.path.0.is_way()
                     && c.sites.is_empty()
-                    && matches!(c.get_biome(), Desert)
+                    && matches!(c.get_biome(&config), Desert)
             },
             false,
         );
@@ -237,7 +239,7 @@ impl SpotGenerate for Spot {
                     && !c.river.near_water()
                     && !c.path.0.is_way()
                     && c.sites.is_empty()
-                    && matches!(c.get_biome(), Desert)
+                    && matches!(c.get_biome(&config), Desert)
             },
             false,
         );
@@ -251,7 +253,7 @@ impl SpotGenerate for Spot {
                     && !c.river.near_water()
                     && !c.path.0.is_way()
                     && c.sites.is_empty()
-                    && !matches!(c.get_biome(), Mountain | Void | Ocean)
+                    && !matches!(c.get_biome(&config), Mountain | Void | Ocean)
             },
             false,
         );
@@ -265,7 +267,7 @@ impl SpotGenerate for Spot {
                     && !c.river.near_water()
                     && !c.path.0.is_way()
                     && c.sites.is_empty()
-                    && matches!(c.get_biome(), Forest)
+                    && matches!(c.get_biome(&config), Forest)
             },
             true,
         );
@@ -279,7 +281,7 @@ impl SpotGenerate for Spot {
                     && !c.river.near_water()
                     && !c.path.0.is_way()
                     && c.sites.is_empty()
-                    && matches!(c.get_biome(), Forest)
+                    && matches!(c.get_biome(&config), Forest)
             },
             false,
         );
@@ -312,7 +314,7 @@ impl SpotGenerate for Spot {
                     && !c.river.near_water()
                     && !c.path.0.is_way()
                     && c.sites.is_empty()
-                    && matches!(c.get_biome(), Forest | Taiga | Jungle | Grassland)
+                    && matches!(c.get_biome(&config), Forest | Taiga | Jungle | Grassland)
             },
             false,
         );
@@ -354,7 +356,7 @@ impl SpotGenerate for Spot {
             if let Some((_, chunk)) = world
                 .get_gradient_approx(pos)
                 .zip(world.get_mut(pos))
-                .filter(|(grad, chunk)| valid(*grad, chunk))
+                .filter(|(grad, chunk)| chunk.landmark.is_none() && valid(*grad, chunk))
             {
                 chunk.spot = Some(spot);
                 if !spawn {
@@ -588,11 +590,11 @@ pub fn apply_spots_to(canvas: &mut Canvas, _dynamic_rng: &mut impl Rng) {
     }
 }
 
-pub fn is_valid(condition: &SpotCondition, g: f32, c: &SimChunk) -> bool {
+pub fn is_valid(condition: &SpotCondition, g: f32, c: &SimChunk, config: &Config) -> bool {
     c.sites.is_empty()
         && match condition {
             SpotCondition::MaxGradient(value) => g < *value,
-            SpotCondition::Biome(biomes) => biomes.contains(&c.get_biome()),
+            SpotCondition::Biome(biomes) => biomes.contains(&c.get_biome(config)),
             SpotCondition::NearCliffs => c.near_cliffs(),
             SpotCondition::NearRiver => c.river.near_water(),
             SpotCondition::IsWay => c.path.0.is_way(),
@@ -601,11 +603,15 @@ pub fn is_valid(condition: &SpotCondition, g: f32, c: &SimChunk) -> bool {
                 !c.near_cliffs() && !c.river.near_water() && !c.path.0.is_way()
             },
             SpotCondition::MinWaterDepth(depth) => {
-                is_valid(&SpotCondition::IsUnderwater, g, c) && c.water_alt > c.alt + depth
+                is_valid(&SpotCondition::IsUnderwater, g, c, config) && c.water_alt > c.alt + depth
+            },
+            SpotCondition::Not(condition) => !is_valid(condition, g, c, config),
+            SpotCondition::All(conditions) => {
+                conditions.iter().all(|cond| is_valid(cond, g, c, config))
+            },
+            SpotCondition::Any(conditions) => {
+                conditions.iter().any(|cond| is_valid(cond, g, c, config))
             },
-            SpotCondition::Not(condition) => !is_valid(condition, g, c),
-            SpotCondition::All(conditions) => conditions.iter().all(|cond| is_valid(cond, g, c)),
-            SpotCondition::Any(conditions) => conditions.iter().any(|cond| is_valid(cond, g, c)),
         }
 }
 
@@ -1,4 +1,4 @@
-use crate::{CONFIG, IndexRef, column::ColumnSample, sim::SimChunk, util::close};
+use crate::{IndexRef, column::ColumnSample, sim::SimChunk, util::close};
 use common::{
     assets::{self, AssetExt},
     calendar::{Calendar, CalendarEvent},
@@ -152,38 +152,41 @@ pub fn spawn_manifest() -> Vec<(&'static str, DensityFn)> {
         // **Tundra**
         // Rock animals
         ("world.wildlife.spawn.tundra.rock", |c, col| {
-            close(c.temp, CONFIG.snow_temp, 0.15) * BASE_DENSITY * col.rock_density * 1.0
+            close(c.temp, col.config.snow_temp, 0.15) * BASE_DENSITY * col.rock_density * 1.0
         }),
         // Core animals
-        ("world.wildlife.spawn.tundra.core", |c, _col| {
-            close(c.temp, CONFIG.snow_temp, 0.15) * BASE_DENSITY * 0.5
+        ("world.wildlife.spawn.tundra.core", |c, col| {
+            close(c.temp, col.config.snow_temp, 0.15) * BASE_DENSITY * 0.5
         }),
         // Core animals events
         (
             "world.wildlife.spawn.calendar.christmas.tundra.core",
-            |c, _col| close(c.temp, CONFIG.snow_temp, 0.15) * BASE_DENSITY * 0.5,
+            |c, col| close(c.temp, col.config.snow_temp, 0.15) * BASE_DENSITY * 0.5,
         ),
         (
             "world.wildlife.spawn.calendar.halloween.tundra.core",
-            |c, _col| close(c.temp, CONFIG.snow_temp, 0.15) * BASE_DENSITY * 1.0,
+            |c, col| close(c.temp, col.config.snow_temp, 0.15) * BASE_DENSITY * 1.0,
         ),
         (
             "world.wildlife.spawn.calendar.april_fools.tundra.core",
-            |c, _col| close(c.temp, CONFIG.snow_temp, 0.15) * BASE_DENSITY * 0.5,
+            |c, col| close(c.temp, col.config.snow_temp, 0.15) * BASE_DENSITY * 0.5,
         ),
         (
             "world.wildlife.spawn.calendar.easter.tundra.core",
-            |c, _col| close(c.temp, CONFIG.snow_temp, 0.15) * BASE_DENSITY * 0.5,
+            |c, col| close(c.temp, col.config.snow_temp, 0.15) * BASE_DENSITY * 0.5,
         ),
         // Snowy animals
         ("world.wildlife.spawn.tundra.snow", |c, col| {
-            close(c.temp, CONFIG.snow_temp, 0.3) * BASE_DENSITY * col.snow_cover as i32 as f32 * 1.0
+            close(c.temp, col.config.snow_temp, 0.3)
+                * BASE_DENSITY
+                * col.snow_cover as i32 as f32
+                * 1.0
         }),
         // Snowy animals event
         (
             "world.wildlife.spawn.calendar.christmas.tundra.snow",
             |c, col| {
-                close(c.temp, CONFIG.snow_temp, 0.3)
+                close(c.temp, col.config.snow_temp, 0.3)
                     * BASE_DENSITY
                     * col.snow_cover as i32 as f32
                     * 1.0
@@ -192,7 +195,7 @@ pub fn spawn_manifest() -> Vec<(&'static str, DensityFn)> {
         (
             "world.wildlife.spawn.calendar.halloween.tundra.snow",
             |c, col| {
-                close(c.temp, CONFIG.snow_temp, 0.3)
+                close(c.temp, col.config.snow_temp, 0.3)
                     * BASE_DENSITY
                     * col.snow_cover as i32 as f32
                     * 1.5
@@ -201,7 +204,7 @@ pub fn spawn_manifest() -> Vec<(&'static str, DensityFn)> {
         (
             "world.wildlife.spawn.calendar.april_fools.tundra.snow",
             |c, col| {
-                close(c.temp, CONFIG.snow_temp, 0.3)
+                close(c.temp, col.config.snow_temp, 0.3)
                     * BASE_DENSITY
                     * col.snow_cover as i32 as f32
                     * 1.0
@@ -210,7 +213,7 @@ pub fn spawn_manifest() -> Vec<(&'static str, DensityFn)> {
         (
             "world.wildlife.spawn.calendar.easter.tundra.snow",
             |c, col| {
-                close(c.temp, CONFIG.snow_temp, 0.3)
+                close(c.temp, col.config.snow_temp, 0.3)
                     * BASE_DENSITY
                     * col.snow_cover as i32 as f32
                     * 1.0
@@ -218,14 +221,14 @@ pub fn spawn_manifest() -> Vec<(&'static str, DensityFn)> {
         ),
         // Forest animals
         ("world.wildlife.spawn.tundra.forest", |c, col| {
-            close(c.temp, CONFIG.snow_temp, 0.3) * col.tree_density * BASE_DENSITY * 1.4
+            close(c.temp, col.config.snow_temp, 0.3) * col.tree_density * BASE_DENSITY * 1.4
         }),
         // River wildlife
         ("world.wildlife.spawn.tundra.river", |c, col| {
-            close(col.temp, CONFIG.snow_temp, 0.3)
+            close(col.temp, col.config.snow_temp, 0.3)
                 * if col.water_dist.map(|d| d < 1.0).unwrap_or(false)
-                    && !matches!(col.chunk.get_biome(), BiomeKind::Ocean)
-                    && c.alt > CONFIG.sea_level + 20.0
+                    && !matches!(col.chunk.get_biome(col.config), BiomeKind::Ocean)
+                    && c.alt > col.config.sea_level + 20.0
                 {
                     0.001
                 } else {
@@ -235,72 +238,92 @@ pub fn spawn_manifest() -> Vec<(&'static str, DensityFn)> {
         // Forest animals event
         (
             "world.wildlife.spawn.calendar.christmas.tundra.forest",
-            |c, col| close(c.temp, CONFIG.snow_temp, 0.3) * col.tree_density * BASE_DENSITY * 1.4,
+            |c, col| {
+                close(c.temp, col.config.snow_temp, 0.3) * col.tree_density * BASE_DENSITY * 1.4
+            },
         ),
         (
             "world.wildlife.spawn.calendar.halloween.tundra.forest",
-            |c, col| close(c.temp, CONFIG.snow_temp, 0.3) * col.tree_density * BASE_DENSITY * 2.0,
+            |c, col| {
+                close(c.temp, col.config.snow_temp, 0.3) * col.tree_density * BASE_DENSITY * 2.0
+            },
         ),
         (
             "world.wildlife.spawn.calendar.april_fools.tundra.forest",
-            |c, col| close(c.temp, CONFIG.snow_temp, 0.3) * col.tree_density * BASE_DENSITY * 1.4,
+            |c, col| {
+                close(c.temp, col.config.snow_temp, 0.3) * col.tree_density * BASE_DENSITY * 1.4
+            },
         ),
         (
             "world.wildlife.spawn.calendar.easter.tundra.forest",
-            |c, col| close(c.temp, CONFIG.snow_temp, 0.3) * col.tree_density * BASE_DENSITY * 1.4,
+            |c, col| {
+                close(c.temp, col.config.snow_temp, 0.3) * col.tree_density * BASE_DENSITY * 1.4
+            },
         ),
         // **Taiga**
         // Forest core animals
         ("world.wildlife.spawn.taiga.core_forest", |c, col| {
-            close(c.temp, CONFIG.snow_temp + 0.2, 0.2) * col.tree_density * BASE_DENSITY * 0.4
+            close(c.temp, col.config.snow_temp + 0.2, 0.2) * col.tree_density * BASE_DENSITY * 0.4
         }),
         // Forest core animals event
         (
             "world.wildlife.spawn.calendar.christmas.taiga.core_forest",
             |c, col| {
-                close(c.temp, CONFIG.snow_temp + 0.2, 0.2) * col.tree_density * BASE_DENSITY * 0.4
+                close(c.temp, col.config.snow_temp + 0.2, 0.2)
+                    * col.tree_density
+                    * BASE_DENSITY
+                    * 0.4
             },
         ),
         (
             "world.wildlife.spawn.calendar.halloween.taiga.core",
             |c, col| {
-                close(c.temp, CONFIG.snow_temp + 0.2, 0.2) * col.tree_density * BASE_DENSITY * 0.8
+                close(c.temp, col.config.snow_temp + 0.2, 0.2)
+                    * col.tree_density
+                    * BASE_DENSITY
+                    * 0.8
             },
         ),
         (
             "world.wildlife.spawn.calendar.april_fools.taiga.core",
             |c, col| {
-                close(c.temp, CONFIG.snow_temp + 0.2, 0.2) * col.tree_density * BASE_DENSITY * 0.4
+                close(c.temp, col.config.snow_temp + 0.2, 0.2)
+                    * col.tree_density
+                    * BASE_DENSITY
+                    * 0.4
             },
         ),
         (
             "world.wildlife.spawn.calendar.easter.taiga.core",
             |c, col| {
-                close(c.temp, CONFIG.snow_temp + 0.2, 0.2) * col.tree_density * BASE_DENSITY * 0.4
+                close(c.temp, col.config.snow_temp + 0.2, 0.2)
+                    * col.tree_density
+                    * BASE_DENSITY
+                    * 0.4
             },
         ),
         // Core animals
-        ("world.wildlife.spawn.taiga.core", |c, _col| {
-            close(c.temp, CONFIG.snow_temp + 0.2, 0.2) * BASE_DENSITY * 1.0
+        ("world.wildlife.spawn.taiga.core", |c, col| {
+            close(c.temp, col.config.snow_temp + 0.2, 0.2) * BASE_DENSITY * 1.0
         }),
         // Forest area animals
         ("world.wildlife.spawn.taiga.forest", |c, col| {
-            close(c.temp, CONFIG.snow_temp + 0.2, 0.6) * col.tree_density * BASE_DENSITY * 0.9
+            close(c.temp, col.config.snow_temp + 0.2, 0.6) * col.tree_density * BASE_DENSITY * 0.9
         }),
         // Area animals
-        ("world.wildlife.spawn.taiga.area", |c, _col| {
-            close(c.temp, CONFIG.snow_temp + 0.2, 0.6) * BASE_DENSITY * 5.0
+        ("world.wildlife.spawn.taiga.area", |c, col| {
+            close(c.temp, col.config.snow_temp + 0.2, 0.6) * BASE_DENSITY * 5.0
         }),
         // Water animals
         ("world.wildlife.spawn.taiga.water", |c, col| {
-            close(c.temp, CONFIG.snow_temp, 0.15) * col.tree_density * BASE_DENSITY * 5.0
+            close(c.temp, col.config.snow_temp, 0.15) * col.tree_density * BASE_DENSITY * 5.0
         }),
         // River wildlife
         ("world.wildlife.spawn.taiga.river", |c, col| {
-            close(col.temp, CONFIG.snow_temp + 0.2, 0.6)
+            close(col.temp, col.config.snow_temp + 0.2, 0.6)
                 * if col.water_dist.map(|d| d < 1.0).unwrap_or(false)
-                    && !matches!(col.chunk.get_biome(), BiomeKind::Ocean)
-                    && c.alt > CONFIG.sea_level + 20.0
+                    && !matches!(col.chunk.get_biome(col.config), BiomeKind::Ocean)
+                    && c.alt > col.config.sea_level + 20.0
                 {
                     0.001
                 } else {
@@ -309,22 +332,22 @@ pub fn spawn_manifest() -> Vec<(&'static str, DensityFn)> {
         }),
         // **Temperate**
         // Area rare
-        ("world.wildlife.spawn.temperate.rare", |c, _col| {
-            close(c.temp, CONFIG.temperate_temp, 0.8) * BASE_DENSITY * 0.08
+        ("world.wildlife.spawn.temperate.rare", |c, col| {
+            close(c.temp, col.config.temperate_temp, 0.8) * BASE_DENSITY * 0.08
         }),
         // Plains
-        ("world.wildlife.spawn.temperate.plains", |c, _col| {
-            close(c.temp, CONFIG.temperate_temp, 0.8)
+        ("world.wildlife.spawn.temperate.plains", |c, col| {
+            close(c.temp, col.config.temperate_temp, 0.8)
                 * close(c.tree_density, 0.0, 0.1)
                 * BASE_DENSITY
                 * 5.0
         }),
         // River wildlife
         ("world.wildlife.spawn.temperate.river", |c, col| {
-            close(col.temp, CONFIG.temperate_temp, 0.6)
+            close(col.temp, col.config.temperate_temp, 0.6)
                 * if col.water_dist.map(|d| d < 1.0).unwrap_or(false)
-                    && !matches!(col.chunk.get_biome(), BiomeKind::Ocean)
-                    && c.alt > CONFIG.sea_level + 20.0
+                    && !matches!(col.chunk.get_biome(col.config), BiomeKind::Ocean)
+                    && c.alt > col.config.sea_level + 20.0
                 {
                     0.001
                 } else {
@@ -333,48 +356,51 @@ pub fn spawn_manifest() -> Vec<(&'static str, DensityFn)> {
         }),
         // Forest animals
         ("world.wildlife.spawn.temperate.wood", |c, col| {
-            close(c.temp, CONFIG.temperate_temp + 0.1, 0.5) * col.tree_density * BASE_DENSITY * 5.0
+            close(c.temp, col.config.temperate_temp + 0.1, 0.5)
+                * col.tree_density
+                * BASE_DENSITY
+                * 5.0
         }),
         // Rainforest animals
-        ("world.wildlife.spawn.temperate.rainforest", |c, _col| {
-            close(c.temp, CONFIG.temperate_temp + 0.1, 0.6)
-                * close(c.humidity, CONFIG.forest_hum, 0.6)
+        ("world.wildlife.spawn.temperate.rainforest", |c, col| {
+            close(c.temp, col.config.temperate_temp + 0.1, 0.6)
+                * close(c.humidity, col.config.forest_hum, 0.6)
                 * BASE_DENSITY
                 * 5.0
         }),
         // Temperate Rainforest animals event
         (
             "world.wildlife.spawn.calendar.halloween.temperate.rainforest",
-            |c, _col| {
-                close(c.temp, CONFIG.temperate_temp + 0.1, 0.6)
-                    * close(c.humidity, CONFIG.forest_hum, 0.6)
+            |c, col| {
+                close(c.temp, col.config.temperate_temp + 0.1, 0.6)
+                    * close(c.humidity, col.config.forest_hum, 0.6)
                     * BASE_DENSITY
                     * 5.0
             },
         ),
         (
             "world.wildlife.spawn.calendar.april_fools.temperate.rainforest",
-            |c, _col| {
-                close(c.temp, CONFIG.temperate_temp + 0.1, 0.6)
-                    * close(c.humidity, CONFIG.forest_hum, 0.6)
+            |c, col| {
+                close(c.temp, col.config.temperate_temp + 0.1, 0.6)
+                    * close(c.humidity, col.config.forest_hum, 0.6)
                     * BASE_DENSITY
                     * 4.0
             },
         ),
         (
             "world.wildlife.spawn.calendar.easter.temperate.rainforest",
-            |c, _col| {
-                close(c.temp, CONFIG.temperate_temp + 0.1, 0.6)
-                    * close(c.humidity, CONFIG.forest_hum, 0.6)
+            |c, col| {
+                close(c.temp, col.config.temperate_temp + 0.1, 0.6)
+                    * close(c.humidity, col.config.forest_hum, 0.6)
                     * BASE_DENSITY
                     * 4.0
             },
         ),
         // Ocean animals
         ("world.wildlife.spawn.temperate.ocean", |_c, col| {
-            close(col.temp, CONFIG.temperate_temp, 1.0) / 10.0
+            close(col.temp, col.config.temperate_temp, 1.0) / 10.0
                 * if col.water_dist.map(|d| d < 1.0).unwrap_or(false)
-                    && matches!(col.chunk.get_biome(), BiomeKind::Ocean)
+                    && matches!(col.chunk.get_biome(col.config), BiomeKind::Ocean)
                 {
                     0.001
                 } else {
@@ -383,10 +409,10 @@ pub fn spawn_manifest() -> Vec<(&'static str, DensityFn)> {
         }),
         // Ocean beach animals
         ("world.wildlife.spawn.temperate.beach", |c, col| {
-            close(col.temp, CONFIG.temperate_temp, 1.0) / 10.0
+            close(col.temp, col.config.temperate_temp, 1.0) / 10.0
                 * if col.water_dist.map(|d| d < 30.0).unwrap_or(false)
-                    && !matches!(col.chunk.get_biome(), BiomeKind::Ocean)
-                    && c.alt < CONFIG.sea_level + 2.0
+                    && !matches!(col.chunk.get_biome(col.config), BiomeKind::Ocean)
+                    && c.alt < col.config.sea_level + 2.0
                 {
                     0.001
                 } else {
@@ -395,43 +421,43 @@ pub fn spawn_manifest() -> Vec<(&'static str, DensityFn)> {
         }),
         // **Jungle**
         // Rainforest animals
-        ("world.wildlife.spawn.jungle.rainforest", |c, _col| {
-            close(c.temp, CONFIG.tropical_temp + 0.2, 0.2)
-                * close(c.humidity, CONFIG.jungle_hum, 0.2)
+        ("world.wildlife.spawn.jungle.rainforest", |c, col| {
+            close(c.temp, col.config.tropical_temp + 0.2, 0.2)
+                * close(c.humidity, col.config.jungle_hum, 0.2)
                 * BASE_DENSITY
                 * 2.8
         }),
         // Rainforest area animals
-        ("world.wildlife.spawn.jungle.rainforest_area", |c, _col| {
-            close(c.temp, CONFIG.tropical_temp + 0.2, 0.3)
-                * close(c.humidity, CONFIG.jungle_hum, 0.2)
+        ("world.wildlife.spawn.jungle.rainforest_area", |c, col| {
+            close(c.temp, col.config.tropical_temp + 0.2, 0.3)
+                * close(c.humidity, col.config.jungle_hum, 0.2)
                 * BASE_DENSITY
                 * 8.0
         }),
         // Jungle animals event
         (
             "world.wildlife.spawn.calendar.halloween.jungle.area",
-            |c, _col| {
-                close(c.temp, CONFIG.tropical_temp + 0.2, 0.3)
-                    * close(c.humidity, CONFIG.jungle_hum, 0.2)
+            |c, col| {
+                close(c.temp, col.config.tropical_temp + 0.2, 0.3)
+                    * close(c.humidity, col.config.jungle_hum, 0.2)
                     * BASE_DENSITY
                     * 10.0
             },
         ),
         (
             "world.wildlife.spawn.calendar.april_fools.jungle.area",
-            |c, _col| {
-                close(c.temp, CONFIG.tropical_temp + 0.2, 0.3)
-                    * close(c.humidity, CONFIG.jungle_hum, 0.2)
+            |c, col| {
+                close(c.temp, col.config.tropical_temp + 0.2, 0.3)
+                    * close(c.humidity, col.config.jungle_hum, 0.2)
                     * BASE_DENSITY
                     * 8.0
             },
         ),
         (
             "world.wildlife.spawn.calendar.easter.jungle.area",
-            |c, _col| {
-                close(c.temp, CONFIG.tropical_temp + 0.2, 0.3)
-                    * close(c.humidity, CONFIG.jungle_hum, 0.2)
+            |c, col| {
+                close(c.temp, col.config.tropical_temp + 0.2, 0.3)
+                    * close(c.humidity, col.config.jungle_hum, 0.2)
                     * BASE_DENSITY
                     * 8.0
             },
@@ -439,10 +465,10 @@ pub fn spawn_manifest() -> Vec<(&'static str, DensityFn)> {
         // **Tropical**
         // River animals
         ("world.wildlife.spawn.tropical.river", |c, col| {
-            close(col.temp, CONFIG.tropical_temp, 0.5)
+            close(col.temp, col.config.tropical_temp, 0.5)
                 * if col.water_dist.map(|d| d < 1.0).unwrap_or(false)
-                    && !matches!(col.chunk.get_biome(), BiomeKind::Ocean)
-                    && c.alt > CONFIG.sea_level + 20.0
+                    && !matches!(col.chunk.get_biome(col.config), BiomeKind::Ocean)
+                    && c.alt > col.config.sea_level + 20.0
                 {
                     0.001
                 } else {
@@ -451,9 +477,9 @@ pub fn spawn_manifest() -> Vec<(&'static str, DensityFn)> {
         }),
         // Ocean animals
         ("world.wildlife.spawn.tropical.ocean", |_c, col| {
-            close(col.temp, CONFIG.tropical_temp, 0.1) / 10.0
+            close(col.temp, col.config.tropical_temp, 0.1) / 10.0
                 * if col.water_dist.map(|d| d < 1.0).unwrap_or(false)
-                    && matches!(col.chunk.get_biome(), BiomeKind::Ocean)
+                    && matches!(col.chunk.get_biome(col.config), BiomeKind::Ocean)
                 {
                     0.001
                 } else {
@@ -462,10 +488,10 @@ pub fn spawn_manifest() -> Vec<(&'static str, DensityFn)> {
         }),
         // Ocean beach animals
         ("world.wildlife.spawn.tropical.beach", |c, col| {
-            close(col.temp, CONFIG.tropical_temp, 1.0) / 10.0
+            close(col.temp, col.config.tropical_temp, 1.0) / 10.0
                 * if col.water_dist.map(|d| d < 30.0).unwrap_or(false)
-                    && !matches!(col.chunk.get_biome(), BiomeKind::Ocean)
-                    && c.alt < CONFIG.sea_level + 2.0
+                    && !matches!(col.chunk.get_biome(col.config), BiomeKind::Ocean)
+                    && c.alt < col.config.sea_level + 2.0
                 {
                     0.001
                 } else {
@@ -474,64 +500,67 @@ pub fn spawn_manifest() -> Vec<(&'static str, DensityFn)> {
         }),
         // Arctic ocean animals
         ("world.wildlife.spawn.arctic.ocean", |_c, col| {
-            close(col.temp, CONFIG.snow_temp, 0.25) / 10.0
-                * if matches!(col.chunk.get_biome(), BiomeKind::Ocean) {
+            close(col.temp, col.config.snow_temp, 0.25) / 10.0
+                * if matches!(col.chunk.get_biome(col.config), BiomeKind::Ocean) {
                     0.001
                 } else {
                     0.0
                 }
         }),
         // Rainforest area animals
-        ("world.wildlife.spawn.tropical.rainforest", |c, _col| {
-            close(c.temp, CONFIG.tropical_temp + 0.1, 0.4)
-                * close(c.humidity, CONFIG.jungle_hum, 0.4)
+        ("world.wildlife.spawn.tropical.rainforest", |c, col| {
+            close(c.temp, col.config.tropical_temp + 0.1, 0.4)
+                * close(c.humidity, col.config.jungle_hum, 0.4)
                 * BASE_DENSITY
                 * 2.0
         }),
         // Tropical Rainforest animals event
         (
             "world.wildlife.spawn.calendar.halloween.tropical.rainforest",
-            |c, _col| {
-                close(c.temp, CONFIG.tropical_temp + 0.1, 0.4)
-                    * close(c.humidity, CONFIG.jungle_hum, 0.4)
+            |c, col| {
+                close(c.temp, col.config.tropical_temp + 0.1, 0.4)
+                    * close(c.humidity, col.config.jungle_hum, 0.4)
                     * BASE_DENSITY
                     * 3.5
             },
         ),
         (
             "world.wildlife.spawn.calendar.april_fools.tropical.rainforest",
-            |c, _col| {
-                close(c.temp, CONFIG.tropical_temp + 0.1, 0.4)
-                    * close(c.humidity, CONFIG.jungle_hum, 0.4)
+            |c, col| {
+                close(c.temp, col.config.tropical_temp + 0.1, 0.4)
+                    * close(c.humidity, col.config.jungle_hum, 0.4)
                     * BASE_DENSITY
                     * 2.0
             },
         ),
         // Rock animals
         ("world.wildlife.spawn.tropical.rock", |c, col| {
-            close(c.temp, CONFIG.tropical_temp + 0.1, 0.5) * col.rock_density * BASE_DENSITY * 5.0
+            close(c.temp, col.config.tropical_temp + 0.1, 0.5)
+                * col.rock_density
+                * BASE_DENSITY
+                * 5.0
         }),
         // **Desert**
         // Area animals
-        ("world.wildlife.spawn.desert.area", |c, _col| {
-            close(c.temp, CONFIG.tropical_temp + 0.1, 0.4)
-                * close(c.humidity, CONFIG.desert_hum, 0.4)
+        ("world.wildlife.spawn.desert.area", |c, col| {
+            close(c.temp, col.config.tropical_temp + 0.1, 0.4)
+                * close(c.humidity, col.config.desert_hum, 0.4)
                 * BASE_DENSITY
                 * 0.8
         }),
         // Wasteland animals
-        ("world.wildlife.spawn.desert.wasteland", |c, _col| {
-            close(c.temp, CONFIG.desert_temp + 0.2, 0.3)
-                * close(c.humidity, CONFIG.desert_hum, 0.5)
+        ("world.wildlife.spawn.desert.wasteland", |c, col| {
+            close(c.temp, col.config.desert_temp + 0.2, 0.3)
+                * close(c.humidity, col.config.desert_hum, 0.5)
                 * BASE_DENSITY
                 * 1.3
         }),
         // River animals
         ("world.wildlife.spawn.desert.river", |c, col| {
-            close(col.temp, CONFIG.desert_temp + 0.2, 0.3)
+            close(col.temp, col.config.desert_temp + 0.2, 0.3)
                 * if col.water_dist.map(|d| d < 1.0).unwrap_or(false)
-                    && !matches!(col.chunk.get_biome(), BiomeKind::Ocean)
-                    && c.alt > CONFIG.sea_level + 20.0
+                    && !matches!(col.chunk.get_biome(col.config), BiomeKind::Ocean)
+                    && c.alt > col.config.sea_level + 20.0
                 {
                     0.001
                 } else {
@@ -539,12 +568,15 @@ pub fn spawn_manifest() -> Vec<(&'static str, DensityFn)> {
                 }
         }),
         // Hot area desert
-        ("world.wildlife.spawn.desert.hot", |c, _col| {
-            close(c.temp, CONFIG.desert_temp + 0.2, 0.3) * BASE_DENSITY * 3.8
+        ("world.wildlife.spawn.desert.hot", |c, col| {
+            close(c.temp, col.config.desert_temp + 0.2, 0.3) * BASE_DENSITY * 3.8
         }),
         // Rock animals
         ("world.wildlife.spawn.desert.rock", |c, col| {
-            close(c.temp, CONFIG.desert_temp + 0.2, 0.05) * col.rock_density * BASE_DENSITY * 4.0
+            close(c.temp, col.config.desert_temp + 0.2, 0.05)
+                * col.rock_density
+                * BASE_DENSITY
+                * 4.0
         }),
     ]
 }
@@ -15,6 +15,7 @@ pub mod canvas;
 pub mod civ;
 mod column;
 pub mod config;
+pub mod export;
 pub mod index;
 pub mod land;
 pub mod layer;
@@ -28,7 +29,7 @@ pub mod util;
 // Reexports
 pub use crate::{
     canvas::{Canvas, CanvasInfo},
-    config::{CONFIG, Features},
+    config::{Config, ConfigError, Features},
     land::Land,
     layer::PathLocals,
 };
@@ -136,7 +137,7 @@ impl World {
         opts: sim::WorldOpts,
         threadpool: &rayon::ThreadPool,
         report_stage: &(dyn Fn(WorldGenerateStage) + Send + Sync),
-    ) -> (Self, IndexOwned) {
+    ) -> Result<(Self, IndexOwned), ConfigError> {
         prof_span!("World::generate");
         // NOTE: Generating index first in order to quickly fail if the color manifest
         // is broken.
@@ -146,20 +147,24 @@ impl World {
 
             let mut sim = sim::WorldSim::generate(seed, opts, threadpool, &|stage| {
                 report_stage(WorldGenerateStage::WorldSimGenerate(stage))
-            });
+            })?;
 
             let civs =
                 civ::Civs::generate(seed, &mut sim, &mut index, calendar.as_ref(), &|stage| {
                     report_stage(WorldGenerateStage::WorldCivGenerate(stage))
                 });
 
+            if index.features().landmarks {
+                layer::landmark::generate(&mut sim, &civs, &mut index);
+            }
+
             report_stage(WorldGenerateStage::EconomySimulation);
             sim2::simulate(&mut index, &mut sim);
 
             report_stage(WorldGenerateStage::SpotGeneration);
             Spot::generate(&mut sim);
 
-            (Self { sim, civs }, IndexOwned::new(index))
+            Ok((Self { sim, civs }, IndexOwned::new(index)))
         })
     }
 
@@ -255,6 +260,12 @@ impl World {
                             }
                         }),
                     )
+                    .chain(index.landmarks.values().map(|landmark| world_msg::Marker {
+                        id: None,
+                        name: Some(Content::Plain(landmark.spec.name.clone())),
+                        kind: landmark.spec.marker.clone(),
+                        wpos: landmark.origin.xy(),
+                    }))
                     .collect(),
                 possible_starting_sites: {
                     const STARTING_SITE_COUNT: usize = 5;
@@ -316,8 +327,9 @@ impl World {
                                 })
                             {
                                 let weight = 1.0 / (distance * std::f32::consts::TAU + 1.0);
-                                let chunk_difficulty = 20.0
-                                    / (20.0 + chunk.get_biome().difficulty().pow(4) as f32 / 5.0);
+                                let biome = chunk.get_biome(&self.sim().config);
+                                let chunk_difficulty =
+                                    20.0 / (20.0 + biome.difficulty().pow(4) as f32 / 5.0);
                                 // let chunk_difficulty = 1.0 / chunk.get_biome().difficulty() as
                                 // f32;
 
@@ -352,6 +364,26 @@ impl World {
 
     pub fn sample_blocks(&self) -> BlockGen { BlockGen::new(ColumnGen::new(&self.sim)) }
 
+    /// One sample per chunk of the given layer, see [`export::Raster`].
+    pub fn export_raster(&self, layer: export::RasterLayer) -> export::Raster {
+        export::Raster::new(&self.sim, layer)
+    }
+
+    /// Sites, landmarks, roads and cave entrances as a GeoJSON
+    /// `FeatureCollection`.
+    pub fn export_overlays(&self, index: IndexRef) -> serde_json::Value {
+        export::overlays(&self.sim, &self.civs, index)
+    }
+
+    /// The terrain within `aabb`, ready to be written as a `.vox` file.
+    pub fn export_region(
+        &self,
+        index: IndexRef,
+        aabb: Aabb<i32>,
+    ) -> Result<export::VoxRegion, export::ExportError> {
+        export::VoxRegion::sample(self, index, aabb)
+    }
+
     /// Find a position that's accessible to a player at the given world
     /// position by searching blocks vertically.
     ///
@@ -424,8 +456,13 @@ impl World {
             },
         };
         let meta = TerrainChunkMeta::new(
-            sim_chunk.get_location_name(&index.sites, &self.civs.pois, chunk_center_wpos2d),
-            sim_chunk.get_biome(),
+            sim_chunk.get_location_name(
+                &index.sites,
+                &index.landmarks,
+                &self.civs.pois,
+                chunk_center_wpos2d,
+            ),
+            sim_chunk.get_biome(&self.sim().config),
             sim_chunk.alt,
             sim_chunk.tree_density,
             sim_chunk.river.is_river(),
@@ -540,6 +577,9 @@ impl World {
         if index.features.spots {
             layer::apply_spots_to(&mut canvas, &mut dynamic_rng);
         }
+        if index.features.landmarks {
+            layer::apply_landmarks_to(&mut canvas);
+        }
         // layer::apply_coral_to(&mut canvas);
 
         // Apply site generation
@@ -815,6 +855,6 @@ impl World {
     pub fn get_location_name(&self, index: IndexRef, wpos2d: Vec2<i32>) -> Option<String> {
         let chunk_pos = wpos2d.wpos_to_cpos();
         let sim_chunk = self.sim.get(chunk_pos)?;
-        sim_chunk.get_location_name(&index.sites, &self.civs.pois, wpos2d)
+        sim_chunk.get_location_name(&index.sites, &index.landmarks, &self.civs.pois, wpos2d)
     }
 }
@@ -1,5 +1,5 @@
 use super::{diffusion, downhill, uphill};
-use crate::{config::CONFIG, util::RandomField};
+use crate::{config::Config, util::RandomField};
 use common::{
     terrain::{
         MapSizeLg, NEIGHBOR_DELTA, TerrainChunkSize, neighbors, uniform_idx_as_vec2,
@@ -224,6 +224,7 @@ pub fn get_rivers<F: fmt::Debug + Float + Into<f64>, G: Float + Into<f64>>(
     downhill: &[isize],
     indirection: &[i32],
     drainage: &[G],
+    config: &Config,
 ) -> Box<[RiverData]> {
     // For continuity-preserving quadratic spline interpolation, we (appear to) need
     // to build up the derivatives from the top down.  Fortunately this
@@ -266,7 +267,7 @@ pub fn get_rivers<F: fmt::Debug + Float + Into<f64>, G: Float + Into<f64>>(
         // TODO: consider having different rainfall rates (and including this
         // information in the computation of drainage).
         let volumetric_flow_rate =
-            chunk_drainage * chunk_area_factor * CONFIG.rainfall_chunk_rate as f64;
+            chunk_drainage * chunk_area_factor * config.rainfall_chunk_rate as f64;
         let downhill_drainage = drainage[downhill_idx].into();
 
         // We know the drainage to the downhill node is just chunk_drainage - 1.0 (the
@@ -443,8 +444,8 @@ pub fn get_rivers<F: fmt::Debug + Float + Into<f64>, G: Float + Into<f64>>(
         let slope_sqrt = slope.sqrt();
         // Now, we compute a quantity that is proportional to the velocity of the chunk,
         // derived from the Manning formula, equal to
-        // volumetric_flow_rate / slope_sqrt * CONFIG.river_roughness.
-        let almost_velocity = volumetric_flow_rate / slope_sqrt * CONFIG.river_roughness as f64;
+        // volumetric_flow_rate / slope_sqrt * config.river_roughness.
+        let almost_velocity = volumetric_flow_rate / slope_sqrt * config.river_roughness as f64;
         // From this, we can figure out the width of the chunk if we know the height.
         // For now, we hardcode the height to 0.5, but it should almost
         // certainly be much more complicated than this.
@@ -470,16 +471,16 @@ pub fn get_rivers<F: fmt::Debug + Float + Into<f64>, G: Float + Into<f64>>(
         //
         // NOTE: Derived from a paper on estimating river width.
         let mut width = 5.0
-            * (CONFIG.river_width_to_depth as f64
-                * (CONFIG.river_width_to_depth as f64 + 2.0).powf(2.0 / 3.0))
+            * (config.river_width_to_depth as f64
+                * (config.river_width_to_depth as f64 + 2.0).powf(2.0 / 3.0))
             .powf(3.0 / 8.0)
             * volumetric_flow_rate.powf(3.0 / 8.0)
             * slope.powf(-3.0 / 16.0)
-            * (CONFIG.river_roughness as f64).powf(3.0 / 8.0);
+            * (config.river_roughness as f64).powf(3.0 / 8.0);
         width = width.max(0.0);
 
         let mut height = if width == 0.0 {
-            CONFIG.river_min_height as f64
+            config.river_min_height as f64
         } else {
             (almost_velocity / width).powf(3.0 / 5.0)
         };
@@ -490,9 +491,9 @@ pub fn get_rivers<F: fmt::Debug + Float + Into<f64>, G: Float + Into<f64>>(
 
         // Now, we can check whether this is "really" a river.
         // Currently, we just check that width and height are at least 0.5 and
-        // CONFIG.river_min_height.
+        // config.river_min_height.
         let river = &rivers[chunk_idx];
-        let is_river = river.is_river() || width >= 0.5 && height >= CONFIG.river_min_height as f64;
+        let is_river = river.is_river() || width >= 0.5 && height >= config.river_min_height as f64;
         let downhill_river = &mut rivers[downhill_idx];
 
         if is_river {
@@ -513,7 +514,7 @@ pub fn get_rivers<F: fmt::Debug + Float + Into<f64>, G: Float + Into<f64>>(
             // problem by making the river deeper when it hits the max width,
             // until it consumes all the available energy in this part of the
             // river.
-            let max_width = TerrainChunkSize::RECT_SIZE.x as f64 * CONFIG.river_max_width as f64;
+            let max_width = TerrainChunkSize::RECT_SIZE.x as f64 * config.river_max_width as f64;
             if width > max_width {
                 width = max_width;
                 height = (almost_velocity / width).powf(3.0 / 5.0);
@@ -521,7 +522,7 @@ pub fn get_rivers<F: fmt::Debug + Float + Into<f64>, G: Float + Into<f64>>(
         }
         // Now we can compute the river's approximate velocity magnitude as well, as
         let velocity_magnitude =
-            1.0 / CONFIG.river_roughness as f64 * height.powf(2.0 / 3.0) * slope_sqrt;
+            1.0 / config.river_roughness as f64 * height.powf(2.0 / 3.0) * slope_sqrt;
 
         // Set up the river's cross-sectional area.
         let cross_section = Vec2::new(width as f32, height as f32);
@@ -552,6 +553,7 @@ fn get_max_slope(
     h: &[Alt],
     rock_strength_nz: &(impl NoiseFn<f64, 3> + Sync),
     height_scale: impl Fn(usize) -> Alt + Sync,
+    mountain_scale: f32,
 ) -> Box<[f64]> {
     let min_max_angle = (15.0 / 360.0 * 2.0 * std::f64::consts::PI).tan();
     let max_max_angle = (60.0 / 360.0 * 2.0 * std::f64::consts::PI).tan();
@@ -588,12 +590,7 @@ fn get_max_slope(
             let log_odds = |x: f64| logit(x) - logit(center);
             let rock_strength = logistic_cdf(
                 1.0 * logit(rock_strength.clamp(1e-7, 1.0f64 - 1e-7))
-                    + 1.0
-                        * log_odds(
-                            (wposz / CONFIG.mountain_scale as f64)
-                                .abs()
-                                .clamp(dmin, dmax),
-                        ),
+                    + 1.0 * log_odds((wposz / mountain_scale as f64).abs().clamp(dmin, dmax)),
             );
             // NOTE: If you want to disable varying rock strength entirely, uncomment  this
             // line. let max_slope = 3.0.sqrt() / 3.0;
@@ -722,6 +719,7 @@ fn erode(
     // scaling factors
     height_scale: impl Fn(f32) -> Alt + Sync,
     k_da_scale: impl Fn(f64) -> f64,
+    mountain_scale: f32,
     threadpool: &rayon::ThreadPool,
 ) {
     let compute_stats = true;
@@ -845,9 +843,13 @@ fn erode(
         || {
             threadpool.join(
                 || {
-                    let max_slope = get_max_slope(map_size_lg, h, rock_strength_nz, |posi| {
-                        height_scale(n_f(posi))
-                    });
+                    let max_slope = get_max_slope(
+                        map_size_lg,
+                        h,
+                        rock_strength_nz,
+                        |posi| height_scale(n_f(posi)),
+                        mountain_scale,
+                    );
                     debug!("Got max slopes...");
                     max_slope
                 },
@@ -2542,6 +2544,7 @@ pub fn do_erosion(
     height_scale: impl Fn(f32) -> Alt + Sync,
     k_d_scale: f64,
     k_da_scale: impl Fn(f64) -> f64,
+    mountain_scale: f32,
     threadpool: &rayon::ThreadPool,
     report_progress: &mut dyn FnMut(f64),
 ) -> (Box<[Alt]>, Box<[Alt]> /* , Box<[Alt]> */) {
@@ -2676,6 +2679,7 @@ pub fn do_erosion(
             &is_ocean,
             &height_scale,
             &k_da_scale,
+            mountain_scale,
             threadpool,
         );
     });
@@ -0,0 +1,197 @@
+use super::{GenOpts, get_oceans};
+use crate::config::Config;
+use bitvec::prelude::BitBox;
+use common::terrain::{MapSizeLg, uniform_idx_as_vec2};
+use image::{ImageBuffer, Luma};
+use serde::{Deserialize, Serialize};
+use std::path::{Path, PathBuf};
+use vek::*;
+
+/// Options for generating a world from an external heightmap rather than the
+/// erosion simulation.
+///
+/// Images are grayscale (16-bit for the heightmap, any depth for masks), with
+/// the top row being the northern edge of the world. They are resampled to the
+/// world size given in `opts`, so they needn't match it.
+#[derive(Clone, Debug, Deserialize, Serialize)]
+pub struct HeightmapOpts {
+    pub path: PathBuf,
+    /// Altitude of black pixels, in blocks.
+    #[serde(default)]
+    pub min_alt: f32,
+    /// Altitude of white pixels, in blocks. Defaults to the top of the
+    /// preset's mountains.
+    #[serde(default)]
+    pub max_alt: Option<f32>,
+    /// Brighter areas are warmer. Temperature still falls with altitude.
+    #[serde(default)]
+    pub temperature: Option<PathBuf>,
+    /// Brighter areas are more humid. Humidity still rises near rivers.
+    #[serde(default)]
+    pub humidity: Option<PathBuf>,
+    /// Erosion steps run over the heightmap to carve rivers and soften
+    /// slopes. A handful is usually enough.
+    #[serde(default = "default_erosion_steps")]
+    pub erosion_steps: usize,
+    /// World size, scale and preset.
+    #[serde(default)]
+    pub opts: GenOpts,
+}
+
+fn default_erosion_steps() -> usize { 8 }
+
+/// A heightmap loaded from [`HeightmapOpts`], resampled to the world size.
+pub struct Heightmap {
+    /// Altitude relative to sea level, in blocks.
+    alt: Box<[f32]>,
+    pub is_ocean: BitBox,
+    pub temperature: Option<Mask>,
+    pub humidity: Option<Mask>,
+    pub erosion_steps: usize,
+}
+
+impl Heightmap {
+    pub fn load(
+        opts: &HeightmapOpts,
+        map_size_lg: MapSizeLg,
+        config: &Config,
+    ) -> Result<Self, image::ImageError> {
+        let max_alt = opts
+            .max_alt
+            .unwrap_or(config.sea_level + config.mountain_scale);
+        let Mask(heights) = Mask::load(&opts.path, map_size_lg)?;
+        let alt = heights
+            .iter()
+            .map(|h| Lerp::lerp(opts.min_alt, max_alt, *h) - config.sea_level)
+            .collect::<Box<[_]>>();
+        let is_ocean = get_oceans(map_size_lg, |posi| alt[posi]);
+        let load_mask = |path: &Option<PathBuf>| {
+            path.as_ref()
+                .map(|path| Mask::load(path, map_size_lg))
+                .transpose()
+        };
+
+        Ok(Self {
+            is_ocean,
+            temperature: load_mask(&opts.temperature)?,
+            humidity: load_mask(&opts.humidity)?,
+            erosion_steps: opts.erosion_steps,
+            alt,
+        })
+    }
+
+    pub fn alt(&self, posi: usize) -> f32 { self.alt[posi] }
+}
+
+/// A grayscale image resampled to one value per chunk, in `0.0..=1.0`.
+pub struct Mask(Box<[f32]>);
+
+impl Mask {
+    pub fn load(path: &Path, map_size_lg: MapSizeLg) -> Result<Self, image::ImageError> {
+        Ok(Self::resample(
+            &image::open(path)?.into_luma16(),
+            map_size_lg,
+        ))
+    }
+
+    fn resample(image: &ImageBuffer<Luma<u16>, Vec<u16>>, map_size_lg: MapSizeLg) -> Self {
+        let chunks = map_size_lg.chunks().map(f32::from);
+        let size = Vec2::new(image.width(), image.height());
+        let pixel = |x: i32, y: i32| {
+            let x = x.clamp(0, size.x as i32 - 1) as u32;
+            let y = y.clamp(0, size.y as i32 - 1) as u32;
+            image.get_pixel(x, y).0[0] as f32 / u16::MAX as f32
+        };
+
+        let values = (0..map_size_lg.chunks_len())
+            .map(|posi| {
+                let pos = uniform_idx_as_vec2(map_size_lg, posi).map(|e| e as f32);
+                // Chunk centres in image space, flipped so that north is up
+                let pos = Vec2::new(pos.x + 0.5, chunks.y - pos.y - 0.5) / chunks
+                    * size.map(|e| e as f32)
+                    - 0.5;
+                // Bilinear interpolation between the four nearest pixels
+                let base = pos.map(|e| e.floor());
+                let t = pos - base;
+                let (x, y) = (base.x as i32, base.y as i32);
+                Lerp::lerp(
+                    Lerp::lerp(pixel(x, y), pixel(x + 1, y), t.x),
+                    Lerp::lerp(pixel(x, y + 1), pixel(x + 1, y + 1), t.x),
+                    t.y,
+                )
+            })
+            .collect();
+
+        Self(values)
+    }
+
+    pub fn get(&self, posi: usize) -> f32 { self.0[posi] }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use common::terrain::vec2_as_uniform_idx;
+
+    #[test]
+    fn resample_flips_and_interpolates() {
+        let map_size_lg = MapSizeLg::new(Vec2::new(1, 1)).unwrap();
+        // Black south edge, white north edge
+        let image = ImageBuffer::from_fn(4, 4, |_, y| Luma([if y < 2 { u16::MAX } else { 0 }]));
+        let mask = Mask::resample(&image, map_size_lg);
+
+        let at = |x, y| mask.get(vec2_as_uniform_idx(map_size_lg, Vec2::new(x, y)));
+        assert_eq!(at(0, 1), 1.0);
+        assert_eq!(at(1, 0), 0.0);
+
+        // A smaller image is stretched over the world
+        let image = ImageBuffer::from_fn(1, 1, |_, _| Luma([u16::MAX / 2]));
+        let mask = Mask::resample(&image, map_size_lg);
+        assert!((0..4).all(|posi| (mask.get(posi) - 0.5).abs() < 0.01));
+    }
+
+    #[test]
+    fn load_small_image() {
+        let dir =
+            std::env::temp_dir().join(format!("veloren-heightmap-test-{}", std::process::id()));
+        std::fs::create_dir_all(&dir).unwrap();
+        let path = dir.join("heightmap.png");
+        // White in the north, black in the south
+        ImageBuffer::from_fn(2, 2, |_, y| Luma([if y == 0 { u16::MAX } else { 0u16 }]))
+            .save(&path)
+            .unwrap();
+
+        let map_size_lg = MapSizeLg::new(Vec2::new(1, 1)).unwrap();
+        let config = Config::default();
+        let opts = HeightmapOpts {
+            path: path.clone(),
+            min_alt: 0.0,
+            max_alt: Some(config.sea_level * 2.0),
+            temperature: None,
+            humidity: Some(path),
+            erosion_steps: default_erosion_steps(),
+            opts: GenOpts::default(),
+        };
+        let heightmap = Heightmap::load(&opts, map_size_lg, &config).unwrap();
+
+        let at = |x, y| vec2_as_uniform_idx(map_size_lg, Vec2::new(x, y));
+        assert_eq!(heightmap.alt(at(0, 1)), config.sea_level);
+        assert_eq!(heightmap.alt(at(0, 0)), -config.sea_level);
+        assert!(heightmap.is_ocean[at(0, 0)]);
+        assert!(!heightmap.is_ocean[at(0, 1)]);
+        assert!(heightmap.temperature.is_none());
+        assert_eq!(
+            heightmap.humidity.as_ref().map(|h| h.get(at(0, 1))),
+            Some(1.0)
+        );
+
+        // Missing images are an error, so the world is generated instead
+        let opts = HeightmapOpts {
+            path: dir.join("missing.png"),
+            ..opts
+        };
+        assert!(Heightmap::load(&opts, map_size_lg, &config).is_err());
+
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+}
@@ -1,5 +1,5 @@
 use crate::{
-    CONFIG, IndexRef,
+    IndexRef,
     column::ColumnSample,
     sim::{RiverKind, WorldSim},
     site::SiteKind,
@@ -52,7 +52,7 @@ pub fn sample_wpos(config: &MapConfig, sampler: &WorldSim, wpos: Vec2<i32>) -> f
                 -f32::INFINITY
             })
         })
-        .unwrap_or(CONFIG.sea_level)
+        .unwrap_or(sampler.config.sea_level)
         - focus.z as f32)
         / gain
 }
@@ -92,7 +92,7 @@ pub fn sample_pos(
         ..
     } = *config;
 
-    let true_sea_level = (CONFIG.sea_level as f64 - focus.z) / gain as f64;
+    let true_sea_level = (sampler.config.sea_level as f64 - focus.z) / gain as f64;
 
     let (
         chunk_idx,
@@ -139,9 +139,9 @@ pub fn sample_pos(
         })
         .unwrap_or((
             None,
-            CONFIG.sea_level,
-            CONFIG.sea_level,
-            CONFIG.sea_level,
+            sampler.config.sea_level,
+            sampler.config.sea_level,
+            sampler.config.sea_level,
             0.0,
             0.0,
             None,
@@ -235,7 +235,7 @@ pub fn sample_pos(
     };
     let rgb = if is_water && is_ice && column_data.is_some_and(|(_, _, ice_depth)| ice_depth > 0.0)
     {
-        CONFIG.ice_color
+        sampler.config.ice_color
     } else {
         match (river_kind, (is_water, true_alt >= true_sea_level)) {
             (_, (false, _)) | (None, (_, true)) | (Some(RiverKind::River { .. }), _) => {
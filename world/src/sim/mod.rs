@@ -1,19 +1,21 @@
 mod diffusion;
 mod erosion;
+mod heightmap;
 mod location;
 mod map;
 mod util;
 mod way;
 
 // Reexports
-use self::erosion::Compute;
 pub use self::{
     diffusion::diffusion,
+    heightmap::HeightmapOpts,
     location::Location,
     map::{sample_pos, sample_wpos},
     util::get_horizon_map,
     way::{Path, Way},
 };
+use self::{erosion::Compute, heightmap::Heightmap};
 pub(crate) use self::{
     erosion::{
         Alt, RiverData, RiverKind, do_erosion, fill_sinks, get_lakes, get_multi_drainage,
@@ -26,11 +28,13 @@ pub(crate) use self::{
 };
 
 use crate::{
-    CONFIG, IndexRef,
+    IndexRef,
     all::{Environment, ForestKind, TreeAttr},
     block::BlockGen,
     civ::{Place, PointOfInterest},
     column::ColumnGen,
+    config::{Config, ConfigError, DEFAULT_WORLD_PRESET},
+    layer::landmark::Landmark,
     site::Site,
     util::{
         CARDINALS, DHashSet, FastNoise, FastNoise2d, LOCALITY, NEIGHBORS, RandomField, Sampler,
@@ -145,6 +149,8 @@ pub struct GenOpts {
     pub scale: f64,
     pub map_kind: MapKind,
     pub erosion_quality: f32,
+    /// Asset specifier of the world preset to generate the world with.
+    pub preset: String,
 }
 
 impl Default for GenOpts {
@@ -155,6 +161,7 @@ impl Default for GenOpts {
             scale: 2.0,
             map_kind: MapKind::Square,
             erosion_quality: 1.0,
+            preset: DEFAULT_WORLD_PRESET.to_string(),
         }
     }
 }
@@ -190,6 +197,9 @@ pub enum FileOpts {
     /// NOTE: Could stand to merge this with `Load` and construct an enum that
     /// can handle either a PathBuf or an asset specifier, at some point.
     LoadAsset(String),
+    /// If set, generate the world from an external heightmap instead of the
+    /// erosion simulation (errors if the image can't be loaded).
+    Heightmap(HeightmapOpts),
 }
 
 impl Default for FileOpts {
@@ -197,11 +207,19 @@ impl Default for FileOpts {
 }
 
 impl FileOpts {
-    fn load_content(&self) -> (Option<ModernMap>, MapSizeLg, GenOpts) {
+    fn load_content(&self) -> Result<(Option<ModernMap>, MapSizeLg, GenOpts, Config), ConfigError> {
         let parsed_world_file = self.try_load_map();
 
         let mut gen_opts = self.gen_opts().unwrap_or_default();
 
+        // Loaded worlds keep the preset they were generated with, so that they
+        // are reproduced exactly.
+        let config = if let Some(map) = &parsed_world_file {
+            map.config.clone()
+        } else {
+            Config::load_preset(&gen_opts.preset)?
+        };
+
         let map_size_lg = if let Some(map) = &parsed_world_file {
             MapSizeLg::new(map.map_size_lg)
                 .expect("World size of loaded map does not satisfy invariants.")
@@ -221,14 +239,15 @@ impl FileOpts {
             gen_opts.scale = map.continent_scale_hack;
         };
 
-        (parsed_world_file, map_size_lg, gen_opts)
+        Ok((parsed_world_file, map_size_lg, gen_opts, config))
     }
 
     fn gen_opts(&self) -> Option<GenOpts> {
         match self {
-            Self::Generate(opts) | Self::Save(_, opts) | Self::LoadOrGenerate { opts, .. } => {
-                Some(opts.clone())
-            },
+            Self::Generate(opts)
+            | Self::Save(_, opts)
+            | Self::LoadOrGenerate { opts, .. }
+            | Self::Heightmap(HeightmapOpts { opts, .. }) => Some(opts.clone()),
             _ => None,
         }
     }
@@ -236,16 +255,17 @@ impl FileOpts {
     // TODO: this should return Option so that caller can choose fallback
     fn map_size(&self) -> MapSizeLg {
         match self {
-            Self::Generate(opts) | Self::Save(_, opts) | Self::LoadOrGenerate { opts, .. } => {
-                MapSizeLg::new(Vec2 {
-                    x: opts.x_lg,
-                    y: opts.y_lg,
-                })
-                .unwrap_or_else(|e| {
-                    warn!("World size does not satisfy invariants: {:?}", e);
-                    DEFAULT_WORLD_CHUNKS_LG
-                })
-            },
+            Self::Generate(opts)
+            | Self::Save(_, opts)
+            | Self::LoadOrGenerate { opts, .. }
+            | Self::Heightmap(HeightmapOpts { opts, .. }) => MapSizeLg::new(Vec2 {
+                x: opts.x_lg,
+                y: opts.y_lg,
+            })
+            .unwrap_or_else(|e| {
+                warn!("World size does not satisfy invariants: {:?}", e);
+                DEFAULT_WORLD_CHUNKS_LG
+            }),
             _ => DEFAULT_WORLD_CHUNKS_LG,
         }
     }
@@ -357,16 +377,26 @@ impl FileOpts {
                 // options, so that when gen opts get another field, compiler
                 // will force you to update following logic
                 let GenOpts {
-                    x_lg, y_lg, scale, ..
+                    x_lg,
+                    y_lg,
+                    scale,
+                    preset,
+                    ..
                 } = opts;
-                let map = match map {
-                    WorldFile::Veloren0_7_0(map) => map,
-                    WorldFile::Veloren0_5_0(_) => {
-                        panic!("World file v0.5.0 isn't supported with LoadOrGenerate.")
+                if let WorldFile::Veloren0_5_0(_) = map {
+                    panic!("World file v0.5.0 isn't supported with LoadOrGenerate.")
+                }
+                let map = match map.into_modern() {
+                    Ok(map) => map,
+                    Err(e) => {
+                        warn!(?e, "Loaded map is invalid. Generating...");
+                        return None;
                     },
                 };
 
-                if map.continent_scale_hack != *scale || map.map_size_lg != Vec2::new(*x_lg, *y_lg)
+                if map.continent_scale_hack != *scale
+                    || map.map_size_lg != Vec2::new(*x_lg, *y_lg)
+                    || !Config::load_preset(preset).is_ok_and(|config| config == map.config)
                 {
                     if *overwrite {
                         warn!(
@@ -385,9 +415,9 @@ impl FileOpts {
                     return None;
                 }
 
-                map.into_modern()
+                Ok(map)
             },
-            Self::Generate { .. } | Self::Save { .. } => return None,
+            Self::Generate { .. } | Self::Save { .. } | Self::Heightmap(_) => return None,
         };
 
         match map {
@@ -397,6 +427,9 @@ impl FileOpts {
                     WorldFileError::WorldSizeInvalid => {
                         warn!("World size of map is invalid.");
                     },
+                    WorldFileError::PresetInvalid(e) => {
+                        warn!("World preset of map is invalid: {e}");
+                    },
                 }
                 None
             },
@@ -504,6 +537,25 @@ pub struct WorldMap_0_7_0 {
     pub basement: Box<[Alt]>,
 }
 
+/// Version of the world map intended for use in Veloren 0.18.0.
+#[derive(Serialize, Deserialize)]
+#[repr(C)]
+pub struct WorldMap_0_18_0 {
+    /// Saved map size.
+    pub map_size_lg: Vec2<u32>,
+    /// Saved continent_scale hack, to try to better approximate the correct
+    /// seed according to varying map size.
+    ///
+    /// TODO: Remove when generating new maps becomes more principled.
+    pub continent_scale_hack: f64,
+    /// Saved altitude height map.
+    pub alt: Box<[Alt]>,
+    /// Saved basement height map.
+    pub basement: Box<[Alt]>,
+    /// Saved world preset the map was generated with.
+    pub config: Config,
+}
+
 /// Errors when converting a map to the most recent type (currently,
 /// shared by the various map types, but at some point we might switch to
 /// version-specific errors if it feels worthwhile).
@@ -511,6 +563,8 @@ pub struct WorldMap_0_7_0 {
 pub enum WorldFileError {
     /// Map size was invalid, and it can't be converted to a valid one.
     WorldSizeInvalid,
+    /// The saved world preset can't be used to generate a world.
+    PresetInvalid(ConfigError),
 }
 
 /// WORLD MAP.
@@ -543,6 +597,7 @@ pub enum WorldFileError {
 pub enum WorldFile {
     Veloren0_5_0(WorldMap_0_5_0) = 0,
     Veloren0_7_0(WorldMap_0_7_0) = 1,
+    Veloren0_18_0(WorldMap_0_18_0) = 2,
 }
 
 impl assets::Asset for WorldFile {
@@ -553,7 +608,7 @@ impl assets::Asset for WorldFile {
 
 /// Data for the most recent map type.  Update this when you add a new map
 /// version.
-pub type ModernMap = WorldMap_0_7_0;
+pub type ModernMap = WorldMap_0_18_0;
 
 /// The default world map.
 ///
@@ -631,6 +686,22 @@ impl WorldMap_0_5_0 {
 }
 
 impl WorldMap_0_7_0 {
+    #[inline]
+    pub fn into_modern(self) -> Result<ModernMap, WorldFileError> {
+        // Maps from before world presets were all generated with the default one.
+        let map = WorldMap_0_18_0 {
+            map_size_lg: self.map_size_lg,
+            continent_scale_hack: self.continent_scale_hack,
+            alt: self.alt,
+            basement: self.basement,
+            config: Config::default(),
+        };
+
+        map.into_modern()
+    }
+}
+
+impl WorldMap_0_18_0 {
     #[inline]
     pub fn into_modern(self) -> Result<ModernMap, WorldFileError> {
         if self.alt.len() != self.basement.len()
@@ -639,6 +710,9 @@ impl WorldMap_0_7_0 {
         {
             return Err(WorldFileError::WorldSizeInvalid);
         }
+        self.config
+            .validate()
+            .map_err(WorldFileError::PresetInvalid)?;
 
         Ok(self)
     }
@@ -649,7 +723,7 @@ impl WorldFile {
     /// for serialization. Whenever a new map is updated, just change the
     /// variant we construct here to make sure we're using the latest map
     /// version.
-    pub fn new(map: ModernMap) -> Self { WorldFile::Veloren0_7_0(map) }
+    pub fn new(map: ModernMap) -> Self { WorldFile::Veloren0_18_0(map) }
 
     #[inline]
     /// Turns a WorldFile into the latest version.  Whenever a new map version
@@ -658,6 +732,7 @@ impl WorldFile {
         match self {
             WorldFile::Veloren0_5_0(map) => map.into_modern(),
             WorldFile::Veloren0_7_0(map) => map.into_modern(),
+            WorldFile::Veloren0_18_0(map) => map.into_modern(),
         }
     }
 }
@@ -686,10 +761,13 @@ pub struct WorldSim {
     pub rng: ChaChaRng,
 
     pub(crate) calendar: Option<Calendar>,
+    /// The preset the world was generated with
+    pub config: Arc<Config>,
 }
 
 impl WorldSim {
     pub fn empty() -> Self {
+        let config = Arc::new(Config::default());
         let gen_ctx = GenCtx {
             turb_x_nz: SuperSimplex::new(0),
             turb_y_nz: SuperSimplex::new(0),
@@ -721,51 +799,58 @@ impl WorldSim {
             seed: 0,
             map_size_lg: MapSizeLg::new(Vec2::one()).unwrap(),
             max_height: 0.0,
-            chunks: vec![SimChunk {
-                chaos: 0.0,
-                alt: 0.0,
-                basement: 0.0,
-                water_alt: 0.0,
-                downhill: None,
-                flux: 0.0,
-                temp: 0.0,
-                humidity: 0.0,
-                rockiness: 0.0,
-                tree_density: 0.0,
-                forest_kind: ForestKind::Dead,
-                spawn_rate: 0.0,
-                river: RiverData::default(),
-                surface_veg: 0.0,
-                sites: vec![],
-                place: None,
-                poi: None,
-                path: Default::default(),
-                cliff_height: 0.0,
-                spot: None,
-                contains_waypoint: false,
-            }],
+            chunks: vec![SimChunk::empty()],
             _locations: Vec::new(),
             gen_ctx,
             rng: rand_chacha::ChaCha20Rng::from_seed([0; 32]),
             calendar: None,
+            config,
         }
     }
 
+    /// A flat world of dry land, for tests
+    #[cfg(test)]
+    pub(crate) fn flat(map_size_lg: MapSizeLg) -> Self {
+        let mut sim = Self::empty();
+        let alt = sim.config.sea_level + 10.0;
+        sim.map_size_lg = map_size_lg;
+        sim.chunks = (0..map_size_lg.chunks_len())
+            .map(|_| SimChunk {
+                alt,
+                basement: alt,
+                water_alt: sim.config.sea_level,
+                ..SimChunk::empty()
+            })
+            .collect();
+        sim
+    }
+
     pub fn generate(
         seed: u32,
         opts: WorldOpts,
         threadpool: &rayon::ThreadPool,
         stage_report: &dyn Fn(WorldSimStage),
-    ) -> Self {
+    ) -> Result<Self, ConfigError> {
         prof_span!("WorldSim::generate");
         let calendar = opts.calendar; // separate lifetime of elements
         let world_file = opts.world_file;
 
         // Parse out the contents of various map formats into the values we need.
-        let (parsed_world_file, map_size_lg, gen_opts) = world_file.load_content();
+        let (parsed_world_file, map_size_lg, gen_opts, config) = world_file.load_content()?;
+        let config = Arc::new(config);
         // Currently only used with LoadOrGenerate to know if we need to
         // overwrite world file
         let fresh = parsed_world_file.is_none();
+        let heightmap = match &world_file {
+            FileOpts::Heightmap(opts) => match Heightmap::load(opts, map_size_lg, &config) {
+                Ok(heightmap) => Some(heightmap),
+                Err(e) => {
+                    warn!(?e, path = ?opts.path, "Couldn't load heightmap. Generating...");
+                    None
+                },
+            },
+            _ => None,
+        };
 
         let mut rng = ChaChaRng::from_seed(seed_expan::rng_state(seed));
         let continent_scale = gen_opts.scale
@@ -905,10 +990,10 @@ impl WorldSim {
                 uniform_noise(map_size_lg, |_, wposf| {
                     match gen_opts.map_kind {
                         MapKind::Square => {
-                            // "Base" of the chunk, to be multiplied by CONFIG.mountain_scale
+                            // "Base" of the chunk, to be multiplied by config.mountain_scale
                             // (multiplied value is from -0.35 *
-                            // (CONFIG.mountain_scale * 1.05) to
-                            // 0.35 * (CONFIG.mountain_scale * 0.95), but value here is from -0.3675
+                            // (config.mountain_scale * 1.05) to
+                            // 0.35 * (config.mountain_scale * 0.95), but value here is from -0.3675
                             // to 0.3325).
                             Some(
                                 (gen_ctx
@@ -1004,7 +1089,7 @@ impl WorldSim {
         );
 
         // We ignore sea level because we actually want to be relative to sea level here
-        // and want things in CONFIG.mountain_scale units, but otherwise this is
+        // and want things in config.mountain_scale units, but otherwise this is
         // a correct altitude calculation.  Note that this is using the
         // "unadjusted" temperature.
         //
@@ -1031,7 +1116,7 @@ impl WorldSim {
             // get [-0.445, 0.565].
             let alt_main = {
                 // Extension upwards from the base.  A positive number from 0 to 1 curved to be
-                // maximal at 0.  Also to be multiplied by CONFIG.mountain_scale.
+                // maximal at 0.  Also to be multiplied by config.mountain_scale.
                 let alt_main = (gen_ctx
                     .alt_nz
                     .get((wposf.div(2_000.0)).into_array())
@@ -1061,7 +1146,7 @@ impl WorldSim {
 
             // Now we can compute the final altitude using chaos.
             // We multiply by chaos clamped to [0.1, 1.32] to get a value between [0.03,
-            // 2.232] for alt_pre, then multiply by CONFIG.mountain_scale and
+            // 2.232] for alt_pre, then multiply by config.mountain_scale and
             // add to the base and sea level to get an adjusted value, then
             // multiply the whole thing by map_edge_factor (TODO: compute final
             // bounds).
@@ -1074,11 +1159,11 @@ impl WorldSim {
                 ((alt_base[posi].1 + alt_main.mul((chaos[posi].1 as f64).powf(1.2)))
                     .mul(map_edge_factor(map_size_lg, posi) as f64)
                     .add(
-                        (CONFIG.sea_level as f64)
-                            .div(CONFIG.mountain_scale as f64)
+                        (config.sea_level as f64)
+                            .div(config.mountain_scale as f64)
                             .mul(map_edge_factor(map_size_lg, posi) as f64),
                     )
-                    .sub((CONFIG.sea_level as f64).div(CONFIG.mountain_scale as f64)))
+                    .sub((config.sea_level as f64).div(config.mountain_scale as f64)))
                     as f32,
             )
         });
@@ -1101,7 +1186,7 @@ impl WorldSim {
             1.0
         };
         let old_height = |posi: usize| {
-            alt_old[posi].1 * CONFIG.mountain_scale * height_scale(n_func(posi)) as f32
+            alt_old[posi].1 * config.mountain_scale * height_scale(n_func(posi)) as f32
         };
 
         // NOTE: Needed if you wish to use the distance to the point defining the Worley
@@ -1225,7 +1310,7 @@ impl WorldSim {
             let wposf3 = Vec3::new(
                 wposf.x,
                 wposf.y,
-                uheight * CONFIG.mountain_scale as f64 * rock_strength_div_factor,
+                uheight * config.mountain_scale as f64 * rock_strength_div_factor,
             );
             let rock_strength = gen_ctx
                 .rock_strength_nz
@@ -1278,7 +1363,7 @@ impl WorldSim {
             let wposf3 = Vec3::new(
                 wposf.x,
                 wposf.y,
-                uheight * CONFIG.mountain_scale as f64 * rock_strength_div_factor,
+                uheight * config.mountain_scale as f64 * rock_strength_div_factor,
             );
             let rock_strength = gen_ctx
                 .rock_strength_nz
@@ -1327,7 +1412,7 @@ impl WorldSim {
             if is_ocean_fn(posi) {
                 old_height(posi)
             } else {
-                (old_height(posi) as f64 / CONFIG.mountain_scale as f64) as f32 - 0.5
+                (old_height(posi) as f64 / config.mountain_scale as f64) as f32 - 0.5
             }
         };
 
@@ -1361,6 +1446,33 @@ impl WorldSim {
 
         let (alt, basement) = if let Some(map) = parsed_world_file {
             (map.alt, map.basement)
+        } else if let Some(heightmap) = &heightmap {
+            // Like the small scale cycle below, this only wears down the imported
+            // terrain to carve rivers, without raising any of it.
+            do_erosion(
+                map_size_lg,
+                1.0f32,
+                heightmap.erosion_steps,
+                river_seed,
+                &rock_strength_nz,
+                |posi| heightmap.alt(posi),
+                |posi| heightmap.alt(posi),
+                |posi| heightmap.is_ocean[posi],
+                |_| 0.0,
+                n_func,
+                theta_func,
+                kf_func,
+                kd_func,
+                g_func,
+                epsilon_0_func,
+                alpha_func,
+                height_scale,
+                k_d_scale(n_approx),
+                k_da_scale,
+                config.mountain_scale,
+                threadpool,
+                report_erosion,
+            )
         } else {
             let (alt, basement) = do_erosion(
                 map_size_lg,
@@ -1386,6 +1498,7 @@ impl WorldSim {
                 height_scale,
                 k_d_scale(n_approx),
                 k_da_scale,
+                config.mountain_scale,
                 threadpool,
                 report_erosion,
             );
@@ -1411,6 +1524,7 @@ impl WorldSim {
                 height_scale,
                 k_d_scale(n_approx),
                 k_da_scale,
+                config.mountain_scale,
                 threadpool,
                 report_erosion,
             )
@@ -1423,6 +1537,7 @@ impl WorldSim {
             map_size_lg: map_size_lg.vec(),
             alt,
             basement,
+            config: Config::clone(&config),
         });
         if fresh {
             world_file.save(&map);
@@ -1435,6 +1550,7 @@ impl WorldSim {
             map_size_lg: _,
             alt,
             basement,
+            config: _,
         } = map.into_modern().unwrap();
 
         // Additional small-scale erosion after map load, only used during testing.
@@ -1461,6 +1577,7 @@ impl WorldSim {
                 height_scale,
                 k_d_scale(n_approx),
                 k_da_scale,
+                config.mountain_scale,
                 threadpool,
                 report_erosion,
             )
@@ -1556,6 +1673,7 @@ impl WorldSim {
             &dh,
             &indirection,
             &flux_rivers,
+            &config,
         );
 
         let water_alt = indirection
@@ -1613,6 +1731,10 @@ impl WorldSim {
             true
         };
 
+        // Masks imported with the heightmap replace the noise below
+        let temp_mask = heightmap.as_ref().and_then(|h| h.temperature.as_ref());
+        let humid_mask = heightmap.as_ref().and_then(|h| h.humidity.as_ref());
+
         // NaNs in these uniform vectors wherever pure_water() returns true.
         let (((alt_no_water, _), (pure_flux, _)), ((temp_base, _), (humid_base, _))) = threadpool
             .join(
@@ -1648,7 +1770,10 @@ impl WorldSim {
                                     None
                                 } else {
                                     // -1 to 1.
-                                    Some(gen_ctx.temp_nz.get((wposf).into_array()) as f32)
+                                    Some(match temp_mask {
+                                        Some(mask) => mask.get(posi),
+                                        None => gen_ctx.temp_nz.get((wposf).into_array()) as f32,
+                                    })
                                 }
                             })
                         },
@@ -1659,12 +1784,15 @@ impl WorldSim {
                                     None
                                 } else {
                                     // 0 to 1, hopefully.
-                                    Some(
-                                        (gen_ctx.humid_nz.get(wposf.div(1024.0).into_array())
-                                            as f32)
-                                            .add(1.0)
-                                            .mul(0.5),
-                                    )
+                                    Some(match humid_mask {
+                                        Some(mask) => mask.get(posi),
+                                        None => {
+                                            (gen_ctx.humid_nz.get(wposf.div(1024.0).into_array())
+                                                as f32)
+                                                .add(1.0)
+                                                .mul(0.5)
+                                        },
+                                    })
                                 }
                             })
                         },
@@ -1688,7 +1816,7 @@ impl WorldSim {
 
         let chunks = (0..map_size_lg.chunks_len())
             .into_par_iter()
-            .map(|i| SimChunk::generate(map_size_lg, i, &gen_ctx, &gen_cdf))
+            .map(|i| SimChunk::generate(map_size_lg, i, &gen_ctx, &gen_cdf, &config))
             .collect::<Vec<_>>();
 
         let mut this = Self {
@@ -1700,6 +1828,7 @@ impl WorldSim {
             gen_ctx,
             rng,
             calendar,
+            config,
         };
 
         this.generate_cliffs();
@@ -1708,7 +1837,7 @@ impl WorldSim {
             this.seed_elements();
         }
 
-        this
+        Ok(this)
     }
 
     #[inline(always)]
@@ -1728,7 +1857,7 @@ impl WorldSim {
     }
 
     pub fn generate_oob_chunk(&self) -> TerrainChunk {
-        TerrainChunk::water(CONFIG.sea_level as i32)
+        TerrainChunk::water(self.config.sea_level as i32)
     }
 
     pub fn approx_chunk_terrain_normal(&self, chunk_pos: Vec2<i32>) -> Option<Vec3<f32>> {
@@ -1757,7 +1886,10 @@ impl WorldSim {
         prof_span!("WorldSim::get_map");
         let mut map_config = MapConfig::orthographic(
             self.map_size_lg(),
-            core::ops::RangeInclusive::new(CONFIG.sea_level, CONFIG.sea_level + self.max_height),
+            core::ops::RangeInclusive::new(
+                self.config.sea_level,
+                self.config.sea_level + self.max_height,
+            ),
         );
         // Build a horizon map.
         let scale_angle = |angle: Alt| {
@@ -1786,7 +1918,7 @@ impl WorldSim {
                                 calendar,
                             )
                         )?;
-                        // sample.water_level = CONFIG.sea_level.max(sample.water_level);
+                        // sample.water_level = self.config.sea_level.max(sample.water_level);
 
                         Some(sample)
                     },
@@ -1806,15 +1938,15 @@ impl WorldSim {
                 min: Vec2::zero(),
                 max: self.map_size_lg().chunks().map(|e| e as i32),
             },
-            CONFIG.sea_level,
-            CONFIG.sea_level + self.max_height,
+            self.config.sea_level,
+            self.config.sea_level + self.max_height,
             |posi| {
                 /* let chunk = &self.chunks[posi];
                 chunk.alt.max(chunk.water_alt) as Alt */
                 let sample = samples_data[posi].as_ref();
                 sample
                     .map(|s| s.alt.max(s.water_level))
-                    .unwrap_or(CONFIG.sea_level)
+                    .unwrap_or(self.config.sea_level)
             },
             |a| scale_angle(a.into()),
             |h| scale_height(h.into()),
@@ -2090,7 +2222,7 @@ impl WorldSim {
         self.get_interpolated(wpos, |chunk| chunk.alt)
             .zip(self.get_interpolated(wpos, |chunk| chunk.water_alt))
             .map(|(alt, water_alt)| alt.max(water_alt))
-            .unwrap_or(CONFIG.sea_level)
+            .unwrap_or(self.config.sea_level)
     }
 
     pub fn get_alt_approx(&self, wpos: Vec2<i32>) -> Option<f32> {
@@ -2418,7 +2550,7 @@ impl WorldSim {
         } else {
             return Lottery::from(vec![(1.0, None)]);
         };
-        let env = chunk.get_environment();
+        let env = chunk.get_environment(&self.config);
         Lottery::from(
             ForestKind::iter()
                 .enumerate()
@@ -2502,6 +2634,7 @@ pub struct SimChunk {
     pub path: (Way, Path),
     pub cliff_height: f32,
     pub spot: Option<Spot>,
+    pub landmark: Option<Id<Landmark>>,
 
     pub contains_waypoint: bool,
 }
@@ -2524,7 +2657,40 @@ pub struct NearestWaysData<M, F: FnOnce() -> Vec2<f32>> {
 }
 
 impl SimChunk {
-    fn generate(map_size_lg: MapSizeLg, posi: usize, gen_ctx: &GenCtx, gen_cdf: &GenCdf) -> Self {
+    fn empty() -> Self {
+        Self {
+            chaos: 0.0,
+            alt: 0.0,
+            basement: 0.0,
+            water_alt: 0.0,
+            downhill: None,
+            flux: 0.0,
+            temp: 0.0,
+            humidity: 0.0,
+            rockiness: 0.0,
+            tree_density: 0.0,
+            forest_kind: ForestKind::Dead,
+            spawn_rate: 0.0,
+            river: RiverData::default(),
+            surface_veg: 0.0,
+            sites: vec![],
+            place: None,
+            poi: None,
+            path: Default::default(),
+            cliff_height: 0.0,
+            spot: None,
+            landmark: None,
+            contains_waypoint: false,
+        }
+    }
+
+    fn generate(
+        map_size_lg: MapSizeLg,
+        posi: usize,
+        gen_ctx: &GenCtx,
+        gen_cdf: &GenCdf,
+        config: &Config,
+    ) -> Self {
         let pos = uniform_idx_as_vec2(map_size_lg, posi);
         let wposf = (pos * TerrainChunkSize::RECT_SIZE.map(|e| e as i32)).map(|e| e as f64);
 
@@ -2575,14 +2741,14 @@ impl SimChunk {
         // Moisture evaporates more in hot places
         let humidity = humidity
             * (1.0
-                - (temp - CONFIG.tropical_temp)
+                - (temp - config.tropical_temp)
                     .max(0.0)
-                    .div(1.0 - CONFIG.tropical_temp))
+                    .div(1.0 - config.tropical_temp))
             .max(0.0);
 
-        let mut alt = CONFIG.sea_level.add(alt_pre);
-        let basement = CONFIG.sea_level.add(basement_pre);
-        let water_alt = CONFIG.sea_level.add(water_alt_pre);
+        let mut alt = config.sea_level.add(alt_pre);
+        let basement = config.sea_level.add(basement_pre);
+        let water_alt = config.sea_level.add(water_alt_pre);
         let (downhill, _gradient) = if downhill_pre == -2 {
             (None, 0.0)
         } else if downhill_pre < 0 {
@@ -2615,7 +2781,7 @@ impl SimChunk {
         let river_slope = river.velocity.z / river_xy;
         match river.river_kind {
             Some(RiverKind::River { cross_section }) => {
-                if cross_section.x >= 0.5 && cross_section.y >= CONFIG.river_min_height {
+                if cross_section.x >= 0.5 && cross_section.y >= config.river_min_height {
                     /* println!(
                         "Big area! Pos area: {:?}, River data: {:?}, slope: {:?}",
                         wposf, river, river_slope
@@ -2702,7 +2868,7 @@ impl SimChunk {
                 const SOIL_SCALE: f32 = 16.0;
                 let soil = soil_nz * SOIL_SCALE * tree_density.sqrt() * humidity.sqrt();
 
-                let warp_factor = ((alt - CONFIG.sea_level) / 16.0).clamped(0.0, 1.0);
+                let warp_factor = ((alt - config.sea_level) / 16.0).clamped(0.0, 1.0);
 
                 let warp = (dune + soil) * warp_factor;
 
@@ -2758,6 +2924,7 @@ impl SimChunk {
             path: Default::default(),
             cliff_height: 0.0,
             spot: None,
+            landmark: None,
 
             contains_waypoint: false,
         }
@@ -2769,18 +2936,18 @@ impl SimChunk {
 
     pub fn get_base_z(&self) -> f32 { self.alt - self.chaos * 50.0 - 16.0 }
 
-    pub fn get_biome(&self) -> BiomeKind {
+    pub fn get_biome(&self, config: &Config) -> BiomeKind {
         let savannah_hum_temp = [0.05..0.55, 0.3..1.6];
         let taiga_hum_temp = [0.2..1.4, -0.7..-0.3];
         if self.river.is_ocean() {
             BiomeKind::Ocean
         } else if self.river.is_lake() {
             BiomeKind::Lake
-        } else if self.temp < CONFIG.snow_temp {
+        } else if self.temp < config.snow_temp {
             BiomeKind::Snowland
         } else if self.alt > 500.0 && self.chaos > 0.3 && self.tree_density < 0.6 {
             BiomeKind::Mountain
-        } else if self.temp > CONFIG.desert_temp && self.humidity < CONFIG.desert_hum {
+        } else if self.temp > config.desert_temp && self.humidity < config.desert_hum {
             BiomeKind::Desert
         } else if self.tree_density > 0.65 && self.humidity > 0.65 && self.temp > 0.45 {
             BiomeKind::Jungle
@@ -2804,13 +2971,13 @@ impl SimChunk {
 
     pub fn near_cliffs(&self) -> bool { self.cliff_height > 0.0 }
 
-    pub fn get_environment(&self) -> Environment {
+    pub fn get_environment(&self, config: &Config) -> Environment {
         Environment {
             humid: self.humidity,
             temp: self.temp,
             near_water: if self.river.is_lake()
                 || self.river.near_river()
-                || self.alt < CONFIG.sea_level + 6.0
+                || self.alt < config.sea_level + 6.0
             // Close to sea in altitude
             {
                 1.0
@@ -2823,6 +2990,7 @@ impl SimChunk {
     pub fn get_location_name(
         &self,
         index_sites: &Store<crate::site::Site>,
+        index_landmarks: &Store<Landmark>,
         civs_pois: &Store<PointOfInterest>,
         wpos2d: Vec2<i32>,
     ) -> Option<String> {
@@ -2834,6 +3002,10 @@ impl SimChunk {
             })
             .min_by_key(|id| index_sites[**id].get_origin().distance_squared(wpos2d))
             .map(|id| index_sites[*id].name().to_string())
+            .or_else(|| {
+                self.landmark
+                    .map(|id| index_landmarks[id].spec.name.clone())
+            })
             .or_else(|| self.poi.map(|poi| civs_pois[poi].name.clone()))
     }
 }
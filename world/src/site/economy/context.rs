@@ -356,7 +356,7 @@ mod tests {
             };
             let mut index = crate::index::Index::new(seed);
             info!("Index created");
-            let mut sim = sim::WorldSim::generate(seed, opts, &threadpool, &|_| {});
+            let mut sim = sim::WorldSim::generate(seed, opts, &threadpool, &|_| {}).unwrap();
             info!("World loaded");
             let _civs = crate::civ::Civs::generate(seed, &mut sim, &mut index, None, &|_| {});
             info!("Civs created");
@@ -382,7 +382,7 @@ mod tests {
             };
             let mut index = crate::index::Index::new(seed);
             info!("Index created");
-            let mut sim = sim::WorldSim::generate(seed, opts, &threadpool, &|_| {});
+            let mut sim = sim::WorldSim::generate(seed, opts, &threadpool, &|_| {}).unwrap();
             info!("World loaded");
             let mut names = None;
             let regenerate_input = false;
@@ -514,7 +514,7 @@ mod tests {
             };
             let index = crate::index::Index::new(seed);
             info!("Index created");
-            let mut sim = sim::WorldSim::generate(seed, opts, &threadpool, &|_| {});
+            let mut sim = sim::WorldSim::generate(seed, opts, &threadpool, &|_| {}).unwrap();
             info!("World loaded");
             let rng = ChaChaRng::from_seed(seed_expan::rng_state(seed));
             let mut env = Simenv {
@@ -26,6 +26,8 @@ pub struct Colors {
 
 pub struct SpawnRules {
     pub trees: bool,
+    /// Whether wildlife may spawn here.
+    pub wildlife: bool,
     pub max_warp: f32,
     pub paths: bool,
     pub waypoints: bool,
@@ -37,6 +39,7 @@ impl SpawnRules {
         // Should be commutative
         Self {
             trees: self.trees && other.trees,
+            wildlife: self.wildlife && other.wildlife,
             max_warp: self.max_warp.min(other.max_warp),
             paths: self.paths && other.paths,
             waypoints: self.waypoints && other.waypoints,
@@ -48,6 +51,7 @@ impl Default for SpawnRules {
     fn default() -> Self {
         Self {
             trees: true,
+            wildlife: true,
             max_warp: 1.0,
             paths: true,
             waypoints: true,
@@ -14,7 +14,6 @@ pub use self::{
 };
 use crate::{
     Canvas, IndexRef, Land,
-    config::CONFIG,
     sim::Path,
     site::{SpawnRules, namegen::NameGen},
     util::{CARDINALS, DHashSet, Grid, SQUARE_4, SQUARE_9, attempt},
@@ -100,6 +99,7 @@ impl Site {
             .unwrap_or(1.0);
         let base_spawn_rules = SpawnRules {
             trees: max_warp == 1.0,
+            wildlife: true,
             max_warp,
             paths: max_warp > f32::EPSILON,
             waypoints: true,
@@ -3052,8 +3052,7 @@ fn wpos_is_hazard(land: &Land, wpos: Vec2<i32>) -> Option<HazardKind> {
 
 fn temp_at_wpos(land: &Land, wpos: Vec2<i32>) -> f32 {
     land.get_chunk_wpos(wpos)
-        .map(|c| c.temp)
-        .unwrap_or(CONFIG.temperate_temp)
+        .map_or(land.config().temperate_temp, |c| c.temp)
 }
 
 pub fn aabr_tiles(aabr: Aabr<i32>) -> impl Iterator<Item = Vec2<i32>> {
@@ -54,9 +54,9 @@ impl Structure for Camp {
         let mut thread_rng = thread_rng();
         let model_pos = center.with_z(base);
         let temp = self.temp;
-        let camp_type = if temp >= CONFIG.tropical_temp {
+        let camp_type = if temp >= land.config().tropical_temp {
             CampType::Pirate
-        } else if temp <= (CONFIG.snow_temp) {
+        } else if temp <= land.config().snow_temp {
             CampType::Snow
         } else {
             CampType::Forest
@@ -1,8 +1,11 @@
 use super::*;
 use crate::{ColumnSample, Land};
-use common::terrain::{
-    Block, BlockKind, SpriteKind,
-    sprite::{Owned, RelativeNeighborPosition},
+use common::{
+    calendar::Season,
+    terrain::{
+        Block, BlockKind, SpriteKind,
+        sprite::{Owned, RelativeNeighborPosition},
+    },
 };
 use rand::prelude::*;
 use strum::{EnumIter, IntoEnumIterator};
@@ -42,6 +45,25 @@ impl Crop {
         }
     }
 
+    /// The proportion of the crop that's grown at this time of year. Without
+    /// seasons, crops are always fully grown.
+    fn growth(&self, season: Option<Season>) -> f64 {
+        match (self, season) {
+            (Self::Cactus, _) | (_, None | Some(Season::Summer | Season::Autumn)) => 1.0,
+            (_, Some(Season::Spring)) => 0.4,
+            (_, Some(Season::Winter)) => 0.0,
+        }
+    }
+
+    /// Ripens or unripens a crop's sprite to suit the season.
+    fn ripeness(sprite: SpriteKind, season: Option<Season>) -> SpriteKind {
+        match (sprite, season) {
+            (SpriteKind::WheatGreen, Some(Season::Autumn)) => SpriteKind::WheatYellow,
+            (SpriteKind::WheatYellow, Some(Season::Spring)) => SpriteKind::WheatGreen,
+            (sprite, _) => sprite,
+        }
+    }
+
     fn sprites(&self) -> &[(f32, Option<SpriteKind>)] {
         match self {
             Self::Wheat => &[
@@ -247,14 +269,22 @@ impl Structure for FarmField {
                     .with_adjacent_type(adjacent_type)
                     .unwrap(),
             )
-        } else if z_off == 1 && (is_trench || self.crop.row_spacing().is_none()) {
+        } else if z_off == 1
+            && (is_trench || self.crop.row_spacing().is_none())
+            && match self.crop.growth(col.season) {
+                growth if growth >= 1.0 => true,
+                growth => rng.gen_bool(growth),
+            }
+        {
             // crops
             self.crop
                 .sprites()
                 .choose_weighted(rng, |(w, _)| *w)
                 .ok()
                 .and_then(|&(_, s)| {
-                    let new = old.into_vacant().with_sprite(s?);
+                    let new = old
+                        .into_vacant()
+                        .with_sprite(Crop::ripeness(s?, col.season));
                     let new = new.with_attr(Owned(true)).unwrap_or(new);
 
                     Some(new)
@@ -49,7 +49,7 @@ impl Sahagin {
 
         Self {
             bounds,
-            alt: CONFIG.sea_level as i32,
+            alt: land.config().sea_level as i32,
             surface_color,
             sub_surface_color,
             center,
@@ -1,6 +1,6 @@
 use super::*;
 use crate::{
-    CONFIG, Land,
+    Land,
     site2::gen::{PrimitiveTransform, spiral_staircase},
     util::{DIAGONALS, NEIGHBORS, RandomField, sampler::Sampler},
 };
@@ -22,7 +22,7 @@ pub struct SeaChapel {
     pub(crate) alt: i32,
 }
 impl SeaChapel {
-    pub fn generate(_land: &Land, _rng: &mut impl Rng, site: &Site, tile_aabr: Aabr<i32>) -> Self {
+    pub fn generate(land: &Land, _rng: &mut impl Rng, site: &Site, tile_aabr: Aabr<i32>) -> Self {
         let bounds = Aabr {
             min: site.tile_wpos(tile_aabr.min),
             max: site.tile_wpos(tile_aabr.max),
@@ -30,7 +30,7 @@ impl SeaChapel {
         let center = bounds.center();
         Self {
             center,
-            alt: CONFIG.sea_level as i32,
+            alt: land.config().sea_level as i32,
         }
     }
 